@@ -4,7 +4,7 @@
 use std::collections::HashMap;
 
 use crate::common::{get_bacnet_socket, MySocket};
-use clap::{command, Parser};
+use clap::Parser;
 use embedded_bacnet::{
     application_protocol::{
         primitives::data_value::{ApplicationDataValue, BitString, Enumerated},
@@ -68,7 +68,7 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
                 | ObjectType::ObjectAnalogValue
                 | ObjectType::ObjectSchedule
                 | ObjectType::ObjectTrendlog => {
-                    let list = map.entry(item.object_type.clone() as u32).or_insert(vec![]);
+                    let list = map.entry(item.object_type as u32).or_insert(vec![]);
                     list.push(item);
                 }
                 _ => {}
@@ -82,7 +82,7 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
             ObjectType::ObjectBinaryInput
             | ObjectType::ObjectBinaryOutput
             | ObjectType::ObjectBinaryValue => {
-                for chunk in ids.as_slice().chunks(10).into_iter() {
+                for chunk in ids.as_slice().chunks(10) {
                     let _values = get_multi_binary(&mut bacnet, &mut buf, chunk).await?;
                     println!("{:?}", _values);
                 }
@@ -90,7 +90,7 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
             ObjectType::ObjectAnalogInput
             | ObjectType::ObjectAnalogOutput
             | ObjectType::ObjectAnalogValue => {
-                for chunk in ids.as_slice().chunks(10).into_iter() {
+                for chunk in ids.as_slice().chunks(10) {
                     let _values = get_multi_analog(&mut bacnet, &mut buf, chunk).await?;
                     println!("{:?}", _values);
                 }
@@ -102,7 +102,7 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
                 }
             }
             ObjectType::ObjectTrendlog => {
-                for chunk in ids.as_slice().chunks(10).into_iter() {
+                for chunk in ids.as_slice().chunks(10) {
                     let values = get_multi_trend_log(&mut bacnet, &mut buf, chunk).await?;
                     println!("{:?}", values);
                 }
@@ -165,7 +165,7 @@ async fn get_multi_binary(
     ];
     let items: Vec<ReadPropertyMultipleObject> = object_ids
         .iter()
-        .map(|x| ReadPropertyMultipleObject::new(x.clone(), property_ids.clone()))
+        .map(|x| ReadPropertyMultipleObject::new(*x, property_ids.clone()))
         .collect();
     let request = ReadPropertyMultiple::new(items);
     let result = bacnet.read_property_multiple(buf, request).await?;
@@ -174,12 +174,12 @@ async fn get_multi_binary(
     for obj in &result.objects_with_results {
         let x = &obj.property_results;
         let name = x[0].value.to_string();
-        let value = match &x[1].value {
+        let value = matches!(
+            &x[1].value,
             PropertyValue::PropValue(ApplicationDataValue::Enumerated(Enumerated::Binary(
                 Binary::On,
-            ))) => true,
-            _ => false,
-        };
+            )))
+        );
         let status = match &x[2].value {
             PropertyValue::PropValue(ApplicationDataValue::BitString(BitString::Status(x))) => {
                 x.clone()
@@ -195,7 +195,7 @@ async fn get_multi_binary(
         });
     }
 
-    return Ok(items);
+    Ok(items)
 }
 
 #[cfg(feature = "alloc")]
@@ -213,7 +213,7 @@ async fn get_multi_analog(
 
     let items: Vec<ReadPropertyMultipleObject> = object_ids
         .iter()
-        .map(|x| ReadPropertyMultipleObject::new(x.clone(), property_ids.clone()))
+        .map(|x| ReadPropertyMultipleObject::new(*x, property_ids.clone()))
         .collect();
 
     let request = ReadPropertyMultiple::new(items);
@@ -249,7 +249,7 @@ async fn get_multi_analog(
         })
     }
 
-    return Ok(items);
+    Ok(items)
 }
 
 #[cfg(feature = "alloc")]
@@ -262,7 +262,7 @@ async fn get_multi_trend_log(
 
     let items: Vec<ReadPropertyMultipleObject> = object_ids
         .iter()
-        .map(|x| ReadPropertyMultipleObject::new(x.clone(), property_ids.clone()))
+        .map(|x| ReadPropertyMultipleObject::new(*x, property_ids.clone()))
         .collect();
 
     let request = ReadPropertyMultiple::new(items);
@@ -285,7 +285,7 @@ async fn get_multi_trend_log(
         })
     }
 
-    return Ok(items);
+    Ok(items)
 }
 
 #[cfg(feature = "alloc")]
@@ -295,10 +295,7 @@ async fn get_multi_schedule(
     object_id: &ObjectId,
 ) -> Result<Vec<ScheduleValue>, BacnetError<MySocket>> {
     let property_ids = vec![PropertyId::PropObjectName, PropertyId::PropWeeklySchedule];
-    let objects = vec![ReadPropertyMultipleObject::new(
-        object_id.clone(),
-        property_ids,
-    )];
+    let objects = vec![ReadPropertyMultipleObject::new(*object_id, property_ids)];
     let request = ReadPropertyMultiple::new(objects);
     let result = bacnet.read_property_multiple(buf, request).await?;
 
@@ -327,5 +324,5 @@ async fn get_multi_schedule(
         });
     }
 
-    return Ok(items);
+    Ok(items)
 }
@@ -4,7 +4,7 @@
 
 use std::collections::HashMap;
 
-use clap::{command, Parser};
+use clap::Parser;
 use common::MySocket;
 use embedded_bacnet::{
     application_protocol::{
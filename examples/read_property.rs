@@ -1,7 +1,7 @@
 // cargo run --example read_property -- --addr "192.168.1.249:47808"
 // cargo run --example read_property --no-default-features -- --addr "192.168.1.249:47808"
 
-use clap::{command, Parser};
+use clap::Parser;
 use common::MySocket;
 use embedded_bacnet::{
     application_protocol::{
@@ -7,14 +7,16 @@
 use core::ops::Range;
 
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
-use clap::{command, Parser};
+use clap::Parser;
 use common::MySocket;
 use embedded_bacnet::{
     application_protocol::{
         primitives::data_value::ApplicationDataValue,
         services::{
             read_property::{ReadProperty, ReadPropertyValue},
-            read_range::{ReadRange, ReadRangeByPosition, ReadRangeRequestType, ReadRangeValue},
+            read_range::{
+                ReadRange, ReadRangeByPosition, ReadRangeItem, ReadRangeRequestType, ReadRangeValue,
+            },
         },
     },
     common::{
@@ -43,19 +45,13 @@ async fn main() -> Result<(), BacnetError<MySocket>> {
 
     // fetch record count
     let object_id = ObjectId::new(ObjectType::ObjectTrendlog, 1);
-    let record_count = get_record_count(&mut bacnet, &mut buf, object_id.clone()).await?;
+    let record_count = get_record_count(&mut bacnet, &mut buf, object_id).await?;
     println!("Record count {record_count}");
 
     // fetch records in batches and print
     const MAX_LOG_COUNT_PER_REQ: usize = 55;
     for row in (1..=record_count).step_by(MAX_LOG_COUNT_PER_REQ) {
-        get_items_for_range(
-            &mut bacnet,
-            &mut buf,
-            object_id.clone(),
-            row..MAX_LOG_COUNT_PER_REQ,
-        )
-        .await?;
+        get_items_for_range(&mut bacnet, &mut buf, object_id, row..MAX_LOG_COUNT_PER_REQ).await?;
     }
 
     Ok(())
@@ -93,7 +89,10 @@ async fn get_items_for_range(
     let result = bacnet.read_range(buf, request).await?;
 
     for item in &result.item_data {
-        let item = item?;
+        let item = match item? {
+            ReadRangeItem::Log(x) => x,
+            _ => continue, // not a trend log
+        };
         let value = match item.value {
             ReadRangeValue::Real(x) => x,
             _ => 0.0,
@@ -129,6 +128,10 @@ async fn get_items_for_range(
     let result = bacnet.read_range(buf, request).await?;
 
     for item in result.item_data.items {
+        let item = match item {
+            ReadRangeItem::Log(x) => x,
+            _ => continue, // not a trend log
+        };
         let value = match item.value {
             ReadRangeValue::Real(x) => x,
             _ => 0.0,
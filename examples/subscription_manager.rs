@@ -0,0 +1,68 @@
+// cargo run --example subscription_manager -- --addr "192.168.1.249:47808" --device-id 79079
+
+use std::time::{Duration, Instant};
+
+use clap::Parser;
+use common::MySocket;
+use embedded_bacnet::{
+    common::object_id::{ObjectId, ObjectType},
+    simple::{
+        subscription_manager::{SubscriptionKey, SubscriptionManager},
+        BacnetError,
+    },
+};
+
+mod common;
+
+/// A Bacnet Client example to keep a change of value subscription to analog input #1 alive
+/// Falls back to polling if the device does not support change of value subscriptions
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    /// IP address with port e.g. "192.168.1.249:47808"
+    #[arg(short, long)]
+    addr: String,
+
+    /// Device instance number of the device at the address above
+    #[arg(short, long)]
+    device_id: u32,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), BacnetError<MySocket>> {
+    // setup
+    let args = Args::parse();
+    let bacnet = common::get_bacnet_socket(&args.addr).await?;
+    let mut buf = vec![0; 1500];
+    let start = Instant::now();
+
+    // subscribe
+    let device_id = ObjectId::new(ObjectType::ObjectDevice, args.device_id);
+    let mut manager = SubscriptionManager::new(device_id, 10);
+    let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+    manager.subscribe(SubscriptionKey::new(object_id, 1), false, 60);
+
+    loop {
+        // renew and poll
+        let now = start.elapsed().as_secs();
+        let updates = match manager.service(&bacnet, &mut buf, now).await {
+            Ok(updates) => updates,
+            Err(e) => {
+                // whatever was not serviced is tried again next time round
+                println!("network error: {:?}", e.error);
+                e.updates
+            }
+        };
+        for update in updates {
+            println!("{:?}", update);
+        }
+
+        // wait for notifications
+        let receive = manager.receive(&bacnet, &mut buf);
+        if let Ok(update) = tokio::time::timeout(Duration::from_secs(1), receive).await {
+            if let Some(update) = update? {
+                println!("{:?}", update);
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+// A Bacnet Client example that downloads the records a trend log collected since the last time it was run
+// The sequence number of the last record downloaded is saved to a file so that the next run can carry on from there
+// cargo run --example trend_log_download -- --addr "192.168.1.249:47808" --instance 1
+
+use clap::Parser;
+use common::MySocket;
+use embedded_bacnet::{
+    application_protocol::services::read_range::ReadRangeItem,
+    common::object_id::{ObjectId, ObjectType},
+    simple::{trend_log::TrendLogDownload, BacnetError},
+};
+
+mod common;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    /// IP address with port e.g. "192.168.1.249:47808"
+    #[arg(short, long)]
+    addr: String,
+
+    /// Trend log object instance
+    #[arg(short, long, default_value_t = 1)]
+    instance: u32,
+
+    /// File used to remember the last sequence number downloaded
+    #[arg(short, long, default_value = "trend_log_sequence.txt")]
+    state_file: String,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), BacnetError<MySocket>> {
+    // setup
+    let args = Args::parse();
+    let bacnet = common::get_bacnet_socket(&args.addr).await?;
+    let mut buf = vec![0; 1500];
+
+    let object_id = ObjectId::new(ObjectType::ObjectTrendlog, args.instance);
+    let last_sequence_number = std::fs::read_to_string(&args.state_file)
+        .ok()
+        .and_then(|x| x.trim().parse().ok());
+    let mut download = match last_sequence_number {
+        Some(last) => TrendLogDownload::resume(object_id, last),
+        None => TrendLogDownload::new(object_id),
+    };
+
+    let records = download.download(&bacnet, &mut buf).await?;
+    for record in records.iter() {
+        match &record.item {
+            ReadRangeItem::Log(x) => println!(
+                "{} {:?} {:?} {:?}",
+                record.sequence_number, x.date, x.time, x.value
+            ),
+            item => println!("{} {:?}", record.sequence_number, item),
+        }
+    }
+    println!(
+        "Downloaded {} records ({} missed)",
+        records.len(),
+        download.missed_records
+    );
+
+    if let Some(last) = download.last_sequence_number {
+        std::fs::write(&args.state_file, last.to_string()).unwrap();
+    }
+
+    Ok(())
+}
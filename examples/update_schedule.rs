@@ -2,7 +2,7 @@
 
 #![allow(unused_imports)]
 
-use clap::{command, Parser};
+use clap::Parser;
 use common::MySocket;
 use embedded_bacnet::{
     application_protocol::{
@@ -7,7 +7,7 @@ mod common;
 fn main() {}
 
 use crate::common::{get_bacnet_socket, MySocket};
-use clap::{command, Parser};
+use clap::Parser;
 use embedded_bacnet::{
     application_protocol::{
         primitives::data_value::ApplicationDataValue,
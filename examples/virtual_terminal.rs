@@ -0,0 +1,95 @@
+// cargo run --example virtual_terminal -- --addr "192.168.1.249:47808"
+
+use std::{
+    io::{self, BufRead, Write},
+    sync::mpsc::{self, TryRecvError},
+    thread,
+    time::Duration,
+};
+
+use clap::Parser;
+use common::MySocket;
+use embedded_bacnet::{
+    common::spec::VtClass,
+    simple::{
+        vt_session::{VtOutput, VtSession},
+        BacnetError,
+    },
+};
+
+mod common;
+
+/// A Bacnet Client example that opens an interactive virtual terminal session on a controller
+/// Lines typed on stdin are sent to the controller and its output is printed to stdout. Press Ctrl-D to close the session.
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    /// IP address with port e.g. "192.168.1.249:47808"
+    #[arg(short, long)]
+    addr: String,
+
+    /// Our session id (the controller addresses its output to this id)
+    #[arg(short, long, default_value_t = 1)]
+    session_id: u8,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), BacnetError<MySocket>> {
+    // setup
+    let args = Args::parse();
+    let bacnet = common::get_bacnet_socket(&args.addr).await?;
+    let mut buf = vec![0; 1500];
+
+    let session =
+        VtSession::open(&bacnet, &mut buf, VtClass::DefaultTerminal, args.session_id).await?;
+    println!(
+        "Session opened (remote session id {})",
+        session.remote_session_id
+    );
+
+    // read stdin on a separate thread so that we can print output while the user is typing
+    let (sender, receiver) = mpsc::channel::<String>();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines().map_while(Result::ok) {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    loop {
+        // keystrokes
+        match receiver.try_recv() {
+            Ok(line) => {
+                let data = format!("{line}\r");
+                let sent = session.send(&bacnet, &mut buf, data.as_bytes()).await?;
+                if sent < data.len() {
+                    println!("[controller busy, {} bytes dropped]", data.len() - sent);
+                }
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => break,
+        }
+
+        // output
+        let receive = session.receive(&bacnet, &mut buf);
+        if let Ok(output) = tokio::time::timeout(Duration::from_millis(100), receive).await {
+            match output? {
+                Some(VtOutput::Data(data)) => {
+                    let mut stdout = io::stdout();
+                    stdout.write_all(&data).unwrap();
+                    stdout.flush().unwrap();
+                }
+                Some(VtOutput::Closed) => {
+                    println!("Session closed by the controller");
+                    return Ok(());
+                }
+                None => {}
+            }
+        }
+    }
+
+    session.close(&bacnet, &mut buf).await?;
+    println!("Session closed");
+    Ok(())
+}
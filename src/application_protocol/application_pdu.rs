@@ -1,6 +1,8 @@
 use crate::{
     application_protocol::{
-        confirmed::{ComplexAck, ConfirmedBacnetError, ConfirmedRequest, SegmentAck, SimpleAck},
+        confirmed::{
+            ComplexAck, ConfirmedBacnetError, ConfirmedRequest, Reject, SegmentAck, SimpleAck,
+        },
         segment::Segment,
         unconfirmed::UnconfirmedRequest,
     },
@@ -19,6 +21,7 @@ pub enum ApplicationPdu<'a> {
     ComplexAck(ComplexAck<'a>),
     SimpleAck(SimpleAck),
     Error(ConfirmedBacnetError),
+    Reject(Reject),
     Segment(Segment<'a>),
     SegmentAck(SegmentAck),
     // add more here (see ApduType)
@@ -131,7 +134,8 @@ impl<'a> ApplicationPdu<'a> {
             Self::SimpleAck(ack) => ack.encode(writer),
             Self::SegmentAck(ack) => ack.encode(writer),
             Self::Segment(segment) => segment.encode(writer),
-            Self::Error(_) => todo!(),
+            Self::Error(error) => error.encode(writer),
+            Self::Reject(reject) => reject.encode(writer),
         };
     }
 
@@ -175,6 +179,10 @@ impl<'a> ApplicationPdu<'a> {
                 let apdu = ConfirmedBacnetError::decode(reader, buf)?;
                 Ok(Self::Error(apdu))
             }
+            ApduType::Reject => {
+                let apdu = Reject::decode(reader, buf)?;
+                Ok(Self::Reject(apdu))
+            }
             apdu_type => Err(Error::ApduTypeNotSupported(apdu_type)),
         }
     }
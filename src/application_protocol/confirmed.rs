@@ -2,18 +2,37 @@ use crate::{
     application_protocol::{
         application_pdu::{ApduType, ApplicationPdu, MaxAdpu, MaxSegments, PduFlags},
         services::{
-            change_of_value::SubscribeCov,
+            acknowledge_alarm::AcknowledgeAlarm,
+            atomic_read_file::{AtomicReadFile, AtomicReadFileAck},
+            atomic_write_file::{AtomicWriteFile, AtomicWriteFileAck},
+            audit_log_query::{AuditLogQuery, AuditLogQueryAck},
+            audit_notification::AuditNotificationRequest,
+            change_of_value::{CovNotification, SubscribeCov},
+            change_of_value_multiple::{CovNotificationMultiple, SubscribeCovPropertyMultiple},
+            create_object::{CreateObject, CreateObjectAck},
+            delete_object::DeleteObject,
+            device_communication_control::DeviceCommunicationControl,
+            event_notification::EventNotification,
+            get_alarm_summary::{GetAlarmSummary, GetAlarmSummaryAck},
+            get_enrollment_summary::{GetEnrollmentSummary, GetEnrollmentSummaryAck},
+            get_event_information::{GetEventInformation, GetEventInformationAck},
+            life_safety_operation::LifeSafetyOperationRequest,
+            list_element::{AddListElement, RemoveListElement},
+            private_transfer::{PrivateTransfer, PrivateTransferAck},
             read_property::{ReadProperty, ReadPropertyAck},
             read_property_multiple::{ReadPropertyMultiple, ReadPropertyMultipleAck},
             read_range::{ReadRange, ReadRangeAck},
+            reinitialize_device::ReinitializeDevice,
+            text_message::TextMessage,
+            virtual_terminal::{VtClose, VtData, VtDataAck, VtOpen, VtOpenAck},
             write_property::WriteProperty,
         },
     },
     common::{
         error::{Error, Unimplemented},
-        helper::decode_unsigned,
+        helper::{decode_unsigned, encode_enumerated},
         io::{Reader, Writer},
-        spec::{ErrorClass, ErrorCode},
+        spec::{ErrorClass, ErrorCode, RejectReason},
         tag::{ApplicationTagNumber, Tag, TagNumber},
     },
     network_protocol::{data_link::DataLink, network_pdu::NetworkMessage},
@@ -75,6 +94,102 @@ impl<'a> ConfirmedRequest<'a> {
                 writer.push(ConfirmedServiceChoice::ReadRange as u8);
                 service.encode(writer)
             }
+            ConfirmedRequestService::CovNotification(service) => {
+                writer.push(ConfirmedServiceChoice::CovNotification as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::SubscribeCovPropertyMultiple(service) => {
+                writer.push(ConfirmedServiceChoice::SubscribeCovPropertyMultiple as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::CovNotificationMultiple(service) => {
+                writer.push(ConfirmedServiceChoice::CovNotificationMultiple as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::EventNotification(service) => {
+                writer.push(ConfirmedServiceChoice::EventNotification as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::AcknowledgeAlarm(service) => {
+                writer.push(ConfirmedServiceChoice::AcknowledgeAlarm as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::GetEventInformation(service) => {
+                writer.push(ConfirmedServiceChoice::GetEventInformation as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::GetAlarmSummary(service) => {
+                writer.push(ConfirmedServiceChoice::GetAlarmSummary as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::GetEnrollmentSummary(service) => {
+                writer.push(ConfirmedServiceChoice::GetEnrollmentSummary as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::AtomicReadFile(service) => {
+                writer.push(ConfirmedServiceChoice::AtomicReadFile as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::AtomicWriteFile(service) => {
+                writer.push(ConfirmedServiceChoice::AtomicWriteFile as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::CreateObject(service) => {
+                writer.push(ConfirmedServiceChoice::CreateObject as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::DeleteObject(service) => {
+                writer.push(ConfirmedServiceChoice::DeleteObject as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::AddListElement(service) => {
+                writer.push(ConfirmedServiceChoice::AddListElement as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::RemoveListElement(service) => {
+                writer.push(ConfirmedServiceChoice::RemoveListElement as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::DeviceCommunicationControl(service) => {
+                writer.push(ConfirmedServiceChoice::DeviceCommunicationControl as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::ReinitializeDevice(service) => {
+                writer.push(ConfirmedServiceChoice::ReinitializeDevice as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::PrivateTransfer(service) => {
+                writer.push(ConfirmedServiceChoice::PrivateTransfer as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::TextMessage(service) => {
+                writer.push(ConfirmedServiceChoice::TextMessage as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::LifeSafetyOperation(service) => {
+                writer.push(ConfirmedServiceChoice::LifeSafetyOperation as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::AuditNotification(service) => {
+                writer.push(ConfirmedServiceChoice::AuditNotification as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::AuditLogQuery(service) => {
+                writer.push(ConfirmedServiceChoice::AuditLogQuery as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::VtOpen(service) => {
+                writer.push(ConfirmedServiceChoice::VtOpen as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::VtData(service) => {
+                writer.push(ConfirmedServiceChoice::VtData as u8);
+                service.encode(writer)
+            }
+            ConfirmedRequestService::VtClose(service) => {
+                writer.push(ConfirmedServiceChoice::VtClose as u8);
+                service.encode(writer)
+            }
         };
     }
 
@@ -102,7 +217,7 @@ impl<'a> ConfirmedRequest<'a> {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
@@ -269,6 +384,15 @@ pub struct ConfirmedBacnetError {
 }
 
 impl ConfirmedBacnetError {
+    pub fn encode(&self, writer: &mut Writer) {
+        let control = (ApduType::Error as u8) << 4;
+        writer.push(control);
+        writer.push(self.invoke_id);
+        writer.push(self.service_choice.clone() as u8);
+        encode_enumerated(writer, u32::from(&self.error_class), None);
+        encode_enumerated(writer, u32::from(&self.error_code), None);
+    }
+
     pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
         let invoke_id = reader.read_byte(buf)?;
         let service_choice: ConfirmedServiceChoice =
@@ -308,6 +432,35 @@ impl ConfirmedBacnetError {
     }
 }
 
+// sent instead of an ack when a confirmed request is malformed or its parameters cannot be used
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Reject {
+    pub invoke_id: u8,
+    pub reason: RejectReason,
+}
+
+impl Reject {
+    pub fn new(invoke_id: u8, reason: RejectReason) -> Self {
+        Self { invoke_id, reason }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        let control = (ApduType::Reject as u8) << 4;
+        writer.push(control);
+        writer.push(self.invoke_id);
+        writer.push(u8::from(&self.reason));
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let invoke_id = reader.read_byte(buf)?;
+        let reason = RejectReason::try_from(reader.read_byte(buf)?)
+            .map_err(|e| Error::InvalidVariant(("Reject decode RejectReason", e as u32)))?;
+
+        Ok(Self { invoke_id, reason })
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ComplexAck<'a> {
@@ -341,6 +494,16 @@ impl<'a> ComplexAck<'a> {
             ComplexAckService::ReadProperty(service) => service.encode(writer),
             ComplexAckService::ReadPropertyMultiple(service) => service.encode(writer),
             ComplexAckService::ReadRange(service) => service.encode(writer),
+            ComplexAckService::GetEventInformation(service) => service.encode(writer),
+            ComplexAckService::GetAlarmSummary(service) => service.encode(writer),
+            ComplexAckService::GetEnrollmentSummary(service) => service.encode(writer),
+            ComplexAckService::AtomicReadFile(service) => service.encode(writer),
+            ComplexAckService::AtomicWriteFile(service) => service.encode(writer),
+            ComplexAckService::CreateObject(service) => service.encode(writer),
+            ComplexAckService::PrivateTransfer(service) => service.encode(writer),
+            ComplexAckService::AuditLogQuery(service) => service.encode(writer),
+            ComplexAckService::VtOpen(service) => service.encode(writer),
+            ComplexAckService::VtData(service) => service.encode(writer),
         }
     }
 
@@ -362,6 +525,16 @@ pub enum ComplexAckService<'a> {
     ReadProperty(ReadPropertyAck<'a>),
     ReadPropertyMultiple(ReadPropertyMultipleAck<'a>),
     ReadRange(ReadRangeAck<'a>),
+    GetEventInformation(GetEventInformationAck<'a>),
+    GetAlarmSummary(GetAlarmSummaryAck<'a>),
+    GetEnrollmentSummary(GetEnrollmentSummaryAck<'a>),
+    AtomicReadFile(AtomicReadFileAck<'a>),
+    AtomicWriteFile(AtomicWriteFileAck),
+    CreateObject(CreateObjectAck),
+    PrivateTransfer(PrivateTransferAck<'a>),
+    AuditLogQuery(AuditLogQueryAck<'a>),
+    VtOpen(VtOpenAck),
+    VtData(VtDataAck),
     // add more here
 }
 
@@ -385,6 +558,46 @@ impl<'a> ComplexAckService<'a> {
                 let service = ReadRangeAck::decode(reader, buf)?;
                 Ok(ComplexAckService::ReadRange(service))
             }
+            ConfirmedServiceChoice::GetEventInformation => {
+                let service = GetEventInformationAck::decode(reader, buf)?;
+                Ok(ComplexAckService::GetEventInformation(service))
+            }
+            ConfirmedServiceChoice::GetAlarmSummary => {
+                let service = GetAlarmSummaryAck::decode(reader, buf)?;
+                Ok(ComplexAckService::GetAlarmSummary(service))
+            }
+            ConfirmedServiceChoice::GetEnrollmentSummary => {
+                let service = GetEnrollmentSummaryAck::decode(reader, buf)?;
+                Ok(ComplexAckService::GetEnrollmentSummary(service))
+            }
+            ConfirmedServiceChoice::AtomicReadFile => {
+                let service = AtomicReadFileAck::decode(reader, buf)?;
+                Ok(ComplexAckService::AtomicReadFile(service))
+            }
+            ConfirmedServiceChoice::AtomicWriteFile => {
+                let service = AtomicWriteFileAck::decode(reader, buf)?;
+                Ok(ComplexAckService::AtomicWriteFile(service))
+            }
+            ConfirmedServiceChoice::CreateObject => {
+                let service = CreateObjectAck::decode(reader, buf)?;
+                Ok(ComplexAckService::CreateObject(service))
+            }
+            ConfirmedServiceChoice::PrivateTransfer => {
+                let service = PrivateTransferAck::decode(reader, buf)?;
+                Ok(ComplexAckService::PrivateTransfer(service))
+            }
+            ConfirmedServiceChoice::AuditLogQuery => {
+                let service = AuditLogQueryAck::decode(reader, buf)?;
+                Ok(ComplexAckService::AuditLogQuery(service))
+            }
+            ConfirmedServiceChoice::VtOpen => {
+                let service = VtOpenAck::decode(reader, buf)?;
+                Ok(ComplexAckService::VtOpen(service))
+            }
+            ConfirmedServiceChoice::VtData => {
+                let service = VtDataAck::decode(reader, buf)?;
+                Ok(ComplexAckService::VtData(service))
+            }
             s => Err(Error::Unimplemented(Unimplemented::ConfirmedServiceChoice(
                 s,
             ))),
@@ -400,6 +613,30 @@ pub enum ConfirmedRequestService<'a> {
     SubscribeCov(SubscribeCov),
     WriteProperty(WriteProperty<'a>),
     ReadRange(ReadRange),
+    CovNotification(CovNotification<'a>),
+    SubscribeCovPropertyMultiple(SubscribeCovPropertyMultiple<'a>),
+    CovNotificationMultiple(CovNotificationMultiple<'a>),
+    EventNotification(EventNotification<'a>),
+    AcknowledgeAlarm(AcknowledgeAlarm<'a>),
+    GetEventInformation(GetEventInformation),
+    GetAlarmSummary(GetAlarmSummary),
+    GetEnrollmentSummary(GetEnrollmentSummary<'a>),
+    AtomicReadFile(AtomicReadFile),
+    AtomicWriteFile(AtomicWriteFile<'a>),
+    CreateObject(CreateObject<'a>),
+    DeleteObject(DeleteObject),
+    AddListElement(AddListElement<'a>),
+    RemoveListElement(RemoveListElement<'a>),
+    DeviceCommunicationControl(DeviceCommunicationControl<'a>),
+    ReinitializeDevice(ReinitializeDevice<'a>),
+    PrivateTransfer(PrivateTransfer<'a>),
+    TextMessage(TextMessage<'a>),
+    LifeSafetyOperation(LifeSafetyOperationRequest<'a>),
+    AuditNotification(AuditNotificationRequest<'a>),
+    AuditLogQuery(AuditLogQuery<'a>),
+    VtOpen(VtOpen),
+    VtData(VtData<'a>),
+    VtClose(VtClose<'a>),
     // add more here (see ConfirmedServiceChoice enum)
 }
 
@@ -427,6 +664,104 @@ impl<'a> ConfirmedRequestService<'a> {
                 let service = WriteProperty::decode(reader, buf)?;
                 Ok(ConfirmedRequestService::WriteProperty(service))
             }
+            ConfirmedServiceChoice::CovNotification => {
+                let service = CovNotification::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::CovNotification(service))
+            }
+            ConfirmedServiceChoice::SubscribeCovPropertyMultiple => {
+                let service = SubscribeCovPropertyMultiple::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::SubscribeCovPropertyMultiple(
+                    service,
+                ))
+            }
+            ConfirmedServiceChoice::CovNotificationMultiple => {
+                let service = CovNotificationMultiple::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::CovNotificationMultiple(service))
+            }
+            ConfirmedServiceChoice::EventNotification => {
+                let service = EventNotification::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::EventNotification(service))
+            }
+            ConfirmedServiceChoice::AcknowledgeAlarm => {
+                let service = AcknowledgeAlarm::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::AcknowledgeAlarm(service))
+            }
+            ConfirmedServiceChoice::GetEventInformation => {
+                let service = GetEventInformation::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::GetEventInformation(service))
+            }
+            ConfirmedServiceChoice::GetAlarmSummary => {
+                let service = GetAlarmSummary::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::GetAlarmSummary(service))
+            }
+            ConfirmedServiceChoice::GetEnrollmentSummary => {
+                let service = GetEnrollmentSummary::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::GetEnrollmentSummary(service))
+            }
+            ConfirmedServiceChoice::AtomicReadFile => {
+                let service = AtomicReadFile::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::AtomicReadFile(service))
+            }
+            ConfirmedServiceChoice::AtomicWriteFile => {
+                let service = AtomicWriteFile::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::AtomicWriteFile(service))
+            }
+            ConfirmedServiceChoice::CreateObject => {
+                let service = CreateObject::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::CreateObject(service))
+            }
+            ConfirmedServiceChoice::DeleteObject => {
+                let service = DeleteObject::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::DeleteObject(service))
+            }
+            ConfirmedServiceChoice::AddListElement => {
+                let service = AddListElement::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::AddListElement(service))
+            }
+            ConfirmedServiceChoice::RemoveListElement => {
+                let service = RemoveListElement::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::RemoveListElement(service))
+            }
+            ConfirmedServiceChoice::DeviceCommunicationControl => {
+                let service = DeviceCommunicationControl::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::DeviceCommunicationControl(service))
+            }
+            ConfirmedServiceChoice::ReinitializeDevice => {
+                let service = ReinitializeDevice::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::ReinitializeDevice(service))
+            }
+            ConfirmedServiceChoice::PrivateTransfer => {
+                let service = PrivateTransfer::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::PrivateTransfer(service))
+            }
+            ConfirmedServiceChoice::TextMessage => {
+                let service = TextMessage::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::TextMessage(service))
+            }
+            ConfirmedServiceChoice::LifeSafetyOperation => {
+                let service = LifeSafetyOperationRequest::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::LifeSafetyOperation(service))
+            }
+            ConfirmedServiceChoice::AuditNotification => {
+                let service = AuditNotificationRequest::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::AuditNotification(service))
+            }
+            ConfirmedServiceChoice::AuditLogQuery => {
+                let service = AuditLogQuery::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::AuditLogQuery(service))
+            }
+            ConfirmedServiceChoice::VtOpen => {
+                let service = VtOpen::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::VtOpen(service))
+            }
+            ConfirmedServiceChoice::VtData => {
+                let service = VtData::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::VtData(service))
+            }
+            ConfirmedServiceChoice::VtClose => {
+                let service = VtClose::decode(reader, buf)?;
+                Ok(ConfirmedRequestService::VtClose(service))
+            }
             s => Err(Error::Unimplemented(Unimplemented::ConfirmedServiceChoice(
                 s,
             ))),
@@ -5,13 +5,16 @@ use crate::common::{
     error::Error,
     helper::{
         decode_signed, decode_unsigned, encode_application_enumerated, encode_application_signed,
-        encode_application_unsigned,
+        encode_application_unsigned, encode_closing_tag, encode_context_unsigned,
+        encode_opening_tag,
     },
     io::{Reader, Writer},
     object_id::{ObjectId, ObjectType},
     property_id::PropertyId,
     spec::{
-        Binary, EngineeringUnits, EventState, LogBufferResult, LoggingType, NotifyType, Status,
+        BackupState, Binary, EngineeringUnits, EventState, EventTransitionBits, LifeSafetyMode,
+        LifeSafetyOperation, LifeSafetyState, LogBufferResult, LoggingType, NotifyType,
+        SilencedState, Status,
     },
     tag::{ApplicationTagNumber, Tag, TagNumber},
 };
@@ -53,6 +56,11 @@ pub enum Enumerated {
     EventState(EventState),
     NotifyType(NotifyType),
     LoggingType(LoggingType),
+    BackupState(BackupState),
+    LifeSafetyState(LifeSafetyState),
+    LifeSafetyMode(LifeSafetyMode),
+    LifeSafetyOperation(LifeSafetyOperation),
+    SilencedState(SilencedState),
     Unknown(u32),
 }
 
@@ -62,9 +70,14 @@ impl Enumerated {
             Self::Units(x) => x.clone() as u32,
             Self::Binary(x) => x.clone() as u32,
             Self::ObjectType(x) => *x as u32,
-            Self::EventState(x) => x.clone() as u32,
+            Self::EventState(x) => x.into(),
             Self::NotifyType(x) => x.clone() as u32,
             Self::LoggingType(x) => x.clone() as u32,
+            Self::BackupState(x) => *x as u32,
+            Self::LifeSafetyState(x) => *x as u32,
+            Self::LifeSafetyMode(x) => *x as u32,
+            Self::LifeSafetyOperation(x) => *x as u32,
+            Self::SilencedState(x) => *x as u32,
             Self::Unknown(x) => *x,
         };
         encode_application_enumerated(writer, value);
@@ -156,6 +169,114 @@ impl Time {
     }
 }
 
+// BACnetDateTime: an application tagged date followed by an application tagged time
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DateTime {
+    pub date: Date,
+    pub time: Time,
+}
+
+impl DateTime {
+    pub fn new(date: Date, time: Time) -> Self {
+        Self { date, time }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        Tag::new(
+            TagNumber::Application(ApplicationTagNumber::Date),
+            Date::LEN,
+        )
+        .encode(writer);
+        self.date.encode(writer);
+        Tag::new(
+            TagNumber::Application(ApplicationTagNumber::Time),
+            Time::LEN,
+        )
+        .encode(writer);
+        self.time.encode(writer);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Date),
+            "DateTime decode date",
+        )?;
+        tag.expect_value("DateTime decode date", Date::LEN)?;
+        let date = Date::decode(reader, buf)?;
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Time),
+            "DateTime decode time",
+        )?;
+        tag.expect_value("DateTime decode time", Time::LEN)?;
+        let time = Time::decode(reader, buf)?;
+        Ok(Self { date, time })
+    }
+}
+
+// BACnetTimeStamp: a choice between a time, a sequence number or a date and time
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeStamp {
+    Time(Time),
+    SequenceNumber(u32),
+    DateTime(DateTime),
+}
+
+impl TimeStamp {
+    const TAG_TIME: u8 = 0;
+    const TAG_SEQUENCE_NUMBER: u8 = 1;
+    const TAG_DATE_TIME: u8 = 2;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Time(x) => {
+                Tag::new(TagNumber::ContextSpecific(Self::TAG_TIME), Time::LEN).encode(writer);
+                x.encode(writer);
+            }
+            Self::SequenceNumber(x) => {
+                encode_context_unsigned(writer, Self::TAG_SEQUENCE_NUMBER, *x);
+            }
+            Self::DateTime(x) => {
+                encode_opening_tag(writer, Self::TAG_DATE_TIME);
+                x.encode(writer);
+                encode_closing_tag(writer, Self::TAG_DATE_TIME);
+            }
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode(reader, buf)?;
+        match tag.number {
+            TagNumber::ContextSpecific(Self::TAG_TIME) => {
+                tag.expect_value("TimeStamp decode time", Time::LEN)?;
+                Ok(Self::Time(Time::decode(reader, buf)?))
+            }
+            TagNumber::ContextSpecific(Self::TAG_SEQUENCE_NUMBER) => {
+                let sequence_number = decode_unsigned(tag.value, reader, buf)? as u32;
+                Ok(Self::SequenceNumber(sequence_number))
+            }
+            TagNumber::ContextSpecificOpening(Self::TAG_DATE_TIME) => {
+                let date_time = DateTime::decode(reader, buf)?;
+                Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::ContextSpecificClosing(Self::TAG_DATE_TIME),
+                    "TimeStamp decode date_time",
+                )?;
+                Ok(Self::DateTime(date_time))
+            }
+            x => Err(Error::TagNotSupported(("TimeStamp decode", x))),
+        }
+    }
+}
+
 #[cfg(not(feature = "alloc"))]
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -208,6 +329,7 @@ impl<'a> Display for ApplicationDataValue<'a> {
 pub enum BitString<'a> {
     Status(Status),
     LogBufferResult(LogBufferResult),
+    EventTransitionBits(EventTransitionBits),
     Custom(CustomBitStream<'a>),
 }
 
@@ -251,6 +373,16 @@ impl<'a> CustomBitStream<'a> {
             _phantom: &PHANTOM,
         }
     }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn as_slice(&self) -> &'a [u8] {
+        self.bits
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.bits
+    }
 }
 
 impl<'a> BitString<'a> {
@@ -263,7 +395,12 @@ impl<'a> BitString<'a> {
             }
             Self::LogBufferResult(x) => {
                 Tag::new(TagNumber::Application(ApplicationTagNumber::BitString), 2).encode(writer);
-                writer.push(0); // no unused bits
+                writer.push(5); // only 3 bits are used
+                writer.push(x.inner);
+            }
+            Self::EventTransitionBits(x) => {
+                Tag::new(TagNumber::Application(ApplicationTagNumber::BitString), 2).encode(writer);
+                writer.push(5); // only 3 bits are used
                 writer.push(x.inner);
             }
             Self::Custom(x) => {
@@ -272,7 +409,7 @@ impl<'a> BitString<'a> {
                     x.bits.len() as u32 + 1,
                 )
                 .encode(writer);
-                writer.push(0); // no unused bits
+                writer.push(x.unused_bits);
                 writer.extend_from_slice(&x.bits);
             }
         }
@@ -287,13 +424,18 @@ impl<'a> BitString<'a> {
             }
             Self::LogBufferResult(x) => {
                 Tag::new(TagNumber::ContextSpecific(tag_num), 2).encode(writer);
-                writer.push(0); // no unused bits
+                writer.push(5); // only 3 bits are used
+                writer.push(x.inner);
+            }
+            Self::EventTransitionBits(x) => {
+                Tag::new(TagNumber::ContextSpecific(tag_num), 2).encode(writer);
+                writer.push(5); // only 3 bits are used
                 writer.push(x.inner);
             }
             Self::Custom(x) => {
                 Tag::new(TagNumber::ContextSpecific(tag_num), x.bits.len() as u32 + 1)
                     .encode(writer);
-                writer.push(0); // no unused bits
+                writer.push(x.unused_bits);
                 writer.extend_from_slice(&x.bits);
             }
         }
@@ -316,6 +458,10 @@ impl<'a> BitString<'a> {
                 let flags = LogBufferResult::new(reader.read_byte(buf)?);
                 Ok(Self::LogBufferResult(flags))
             }
+            PropertyId::PropAckedTransitions | PropertyId::PropEventEnable => {
+                let flags = EventTransitionBits::new(reader.read_byte(buf)?);
+                Ok(Self::EventTransitionBits(flags))
+            }
             _ => {
                 let len = (len - 1) as usize; // we have already read a byte
                 let bits = reader.read_slice(len, buf)?;
@@ -341,6 +487,16 @@ impl<'a> CharacterString<'a> {
         }
     }
 
+    #[cfg(not(feature = "alloc"))]
+    pub fn as_str(&self) -> &'a str {
+        self.inner
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn as_str(&self) -> &str {
+        &self.inner
+    }
+
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
     pub fn decode(len: u32, reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
         let character_set = reader.read_byte(buf)?;
@@ -372,6 +528,16 @@ impl<'a> OctetString<'a> {
         }
     }
 
+    #[cfg(not(feature = "alloc"))]
+    pub fn as_slice(&self) -> &'a [u8] {
+        self.inner
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.inner
+    }
+
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
     pub fn decode(len: u32, reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
         let slice = reader.read_slice(len as usize, buf)?;
@@ -579,6 +745,9 @@ fn decode_enumerated(
                     .map_err(|x| Error::InvalidVariant(("Binary", x)))?;
                 Ok(Enumerated::Binary(binary))
             }
+            ObjectType::ObjectLifeSafetyPoint | ObjectType::ObjectLifeSafetyZone => {
+                Ok(life_safety_state(value))
+            }
             _ => Ok(Enumerated::Unknown(value)),
         },
         PropertyId::PropObjectType => {
@@ -587,8 +756,7 @@ fn decode_enumerated(
             Ok(Enumerated::ObjectType(object_type))
         }
         PropertyId::PropEventState => {
-            let event_state = EventState::try_from(value)
-                .map_err(|x| Error::InvalidVariant(("EventState", x)))?;
+            let event_state = EventState::from(value);
             Ok(Enumerated::EventState(event_state))
         }
         PropertyId::PropNotifyType => {
@@ -601,7 +769,41 @@ fn decode_enumerated(
                 .map_err(|x| Error::InvalidVariant(("LoggingType", x)))?;
             Ok(Enumerated::LoggingType(logging_type))
         }
+        PropertyId::PropBackupAndRestoreState => {
+            let backup_state = BackupState::try_from(value)
+                .map_err(|x| Error::InvalidVariant(("BackupState", x)))?;
+            Ok(Enumerated::BackupState(backup_state))
+        }
+        PropertyId::PropTrackingValue
+        | PropertyId::PropLifeSafetyAlarmValues
+        | PropertyId::PropAlarmValues
+        | PropertyId::PropFaultValues
+            if is_life_safety(object_id) =>
+        {
+            Ok(life_safety_state(value))
+        }
+        PropertyId::PropMode | PropertyId::PropAcceptedModes if is_life_safety(object_id) => {
+            Ok(LifeSafetyMode::try_from(value)
+                .map_or(Enumerated::Unknown(value), Enumerated::LifeSafetyMode))
+        }
+        PropertyId::PropOperationExpected => Ok(LifeSafetyOperation::try_from(value)
+            .map_or(Enumerated::Unknown(value), Enumerated::LifeSafetyOperation)),
+        PropertyId::PropSilenced => Ok(SilencedState::try_from(value)
+            .map_or(Enumerated::Unknown(value), Enumerated::SilencedState)),
 
         _ => Ok(Enumerated::Unknown(value)),
     }
 }
+
+// the life safety enumerations can be extended with proprietary values which are left undecoded
+fn life_safety_state(value: u32) -> Enumerated {
+    LifeSafetyState::try_from(value).map_or(Enumerated::Unknown(value), Enumerated::LifeSafetyState)
+}
+
+// other object types reuse some of the life safety property ids with different enumerations
+fn is_life_safety(object_id: &ObjectId) -> bool {
+    matches!(
+        object_id.object_type,
+        ObjectType::ObjectLifeSafetyPoint | ObjectType::ObjectLifeSafetyZone
+    )
+}
@@ -1 +1,4 @@
 pub mod data_value;
+pub mod property_reference;
+pub mod recipient;
+pub mod special_event;
@@ -0,0 +1,137 @@
+use crate::common::{
+    error::Error,
+    helper::{
+        decode_context_object_id, decode_context_property_id, decode_unsigned,
+        encode_context_enumerated, encode_context_object_id, encode_context_unsigned,
+        is_next_context_tag,
+    },
+    io::{Reader, Writer},
+    object_id::ObjectId,
+    property_id::PropertyId,
+    tag::Tag,
+};
+
+// BACnetDeviceObjectPropertyReference: a property of an object on this or another device
+// (e.g. an entry in the Log_DeviceObjectProperty of a trend log multiple)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceObjectPropertyReference {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub device_id: Option<ObjectId>, // the local device if not specified
+}
+
+impl DeviceObjectPropertyReference {
+    const TAG_OBJECT_ID: u8 = 0;
+    const TAG_PROPERTY_ID: u8 = 1;
+    const TAG_ARRAY_INDEX: u8 = 2;
+    const TAG_DEVICE_ID: u8 = 3;
+
+    pub fn new(object_id: ObjectId, property_id: PropertyId) -> Self {
+        Self {
+            object_id,
+            property_id,
+            array_index: None,
+            device_id: None,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
+        encode_context_enumerated(writer, Self::TAG_PROPERTY_ID, &self.property_id);
+        if let Some(array_index) = self.array_index {
+            encode_context_unsigned(writer, Self::TAG_ARRAY_INDEX, array_index);
+        }
+        if let Some(device_id) = &self.device_id {
+            encode_context_object_id(writer, Self::TAG_DEVICE_ID, device_id);
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let object_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_OBJECT_ID,
+            "DeviceObjectPropertyReference decode object_id",
+        )?;
+        let property_id = decode_context_property_id(
+            reader,
+            buf,
+            Self::TAG_PROPERTY_ID,
+            "DeviceObjectPropertyReference decode property_id",
+        )?;
+
+        let mut array_index = None;
+        if is_next_context_tag(reader, buf, Self::TAG_ARRAY_INDEX)? {
+            let tag = Tag::decode(reader, buf)?;
+            array_index = Some(decode_unsigned(tag.value, reader, buf)? as u32);
+        }
+
+        let mut device_id = None;
+        if is_next_context_tag(reader, buf, Self::TAG_DEVICE_ID)? {
+            device_id = Some(decode_context_object_id(
+                reader,
+                buf,
+                Self::TAG_DEVICE_ID,
+                "DeviceObjectPropertyReference decode device_id",
+            )?);
+        }
+
+        Ok(Self {
+            object_id,
+            property_id,
+            array_index,
+            device_id,
+        })
+    }
+}
+
+// BACnetPropertyReference: a property (and optionally an array element) of an object known from the context
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PropertyReference {
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+}
+
+impl PropertyReference {
+    const TAG_PROPERTY_ID: u8 = 0;
+    const TAG_ARRAY_INDEX: u8 = 1;
+
+    pub fn new(property_id: PropertyId, array_index: Option<u32>) -> Self {
+        Self {
+            property_id,
+            array_index,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_enumerated(writer, Self::TAG_PROPERTY_ID, &self.property_id);
+        if let Some(array_index) = self.array_index {
+            encode_context_unsigned(writer, Self::TAG_ARRAY_INDEX, array_index);
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let property_id = decode_context_property_id(
+            reader,
+            buf,
+            Self::TAG_PROPERTY_ID,
+            "PropertyReference decode property_id",
+        )?;
+
+        let mut array_index = None;
+        if is_next_context_tag(reader, buf, Self::TAG_ARRAY_INDEX)? {
+            let tag = Tag::decode(reader, buf)?;
+            array_index = Some(decode_unsigned(tag.value, reader, buf)? as u32);
+        }
+
+        Ok(Self {
+            property_id,
+            array_index,
+        })
+    }
+}
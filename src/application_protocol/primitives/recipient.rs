@@ -0,0 +1,312 @@
+use crate::{
+    application_protocol::primitives::data_value::{BitString, OctetString, Time},
+    common::{
+        error::Error,
+        helper::{
+            decode_bit_string, decode_unsigned, encode_application_object_id,
+            encode_application_unsigned, encode_closing_tag, encode_context_object_id,
+            encode_context_unsigned, encode_opening_tag,
+        },
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        spec::EventTransitionBits,
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+};
+
+// BACnetAddress: a network number (0 for the local network) and a mac address (e.g. ip and port for BACnet/IP)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Address<'a> {
+    pub network_number: u16,
+    pub mac_address: OctetString<'a>,
+}
+
+impl<'a> Address<'a> {
+    pub fn new(network_number: u16, mac_address: OctetString<'a>) -> Self {
+        Self {
+            network_number,
+            mac_address,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_application_unsigned(writer, self.network_number);
+        Tag::new(
+            TagNumber::Application(ApplicationTagNumber::OctetString),
+            self.mac_address.inner.len() as u32,
+        )
+        .encode(writer);
+        writer.extend_from_slice(self.mac_address.as_slice());
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::UnsignedInt),
+            "Address decode network_number",
+        )?;
+        let network_number = decode_unsigned(tag.value, reader, buf)? as u16;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::OctetString),
+            "Address decode mac_address",
+        )?;
+        let mac_address = OctetString::decode(tag.value, reader, buf)?;
+
+        Ok(Self {
+            network_number,
+            mac_address,
+        })
+    }
+}
+
+// BACnetRecipient: a device identified by its object id or by its network address
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Recipient<'a> {
+    Device(ObjectId),
+    Address(Address<'a>),
+}
+
+impl<'a> Recipient<'a> {
+    const TAG_DEVICE: u8 = 0;
+    const TAG_ADDRESS: u8 = 1;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Device(x) => encode_context_object_id(writer, Self::TAG_DEVICE, x),
+            Self::Address(x) => {
+                encode_opening_tag(writer, Self::TAG_ADDRESS);
+                x.encode(writer);
+                encode_closing_tag(writer, Self::TAG_ADDRESS);
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let tag = Tag::decode(reader, buf)?;
+        match tag.number {
+            TagNumber::ContextSpecific(Self::TAG_DEVICE) => {
+                let device_id = ObjectId::decode(tag.value, reader, buf)?;
+                Ok(Self::Device(device_id))
+            }
+            TagNumber::ContextSpecificOpening(Self::TAG_ADDRESS) => {
+                let address = Address::decode(reader, buf)?;
+                Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::ContextSpecificClosing(Self::TAG_ADDRESS),
+                    "Recipient decode address",
+                )?;
+                Ok(Self::Address(address))
+            }
+            x => Err(Error::TagNotSupported(("Recipient decode", x))),
+        }
+    }
+}
+
+// BACnetRecipientProcess: a recipient and the process on that recipient
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RecipientProcess<'a> {
+    pub recipient: Recipient<'a>,
+    pub process_id: u32,
+}
+
+impl<'a> RecipientProcess<'a> {
+    const TAG_RECIPIENT: u8 = 0;
+    const TAG_PROCESS_ID: u8 = 1;
+
+    pub fn new(recipient: Recipient<'a>, process_id: u32) -> Self {
+        Self {
+            recipient,
+            process_id,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_opening_tag(writer, Self::TAG_RECIPIENT);
+        self.recipient.encode(writer);
+        encode_closing_tag(writer, Self::TAG_RECIPIENT);
+        encode_context_unsigned(writer, Self::TAG_PROCESS_ID, self.process_id);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecificOpening(Self::TAG_RECIPIENT),
+            "RecipientProcess decode recipient",
+        )?;
+        let recipient = Recipient::decode(reader, buf)?;
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecificClosing(Self::TAG_RECIPIENT),
+            "RecipientProcess decode recipient",
+        )?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_PROCESS_ID),
+            "RecipientProcess decode process_id",
+        )?;
+        let process_id = decode_unsigned(tag.value, reader, buf)? as u32;
+
+        Ok(Self {
+            recipient,
+            process_id,
+        })
+    }
+}
+
+// BACnetAddressBinding: a device and its network address (e.g. an entry in the Device_Address_Binding list)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AddressBinding<'a> {
+    pub device_id: ObjectId,
+    pub address: Address<'a>,
+}
+
+impl<'a> AddressBinding<'a> {
+    pub fn new(device_id: ObjectId, address: Address<'a>) -> Self {
+        Self { device_id, address }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_application_object_id(writer, &self.device_id);
+        self.address.encode(writer);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::ObjectId),
+            "AddressBinding decode device_id",
+        )?;
+        let device_id = ObjectId::decode(tag.value, reader, buf)?;
+        let address = Address::decode(reader, buf)?;
+
+        Ok(Self { device_id, address })
+    }
+}
+
+// BACnetDestination: a recipient of event notifications and when it wants them (an entry in the Recipient_List of a notification class)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Destination<'a> {
+    pub valid_days: u8, // monday is the most significant bit and sunday the least significant of the 7 bits used
+    pub from_time: Time,
+    pub to_time: Time,
+    pub recipient: Recipient<'a>,
+    pub process_id: u32,
+    pub issue_confirmed_notifications: bool,
+    pub transitions: EventTransitionBits,
+}
+
+impl<'a> Destination<'a> {
+    pub fn encode(&self, writer: &mut Writer) {
+        // 7 bits used (1 unused)
+        Tag::new(TagNumber::Application(ApplicationTagNumber::BitString), 2).encode(writer);
+        writer.push(1);
+        writer.push(self.valid_days);
+
+        Tag::new(
+            TagNumber::Application(ApplicationTagNumber::Time),
+            Time::LEN,
+        )
+        .encode(writer);
+        self.from_time.encode(writer);
+        Tag::new(
+            TagNumber::Application(ApplicationTagNumber::Time),
+            Time::LEN,
+        )
+        .encode(writer);
+        self.to_time.encode(writer);
+
+        self.recipient.encode(writer);
+        encode_application_unsigned(writer, self.process_id);
+        Tag::new(
+            TagNumber::Application(ApplicationTagNumber::Boolean),
+            self.issue_confirmed_notifications as u32,
+        )
+        .encode(writer);
+        BitString::EventTransitionBits(self.transitions.clone()).encode_application(writer);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::BitString),
+            "Destination decode valid_days",
+        )?;
+        let [valid_days] = decode_bit_string::<1>(tag.value, reader, buf)?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Time),
+            "Destination decode from_time",
+        )?;
+        tag.expect_value("Destination decode from_time", Time::LEN)?;
+        let from_time = Time::decode(reader, buf)?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Time),
+            "Destination decode to_time",
+        )?;
+        tag.expect_value("Destination decode to_time", Time::LEN)?;
+        let to_time = Time::decode(reader, buf)?;
+
+        let recipient = Recipient::decode(reader, buf)?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::UnsignedInt),
+            "Destination decode process_id",
+        )?;
+        let process_id = decode_unsigned(tag.value, reader, buf)? as u32;
+
+        // the value of an application tagged boolean is in the tag itself
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Boolean),
+            "Destination decode issue_confirmed_notifications",
+        )?;
+        let issue_confirmed_notifications = tag.value > 0;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::BitString),
+            "Destination decode transitions",
+        )?;
+        let [transitions] = decode_bit_string::<1>(tag.value, reader, buf)?;
+
+        Ok(Self {
+            valid_days,
+            from_time,
+            to_time,
+            recipient,
+            process_id,
+            issue_confirmed_notifications,
+            transitions: EventTransitionBits::new(transitions),
+        })
+    }
+}
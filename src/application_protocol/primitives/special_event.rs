@@ -0,0 +1,287 @@
+#[cfg(feature = "alloc")]
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
+
+#[cfg(not(feature = "alloc"))]
+use crate::common::helper::encode_list;
+
+use crate::{
+    application_protocol::primitives::data_value::Date,
+    common::{
+        error::Error,
+        helper::{
+            decode_unsigned, encode_closing_tag, encode_context_object_id, encode_context_unsigned,
+            encode_opening_tag, get_tagged_body_for_tag, ListIter,
+        },
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+        time_value::TimeValue,
+    },
+};
+
+// BACnetCalendarEntry: the days a special event applies to
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CalendarEntry {
+    Date(Date),
+    DateRange(Date, Date),
+    // month (1-14), week of month (1-9) and day of week (1-7) where 255 matches any
+    WeekNDay {
+        month: u8,
+        week_of_month: u8,
+        day_of_week: u8,
+    },
+}
+
+impl CalendarEntry {
+    const TAG_DATE: u8 = 0;
+    const TAG_DATE_RANGE: u8 = 1;
+    const TAG_WEEK_N_DAY: u8 = 2;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Date(date) => {
+                Tag::new(TagNumber::ContextSpecific(Self::TAG_DATE), Date::LEN).encode(writer);
+                date.encode(writer);
+            }
+            Self::DateRange(start, end) => {
+                encode_opening_tag(writer, Self::TAG_DATE_RANGE);
+                for date in [start, end] {
+                    Tag::new(
+                        TagNumber::Application(ApplicationTagNumber::Date),
+                        Date::LEN,
+                    )
+                    .encode(writer);
+                    date.encode(writer);
+                }
+                encode_closing_tag(writer, Self::TAG_DATE_RANGE);
+            }
+            Self::WeekNDay {
+                month,
+                week_of_month,
+                day_of_week,
+            } => {
+                Tag::new(TagNumber::ContextSpecific(Self::TAG_WEEK_N_DAY), 3).encode(writer);
+                writer.extend_from_slice(&[*month, *week_of_month, *day_of_week]);
+            }
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode(reader, buf)?;
+        match tag.number {
+            TagNumber::ContextSpecific(Self::TAG_DATE) => {
+                Ok(Self::Date(Date::decode(reader, buf)?))
+            }
+            TagNumber::ContextSpecificOpening(Self::TAG_DATE_RANGE) => {
+                let start = Self::decode_application_date(reader, buf)?;
+                let end = Self::decode_application_date(reader, buf)?;
+                Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::ContextSpecificClosing(Self::TAG_DATE_RANGE),
+                    "CalendarEntry decode date range",
+                )?;
+                Ok(Self::DateRange(start, end))
+            }
+            TagNumber::ContextSpecific(Self::TAG_WEEK_N_DAY) => {
+                if tag.value != 3 {
+                    return Err(Error::Length((
+                        "CalendarEntry week n day should have length 3",
+                        tag.value,
+                    )));
+                }
+                let [month, week_of_month, day_of_week] = reader.read_bytes(buf)?;
+                Ok(Self::WeekNDay {
+                    month,
+                    week_of_month,
+                    day_of_week,
+                })
+            }
+            x => Err(Error::TagNotSupported(("CalendarEntry decode", x))),
+        }
+    }
+
+    fn decode_application_date(reader: &mut Reader, buf: &[u8]) -> Result<Date, Error> {
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Date),
+            "CalendarEntry decode date range",
+        )?;
+        Date::decode(reader, buf)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SpecialEventPeriod {
+    CalendarEntry(CalendarEntry),
+    CalendarReference(ObjectId), // a Calendar object
+}
+
+impl SpecialEventPeriod {
+    const TAG_CALENDAR_ENTRY: u8 = 0;
+    const TAG_CALENDAR_REFERENCE: u8 = 1;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::CalendarEntry(x) => {
+                encode_opening_tag(writer, Self::TAG_CALENDAR_ENTRY);
+                x.encode(writer);
+                encode_closing_tag(writer, Self::TAG_CALENDAR_ENTRY);
+            }
+            Self::CalendarReference(x) => {
+                encode_context_object_id(writer, Self::TAG_CALENDAR_REFERENCE, x)
+            }
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode(reader, buf)?;
+        match tag.number {
+            TagNumber::ContextSpecificOpening(Self::TAG_CALENDAR_ENTRY) => {
+                let entry = CalendarEntry::decode(reader, buf)?;
+                Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::ContextSpecificClosing(Self::TAG_CALENDAR_ENTRY),
+                    "SpecialEventPeriod decode calendar entry",
+                )?;
+                Ok(Self::CalendarEntry(entry))
+            }
+            TagNumber::ContextSpecific(Self::TAG_CALENDAR_REFERENCE) => {
+                let object_id = ObjectId::decode(tag.value, reader, buf)?;
+                Ok(Self::CalendarReference(object_id))
+            }
+            x => Err(Error::TagNotSupported(("SpecialEventPeriod decode", x))),
+        }
+    }
+}
+
+// BACnetSpecialEvent: a schedule that overrides the weekly schedule on the days of its period
+// (an entry in the Exception_Schedule of a schedule object)
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SpecialEvent<'a> {
+    pub period: SpecialEventPeriod,
+    pub time_values: &'a [TimeValue],
+    pub priority: u8, // 1 (highest) to 16 (lowest)
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SpecialEvent<'a> {
+    pub period: SpecialEventPeriod,
+    pub time_values: Vec<TimeValue>,
+    pub priority: u8, // 1 (highest) to 16 (lowest)
+    _phantom: &'a Phantom,
+}
+
+impl<'a> SpecialEvent<'a> {
+    const TAG_TIME_VALUES: u8 = 2;
+    const TAG_PRIORITY: u8 = 3;
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(period: SpecialEventPeriod, time_values: &'a [TimeValue], priority: u8) -> Self {
+        Self {
+            period,
+            time_values,
+            priority,
+            buf: &[],
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(period: SpecialEventPeriod, time_values: Vec<TimeValue>, priority: u8) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            period,
+            time_values,
+            priority,
+            _phantom: &PHANTOM,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        self.period.encode(writer);
+
+        encode_opening_tag(writer, Self::TAG_TIME_VALUES);
+        #[cfg(not(feature = "alloc"))]
+        encode_list(writer, self.buf, self.time_values, TimeValue::encode);
+        #[cfg(feature = "alloc")]
+        for time_value in self.time_values.iter() {
+            time_value.encode(writer);
+        }
+        encode_closing_tag(writer, Self::TAG_TIME_VALUES);
+
+        encode_context_unsigned(writer, Self::TAG_PRIORITY, self.priority);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let period = SpecialEventPeriod::decode(reader, buf)?;
+
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_TIME_VALUES,
+            "SpecialEvent decode time_values",
+        )?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_PRIORITY),
+            "SpecialEvent decode priority",
+        )?;
+        let priority = decode_unsigned(tag.value, reader, buf)? as u8;
+
+        #[cfg(not(feature = "alloc"))]
+        return Ok(Self {
+            period,
+            time_values: &[],
+            priority,
+            buf: inner_buf,
+        });
+
+        #[cfg(feature = "alloc")]
+        {
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            let mut time_values = Vec::new();
+            while !inner_reader.eof() {
+                time_values.push(TimeValue::decode(&mut inner_reader, inner_buf)?);
+            }
+            Ok(Self::new(period, time_values, priority))
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ SpecialEvent<'a> {
+    type Item = Result<TimeValue, Error>;
+
+    type IntoIter = SpecialEventIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        SpecialEventIter {
+            list: ListIter::new(self.time_values, self.buf),
+        }
+    }
+}
+
+pub struct SpecialEventIter<'a> {
+    list: ListIter<'a, TimeValue>,
+}
+
+impl<'a> Iterator for SpecialEventIter<'a> {
+    type Item = Result<TimeValue, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.next_with(TimeValue::decode)
+    }
+}
@@ -195,7 +195,7 @@ mod tests {
         let input: [u8; 6] = [1, 12, 1, 1, 2, 3];
         let mut reader = Reader::new_with_len(input.len());
         let decoded = Segment::decode(false, ApduType::ComplexAck, &mut reader, &input).unwrap();
-        assert_eq!(decoded.more_follows, false);
+        assert!(!decoded.more_follows);
         assert_eq!(decoded.sequence_number, 12);
         assert_eq!(decoded.window_size, 1);
         assert_eq!(decoded.apdu_type, ApduType::ComplexAck);
@@ -0,0 +1,226 @@
+// acknowledge alarm
+
+use crate::{
+    application_protocol::primitives::data_value::{CharacterString, TimeStamp},
+    common::{
+        error::Error,
+        helper::{
+            decode_context_object_id, decode_unsigned, encode_closing_tag,
+            encode_context_character_string, encode_context_object_id, encode_context_unsigned,
+            encode_opening_tag,
+        },
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        spec::EventState,
+        tag::{Tag, TagNumber},
+    },
+};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AcknowledgeAlarm<'a> {
+    pub process_id: u32,
+    pub event_object_id: ObjectId,
+    pub event_state_acknowledged: EventState,
+    pub timestamp: TimeStamp, // the timestamp of the event notification being acknowledged
+    pub acknowledgment_source: CharacterString<'a>,
+    pub time_of_acknowledgment: TimeStamp,
+}
+
+impl<'a> AcknowledgeAlarm<'a> {
+    const TAG_PROCESS_ID: u8 = 0;
+    const TAG_OBJECT_ID: u8 = 1;
+    const TAG_EVENT_STATE: u8 = 2;
+    const TAG_TIMESTAMP: u8 = 3;
+    const TAG_SOURCE: u8 = 4;
+    const TAG_TIME_OF_ACK: u8 = 5;
+
+    pub fn new(
+        process_id: u32,
+        event_object_id: ObjectId,
+        event_state_acknowledged: EventState,
+        timestamp: TimeStamp,
+        acknowledgment_source: CharacterString<'a>,
+        time_of_acknowledgment: TimeStamp,
+    ) -> Self {
+        Self {
+            process_id,
+            event_object_id,
+            event_state_acknowledged,
+            timestamp,
+            acknowledgment_source,
+            time_of_acknowledgment,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        // acknowledging process_id
+        encode_context_unsigned(writer, Self::TAG_PROCESS_ID, self.process_id);
+
+        // event object_id
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.event_object_id);
+
+        // event state acknowledged
+        encode_context_unsigned(
+            writer,
+            Self::TAG_EVENT_STATE,
+            u32::from(&self.event_state_acknowledged),
+        );
+
+        // event timestamp
+        encode_opening_tag(writer, Self::TAG_TIMESTAMP);
+        self.timestamp.encode(writer);
+        encode_closing_tag(writer, Self::TAG_TIMESTAMP);
+
+        // acknowledgment source
+        encode_context_character_string(
+            writer,
+            Self::TAG_SOURCE,
+            self.acknowledgment_source.as_str(),
+        );
+
+        // time of acknowledgment
+        encode_opening_tag(writer, Self::TAG_TIME_OF_ACK);
+        self.time_of_acknowledgment.encode(writer);
+        encode_closing_tag(writer, Self::TAG_TIME_OF_ACK);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // acknowledging process_id
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_PROCESS_ID),
+            "AcknowledgeAlarm decode process_id",
+        )?;
+        let process_id = decode_unsigned(tag.value, reader, buf)? as u32;
+
+        // event object_id
+        let event_object_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_OBJECT_ID,
+            "AcknowledgeAlarm decode event_object_id",
+        )?;
+
+        // event state acknowledged
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_EVENT_STATE),
+            "AcknowledgeAlarm decode event_state_acknowledged",
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let event_state_acknowledged = EventState::from(value);
+
+        // event timestamp
+        let timestamp = Self::decode_timestamp(reader, buf, Self::TAG_TIMESTAMP)?;
+
+        // acknowledgment source
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_SOURCE),
+            "AcknowledgeAlarm decode acknowledgment_source",
+        )?;
+        let acknowledgment_source = CharacterString::decode(tag.value, reader, buf)?;
+
+        // time of acknowledgment
+        let time_of_acknowledgment = Self::decode_timestamp(reader, buf, Self::TAG_TIME_OF_ACK)?;
+
+        Ok(Self {
+            process_id,
+            event_object_id,
+            event_state_acknowledged,
+            timestamp,
+            acknowledgment_source,
+            time_of_acknowledgment,
+        })
+    }
+
+    fn decode_timestamp(
+        reader: &mut Reader,
+        buf: &[u8],
+        tag_number: u8,
+    ) -> Result<TimeStamp, Error> {
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecificOpening(tag_number),
+            "AcknowledgeAlarm decode timestamp",
+        )?;
+        let timestamp = TimeStamp::decode(reader, buf)?;
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecificClosing(tag_number),
+            "AcknowledgeAlarm decode timestamp",
+        )?;
+        Ok(timestamp)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ConfirmedRequest, ConfirmedRequestService},
+            primitives::data_value::{CharacterString, Time, TimeStamp},
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            spec::EventState,
+        },
+    };
+
+    use super::AcknowledgeAlarm;
+
+    #[test]
+    fn reversable() {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 3);
+        let time = Time {
+            hour: 10,
+            minute: 15,
+            second: 30,
+            hundredths: 0,
+        };
+        let request = AcknowledgeAlarm::new(
+            1,
+            object_id,
+            EventState::HighLimit,
+            TimeStamp::SequenceNumber(42),
+            CharacterString::new("operator"),
+            TimeStamp::Time(time),
+        );
+
+        // encoding
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        let service = ConfirmedRequestService::AcknowledgeAlarm(request);
+        ConfirmedRequest::new(5, service).encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ConfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        let decoded = match decoded.service {
+            ConfirmedRequestService::AcknowledgeAlarm(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        assert_eq!(decoded.process_id, 1);
+        assert_eq!(decoded.event_object_id, object_id);
+        assert!(matches!(
+            decoded.event_state_acknowledged,
+            EventState::HighLimit
+        ));
+        assert!(matches!(decoded.timestamp, TimeStamp::SequenceNumber(42)));
+        assert_eq!(decoded.acknowledgment_source.inner, "operator");
+        assert!(matches!(
+            decoded.time_of_acknowledgment,
+            TimeStamp::Time(Time { minute: 15, .. })
+        ));
+    }
+}
@@ -0,0 +1,538 @@
+// atomic read file (reads a chunk of a File object using either stream or record access)
+
+#[cfg(feature = "alloc")]
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
+
+#[cfg(not(feature = "alloc"))]
+use crate::common::helper::encode_list;
+
+use crate::{
+    application_protocol::{
+        confirmed::{ComplexAck, ComplexAckService, ConfirmedServiceChoice},
+        primitives::data_value::OctetString,
+    },
+    common::{
+        error::Error,
+        helper::{
+            decode_signed, decode_unsigned, encode_application_object_id,
+            encode_application_signed, encode_application_unsigned, encode_closing_tag,
+            encode_opening_tag, get_tagged_body, ListIter,
+        },
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+    network_protocol::data_link::DataLink,
+};
+
+const TAG_STREAM_ACCESS: u8 = 0;
+const TAG_RECORD_ACCESS: u8 = 1;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ReadFileAccess {
+    Stream {
+        start_position: i32,
+        octet_count: u32,
+    },
+    Record {
+        start_record: i32,
+        record_count: u32,
+    },
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AtomicReadFile {
+    pub file_id: ObjectId,
+    pub access: ReadFileAccess,
+}
+
+impl AtomicReadFile {
+    pub fn new(file_id: ObjectId, access: ReadFileAccess) -> Self {
+        Self { file_id, access }
+    }
+
+    pub fn new_stream(file_id: ObjectId, start_position: i32, octet_count: u32) -> Self {
+        Self::new(
+            file_id,
+            ReadFileAccess::Stream {
+                start_position,
+                octet_count,
+            },
+        )
+    }
+
+    pub fn new_record(file_id: ObjectId, start_record: i32, record_count: u32) -> Self {
+        Self::new(
+            file_id,
+            ReadFileAccess::Record {
+                start_record,
+                record_count,
+            },
+        )
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_application_object_id(writer, &self.file_id);
+        match &self.access {
+            ReadFileAccess::Stream {
+                start_position,
+                octet_count,
+            } => {
+                encode_opening_tag(writer, TAG_STREAM_ACCESS);
+                encode_application_signed(writer, *start_position);
+                encode_application_unsigned(writer, *octet_count);
+                encode_closing_tag(writer, TAG_STREAM_ACCESS);
+            }
+            ReadFileAccess::Record {
+                start_record,
+                record_count,
+            } => {
+                encode_opening_tag(writer, TAG_RECORD_ACCESS);
+                encode_application_signed(writer, *start_record);
+                encode_application_unsigned(writer, *record_count);
+                encode_closing_tag(writer, TAG_RECORD_ACCESS);
+            }
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::ObjectId),
+            "AtomicReadFile decode file_id",
+        )?;
+        let file_id = ObjectId::decode(tag.value, reader, buf)?;
+
+        let tag = Tag::decode(reader, buf)?;
+        let (access, tag_number) = match tag.number {
+            TagNumber::ContextSpecificOpening(TAG_STREAM_ACCESS) => {
+                let start_position =
+                    decode_file_start(reader, buf, "AtomicReadFile decode start_position")?;
+                let octet_count =
+                    decode_file_count(reader, buf, "AtomicReadFile decode octet_count")?;
+                let access = ReadFileAccess::Stream {
+                    start_position,
+                    octet_count,
+                };
+                (access, TAG_STREAM_ACCESS)
+            }
+            TagNumber::ContextSpecificOpening(TAG_RECORD_ACCESS) => {
+                let start_record =
+                    decode_file_start(reader, buf, "AtomicReadFile decode start_record")?;
+                let record_count =
+                    decode_file_count(reader, buf, "AtomicReadFile decode record_count")?;
+                let access = ReadFileAccess::Record {
+                    start_record,
+                    record_count,
+                };
+                (access, TAG_RECORD_ACCESS)
+            }
+            x => return Err(Error::TagNotSupported(("AtomicReadFile decode access", x))),
+        };
+
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecificClosing(tag_number),
+            "AtomicReadFile decode access",
+        )?;
+
+        Ok(Self { file_id, access })
+    }
+}
+
+// the file data returned by an AtomicReadFile ack or sent in an AtomicWriteFile request
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FileData<'a> {
+    Stream {
+        start_position: i32,
+        data: OctetString<'a>,
+    },
+    Record {
+        start_record: i32,
+        record_count: u32,
+        records: FileRecords<'a>,
+    },
+}
+
+impl<'a> FileData<'a> {
+    pub fn new_stream(start_position: i32, data: OctetString<'a>) -> Self {
+        Self::Stream {
+            start_position,
+            data,
+        }
+    }
+
+    pub fn new_record(start_record: i32, records: FileRecords<'a>) -> Self {
+        Self::Record {
+            start_record,
+            record_count: records.records.len() as u32,
+            records,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Stream {
+                start_position,
+                data,
+            } => {
+                encode_opening_tag(writer, TAG_STREAM_ACCESS);
+                encode_application_signed(writer, *start_position);
+                encode_octet_string(writer, data);
+                encode_closing_tag(writer, TAG_STREAM_ACCESS);
+            }
+            Self::Record {
+                start_record,
+                record_count,
+                records,
+            } => {
+                encode_opening_tag(writer, TAG_RECORD_ACCESS);
+                encode_application_signed(writer, *start_record);
+                encode_application_unsigned(writer, *record_count);
+                records.encode(writer);
+                encode_closing_tag(writer, TAG_RECORD_ACCESS);
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let (inner_buf, tag_number) = get_tagged_body(reader, buf)?;
+        let mut inner_reader = Reader::new_with_len(inner_buf.len());
+        let reader = &mut inner_reader;
+        let buf = inner_buf;
+
+        match tag_number {
+            TAG_STREAM_ACCESS => {
+                let start_position =
+                    decode_file_start(reader, buf, "FileData decode start_position")?;
+                let tag = Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::Application(ApplicationTagNumber::OctetString),
+                    "FileData decode data",
+                )?;
+                let data = OctetString::decode(tag.value, reader, buf)?;
+                Ok(Self::Stream {
+                    start_position,
+                    data,
+                })
+            }
+            TAG_RECORD_ACCESS => {
+                // the records run until the closing tag
+                let start_record = decode_file_start(reader, buf, "FileData decode start_record")?;
+                let record_count = decode_file_count(reader, buf, "FileData decode record_count")?;
+                let records = FileRecords::decode(reader, buf)?;
+                Ok(Self::Record {
+                    start_record,
+                    record_count,
+                    records,
+                })
+            }
+            x => Err(Error::TagNotSupported((
+                "FileData decode access",
+                TagNumber::ContextSpecificOpening(x),
+            ))),
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FileRecords<'a> {
+    pub records: &'a [OctetString<'a>],
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FileRecords<'a> {
+    pub records: Vec<OctetString<'a>>,
+    _phantom: &'a Phantom,
+}
+
+impl<'a> FileRecords<'a> {
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(records: &'a [OctetString<'a>]) -> Self {
+        Self { records, buf: &[] }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(records: Vec<OctetString<'a>>) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            records,
+            _phantom: &PHANTOM,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        #[cfg(not(feature = "alloc"))]
+        encode_list(writer, self.buf, self.records, |record, writer| {
+            encode_octet_string(writer, record)
+        });
+        #[cfg(feature = "alloc")]
+        for record in self.records.iter() {
+            encode_octet_string(writer, record);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let mut records = Vec::new();
+        while !reader.eof() {
+            let record = decode_record(reader, buf)?;
+            records.push(record);
+        }
+
+        Ok(Self::new(records))
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let buf = &buf[reader.index..reader.end];
+        reader.index = reader.end;
+        Ok(Self { records: &[], buf })
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ FileRecords<'a> {
+    type Item = Result<OctetString<'a>, Error>;
+
+    type IntoIter = FileRecordIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        FileRecordIter {
+            list: ListIter::new(self.records, self.buf),
+        }
+    }
+}
+
+pub struct FileRecordIter<'a> {
+    list: ListIter<'a, OctetString<'a>>,
+}
+
+impl<'a> Iterator for FileRecordIter<'a> {
+    type Item = Result<OctetString<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.next_with(decode_record)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AtomicReadFileAck<'a> {
+    pub end_of_file: bool,
+    pub data: FileData<'a>,
+}
+
+impl<'a> AtomicReadFileAck<'a> {
+    pub fn new(end_of_file: bool, data: FileData<'a>) -> Self {
+        Self { end_of_file, data }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(ConfirmedServiceChoice::AtomicReadFile as u8);
+        Tag::new(
+            TagNumber::Application(ApplicationTagNumber::Boolean),
+            self.end_of_file as u32,
+        )
+        .encode(writer);
+        self.data.encode(writer);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // the value of an application tagged boolean is stored in the tag itself
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Boolean),
+            "AtomicReadFileAck decode end_of_file",
+        )?;
+        let end_of_file = tag.value > 0;
+        let data = FileData::decode(reader, buf)?;
+
+        Ok(Self { end_of_file, data })
+    }
+}
+
+impl<'a> TryFrom<DataLink<'a>> for AtomicReadFileAck<'a> {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        let ack: ComplexAck = value.try_into()?;
+        match ack.service {
+            ComplexAckService::AtomicReadFile(ack) => Ok(ack),
+            _ => Err(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService AtomicReadFileAck",
+            )),
+        }
+    }
+}
+
+fn encode_octet_string(writer: &mut Writer, value: &OctetString) {
+    Tag::new(
+        TagNumber::Application(ApplicationTagNumber::OctetString),
+        value.inner.len() as u32,
+    )
+    .encode(writer);
+    writer.extend_from_slice(value.as_slice());
+}
+
+#[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+fn decode_record<'a>(reader: &mut Reader, buf: &'a [u8]) -> Result<OctetString<'a>, Error> {
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::Application(ApplicationTagNumber::OctetString),
+        "FileRecords decode record",
+    )?;
+    OctetString::decode(tag.value, reader, buf)
+}
+
+fn decode_file_start(reader: &mut Reader, buf: &[u8], context: &'static str) -> Result<i32, Error> {
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::Application(ApplicationTagNumber::SignedInt),
+        context,
+    )?;
+    Ok(decode_signed(tag.value, reader, buf)? as i32)
+}
+
+fn decode_file_count(reader: &mut Reader, buf: &[u8], context: &'static str) -> Result<u32, Error> {
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::Application(ApplicationTagNumber::UnsignedInt),
+        context,
+    )?;
+    Ok(decode_unsigned(tag.value, reader, buf)? as u32)
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ComplexAck, ComplexAckService},
+            primitives::data_value::OctetString,
+        },
+        common::io::{Reader, Writer},
+    };
+
+    use super::{AtomicReadFileAck, FileData, FileRecords};
+
+    #[test]
+    fn reversable() {
+        use alloc::vec;
+
+        let records = FileRecords::new(vec![
+            OctetString::new(&[1, 2, 3]),
+            OctetString::new(&[]),
+            OctetString::new(&[4, 5]),
+        ]);
+        let ack = AtomicReadFileAck::new(true, FileData::new_record(-1, records));
+
+        // encoding
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        let ack = ComplexAck {
+            invoke_id: 7,
+            service: ComplexAckService::AtomicReadFile(ack),
+        };
+        ack.encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = match ComplexAck::decode(&mut reader, &buf[..len])
+            .unwrap()
+            .service
+        {
+            ComplexAckService::AtomicReadFile(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        assert!(decoded.end_of_file);
+        match decoded.data {
+            FileData::Record {
+                start_record,
+                record_count,
+                records,
+            } => {
+                assert_eq!(start_record, -1);
+                assert_eq!(record_count, 3);
+                assert_eq!(records.records.len(), 3);
+                assert_eq!(records.records[0].inner, [1, 2, 3]);
+                assert!(records.records[1].inner.is_empty());
+                assert_eq!(records.records[2].inner, [4, 5]);
+            }
+            x => panic!("unexpected file data {:?}", x),
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "alloc")))]
+mod no_alloc_tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ComplexAck, ComplexAckService},
+            primitives::data_value::OctetString,
+        },
+        common::{
+            helper::assert_encodes_to,
+            io::{Reader, Writer},
+        },
+    };
+
+    use super::{AtomicReadFileAck, FileData, FileRecords};
+
+    #[test]
+    fn reversable() {
+        let records = [OctetString::new(&[1, 2, 3]), OctetString::new(&[4, 5])];
+        let ack =
+            AtomicReadFileAck::new(false, FileData::new_record(2, FileRecords::new(&records)));
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        ComplexAck {
+            invoke_id: 7,
+            service: ComplexAckService::AtomicReadFile(ack),
+        }
+        .encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ComplexAck::decode(&mut reader, &buf[..len]).unwrap();
+        let records = match &decoded.service {
+            ComplexAckService::AtomicReadFile(AtomicReadFileAck {
+                data:
+                    FileData::Record {
+                        start_record: 2,
+                        record_count: 2,
+                        records,
+                    },
+                ..
+            }) => records,
+            x => panic!("unexpected service {:?}", x),
+        };
+        let mut iter = records.into_iter();
+        assert_eq!(iter.next().unwrap().unwrap().inner, [1, 2, 3]);
+        assert_eq!(iter.next().unwrap().unwrap().inner, [4, 5]);
+        assert!(iter.next().is_none());
+
+        assert_encodes_to(&buf[..len], |writer| decoded.encode(writer));
+    }
+}
@@ -0,0 +1,149 @@
+// atomic write file (writes a chunk of a File object using either stream or record access)
+
+use crate::{
+    application_protocol::{
+        confirmed::{ComplexAck, ComplexAckService, ConfirmedServiceChoice},
+        services::atomic_read_file::FileData,
+    },
+    common::{
+        error::Error,
+        helper::{decode_signed, encode_application_object_id, encode_signed},
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+    network_protocol::data_link::DataLink,
+};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AtomicWriteFile<'a> {
+    pub file_id: ObjectId,
+    pub data: FileData<'a>,
+}
+
+impl<'a> AtomicWriteFile<'a> {
+    pub fn new(file_id: ObjectId, data: FileData<'a>) -> Self {
+        Self { file_id, data }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_application_object_id(writer, &self.file_id);
+        self.data.encode(writer);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::ObjectId),
+            "AtomicWriteFile decode file_id",
+        )?;
+        let file_id = ObjectId::decode(tag.value, reader, buf)?;
+        let data = FileData::decode(reader, buf)?;
+
+        Ok(Self { file_id, data })
+    }
+}
+
+// the position in the file where the data was actually written
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AtomicWriteFileAck {
+    StartPosition(i32),
+    StartRecord(i32),
+}
+
+impl AtomicWriteFileAck {
+    const TAG_START_POSITION: u8 = 0;
+    const TAG_START_RECORD: u8 = 1;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(ConfirmedServiceChoice::AtomicWriteFile as u8);
+        match self {
+            Self::StartPosition(x) => encode_signed(writer, Some(Self::TAG_START_POSITION), *x),
+            Self::StartRecord(x) => encode_signed(writer, Some(Self::TAG_START_RECORD), *x),
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode(reader, buf)?;
+        match tag.number {
+            TagNumber::ContextSpecific(Self::TAG_START_POSITION) => {
+                let value = decode_signed(tag.value, reader, buf)? as i32;
+                Ok(Self::StartPosition(value))
+            }
+            TagNumber::ContextSpecific(Self::TAG_START_RECORD) => {
+                let value = decode_signed(tag.value, reader, buf)? as i32;
+                Ok(Self::StartRecord(value))
+            }
+            x => Err(Error::TagNotSupported(("AtomicWriteFileAck decode", x))),
+        }
+    }
+}
+
+impl<'a> TryFrom<DataLink<'a>> for AtomicWriteFileAck {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        let ack: ComplexAck = value.try_into()?;
+        match ack.service {
+            ComplexAckService::AtomicWriteFile(ack) => Ok(ack),
+            _ => Err(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService AtomicWriteFileAck",
+            )),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ConfirmedRequest, ConfirmedRequestService},
+            primitives::data_value::OctetString,
+            services::atomic_read_file::FileData,
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+        },
+    };
+
+    use super::AtomicWriteFile;
+
+    #[test]
+    fn reversable() {
+        let file_id = ObjectId::new(ObjectType::ObjectFile, 1);
+        let data = FileData::new_stream(300, OctetString::new(&[0xde, 0xad, 0xbe, 0xef]));
+        let request = AtomicWriteFile::new(file_id, data);
+
+        // encoding
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        let service = ConfirmedRequestService::AtomicWriteFile(request);
+        ConfirmedRequest::new(2, service).encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ConfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        let decoded = match decoded.service {
+            ConfirmedRequestService::AtomicWriteFile(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        assert_eq!(decoded.file_id, file_id);
+        match decoded.data {
+            FileData::Stream {
+                start_position,
+                data,
+            } => {
+                assert_eq!(start_position, 300);
+                assert_eq!(data.inner, [0xde, 0xad, 0xbe, 0xef]);
+            }
+            x => panic!("unexpected file data {:?}", x),
+        }
+    }
+}
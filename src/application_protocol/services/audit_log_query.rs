@@ -0,0 +1,939 @@
+// audit log query (addendum 2016-bi)
+// reads the records of an Audit Log object that match a target or source device, a page at a time
+
+#[cfg(feature = "alloc")]
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
+
+#[cfg(not(feature = "alloc"))]
+use crate::common::helper::encode_list;
+
+use crate::{
+    application_protocol::{
+        confirmed::{ComplexAck, ComplexAckService, ConfirmedServiceChoice},
+        primitives::{data_value::DateTime, recipient::Address},
+        services::audit_notification::AuditNotification,
+    },
+    common::{
+        error::Error,
+        helper::{
+            decode_bit_string, decode_context_bool, decode_context_object_id,
+            decode_context_property_id, decode_context_real, decode_unsigned, encode_closing_tag,
+            encode_context_bit_string, encode_context_bool, encode_context_enumerated,
+            encode_context_object_id, encode_context_real, encode_context_unsigned,
+            encode_enumerated, encode_opening_tag, get_tagged_body, get_tagged_body_for_tag,
+            is_next_context_tag, ListIter,
+        },
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        property_id::PropertyId,
+        spec::{AuditOperation, LogStatusBits, SuccessFilter},
+        tag::{Tag, TagNumber},
+    },
+    network_protocol::data_link::DataLink,
+};
+
+// BACnetAuditOperationFlags: one bit per AuditOperation (read is the most significant bit)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuditOperationFlags {
+    pub inner: u16,
+}
+
+impl AuditOperationFlags {
+    pub fn new(inner: u16) -> Self {
+        Self { inner }
+    }
+
+    pub fn with(mut self, operation: AuditOperation) -> Self {
+        self.inner |= Self::mask(operation);
+        self
+    }
+
+    pub const fn contains(&self, operation: AuditOperation) -> bool {
+        self.inner & Self::mask(operation) != 0
+    }
+
+    const fn mask(operation: AuditOperation) -> u16 {
+        0x8000 >> operation as u16
+    }
+
+    pub fn encode_context(&self, tag_number: u8, writer: &mut Writer) {
+        encode_context_bit_string(writer, tag_number, 0, &self.inner.to_be_bytes());
+    }
+
+    pub fn decode(len: u32, reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let bytes = decode_bit_string::<2>(len, reader, buf)?;
+        Ok(Self::new(u16::from_be_bytes(bytes)))
+    }
+}
+
+// records that were written to the target device, optionally narrowed down to an object, property and operation
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AuditLogQueryByTarget<'a> {
+    pub device_id: ObjectId,
+    pub address: Option<Address<'a>>,
+    pub object_id: Option<ObjectId>,
+    pub property_id: Option<PropertyId>,
+    pub array_index: Option<u32>,
+    pub priority: Option<u8>,
+    pub operations: Option<AuditOperationFlags>, // all operations if not specified
+    pub result_filter: SuccessFilter,
+}
+
+impl<'a> AuditLogQueryByTarget<'a> {
+    const TAG_DEVICE_ID: u8 = 0;
+    const TAG_ADDRESS: u8 = 1;
+    const TAG_OBJECT_ID: u8 = 2;
+    const TAG_PROPERTY_ID: u8 = 3;
+    const TAG_ARRAY_INDEX: u8 = 4;
+    const TAG_PRIORITY: u8 = 5;
+    const TAG_OPERATIONS: u8 = 6;
+    const TAG_RESULT_FILTER: u8 = 7;
+
+    pub fn new(device_id: ObjectId) -> Self {
+        Self {
+            device_id,
+            address: None,
+            object_id: None,
+            property_id: None,
+            array_index: None,
+            priority: None,
+            operations: None,
+            result_filter: SuccessFilter::All,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_object_id(writer, Self::TAG_DEVICE_ID, &self.device_id);
+        if let Some(address) = &self.address {
+            encode_opening_tag(writer, Self::TAG_ADDRESS);
+            address.encode(writer);
+            encode_closing_tag(writer, Self::TAG_ADDRESS);
+        }
+        if let Some(object_id) = &self.object_id {
+            encode_context_object_id(writer, Self::TAG_OBJECT_ID, object_id);
+        }
+        if let Some(property_id) = &self.property_id {
+            encode_context_enumerated(writer, Self::TAG_PROPERTY_ID, property_id);
+        }
+        if let Some(array_index) = self.array_index {
+            encode_context_unsigned(writer, Self::TAG_ARRAY_INDEX, array_index);
+        }
+        if let Some(priority) = self.priority {
+            encode_context_unsigned(writer, Self::TAG_PRIORITY, priority);
+        }
+        if let Some(operations) = &self.operations {
+            operations.encode_context(Self::TAG_OPERATIONS, writer);
+        }
+        if self.result_filter != SuccessFilter::All {
+            encode_enumerated(
+                writer,
+                self.result_filter as u32,
+                Some(Self::TAG_RESULT_FILTER),
+            );
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let device_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_DEVICE_ID,
+            "AuditLogQueryByTarget decode device_id",
+        )?;
+        let mut query = Self::new(device_id);
+
+        if is_next_context_tag(reader, buf, Self::TAG_ADDRESS)? {
+            let inner_buf = get_tagged_body_for_tag(
+                reader,
+                buf,
+                Self::TAG_ADDRESS,
+                "AuditLogQueryByTarget decode address",
+            )?;
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            query.address = Some(Address::decode(&mut inner_reader, inner_buf)?);
+        }
+        if is_next_context_tag(reader, buf, Self::TAG_OBJECT_ID)? {
+            query.object_id = Some(decode_context_object_id(
+                reader,
+                buf,
+                Self::TAG_OBJECT_ID,
+                "AuditLogQueryByTarget decode object_id",
+            )?);
+        }
+        if is_next_context_tag(reader, buf, Self::TAG_PROPERTY_ID)? {
+            query.property_id = Some(decode_context_property_id(
+                reader,
+                buf,
+                Self::TAG_PROPERTY_ID,
+                "AuditLogQueryByTarget decode property_id",
+            )?);
+        }
+        if is_next_context_tag(reader, buf, Self::TAG_ARRAY_INDEX)? {
+            let tag = Tag::decode(reader, buf)?;
+            query.array_index = Some(decode_unsigned(tag.value, reader, buf)? as u32);
+        }
+        if is_next_context_tag(reader, buf, Self::TAG_PRIORITY)? {
+            let tag = Tag::decode(reader, buf)?;
+            query.priority = Some(decode_unsigned(tag.value, reader, buf)? as u8);
+        }
+        if is_next_context_tag(reader, buf, Self::TAG_OPERATIONS)? {
+            let tag = Tag::decode(reader, buf)?;
+            query.operations = Some(AuditOperationFlags::decode(tag.value, reader, buf)?);
+        }
+        if is_next_context_tag(reader, buf, Self::TAG_RESULT_FILTER)? {
+            let tag = Tag::decode(reader, buf)?;
+            query.result_filter = decode_success_filter(tag.value, reader, buf)?;
+        }
+
+        Ok(query)
+    }
+}
+
+// records of operations requested by the source device (and object)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AuditLogQueryBySource<'a> {
+    pub device_id: ObjectId,
+    pub address: Option<Address<'a>>,
+    pub object_id: Option<ObjectId>,
+    pub operations: Option<AuditOperationFlags>, // all operations if not specified
+    pub result_filter: SuccessFilter,
+}
+
+impl<'a> AuditLogQueryBySource<'a> {
+    const TAG_DEVICE_ID: u8 = 0;
+    const TAG_ADDRESS: u8 = 1;
+    const TAG_OBJECT_ID: u8 = 2;
+    const TAG_OPERATIONS: u8 = 3;
+    const TAG_RESULT_FILTER: u8 = 4;
+
+    pub fn new(device_id: ObjectId) -> Self {
+        Self {
+            device_id,
+            address: None,
+            object_id: None,
+            operations: None,
+            result_filter: SuccessFilter::All,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_object_id(writer, Self::TAG_DEVICE_ID, &self.device_id);
+        if let Some(address) = &self.address {
+            encode_opening_tag(writer, Self::TAG_ADDRESS);
+            address.encode(writer);
+            encode_closing_tag(writer, Self::TAG_ADDRESS);
+        }
+        if let Some(object_id) = &self.object_id {
+            encode_context_object_id(writer, Self::TAG_OBJECT_ID, object_id);
+        }
+        if let Some(operations) = &self.operations {
+            operations.encode_context(Self::TAG_OPERATIONS, writer);
+        }
+        if self.result_filter != SuccessFilter::All {
+            encode_enumerated(
+                writer,
+                self.result_filter as u32,
+                Some(Self::TAG_RESULT_FILTER),
+            );
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let device_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_DEVICE_ID,
+            "AuditLogQueryBySource decode device_id",
+        )?;
+        let mut query = Self::new(device_id);
+
+        if is_next_context_tag(reader, buf, Self::TAG_ADDRESS)? {
+            let inner_buf = get_tagged_body_for_tag(
+                reader,
+                buf,
+                Self::TAG_ADDRESS,
+                "AuditLogQueryBySource decode address",
+            )?;
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            query.address = Some(Address::decode(&mut inner_reader, inner_buf)?);
+        }
+        if is_next_context_tag(reader, buf, Self::TAG_OBJECT_ID)? {
+            query.object_id = Some(decode_context_object_id(
+                reader,
+                buf,
+                Self::TAG_OBJECT_ID,
+                "AuditLogQueryBySource decode object_id",
+            )?);
+        }
+        if is_next_context_tag(reader, buf, Self::TAG_OPERATIONS)? {
+            let tag = Tag::decode(reader, buf)?;
+            query.operations = Some(AuditOperationFlags::decode(tag.value, reader, buf)?);
+        }
+        if is_next_context_tag(reader, buf, Self::TAG_RESULT_FILTER)? {
+            let tag = Tag::decode(reader, buf)?;
+            query.result_filter = decode_success_filter(tag.value, reader, buf)?;
+        }
+
+        Ok(query)
+    }
+}
+
+fn decode_success_filter(
+    len: u32,
+    reader: &mut Reader,
+    buf: &[u8],
+) -> Result<SuccessFilter, Error> {
+    let value = decode_unsigned(len, reader, buf)? as u32;
+    SuccessFilter::try_from(value)
+        .map_err(|x| Error::InvalidVariant(("AuditLogQuery decode result_filter", x)))
+}
+
+// BACnetAuditLogQueryParameters
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AuditLogQueryParameters<'a> {
+    ByTarget(AuditLogQueryByTarget<'a>),
+    BySource(AuditLogQueryBySource<'a>),
+}
+
+impl<'a> AuditLogQueryParameters<'a> {
+    const TAG_BY_TARGET: u8 = 0;
+    const TAG_BY_SOURCE: u8 = 1;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::ByTarget(x) => {
+                encode_opening_tag(writer, Self::TAG_BY_TARGET);
+                x.encode(writer);
+                encode_closing_tag(writer, Self::TAG_BY_TARGET);
+            }
+            Self::BySource(x) => {
+                encode_opening_tag(writer, Self::TAG_BY_SOURCE);
+                x.encode(writer);
+                encode_closing_tag(writer, Self::TAG_BY_SOURCE);
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let (inner_buf, tag_number) = get_tagged_body(reader, buf)?;
+        let mut inner_reader = Reader::new_with_len(inner_buf.len());
+        match tag_number {
+            Self::TAG_BY_TARGET => {
+                let query = AuditLogQueryByTarget::decode(&mut inner_reader, inner_buf)?;
+                Ok(Self::ByTarget(query))
+            }
+            Self::TAG_BY_SOURCE => {
+                let query = AuditLogQueryBySource::decode(&mut inner_reader, inner_buf)?;
+                Ok(Self::BySource(query))
+            }
+            x => Err(Error::TagNotSupported((
+                "AuditLogQueryParameters decode",
+                TagNumber::ContextSpecificOpening(x),
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AuditLogQuery<'a> {
+    pub audit_log: ObjectId,
+    pub parameters: AuditLogQueryParameters<'a>,
+    pub start_at_sequence_number: Option<u64>, // the oldest matching record if not specified
+    pub requested_count: u16,
+}
+
+impl<'a> AuditLogQuery<'a> {
+    const TAG_AUDIT_LOG: u8 = 0;
+    const TAG_PARAMETERS: u8 = 1;
+    const TAG_START_AT_SEQUENCE_NUMBER: u8 = 2;
+    const TAG_REQUESTED_COUNT: u8 = 3;
+
+    pub fn new(
+        audit_log: ObjectId,
+        parameters: AuditLogQueryParameters<'a>,
+        start_at_sequence_number: Option<u64>,
+        requested_count: u16,
+    ) -> Self {
+        Self {
+            audit_log,
+            parameters,
+            start_at_sequence_number,
+            requested_count,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_object_id(writer, Self::TAG_AUDIT_LOG, &self.audit_log);
+
+        encode_opening_tag(writer, Self::TAG_PARAMETERS);
+        self.parameters.encode(writer);
+        encode_closing_tag(writer, Self::TAG_PARAMETERS);
+
+        if let Some(sequence_number) = self.start_at_sequence_number {
+            encode_context_unsigned(writer, Self::TAG_START_AT_SEQUENCE_NUMBER, sequence_number);
+        }
+        encode_context_unsigned(writer, Self::TAG_REQUESTED_COUNT, self.requested_count);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let audit_log = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_AUDIT_LOG,
+            "AuditLogQuery decode audit_log",
+        )?;
+
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_PARAMETERS,
+            "AuditLogQuery decode parameters",
+        )?;
+        let mut inner_reader = Reader::new_with_len(inner_buf.len());
+        let parameters = AuditLogQueryParameters::decode(&mut inner_reader, inner_buf)?;
+
+        let mut start_at_sequence_number = None;
+        if is_next_context_tag(reader, buf, Self::TAG_START_AT_SEQUENCE_NUMBER)? {
+            let tag = Tag::decode(reader, buf)?;
+            start_at_sequence_number = Some(decode_unsigned(tag.value, reader, buf)?);
+        }
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_REQUESTED_COUNT),
+            "AuditLogQuery decode requested_count",
+        )?;
+        let requested_count = decode_unsigned(tag.value, reader, buf)? as u16;
+
+        Ok(Self {
+            audit_log,
+            parameters,
+            start_at_sequence_number,
+            requested_count,
+        })
+    }
+}
+
+// the content of an audit log record
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AuditLogDatum<'a> {
+    LogStatus(LogStatusBits),
+    Notification(AuditNotification<'a>),
+    TimeChange(f32), // the number of seconds the clock was changed by
+}
+
+impl<'a> AuditLogDatum<'a> {
+    const TAG_LOG_STATUS: u8 = 0;
+    const TAG_NOTIFICATION: u8 = 1;
+    const TAG_TIME_CHANGE: u8 = 2;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            // 3 bits used (5 unused)
+            Self::LogStatus(x) => {
+                encode_context_bit_string(writer, Self::TAG_LOG_STATUS, 5, &[x.inner])
+            }
+            Self::Notification(x) => {
+                encode_opening_tag(writer, Self::TAG_NOTIFICATION);
+                x.encode(writer);
+                encode_closing_tag(writer, Self::TAG_NOTIFICATION);
+            }
+            Self::TimeChange(x) => encode_context_real(writer, Self::TAG_TIME_CHANGE, *x),
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        if is_next_context_tag(reader, buf, Self::TAG_NOTIFICATION)? {
+            let inner_buf = get_tagged_body_for_tag(
+                reader,
+                buf,
+                Self::TAG_NOTIFICATION,
+                "AuditLogDatum decode notification",
+            )?;
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            let notification = AuditNotification::decode(&mut inner_reader, inner_buf)?;
+            return Ok(Self::Notification(notification));
+        }
+
+        if is_next_context_tag(reader, buf, Self::TAG_TIME_CHANGE)? {
+            let seconds = decode_context_real(
+                reader,
+                buf,
+                Self::TAG_TIME_CHANGE,
+                "AuditLogDatum decode time_change",
+            )?;
+            return Ok(Self::TimeChange(seconds));
+        }
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_LOG_STATUS),
+            "AuditLogDatum decode log_status",
+        )?;
+        let [bits] = decode_bit_string::<1>(tag.value, reader, buf)?;
+        Ok(Self::LogStatus(LogStatusBits::new(bits)))
+    }
+}
+
+// BACnetAuditLogRecordResult
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AuditLogRecord<'a> {
+    pub sequence_number: u64,
+    pub timestamp: DateTime,
+    pub datum: AuditLogDatum<'a>,
+}
+
+impl<'a> AuditLogRecord<'a> {
+    const TAG_SEQUENCE_NUMBER: u8 = 0;
+    const TAG_LOG_RECORD: u8 = 1;
+    const TAG_TIMESTAMP: u8 = 0;
+    const TAG_LOG_DATUM: u8 = 1;
+
+    pub fn new(sequence_number: u64, timestamp: DateTime, datum: AuditLogDatum<'a>) -> Self {
+        Self {
+            sequence_number,
+            timestamp,
+            datum,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_unsigned(writer, Self::TAG_SEQUENCE_NUMBER, self.sequence_number);
+
+        // log record
+        encode_opening_tag(writer, Self::TAG_LOG_RECORD);
+        encode_opening_tag(writer, Self::TAG_TIMESTAMP);
+        self.timestamp.encode(writer);
+        encode_closing_tag(writer, Self::TAG_TIMESTAMP);
+        encode_opening_tag(writer, Self::TAG_LOG_DATUM);
+        self.datum.encode(writer);
+        encode_closing_tag(writer, Self::TAG_LOG_DATUM);
+        encode_closing_tag(writer, Self::TAG_LOG_RECORD);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_SEQUENCE_NUMBER),
+            "AuditLogRecord decode sequence_number",
+        )?;
+        let sequence_number = decode_unsigned(tag.value, reader, buf)?;
+
+        // log record
+        let record_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_LOG_RECORD,
+            "AuditLogRecord decode log_record",
+        )?;
+        let mut record_reader = Reader::new_with_len(record_buf.len());
+
+        Tag::decode_expected(
+            &mut record_reader,
+            record_buf,
+            TagNumber::ContextSpecificOpening(Self::TAG_TIMESTAMP),
+            "AuditLogRecord decode timestamp",
+        )?;
+        let timestamp = DateTime::decode(&mut record_reader, record_buf)?;
+        Tag::decode_expected(
+            &mut record_reader,
+            record_buf,
+            TagNumber::ContextSpecificClosing(Self::TAG_TIMESTAMP),
+            "AuditLogRecord decode timestamp",
+        )?;
+
+        let inner_buf = get_tagged_body_for_tag(
+            &mut record_reader,
+            record_buf,
+            Self::TAG_LOG_DATUM,
+            "AuditLogRecord decode log_datum",
+        )?;
+        let mut inner_reader = Reader::new_with_len(inner_buf.len());
+        let datum = AuditLogDatum::decode(&mut inner_reader, inner_buf)?;
+
+        Ok(Self {
+            sequence_number,
+            timestamp,
+            datum,
+        })
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AuditLogQueryAck<'a> {
+    pub audit_log: ObjectId,
+    pub records: &'a [AuditLogRecord<'a>],
+    pub no_more_items: bool,
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AuditLogQueryAck<'a> {
+    pub audit_log: ObjectId,
+    pub records: Vec<AuditLogRecord<'a>>,
+    pub no_more_items: bool,
+    _phantom: &'a Phantom,
+}
+
+impl<'a> AuditLogQueryAck<'a> {
+    const TAG_AUDIT_LOG: u8 = 0;
+    const TAG_RECORDS: u8 = 1;
+    const TAG_NO_MORE_ITEMS: u8 = 2;
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(
+        audit_log: ObjectId,
+        records: &'a [AuditLogRecord<'a>],
+        no_more_items: bool,
+    ) -> Self {
+        Self {
+            audit_log,
+            records,
+            no_more_items,
+            buf: &[],
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(audit_log: ObjectId, records: Vec<AuditLogRecord<'a>>, no_more_items: bool) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            audit_log,
+            records,
+            no_more_items,
+            _phantom: &PHANTOM,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(ConfirmedServiceChoice::AuditLogQuery as u8);
+        encode_context_object_id(writer, Self::TAG_AUDIT_LOG, &self.audit_log);
+
+        encode_opening_tag(writer, Self::TAG_RECORDS);
+        #[cfg(not(feature = "alloc"))]
+        encode_list(writer, self.buf, self.records, AuditLogRecord::encode);
+        #[cfg(feature = "alloc")]
+        for record in self.records.iter() {
+            record.encode(writer);
+        }
+        encode_closing_tag(writer, Self::TAG_RECORDS);
+
+        encode_context_bool(writer, Self::TAG_NO_MORE_ITEMS, self.no_more_items);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let audit_log = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_AUDIT_LOG,
+            "AuditLogQueryAck decode audit_log",
+        )?;
+
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_RECORDS,
+            "AuditLogQueryAck decode records",
+        )?;
+
+        let no_more_items = decode_context_bool(
+            reader,
+            buf,
+            Self::TAG_NO_MORE_ITEMS,
+            "AuditLogQueryAck decode no_more_items",
+        )?;
+
+        #[cfg(not(feature = "alloc"))]
+        return Ok(Self {
+            audit_log,
+            records: &[],
+            no_more_items,
+            buf: inner_buf,
+        });
+
+        #[cfg(feature = "alloc")]
+        {
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            let mut records = Vec::new();
+            while !inner_reader.eof() {
+                records.push(AuditLogRecord::decode(&mut inner_reader, inner_buf)?);
+            }
+            Ok(Self::new(audit_log, records, no_more_items))
+        }
+    }
+}
+
+impl<'a> TryFrom<DataLink<'a>> for AuditLogQueryAck<'a> {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        let ack: ComplexAck = value.try_into()?;
+        match ack.service {
+            ComplexAckService::AuditLogQuery(ack) => Ok(ack),
+            _ => Err(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService AuditLogQueryAck",
+            )),
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ AuditLogQueryAck<'a> {
+    type Item = Result<AuditLogRecord<'a>, Error>;
+
+    type IntoIter = AuditLogRecordIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        AuditLogRecordIter {
+            list: ListIter::new(self.records, self.buf),
+        }
+    }
+}
+
+pub struct AuditLogRecordIter<'a> {
+    list: ListIter<'a, AuditLogRecord<'a>>,
+}
+
+impl<'a> Iterator for AuditLogRecordIter<'a> {
+    type Item = Result<AuditLogRecord<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.next_with(AuditLogRecord::decode)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ComplexAck, ComplexAckService, ConfirmedRequest, ConfirmedRequestService},
+            primitives::{
+                data_value::{Date, DateTime, Time},
+                recipient::Recipient,
+            },
+            services::audit_notification::AuditNotification,
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{AuditOperation, LogStatusBits, SuccessFilter},
+        },
+    };
+
+    use super::{
+        AuditLogDatum, AuditLogQuery, AuditLogQueryAck, AuditLogQueryByTarget,
+        AuditLogQueryParameters, AuditLogRecord, AuditOperationFlags,
+    };
+
+    #[test]
+    fn reversable() {
+        use alloc::vec;
+
+        let audit_log = ObjectId::new(ObjectType::ObjectAuditLog, 1);
+        let device_id = ObjectId::new(ObjectType::ObjectDevice, 2);
+
+        // request
+        let mut by_target = AuditLogQueryByTarget::new(device_id);
+        by_target.object_id = Some(ObjectId::new(ObjectType::ObjectAnalogValue, 7));
+        by_target.property_id = Some(PropertyId::PropPresentValue);
+        by_target.operations = Some(AuditOperationFlags::default().with(AuditOperation::Write));
+        by_target.result_filter = SuccessFilter::SuccessesOnly;
+        let query = AuditLogQuery::new(
+            audit_log,
+            AuditLogQueryParameters::ByTarget(by_target),
+            Some(300),
+            10,
+        );
+
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        let service = ConfirmedRequestService::AuditLogQuery(query);
+        ConfirmedRequest::new(1, service).encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ConfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        let decoded = match decoded.service {
+            ConfirmedRequestService::AuditLogQuery(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        assert_eq!(decoded.audit_log, audit_log);
+        assert_eq!(decoded.start_at_sequence_number, Some(300));
+        assert_eq!(decoded.requested_count, 10);
+        let by_target = match decoded.parameters {
+            AuditLogQueryParameters::ByTarget(x) => x,
+            x => panic!("unexpected parameters {:?}", x),
+        };
+        assert_eq!(by_target.device_id, device_id);
+        assert_eq!(by_target.property_id, Some(PropertyId::PropPresentValue));
+        let operations = by_target.operations.unwrap();
+        assert!(operations.contains(AuditOperation::Write));
+        assert!(!operations.contains(AuditOperation::Read));
+        assert_eq!(by_target.result_filter, SuccessFilter::SuccessesOnly);
+
+        // ack
+        let timestamp = DateTime::new(
+            Date {
+                year: 2024,
+                month: 3,
+                day: 14,
+                wday: 4,
+            },
+            Time {
+                hour: 9,
+                minute: 30,
+                second: 0,
+                hundredths: 0,
+            },
+        );
+        let notification = AuditNotification::new(
+            Recipient::Device(ObjectId::new(ObjectType::ObjectDevice, 1)),
+            AuditOperation::Write,
+            Recipient::Device(device_id),
+        );
+        let records = vec![
+            AuditLogRecord::new(
+                300,
+                timestamp.clone(),
+                AuditLogDatum::Notification(notification),
+            ),
+            AuditLogRecord::new(301, timestamp.clone(), AuditLogDatum::TimeChange(-3.5)),
+            AuditLogRecord::new(
+                302,
+                timestamp,
+                AuditLogDatum::LogStatus(LogStatusBits::new(0b0100_0000)),
+            ),
+        ];
+        let ack = AuditLogQueryAck::new(audit_log, records, true);
+
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        ComplexAck {
+            invoke_id: 1,
+            service: ComplexAckService::AuditLogQuery(ack),
+        }
+        .encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ComplexAck::decode(&mut reader, &buf[..len]).unwrap();
+        let decoded = match decoded.service {
+            ComplexAckService::AuditLogQuery(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        assert_eq!(decoded.audit_log, audit_log);
+        assert!(decoded.no_more_items);
+        assert_eq!(decoded.records.len(), 3);
+        assert_eq!(decoded.records[0].sequence_number, 300);
+        assert!(matches!(
+            &decoded.records[0].datum,
+            AuditLogDatum::Notification(x) if x.operation == AuditOperation::Write
+        ));
+        assert!(matches!(decoded.records[1].datum, AuditLogDatum::TimeChange(x) if x == -3.5));
+        assert!(matches!(
+            &decoded.records[2].datum,
+            AuditLogDatum::LogStatus(x) if x.buffer_purged() && !x.log_disabled()
+        ));
+    }
+}
+
+#[cfg(all(test, not(feature = "alloc")))]
+mod no_alloc_tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ComplexAck, ComplexAckService},
+            primitives::data_value::{Date, DateTime, Time},
+        },
+        common::{
+            helper::assert_encodes_to,
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            spec::LogStatusBits,
+        },
+    };
+
+    use super::{AuditLogDatum, AuditLogQueryAck, AuditLogRecord};
+
+    #[test]
+    fn reversable() {
+        let audit_log = ObjectId::new(ObjectType::ObjectAuditLog, 1);
+        let timestamp = DateTime::new(
+            Date {
+                year: 2024,
+                month: 3,
+                day: 14,
+                wday: 4,
+            },
+            Time {
+                hour: 9,
+                minute: 30,
+                second: 0,
+                hundredths: 0,
+            },
+        );
+        let records = [
+            AuditLogRecord::new(301, timestamp.clone(), AuditLogDatum::TimeChange(-3.5)),
+            AuditLogRecord::new(
+                302,
+                timestamp,
+                AuditLogDatum::LogStatus(LogStatusBits::new(0b0100_0000)),
+            ),
+        ];
+        let ack = AuditLogQueryAck::new(audit_log, &records, false);
+        assert_eq!((&ack).into_iter().count(), 2);
+
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        ComplexAck {
+            invoke_id: 1,
+            service: ComplexAckService::AuditLogQuery(ack),
+        }
+        .encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ComplexAck::decode(&mut reader, &buf[..len]).unwrap();
+        let ack = match &decoded.service {
+            ComplexAckService::AuditLogQuery(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        assert_eq!(ack.audit_log, audit_log);
+        assert!(!ack.no_more_items);
+        let mut records = ack.into_iter();
+        let first = records.next().unwrap().unwrap();
+        assert_eq!(first.sequence_number, 301);
+        assert!(matches!(first.datum, AuditLogDatum::TimeChange(x) if x == -3.5));
+        let second = records.next().unwrap().unwrap();
+        assert_eq!(second.sequence_number, 302);
+        assert!(records.next().is_none());
+
+        assert_encodes_to(&buf[..len], |writer| decoded.encode(writer));
+    }
+}
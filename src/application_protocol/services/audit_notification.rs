@@ -0,0 +1,765 @@
+// audit notification (addendum 2016-bi)
+// reports operations on a device (e.g. who changed which setpoint) to audit reporters and audit logs
+
+#[cfg(feature = "alloc")]
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
+
+#[cfg(not(feature = "alloc"))]
+use crate::common::helper::encode_list;
+
+use crate::{
+    application_protocol::primitives::{
+        data_value::{ApplicationDataValue, CharacterString, OctetString, TimeStamp},
+        property_reference::PropertyReference,
+        recipient::Recipient,
+    },
+    common::{
+        error::Error,
+        helper::{
+            decode_context_object_id, decode_unsigned, encode_closing_tag,
+            encode_context_character_string, encode_context_object_id, encode_context_unsigned,
+            encode_enumerated, encode_opening_tag, get_tagged_body_for_tag, is_next_context_tag,
+            peek_tag, ListIter,
+        },
+        io::{Reader, Writer},
+        object_id::{ObjectId, ObjectType},
+        property_id::PropertyId,
+        spec::{AuditOperation, ErrorClass, ErrorCode, BACNET_MAX_INSTANCE},
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+};
+
+// the value of a property before or after the operation (null when a commanded value is relinquished)
+// values other than a single primitive (e.g. a list written to Recipient_List) are kept as the raw encoded body
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AuditValue<'a> {
+    Null,
+    Value(ApplicationDataValue<'a>),
+    Constructed(OctetString<'a>),
+}
+
+impl<'a> AuditValue<'a> {
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Null => {
+                Tag::new(TagNumber::Application(ApplicationTagNumber::Null), 0).encode(writer)
+            }
+            Self::Value(x) => x.encode(writer),
+            Self::Constructed(x) => writer.extend_from_slice(x.as_slice()),
+        }
+    }
+
+    // decodes the whole body found between the value opening and closing tags
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(
+        object_id: &ObjectId,
+        property_id: &PropertyId,
+        buf: &'a [u8],
+    ) -> Result<Self, Error> {
+        let mut reader = Reader::new_with_len(buf.len());
+        if peek_tag(&reader, buf)?.number == TagNumber::Application(ApplicationTagNumber::Null) {
+            Tag::decode(&mut reader, buf)?;
+            if reader.eof() {
+                return Ok(Self::Null);
+            }
+            reader.index = 0;
+        }
+
+        // a single application tagged value that takes up the whole body
+        if let Ok(value) = ApplicationDataValue::decode(object_id, property_id, &mut reader, buf) {
+            if reader.eof() {
+                return Ok(Self::Value(value));
+            }
+        }
+
+        Ok(Self::Constructed(OctetString::new(buf)))
+    }
+}
+
+// the error returned by the target device when the operation failed
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuditResult {
+    pub error_class: ErrorClass,
+    pub error_code: ErrorCode,
+}
+
+impl AuditResult {
+    pub fn new(error_class: ErrorClass, error_code: ErrorCode) -> Self {
+        Self {
+            error_class,
+            error_code,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_enumerated(writer, u32::from(&self.error_class), None);
+        encode_enumerated(writer, u32::from(&self.error_code), None);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Enumerated),
+            "AuditResult decode error_class",
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let error_class =
+            ErrorClass::try_from(value).map_err(|e| Error::InvalidVariant(("ErrorClass", e)))?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Enumerated),
+            "AuditResult decode error_code",
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let error_code =
+            ErrorCode::try_from(value).map_err(|e| Error::InvalidVariant(("ErrorCode", e)))?;
+
+        Ok(Self {
+            error_class,
+            error_code,
+        })
+    }
+}
+
+// BACnetAuditNotification
+// the source is the device (and user) that requested the operation and the target is the device that carried it out
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AuditNotification<'a> {
+    pub source_timestamp: Option<TimeStamp>,
+    pub target_timestamp: Option<TimeStamp>,
+    pub source_device: Recipient<'a>,
+    pub source_object: Option<ObjectId>,
+    pub operation: AuditOperation,
+    pub source_comment: Option<CharacterString<'a>>,
+    pub target_comment: Option<CharacterString<'a>>,
+    pub invoke_id: Option<u8>,
+    pub source_user_id: Option<u16>,
+    pub source_user_role: Option<u8>,
+    pub target_device: Recipient<'a>,
+    pub target_object: Option<ObjectId>,
+    pub target_property: Option<PropertyReference>,
+    pub target_priority: Option<u8>,
+    pub target_value: Option<AuditValue<'a>>, // the value written
+    pub current_value: Option<AuditValue<'a>>, // the value of the property after the operation
+    pub result: Option<AuditResult>,          // the operation succeeded if not specified
+}
+
+impl<'a> AuditNotification<'a> {
+    const TAG_SOURCE_TIMESTAMP: u8 = 0;
+    const TAG_TARGET_TIMESTAMP: u8 = 1;
+    const TAG_SOURCE_DEVICE: u8 = 2;
+    const TAG_SOURCE_OBJECT: u8 = 3;
+    const TAG_OPERATION: u8 = 4;
+    const TAG_SOURCE_COMMENT: u8 = 5;
+    const TAG_TARGET_COMMENT: u8 = 6;
+    const TAG_INVOKE_ID: u8 = 7;
+    const TAG_SOURCE_USER_ID: u8 = 8;
+    const TAG_SOURCE_USER_ROLE: u8 = 9;
+    const TAG_TARGET_DEVICE: u8 = 10;
+    const TAG_TARGET_OBJECT: u8 = 11;
+    const TAG_TARGET_PROPERTY: u8 = 12;
+    const TAG_TARGET_PRIORITY: u8 = 13;
+    const TAG_TARGET_VALUE: u8 = 14;
+    const TAG_CURRENT_VALUE: u8 = 15;
+    const TAG_RESULT: u8 = 16;
+
+    pub fn new(
+        source_device: Recipient<'a>,
+        operation: AuditOperation,
+        target_device: Recipient<'a>,
+    ) -> Self {
+        Self {
+            source_timestamp: None,
+            target_timestamp: None,
+            source_device,
+            source_object: None,
+            operation,
+            source_comment: None,
+            target_comment: None,
+            invoke_id: None,
+            source_user_id: None,
+            source_user_role: None,
+            target_device,
+            target_object: None,
+            target_property: None,
+            target_priority: None,
+            target_value: None,
+            current_value: None,
+            result: None,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        // timestamps
+        if let Some(timestamp) = &self.source_timestamp {
+            encode_opening_tag(writer, Self::TAG_SOURCE_TIMESTAMP);
+            timestamp.encode(writer);
+            encode_closing_tag(writer, Self::TAG_SOURCE_TIMESTAMP);
+        }
+        if let Some(timestamp) = &self.target_timestamp {
+            encode_opening_tag(writer, Self::TAG_TARGET_TIMESTAMP);
+            timestamp.encode(writer);
+            encode_closing_tag(writer, Self::TAG_TARGET_TIMESTAMP);
+        }
+
+        // source
+        encode_opening_tag(writer, Self::TAG_SOURCE_DEVICE);
+        self.source_device.encode(writer);
+        encode_closing_tag(writer, Self::TAG_SOURCE_DEVICE);
+        if let Some(object_id) = &self.source_object {
+            encode_context_object_id(writer, Self::TAG_SOURCE_OBJECT, object_id);
+        }
+
+        // operation
+        encode_enumerated(writer, self.operation as u32, Some(Self::TAG_OPERATION));
+
+        // comments
+        if let Some(comment) = &self.source_comment {
+            encode_context_character_string(writer, Self::TAG_SOURCE_COMMENT, comment.as_str());
+        }
+        if let Some(comment) = &self.target_comment {
+            encode_context_character_string(writer, Self::TAG_TARGET_COMMENT, comment.as_str());
+        }
+
+        // source request and user
+        if let Some(invoke_id) = self.invoke_id {
+            encode_context_unsigned(writer, Self::TAG_INVOKE_ID, invoke_id);
+        }
+        if let Some(user_id) = self.source_user_id {
+            encode_context_unsigned(writer, Self::TAG_SOURCE_USER_ID, user_id);
+        }
+        if let Some(user_role) = self.source_user_role {
+            encode_context_unsigned(writer, Self::TAG_SOURCE_USER_ROLE, user_role);
+        }
+
+        // target
+        encode_opening_tag(writer, Self::TAG_TARGET_DEVICE);
+        self.target_device.encode(writer);
+        encode_closing_tag(writer, Self::TAG_TARGET_DEVICE);
+        if let Some(object_id) = &self.target_object {
+            encode_context_object_id(writer, Self::TAG_TARGET_OBJECT, object_id);
+        }
+        if let Some(property) = &self.target_property {
+            encode_opening_tag(writer, Self::TAG_TARGET_PROPERTY);
+            property.encode(writer);
+            encode_closing_tag(writer, Self::TAG_TARGET_PROPERTY);
+        }
+        if let Some(priority) = self.target_priority {
+            encode_context_unsigned(writer, Self::TAG_TARGET_PRIORITY, priority);
+        }
+
+        // values
+        if let Some(value) = &self.target_value {
+            encode_opening_tag(writer, Self::TAG_TARGET_VALUE);
+            value.encode(writer);
+            encode_closing_tag(writer, Self::TAG_TARGET_VALUE);
+        }
+        if let Some(value) = &self.current_value {
+            encode_opening_tag(writer, Self::TAG_CURRENT_VALUE);
+            value.encode(writer);
+            encode_closing_tag(writer, Self::TAG_CURRENT_VALUE);
+        }
+
+        // result
+        if let Some(result) = &self.result {
+            encode_opening_tag(writer, Self::TAG_RESULT);
+            result.encode(writer);
+            encode_closing_tag(writer, Self::TAG_RESULT);
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // timestamps
+        let mut source_timestamp = None;
+        if is_next_context_tag(reader, buf, Self::TAG_SOURCE_TIMESTAMP)? {
+            let inner_buf = get_tagged_body_for_tag(
+                reader,
+                buf,
+                Self::TAG_SOURCE_TIMESTAMP,
+                "AuditNotification decode source_timestamp",
+            )?;
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            source_timestamp = Some(TimeStamp::decode(&mut inner_reader, inner_buf)?);
+        }
+        let mut target_timestamp = None;
+        if is_next_context_tag(reader, buf, Self::TAG_TARGET_TIMESTAMP)? {
+            let inner_buf = get_tagged_body_for_tag(
+                reader,
+                buf,
+                Self::TAG_TARGET_TIMESTAMP,
+                "AuditNotification decode target_timestamp",
+            )?;
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            target_timestamp = Some(TimeStamp::decode(&mut inner_reader, inner_buf)?);
+        }
+
+        // source
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_SOURCE_DEVICE,
+            "AuditNotification decode source_device",
+        )?;
+        let mut inner_reader = Reader::new_with_len(inner_buf.len());
+        let source_device = Recipient::decode(&mut inner_reader, inner_buf)?;
+        let mut source_object = None;
+        if is_next_context_tag(reader, buf, Self::TAG_SOURCE_OBJECT)? {
+            source_object = Some(decode_context_object_id(
+                reader,
+                buf,
+                Self::TAG_SOURCE_OBJECT,
+                "AuditNotification decode source_object",
+            )?);
+        }
+
+        // operation
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_OPERATION),
+            "AuditNotification decode operation",
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let operation = AuditOperation::try_from(value)
+            .map_err(|x| Error::InvalidVariant(("AuditNotification decode operation", x)))?;
+
+        // comments
+        let mut source_comment = None;
+        if is_next_context_tag(reader, buf, Self::TAG_SOURCE_COMMENT)? {
+            let tag = Tag::decode(reader, buf)?;
+            source_comment = Some(CharacterString::decode(tag.value, reader, buf)?);
+        }
+        let mut target_comment = None;
+        if is_next_context_tag(reader, buf, Self::TAG_TARGET_COMMENT)? {
+            let tag = Tag::decode(reader, buf)?;
+            target_comment = Some(CharacterString::decode(tag.value, reader, buf)?);
+        }
+
+        // source request and user
+        let mut invoke_id = None;
+        if is_next_context_tag(reader, buf, Self::TAG_INVOKE_ID)? {
+            let tag = Tag::decode(reader, buf)?;
+            invoke_id = Some(decode_unsigned(tag.value, reader, buf)? as u8);
+        }
+        let mut source_user_id = None;
+        if is_next_context_tag(reader, buf, Self::TAG_SOURCE_USER_ID)? {
+            let tag = Tag::decode(reader, buf)?;
+            source_user_id = Some(decode_unsigned(tag.value, reader, buf)? as u16);
+        }
+        let mut source_user_role = None;
+        if is_next_context_tag(reader, buf, Self::TAG_SOURCE_USER_ROLE)? {
+            let tag = Tag::decode(reader, buf)?;
+            source_user_role = Some(decode_unsigned(tag.value, reader, buf)? as u8);
+        }
+
+        // target
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_TARGET_DEVICE,
+            "AuditNotification decode target_device",
+        )?;
+        let mut inner_reader = Reader::new_with_len(inner_buf.len());
+        let target_device = Recipient::decode(&mut inner_reader, inner_buf)?;
+        let mut target_object = None;
+        if is_next_context_tag(reader, buf, Self::TAG_TARGET_OBJECT)? {
+            target_object = Some(decode_context_object_id(
+                reader,
+                buf,
+                Self::TAG_TARGET_OBJECT,
+                "AuditNotification decode target_object",
+            )?);
+        }
+        let mut target_property = None;
+        if is_next_context_tag(reader, buf, Self::TAG_TARGET_PROPERTY)? {
+            let inner_buf = get_tagged_body_for_tag(
+                reader,
+                buf,
+                Self::TAG_TARGET_PROPERTY,
+                "AuditNotification decode target_property",
+            )?;
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            target_property = Some(PropertyReference::decode(&mut inner_reader, inner_buf)?);
+        }
+        let mut target_priority = None;
+        if is_next_context_tag(reader, buf, Self::TAG_TARGET_PRIORITY)? {
+            let tag = Tag::decode(reader, buf)?;
+            target_priority = Some(decode_unsigned(tag.value, reader, buf)? as u8);
+        }
+
+        // values (the target object and property tell us how to decode enumerations)
+        let object_id = target_object
+            .unwrap_or_else(|| ObjectId::new(ObjectType::ObjectDevice, BACNET_MAX_INSTANCE));
+        let property_id = target_property
+            .as_ref()
+            .map(|x| x.property_id)
+            .unwrap_or(PropertyId::PropPresentValue);
+        let mut target_value = None;
+        if is_next_context_tag(reader, buf, Self::TAG_TARGET_VALUE)? {
+            let inner_buf = get_tagged_body_for_tag(
+                reader,
+                buf,
+                Self::TAG_TARGET_VALUE,
+                "AuditNotification decode target_value",
+            )?;
+            target_value = Some(AuditValue::decode(&object_id, &property_id, inner_buf)?);
+        }
+        let mut current_value = None;
+        if is_next_context_tag(reader, buf, Self::TAG_CURRENT_VALUE)? {
+            let inner_buf = get_tagged_body_for_tag(
+                reader,
+                buf,
+                Self::TAG_CURRENT_VALUE,
+                "AuditNotification decode current_value",
+            )?;
+            current_value = Some(AuditValue::decode(&object_id, &property_id, inner_buf)?);
+        }
+
+        // result
+        let mut result = None;
+        if is_next_context_tag(reader, buf, Self::TAG_RESULT)? {
+            let inner_buf = get_tagged_body_for_tag(
+                reader,
+                buf,
+                Self::TAG_RESULT,
+                "AuditNotification decode result",
+            )?;
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            result = Some(AuditResult::decode(&mut inner_reader, inner_buf)?);
+        }
+
+        Ok(Self {
+            source_timestamp,
+            target_timestamp,
+            source_device,
+            source_object,
+            operation,
+            source_comment,
+            target_comment,
+            invoke_id,
+            source_user_id,
+            source_user_role,
+            target_device,
+            target_object,
+            target_property,
+            target_priority,
+            target_value,
+            current_value,
+            result,
+        })
+    }
+}
+
+// the request carries a list of notifications so that a device can report many operations at once
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AuditNotificationRequest<'a> {
+    pub notifications: &'a [AuditNotification<'a>],
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AuditNotificationRequest<'a> {
+    pub notifications: Vec<AuditNotification<'a>>,
+    _phantom: &'a Phantom,
+}
+
+impl<'a> AuditNotificationRequest<'a> {
+    const TAG_NOTIFICATIONS: u8 = 0;
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(notifications: &'a [AuditNotification<'a>]) -> Self {
+        Self {
+            notifications,
+            buf: &[],
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(notifications: Vec<AuditNotification<'a>>) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            notifications,
+            _phantom: &PHANTOM,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_opening_tag(writer, Self::TAG_NOTIFICATIONS);
+        #[cfg(not(feature = "alloc"))]
+        encode_list(
+            writer,
+            self.buf,
+            self.notifications,
+            AuditNotification::encode,
+        );
+        #[cfg(feature = "alloc")]
+        for notification in self.notifications.iter() {
+            notification.encode(writer);
+        }
+        encode_closing_tag(writer, Self::TAG_NOTIFICATIONS);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_NOTIFICATIONS,
+            "AuditNotificationRequest decode notifications",
+        )?;
+
+        #[cfg(not(feature = "alloc"))]
+        return Ok(Self {
+            notifications: &[],
+            buf: inner_buf,
+        });
+
+        #[cfg(feature = "alloc")]
+        {
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            let mut notifications = Vec::new();
+            while !inner_reader.eof() {
+                notifications.push(AuditNotification::decode(&mut inner_reader, inner_buf)?);
+            }
+            Ok(Self::new(notifications))
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ AuditNotificationRequest<'a> {
+    type Item = Result<AuditNotification<'a>, Error>;
+
+    type IntoIter = AuditNotificationIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        AuditNotificationIter {
+            list: ListIter::new(self.notifications, self.buf),
+        }
+    }
+}
+
+pub struct AuditNotificationIter<'a> {
+    list: ListIter<'a, AuditNotification<'a>>,
+}
+
+impl<'a> Iterator for AuditNotificationIter<'a> {
+    type Item = Result<AuditNotification<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.next_with(AuditNotification::decode)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ConfirmedRequest, ConfirmedRequestService},
+            primitives::{
+                data_value::{
+                    ApplicationDataValue, CharacterString, Date, DateTime, OctetString, Time,
+                    TimeStamp,
+                },
+                property_reference::PropertyReference,
+                recipient::Recipient,
+            },
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{AuditOperation, ErrorClass, ErrorCode},
+        },
+    };
+
+    use super::{AuditNotification, AuditNotificationRequest, AuditResult, AuditValue};
+
+    #[test]
+    fn reversable() {
+        use alloc::vec;
+
+        let source = Recipient::Device(ObjectId::new(ObjectType::ObjectDevice, 1));
+        let target = Recipient::Device(ObjectId::new(ObjectType::ObjectDevice, 2));
+        let setpoint = ObjectId::new(ObjectType::ObjectAnalogValue, 7);
+        let date_time = DateTime::new(
+            Date {
+                year: 2024,
+                month: 3,
+                day: 14,
+                wday: 4,
+            },
+            Time {
+                hour: 9,
+                minute: 30,
+                second: 0,
+                hundredths: 0,
+            },
+        );
+
+        let mut write =
+            AuditNotification::new(source.clone(), AuditOperation::Write, target.clone());
+        write.source_timestamp = Some(TimeStamp::DateTime(date_time));
+        write.source_comment = Some(CharacterString::new("raised for event"));
+        write.invoke_id = Some(12);
+        write.source_user_id = Some(1001);
+        write.target_object = Some(setpoint);
+        write.target_property = Some(PropertyReference::new(PropertyId::PropPresentValue, None));
+        write.target_priority = Some(8);
+        write.target_value = Some(AuditValue::Value(ApplicationDataValue::Real(21.5)));
+        write.current_value = Some(AuditValue::Value(ApplicationDataValue::Real(20.0)));
+
+        let mut relinquish = AuditNotification::new(source, AuditOperation::Write, target);
+        relinquish.target_object = Some(setpoint);
+        relinquish.target_value = Some(AuditValue::Null);
+        relinquish.result = Some(AuditResult::new(
+            ErrorClass::Property,
+            ErrorCode::WriteAccessDenied,
+        ));
+
+        // a recipient (device 12) added to a Recipient_List
+        let recipient = [0x0C, 0x02, 0x00, 0x00, 0x0C];
+        let mut add = AuditNotification::new(
+            Recipient::Device(ObjectId::new(ObjectType::ObjectDevice, 1)),
+            AuditOperation::Write,
+            Recipient::Device(ObjectId::new(ObjectType::ObjectDevice, 2)),
+        );
+        add.target_object = Some(ObjectId::new(ObjectType::ObjectNotificationClass, 1));
+        add.target_property = Some(PropertyReference::new(PropertyId::PropRecipientList, None));
+        add.target_value = Some(AuditValue::Constructed(OctetString::new(&recipient)));
+
+        let request = AuditNotificationRequest::new(vec![write, relinquish, add]);
+
+        // encoding
+        let mut buf = [0; 256];
+        let mut writer = Writer::new(&mut buf);
+        let service = ConfirmedRequestService::AuditNotification(request);
+        ConfirmedRequest::new(1, service).encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ConfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        let decoded = match decoded.service {
+            ConfirmedRequestService::AuditNotification(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        assert_eq!(decoded.notifications.len(), 3);
+
+        let write = &decoded.notifications[0];
+        assert_eq!(write.operation, AuditOperation::Write);
+        assert!(matches!(
+            write.source_timestamp,
+            Some(TimeStamp::DateTime(_))
+        ));
+        assert!(matches!(write.source_device, Recipient::Device(x) if x.id == 1));
+        assert!(matches!(write.target_device, Recipient::Device(x) if x.id == 2));
+        assert_eq!(
+            write.source_comment.as_ref().unwrap().inner,
+            "raised for event"
+        );
+        assert_eq!(write.invoke_id, Some(12));
+        assert_eq!(write.source_user_id, Some(1001));
+        assert_eq!(write.target_object, Some(setpoint));
+        assert_eq!(
+            write.target_property.as_ref().unwrap().property_id,
+            PropertyId::PropPresentValue
+        );
+        assert_eq!(write.target_priority, Some(8));
+        assert!(matches!(
+            write.target_value,
+            Some(AuditValue::Value(ApplicationDataValue::Real(x))) if x == 21.5
+        ));
+        assert!(matches!(
+            write.current_value,
+            Some(AuditValue::Value(ApplicationDataValue::Real(x))) if x == 20.0
+        ));
+        assert!(write.result.is_none());
+
+        let relinquish = &decoded.notifications[1];
+        assert!(matches!(relinquish.target_value, Some(AuditValue::Null)));
+        let result = relinquish.result.as_ref().unwrap();
+        assert!(matches!(result.error_class, ErrorClass::Property));
+        assert!(matches!(result.error_code, ErrorCode::WriteAccessDenied));
+
+        let add = &decoded.notifications[2];
+        match &add.target_value {
+            Some(AuditValue::Constructed(x)) => assert_eq!(x.as_slice(), recipient),
+            x => panic!("unexpected value {:?}", x),
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "alloc")))]
+mod no_alloc_tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ConfirmedRequest, ConfirmedRequestService},
+            primitives::{data_value::ApplicationDataValue, recipient::Recipient},
+        },
+        common::{
+            helper::assert_encodes_to,
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            spec::AuditOperation,
+        },
+    };
+
+    use super::{AuditNotification, AuditNotificationRequest, AuditValue};
+
+    #[test]
+    fn reversable() {
+        let source = Recipient::Device(ObjectId::new(ObjectType::ObjectDevice, 1));
+        let target = Recipient::Device(ObjectId::new(ObjectType::ObjectDevice, 2));
+        let mut write =
+            AuditNotification::new(source.clone(), AuditOperation::Write, target.clone());
+        write.target_object = Some(ObjectId::new(ObjectType::ObjectAnalogValue, 7));
+        write.target_value = Some(AuditValue::Value(ApplicationDataValue::Real(21.5)));
+        let notifications = [
+            write,
+            AuditNotification::new(source, AuditOperation::DeviceReset, target),
+        ];
+        let request = AuditNotificationRequest::new(&notifications);
+        assert_eq!((&request).into_iter().count(), 2);
+
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        let service = ConfirmedRequestService::AuditNotification(request);
+        ConfirmedRequest::new(1, service).encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ConfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        let request = match &decoded.service {
+            ConfirmedRequestService::AuditNotification(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        let mut notifications = request.into_iter();
+        let first = notifications.next().unwrap().unwrap();
+        assert_eq!(first.operation, AuditOperation::Write);
+        assert!(matches!(
+            first.target_value,
+            Some(AuditValue::Value(ApplicationDataValue::Real(x))) if x == 21.5
+        ));
+        let second = notifications.next().unwrap().unwrap();
+        assert_eq!(second.operation, AuditOperation::DeviceReset);
+        assert!(notifications.next().is_none());
+
+        assert_encodes_to(&buf[..len], |writer| decoded.encode(writer));
+    }
+}
@@ -3,13 +3,21 @@
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
+#[cfg(not(feature = "alloc"))]
+use crate::common::helper::encode_list;
+
 use crate::{
-    application_protocol::primitives::data_value::ApplicationDataValue,
+    application_protocol::primitives::{
+        data_value::ApplicationDataValue, recipient::RecipientProcess,
+    },
     common::{
         error::Error,
         helper::{
-            decode_unsigned, encode_context_bool, encode_context_object_id,
-            encode_context_unsigned, get_tagged_body_for_tag,
+            decode_context_bool, decode_context_object_id, decode_context_property_id,
+            decode_context_real, decode_unsigned, encode_closing_tag, encode_context_bool,
+            encode_context_enumerated, encode_context_object_id, encode_context_real,
+            encode_context_unsigned, encode_opening_tag, get_tagged_body_for_tag,
+            is_next_context_tag, ListIter,
         },
         io::{Reader, Writer},
         object_id::{ObjectId, ObjectType},
@@ -32,7 +40,7 @@ pub struct CovNotification<'a> {
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct CovNotificationValues<'a> {
-    _property_results: &'a [PropertyResult<'a>], // use this when encoding is implemented
+    pub property_results: &'a [PropertyResult<'a>],
     object_id: ObjectId,
     buf: &'a [u8],
 }
@@ -41,16 +49,50 @@ pub struct CovNotificationValues<'a> {
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct CovNotificationValues<'a> {
-    pub property_results: Vec<PropertyResult<'a>>, // use this when encoding is implemented
+    pub property_results: Vec<PropertyResult<'a>>,
     pub object_id: ObjectId,
 }
 
 impl<'a> CovNotificationValues<'a> {
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(object_id: ObjectId, property_results: &'a [PropertyResult<'a>]) -> Self {
+        Self {
+            property_results,
+            object_id,
+            buf: &[],
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(object_id: ObjectId, property_results: Vec<PropertyResult<'a>>) -> Self {
+        Self {
+            property_results,
+            object_id,
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_list(
+            writer,
+            self.buf,
+            self.property_results,
+            PropertyResult::encode,
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn encode(&self, writer: &mut Writer) {
+        for item in self.property_results.iter() {
+            item.encode(writer);
+        }
+    }
+
     #[cfg(not(feature = "alloc"))]
     pub fn decode(_reader: &mut Reader, buf: &'a [u8], object_id: ObjectId) -> Result<Self, Error> {
         Ok(CovNotificationValues {
             buf,
-            _property_results: &[],
+            property_results: &[],
             object_id,
         })
     }
@@ -79,13 +121,30 @@ pub struct PropertyResult<'a> {
 }
 
 impl<'a> PropertyResult<'a> {
+    const TAG_PROPERTY_ID: u8 = 0;
+    const TAG_VALUE: u8 = 2;
+
+    pub fn new(id: PropertyId, value: ApplicationDataValue<'a>) -> Self {
+        Self { id, value }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        // property id
+        encode_context_enumerated(writer, Self::TAG_PROPERTY_ID, &self.id);
+
+        // value
+        encode_opening_tag(writer, Self::TAG_VALUE);
+        self.value.encode(writer);
+        encode_closing_tag(writer, Self::TAG_VALUE);
+    }
+
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
     pub fn decode(reader: &mut Reader, buf: &'a [u8], object_id: &ObjectId) -> Result<Self, Error> {
         // property id
         let tag = Tag::decode_expected(
             reader,
             buf,
-            TagNumber::ContextSpecific(0),
+            TagNumber::ContextSpecific(Self::TAG_PROPERTY_ID),
             "CovNotification next property_id",
         )?;
         let property_id: PropertyId = (decode_unsigned(tag.value, reader, buf)? as u32).into();
@@ -94,14 +153,14 @@ impl<'a> PropertyResult<'a> {
         Tag::decode_expected(
             reader,
             buf,
-            TagNumber::ContextSpecificOpening(2),
+            TagNumber::ContextSpecificOpening(Self::TAG_VALUE),
             "CovNotification next expected value opening tag",
         )?;
         let value = ApplicationDataValue::decode(object_id, &property_id, reader, buf)?;
         Tag::decode_expected(
             reader,
             buf,
-            TagNumber::ContextSpecificClosing(2),
+            TagNumber::ContextSpecificClosing(Self::TAG_VALUE),
             "CovNotification next expected value closing tag",
         )?;
 
@@ -119,6 +178,42 @@ impl<'a> CovNotification<'a> {
     const TAG_LIFETIME: u8 = 3;
     const TAG_LIST_OF_VALUES: u8 = 4;
 
+    pub fn new(
+        process_id: u32,
+        device_id: ObjectId,
+        time_remaining_seconds: u32,
+        values: CovNotificationValues<'a>,
+    ) -> Self {
+        Self {
+            process_id,
+            device_id,
+            object_id: values.object_id,
+            time_remaining_seconds,
+            values,
+        }
+    }
+
+    // the service choice is not encoded here because this notification can be sent
+    // as either a confirmed or an unconfirmed request
+    pub fn encode(&self, writer: &mut Writer) {
+        // process_id
+        encode_context_unsigned(writer, Self::TAG_PROCESS_ID, self.process_id);
+
+        // device_id
+        encode_context_object_id(writer, Self::TAG_DEVICE_ID, &self.device_id);
+
+        // object_id
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
+
+        // lifetime
+        encode_context_unsigned(writer, Self::TAG_LIFETIME, self.time_remaining_seconds);
+
+        // values
+        encode_opening_tag(writer, Self::TAG_LIST_OF_VALUES);
+        self.values.encode(writer);
+        encode_closing_tag(writer, Self::TAG_LIST_OF_VALUES);
+    }
+
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
     pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
         // parse a tag, starting from after the pdu type and service choice
@@ -191,29 +286,24 @@ impl<'a> IntoIterator for &'_ CovNotificationValues<'a> {
 
     fn into_iter(self) -> Self::IntoIter {
         CovNotificationIter {
-            buf: self.buf,
-            reader: Reader::new_with_len(self.buf.len()),
+            list: ListIter::new(self.property_results, self.buf),
             object_id: self.object_id,
         }
     }
 }
 
 pub struct CovNotificationIter<'a> {
+    list: ListIter<'a, PropertyResult<'a>>,
     object_id: ObjectId,
-    reader: Reader,
-    buf: &'a [u8],
 }
 
 impl<'a> Iterator for CovNotificationIter<'a> {
     type Item = Result<PropertyResult<'a>, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.reader.eof() {
-            return None;
-        }
-
-        let result = PropertyResult::decode(&mut self.reader, self.buf, &self.object_id);
-        Some(result)
+        let object_id = &self.object_id;
+        self.list
+            .next_with(|reader, buf| PropertyResult::decode(reader, buf, object_id))
     }
 }
 
@@ -222,8 +312,8 @@ impl<'a> Iterator for CovNotificationIter<'a> {
 pub struct SubscribeCov {
     process_id: u32,
     object_id: ObjectId,
-    issue_confirmed_notifications: bool,
-    lifetime_seconds: u32, // zero for indefinite
+    issue_confirmed_notifications: Option<bool>, // None to cancel the subscription
+    lifetime_seconds: Option<u32>,               // zero for indefinite
 }
 
 impl SubscribeCov {
@@ -241,8 +331,18 @@ impl SubscribeCov {
         Self {
             process_id,
             object_id,
-            issue_confirmed_notifications,
-            lifetime_seconds,
+            issue_confirmed_notifications: Some(issue_confirmed_notifications),
+            lifetime_seconds: Some(lifetime_seconds),
+        }
+    }
+
+    // cancels an existing subscription with the same process_id and object_id
+    pub fn cancel(process_id: u32, object_id: ObjectId) -> Self {
+        Self {
+            process_id,
+            object_id,
+            issue_confirmed_notifications: None,
+            lifetime_seconds: None,
         }
     }
 
@@ -254,13 +354,266 @@ impl SubscribeCov {
         encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
 
         // issue confirmed notifications
+        if let Some(confirmed) = self.issue_confirmed_notifications {
+            encode_context_bool(writer, Self::TAG_CONFIRMED, confirmed);
+        }
+
+        // lifetime of subscription
+        if let Some(lifetime_seconds) = self.lifetime_seconds {
+            encode_context_unsigned(writer, Self::TAG_LIFETIME, lifetime_seconds);
+        }
+    }
+}
+
+// BACnetCOVSubscription: an entry in the Active_COV_Subscriptions list of a device
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CovSubscription<'a> {
+    pub recipient: RecipientProcess<'a>,
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub issue_confirmed_notifications: bool,
+    pub time_remaining_seconds: u32,
+    pub cov_increment: Option<f32>,
+}
+
+impl<'a> CovSubscription<'a> {
+    const TAG_RECIPIENT: u8 = 0;
+    const TAG_MONITORED_PROPERTY: u8 = 1;
+    const TAG_CONFIRMED: u8 = 2;
+    const TAG_TIME_REMAINING: u8 = 3;
+    const TAG_COV_INCREMENT: u8 = 4;
+
+    // tags inside the monitored property reference
+    const TAG_OBJECT_ID: u8 = 0;
+    const TAG_PROPERTY_ID: u8 = 1;
+    const TAG_ARRAY_INDEX: u8 = 2;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_opening_tag(writer, Self::TAG_RECIPIENT);
+        self.recipient.encode(writer);
+        encode_closing_tag(writer, Self::TAG_RECIPIENT);
+
+        encode_opening_tag(writer, Self::TAG_MONITORED_PROPERTY);
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
+        encode_context_enumerated(writer, Self::TAG_PROPERTY_ID, &self.property_id);
+        if let Some(array_index) = self.array_index {
+            encode_context_unsigned(writer, Self::TAG_ARRAY_INDEX, array_index);
+        }
+        encode_closing_tag(writer, Self::TAG_MONITORED_PROPERTY);
+
         encode_context_bool(
             writer,
             Self::TAG_CONFIRMED,
             self.issue_confirmed_notifications,
         );
+        encode_context_unsigned(
+            writer,
+            Self::TAG_TIME_REMAINING,
+            self.time_remaining_seconds,
+        );
+        if let Some(cov_increment) = self.cov_increment {
+            encode_context_real(writer, Self::TAG_COV_INCREMENT, cov_increment);
+        }
+    }
 
-        // lifetime of subscription
-        encode_context_unsigned(writer, Self::TAG_LIFETIME, self.lifetime_seconds);
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // recipient
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_RECIPIENT,
+            "CovSubscription decode recipient",
+        )?;
+        let mut inner_reader = Reader::new_with_len(inner_buf.len());
+        let recipient = RecipientProcess::decode(&mut inner_reader, inner_buf)?;
+
+        // monitored property
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_MONITORED_PROPERTY,
+            "CovSubscription decode monitored_property",
+        )?;
+        let mut inner_reader = Reader::new_with_len(inner_buf.len());
+        let object_id = decode_context_object_id(
+            &mut inner_reader,
+            inner_buf,
+            Self::TAG_OBJECT_ID,
+            "CovSubscription decode object_id",
+        )?;
+        let property_id = decode_context_property_id(
+            &mut inner_reader,
+            inner_buf,
+            Self::TAG_PROPERTY_ID,
+            "CovSubscription decode property_id",
+        )?;
+        let mut array_index = None;
+        if !inner_reader.eof() {
+            let tag = Tag::decode_expected(
+                &mut inner_reader,
+                inner_buf,
+                TagNumber::ContextSpecific(Self::TAG_ARRAY_INDEX),
+                "CovSubscription decode array_index",
+            )?;
+            array_index = Some(decode_unsigned(tag.value, &mut inner_reader, inner_buf)? as u32);
+        }
+
+        let issue_confirmed_notifications = decode_context_bool(
+            reader,
+            buf,
+            Self::TAG_CONFIRMED,
+            "CovSubscription decode issue_confirmed_notifications",
+        )?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_TIME_REMAINING),
+            "CovSubscription decode time_remaining",
+        )?;
+        let time_remaining_seconds = decode_unsigned(tag.value, reader, buf)? as u32;
+
+        let mut cov_increment = None;
+        if is_next_context_tag(reader, buf, Self::TAG_COV_INCREMENT)? {
+            cov_increment = Some(decode_context_real(
+                reader,
+                buf,
+                Self::TAG_COV_INCREMENT,
+                "CovSubscription decode cov_increment",
+            )?);
+        }
+
+        Ok(Self {
+            recipient,
+            object_id,
+            property_id,
+            array_index,
+            issue_confirmed_notifications,
+            time_remaining_seconds,
+            cov_increment,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::{
+            primitives::data_value::ApplicationDataValue, unconfirmed::UnconfirmedRequest,
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+    };
+
+    use super::{CovNotification, CovNotificationValues, PropertyResult};
+
+    #[test]
+    fn reversable() {
+        use alloc::vec;
+
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+        let property_results = vec![
+            PropertyResult::new(
+                PropertyId::PropPresentValue,
+                ApplicationDataValue::Real(21.5),
+            ),
+            PropertyResult::new(
+                PropertyId::PropCovIncrement,
+                ApplicationDataValue::Real(0.5),
+            ),
+        ];
+        let values = CovNotificationValues::new(object_id, property_results);
+        let device_id = ObjectId::new(ObjectType::ObjectDevice, 79079);
+        let notification = CovNotification::new(7, device_id, 300, values);
+
+        // encoding
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        UnconfirmedRequest::CovNotification(notification).encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = match UnconfirmedRequest::decode(&mut reader, &buf[..len]).unwrap() {
+            UnconfirmedRequest::CovNotification(x) => x,
+            x => panic!("unexpected request {:?}", x),
+        };
+        assert_eq!(decoded.process_id, 7);
+        assert_eq!(decoded.device_id, device_id);
+        assert_eq!(decoded.object_id, object_id);
+        assert_eq!(decoded.time_remaining_seconds, 300);
+        assert_eq!(decoded.values.property_results.len(), 2);
+        assert_eq!(
+            decoded.values.property_results[1].id,
+            PropertyId::PropCovIncrement
+        );
+    }
+}
+
+#[cfg(all(test, not(feature = "alloc")))]
+mod no_alloc_tests {
+    use crate::{
+        application_protocol::{
+            primitives::data_value::ApplicationDataValue, unconfirmed::UnconfirmedRequest,
+        },
+        common::{
+            helper::assert_encodes_to,
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+    };
+
+    use super::{CovNotification, CovNotificationValues, PropertyResult};
+
+    #[test]
+    fn reversable() {
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+        let property_results = [
+            PropertyResult::new(
+                PropertyId::PropPresentValue,
+                ApplicationDataValue::Real(21.5),
+            ),
+            PropertyResult::new(
+                PropertyId::PropCovIncrement,
+                ApplicationDataValue::Real(0.5),
+            ),
+        ];
+        let values = CovNotificationValues::new(object_id, &property_results);
+        assert_eq!((&values).into_iter().count(), 2);
+        let device_id = ObjectId::new(ObjectType::ObjectDevice, 79079);
+        let notification = CovNotification::new(7, device_id, 300, values);
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        UnconfirmedRequest::CovNotification(notification).encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = match UnconfirmedRequest::decode(&mut reader, &buf[..len]).unwrap() {
+            UnconfirmedRequest::CovNotification(x) => x,
+            x => panic!("unexpected request {:?}", x),
+        };
+        let mut values = (&decoded.values).into_iter();
+        let first = values.next().unwrap().unwrap();
+        assert_eq!(first.id, PropertyId::PropPresentValue);
+        assert!(matches!(first.value, ApplicationDataValue::Real(x) if x == 21.5));
+        assert_eq!(
+            values.next().unwrap().unwrap().id,
+            PropertyId::PropCovIncrement
+        );
+        assert!(values.next().is_none());
+
+        assert_encodes_to(&buf[..len], |writer| {
+            UnconfirmedRequest::CovNotification(decoded).encode(writer)
+        });
     }
 }
@@ -0,0 +1,1123 @@
+// change of value for multiple objects and properties (addendum 2012-aq)
+
+#[cfg(feature = "alloc")]
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
+
+use crate::{
+    application_protocol::primitives::data_value::{ApplicationDataValue, DateTime, Time},
+    common::{
+        error::Error,
+        helper::{
+            decode_context_bool, decode_context_object_id, decode_context_property_id,
+            decode_unsigned, encode_closing_tag, encode_context_bool, encode_context_enumerated,
+            encode_context_object_id, encode_context_real, encode_context_unsigned, encode_list,
+            encode_opening_tag, get_tagged_body_for_tag, peek_tag, ListIter,
+        },
+        io::{Reader, Writer},
+        object_id::{ObjectId, ObjectType},
+        property_id::PropertyId,
+        tag::{Tag, TagNumber},
+    },
+};
+
+// a property (and optional array index) on the monitored object to subscribe to
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CovReference {
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub cov_increment: Option<f32>,
+    pub timestamped: bool,
+}
+
+impl CovReference {
+    const TAG_MONITORED_PROPERTY: u8 = 0;
+    const TAG_PROPERTY_ID: u8 = 0;
+    const TAG_ARRAY_INDEX: u8 = 1;
+    const TAG_COV_INCREMENT: u8 = 1;
+    const TAG_TIMESTAMPED: u8 = 2;
+
+    pub fn new(property_id: PropertyId, timestamped: bool) -> Self {
+        Self {
+            property_id,
+            array_index: None,
+            cov_increment: None,
+            timestamped,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        // monitored property
+        encode_opening_tag(writer, Self::TAG_MONITORED_PROPERTY);
+        encode_context_enumerated(writer, Self::TAG_PROPERTY_ID, &self.property_id);
+        if let Some(array_index) = self.array_index {
+            encode_context_unsigned(writer, Self::TAG_ARRAY_INDEX, array_index);
+        }
+        encode_closing_tag(writer, Self::TAG_MONITORED_PROPERTY);
+
+        // cov increment
+        if let Some(cov_increment) = self.cov_increment {
+            encode_context_real(writer, Self::TAG_COV_INCREMENT, cov_increment);
+        }
+
+        // timestamped
+        encode_context_bool(writer, Self::TAG_TIMESTAMPED, self.timestamped);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        // monitored property
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecificOpening(Self::TAG_MONITORED_PROPERTY),
+            "CovReference decode monitored property",
+        )?;
+        let property_id = decode_context_property_id(
+            reader,
+            buf,
+            Self::TAG_PROPERTY_ID,
+            "CovReference decode property_id",
+        )?;
+        let mut tag = Tag::decode(reader, buf)?;
+        let mut array_index = None;
+        if tag.number == TagNumber::ContextSpecific(Self::TAG_ARRAY_INDEX) {
+            array_index = Some(decode_unsigned(tag.value, reader, buf)? as u32);
+            tag = Tag::decode(reader, buf)?;
+        }
+        tag.expect_number(
+            "CovReference decode monitored property",
+            TagNumber::ContextSpecificClosing(Self::TAG_MONITORED_PROPERTY),
+        )?;
+
+        // cov increment
+        let mut cov_increment = None;
+        if peek_tag(reader, buf)?.number == TagNumber::ContextSpecific(Self::TAG_COV_INCREMENT) {
+            let tag = Tag::decode(reader, buf)?;
+            tag.expect_value("CovReference decode cov_increment", 4)?;
+            cov_increment = Some(f32::from_be_bytes(reader.read_bytes(buf)?));
+        }
+
+        // timestamped
+        let timestamped = decode_context_bool(
+            reader,
+            buf,
+            Self::TAG_TIMESTAMPED,
+            "CovReference decode timestamped",
+        )?;
+
+        Ok(Self {
+            property_id,
+            array_index,
+            cov_increment,
+            timestamped,
+        })
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CovSubscriptionSpecification<'a> {
+    pub object_id: ObjectId,
+    pub references: CovReferenceList<'a>,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CovSubscriptionSpecification<'a> {
+    pub object_id: ObjectId,
+    pub references: Vec<CovReference>,
+    _phantom: &'a Phantom,
+}
+
+impl<'a> CovSubscriptionSpecification<'a> {
+    const TAG_OBJECT_ID: u8 = 0;
+    const TAG_REFERENCES: u8 = 1;
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(object_id: ObjectId, references: &'a [CovReference]) -> Self {
+        Self {
+            object_id,
+            references: CovReferenceList::new(references),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(object_id: ObjectId, references: Vec<CovReference>) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            object_id,
+            references,
+            _phantom: &PHANTOM,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
+        encode_opening_tag(writer, Self::TAG_REFERENCES);
+        #[cfg(not(feature = "alloc"))]
+        self.references.encode(writer);
+        #[cfg(feature = "alloc")]
+        for reference in self.references.iter() {
+            reference.encode(writer);
+        }
+        encode_closing_tag(writer, Self::TAG_REFERENCES);
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let object_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_OBJECT_ID,
+            "CovSubscriptionSpecification decode object_id",
+        )?;
+        let buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_REFERENCES,
+            "CovSubscriptionSpecification decode references",
+        )?;
+
+        Ok(Self {
+            object_id,
+            references: CovReferenceList::new_from_buf(buf),
+        })
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let object_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_OBJECT_ID,
+            "CovSubscriptionSpecification decode object_id",
+        )?;
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_REFERENCES,
+            "CovSubscriptionSpecification decode references",
+        )?;
+        let mut inner_reader = Reader::new_with_len(inner_buf.len());
+        let mut references = Vec::new();
+        while !inner_reader.eof() {
+            references.push(CovReference::decode(&mut inner_reader, inner_buf)?);
+        }
+
+        Ok(Self::new(object_id, references))
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CovReferenceList<'a> {
+    pub references: &'a [CovReference],
+    buf: &'a [u8],
+}
+
+impl<'a> CovReferenceList<'a> {
+    pub fn new(references: &'a [CovReference]) -> Self {
+        Self {
+            references,
+            buf: &[],
+        }
+    }
+
+    pub fn new_from_buf(buf: &'a [u8]) -> Self {
+        Self {
+            references: &[],
+            buf,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_list(writer, self.buf, self.references, CovReference::encode);
+    }
+}
+
+impl<'a> IntoIterator for &'_ CovReferenceList<'a> {
+    type Item = Result<CovReference, Error>;
+    type IntoIter = CovReferenceIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        CovReferenceIter {
+            list: ListIter::new(self.references, self.buf),
+        }
+    }
+}
+
+pub struct CovReferenceIter<'a> {
+    list: ListIter<'a, CovReference>,
+}
+
+impl<'a> Iterator for CovReferenceIter<'a> {
+    type Item = Result<CovReference, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.next_with(CovReference::decode)
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SubscribeCovPropertyMultiple<'a> {
+    pub process_id: u32,
+    pub issue_confirmed_notifications: Option<bool>,
+    pub lifetime_seconds: Option<u32>, // zero for indefinite
+    pub max_notification_delay_seconds: Option<u32>,
+    pub subscriptions: &'a [CovSubscriptionSpecification<'a>],
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SubscribeCovPropertyMultiple<'a> {
+    pub process_id: u32,
+    pub issue_confirmed_notifications: Option<bool>,
+    pub lifetime_seconds: Option<u32>, // zero for indefinite
+    pub max_notification_delay_seconds: Option<u32>,
+    pub subscriptions: Vec<CovSubscriptionSpecification<'a>>,
+}
+
+impl<'a> SubscribeCovPropertyMultiple<'a> {
+    const TAG_PROCESS_ID: u8 = 0;
+    const TAG_CONFIRMED: u8 = 1;
+    const TAG_LIFETIME: u8 = 2;
+    const TAG_MAX_NOTIFICATION_DELAY: u8 = 3;
+    const TAG_SUBSCRIPTIONS: u8 = 4;
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(
+        process_id: u32,
+        issue_confirmed_notifications: bool,
+        lifetime_seconds: u32,
+        subscriptions: &'a [CovSubscriptionSpecification<'a>],
+    ) -> Self {
+        Self {
+            process_id,
+            issue_confirmed_notifications: Some(issue_confirmed_notifications),
+            lifetime_seconds: Some(lifetime_seconds),
+            max_notification_delay_seconds: None,
+            subscriptions,
+            buf: &[],
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(
+        process_id: u32,
+        issue_confirmed_notifications: bool,
+        lifetime_seconds: u32,
+        subscriptions: Vec<CovSubscriptionSpecification<'a>>,
+    ) -> Self {
+        Self {
+            process_id,
+            issue_confirmed_notifications: Some(issue_confirmed_notifications),
+            lifetime_seconds: Some(lifetime_seconds),
+            max_notification_delay_seconds: None,
+            subscriptions,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        // subscriber process_id
+        encode_context_unsigned(writer, Self::TAG_PROCESS_ID, self.process_id);
+
+        // issue confirmed notifications (omit to cancel the subscription)
+        if let Some(confirmed) = self.issue_confirmed_notifications {
+            encode_context_bool(writer, Self::TAG_CONFIRMED, confirmed);
+        }
+
+        // lifetime of subscription
+        if let Some(lifetime_seconds) = self.lifetime_seconds {
+            encode_context_unsigned(writer, Self::TAG_LIFETIME, lifetime_seconds);
+        }
+
+        // max notification delay
+        if let Some(delay) = self.max_notification_delay_seconds {
+            encode_context_unsigned(writer, Self::TAG_MAX_NOTIFICATION_DELAY, delay);
+        }
+
+        // list of cov subscription specifications
+        encode_opening_tag(writer, Self::TAG_SUBSCRIPTIONS);
+        #[cfg(not(feature = "alloc"))]
+        encode_list(
+            writer,
+            self.buf,
+            self.subscriptions,
+            CovSubscriptionSpecification::encode,
+        );
+        #[cfg(feature = "alloc")]
+        for subscription in self.subscriptions.iter() {
+            subscription.encode(writer);
+        }
+        encode_closing_tag(writer, Self::TAG_SUBSCRIPTIONS);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // process_id
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_PROCESS_ID),
+            "SubscribeCovPropertyMultiple decode process_id",
+        )?;
+        let process_id = decode_unsigned(tag.value, reader, buf)? as u32;
+
+        // issue confirmed notifications
+        let mut issue_confirmed_notifications = None;
+        if peek_tag(reader, buf)?.number == TagNumber::ContextSpecific(Self::TAG_CONFIRMED) {
+            issue_confirmed_notifications = Some(decode_context_bool(
+                reader,
+                buf,
+                Self::TAG_CONFIRMED,
+                "SubscribeCovPropertyMultiple decode confirmed",
+            )?);
+        }
+
+        // lifetime
+        let mut lifetime_seconds = None;
+        if peek_tag(reader, buf)?.number == TagNumber::ContextSpecific(Self::TAG_LIFETIME) {
+            let tag = Tag::decode(reader, buf)?;
+            lifetime_seconds = Some(decode_unsigned(tag.value, reader, buf)? as u32);
+        }
+
+        // max notification delay
+        let mut max_notification_delay_seconds = None;
+        if peek_tag(reader, buf)?.number
+            == TagNumber::ContextSpecific(Self::TAG_MAX_NOTIFICATION_DELAY)
+        {
+            let tag = Tag::decode(reader, buf)?;
+            max_notification_delay_seconds = Some(decode_unsigned(tag.value, reader, buf)? as u32);
+        }
+
+        // list of cov subscription specifications
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_SUBSCRIPTIONS,
+            "SubscribeCovPropertyMultiple decode subscriptions",
+        )?;
+
+        #[cfg(not(feature = "alloc"))]
+        return Ok(Self {
+            process_id,
+            issue_confirmed_notifications,
+            lifetime_seconds,
+            max_notification_delay_seconds,
+            subscriptions: &[],
+            buf: inner_buf,
+        });
+
+        #[cfg(feature = "alloc")]
+        {
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            let mut subscriptions = Vec::new();
+            while !inner_reader.eof() {
+                let subscription =
+                    CovSubscriptionSpecification::decode(&mut inner_reader, inner_buf)?;
+                subscriptions.push(subscription);
+            }
+
+            Ok(Self {
+                process_id,
+                issue_confirmed_notifications,
+                lifetime_seconds,
+                max_notification_delay_seconds,
+                subscriptions,
+            })
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ SubscribeCovPropertyMultiple<'a> {
+    type Item = Result<CovSubscriptionSpecification<'a>, Error>;
+    type IntoIter = CovSubscriptionSpecificationIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        CovSubscriptionSpecificationIter {
+            list: ListIter::new(self.subscriptions, self.buf),
+        }
+    }
+}
+
+pub struct CovSubscriptionSpecificationIter<'a> {
+    list: ListIter<'a, CovSubscriptionSpecification<'a>>,
+}
+
+impl<'a> Iterator for CovSubscriptionSpecificationIter<'a> {
+    type Item = Result<CovSubscriptionSpecification<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.next_with(CovSubscriptionSpecification::decode)
+    }
+}
+
+// a property value of a monitored object with an optional time of change
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CovPropertyValue<'a> {
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub value: ApplicationDataValue<'a>,
+    pub time_of_change: Option<Time>,
+}
+
+impl<'a> CovPropertyValue<'a> {
+    const TAG_PROPERTY_ID: u8 = 0;
+    const TAG_ARRAY_INDEX: u8 = 1;
+    const TAG_VALUE: u8 = 2;
+    const TAG_TIME_OF_CHANGE: u8 = 3;
+
+    pub fn new(
+        property_id: PropertyId,
+        value: ApplicationDataValue<'a>,
+        time_of_change: Option<Time>,
+    ) -> Self {
+        Self {
+            property_id,
+            array_index: None,
+            value,
+            time_of_change,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        // property_id
+        encode_context_enumerated(writer, Self::TAG_PROPERTY_ID, &self.property_id);
+
+        // array_index
+        if let Some(array_index) = self.array_index {
+            encode_context_unsigned(writer, Self::TAG_ARRAY_INDEX, array_index);
+        }
+
+        // value
+        encode_opening_tag(writer, Self::TAG_VALUE);
+        self.value.encode(writer);
+        encode_closing_tag(writer, Self::TAG_VALUE);
+
+        // time of change
+        if let Some(time) = &self.time_of_change {
+            Tag::new(
+                TagNumber::ContextSpecific(Self::TAG_TIME_OF_CHANGE),
+                Time::LEN,
+            )
+            .encode(writer);
+            time.encode(writer);
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8], object_id: &ObjectId) -> Result<Self, Error> {
+        // property_id
+        let property_id = decode_context_property_id(
+            reader,
+            buf,
+            Self::TAG_PROPERTY_ID,
+            "CovPropertyValue decode property_id",
+        )?;
+
+        // array_index
+        let mut tag = Tag::decode(reader, buf)?;
+        let mut array_index = None;
+        if tag.number == TagNumber::ContextSpecific(Self::TAG_ARRAY_INDEX) {
+            array_index = Some(decode_unsigned(tag.value, reader, buf)? as u32);
+            tag = Tag::decode(reader, buf)?;
+        }
+
+        // value
+        tag.expect_number(
+            "CovPropertyValue decode value",
+            TagNumber::ContextSpecificOpening(Self::TAG_VALUE),
+        )?;
+        let value = ApplicationDataValue::decode(object_id, &property_id, reader, buf)?;
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecificClosing(Self::TAG_VALUE),
+            "CovPropertyValue decode value",
+        )?;
+
+        // time of change
+        let mut time_of_change = None;
+        if !reader.eof()
+            && peek_tag(reader, buf)?.number == TagNumber::ContextSpecific(Self::TAG_TIME_OF_CHANGE)
+        {
+            let tag = Tag::decode(reader, buf)?;
+            tag.expect_value("CovPropertyValue decode time_of_change", Time::LEN)?;
+            time_of_change = Some(Time::decode(reader, buf)?);
+        }
+
+        Ok(Self {
+            property_id,
+            array_index,
+            value,
+            time_of_change,
+        })
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CovObjectNotification<'a> {
+    pub object_id: ObjectId,
+    pub values: CovPropertyValueList<'a>,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CovObjectNotification<'a> {
+    pub object_id: ObjectId,
+    pub values: Vec<CovPropertyValue<'a>>,
+}
+
+impl<'a> CovObjectNotification<'a> {
+    const TAG_OBJECT_ID: u8 = 0;
+    const TAG_VALUES: u8 = 1;
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(object_id: ObjectId, values: &'a [CovPropertyValue<'a>]) -> Self {
+        Self {
+            object_id,
+            values: CovPropertyValueList::new(object_id, values),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(object_id: ObjectId, values: Vec<CovPropertyValue<'a>>) -> Self {
+        Self { object_id, values }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
+        encode_opening_tag(writer, Self::TAG_VALUES);
+        #[cfg(not(feature = "alloc"))]
+        self.values.encode(writer);
+        #[cfg(feature = "alloc")]
+        for value in self.values.iter() {
+            value.encode(writer);
+        }
+        encode_closing_tag(writer, Self::TAG_VALUES);
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let object_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_OBJECT_ID,
+            "CovObjectNotification decode object_id",
+        )?;
+        let buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_VALUES,
+            "CovObjectNotification decode values",
+        )?;
+
+        Ok(Self {
+            object_id,
+            values: CovPropertyValueList::new_from_buf(object_id, buf),
+        })
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let object_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_OBJECT_ID,
+            "CovObjectNotification decode object_id",
+        )?;
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_VALUES,
+            "CovObjectNotification decode values",
+        )?;
+        let mut inner_reader = Reader::new_with_len(inner_buf.len());
+        let mut values = Vec::new();
+        while !inner_reader.eof() {
+            let value = CovPropertyValue::decode(&mut inner_reader, inner_buf, &object_id)?;
+            values.push(value);
+        }
+
+        Ok(Self::new(object_id, values))
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CovPropertyValueList<'a> {
+    pub values: &'a [CovPropertyValue<'a>],
+    object_id: ObjectId,
+    buf: &'a [u8],
+}
+
+impl<'a> CovPropertyValueList<'a> {
+    pub fn new(object_id: ObjectId, values: &'a [CovPropertyValue<'a>]) -> Self {
+        Self {
+            values,
+            object_id,
+            buf: &[],
+        }
+    }
+
+    pub fn new_from_buf(object_id: ObjectId, buf: &'a [u8]) -> Self {
+        Self {
+            values: &[],
+            object_id,
+            buf,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_list(writer, self.buf, self.values, CovPropertyValue::encode);
+    }
+}
+
+impl<'a> IntoIterator for &'_ CovPropertyValueList<'a> {
+    type Item = Result<CovPropertyValue<'a>, Error>;
+    type IntoIter = CovPropertyValueIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        CovPropertyValueIter {
+            list: ListIter::new(self.values, self.buf),
+            object_id: self.object_id,
+        }
+    }
+}
+
+pub struct CovPropertyValueIter<'a> {
+    list: ListIter<'a, CovPropertyValue<'a>>,
+    object_id: ObjectId,
+}
+
+impl<'a> Iterator for CovPropertyValueIter<'a> {
+    type Item = Result<CovPropertyValue<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let object_id = &self.object_id;
+        self.list
+            .next_with(|reader, buf| CovPropertyValue::decode(reader, buf, object_id))
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CovNotificationMultiple<'a> {
+    pub process_id: u32,
+    pub device_id: ObjectId,
+    pub time_remaining_seconds: u32,
+    pub timestamp: Option<DateTime>,
+    pub notifications: &'a [CovObjectNotification<'a>],
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CovNotificationMultiple<'a> {
+    pub process_id: u32,
+    pub device_id: ObjectId,
+    pub time_remaining_seconds: u32,
+    pub timestamp: Option<DateTime>,
+    pub notifications: Vec<CovObjectNotification<'a>>,
+}
+
+impl<'a> CovNotificationMultiple<'a> {
+    const TAG_PROCESS_ID: u8 = 0;
+    const TAG_DEVICE_ID: u8 = 1;
+    const TAG_LIFETIME: u8 = 2;
+    const TAG_TIMESTAMP: u8 = 3;
+    const TAG_NOTIFICATIONS: u8 = 4;
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(
+        process_id: u32,
+        device_id: ObjectId,
+        time_remaining_seconds: u32,
+        timestamp: Option<DateTime>,
+        notifications: &'a [CovObjectNotification<'a>],
+    ) -> Self {
+        Self {
+            process_id,
+            device_id,
+            time_remaining_seconds,
+            timestamp,
+            notifications,
+            buf: &[],
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(
+        process_id: u32,
+        device_id: ObjectId,
+        time_remaining_seconds: u32,
+        timestamp: Option<DateTime>,
+        notifications: Vec<CovObjectNotification<'a>>,
+    ) -> Self {
+        Self {
+            process_id,
+            device_id,
+            time_remaining_seconds,
+            timestamp,
+            notifications,
+        }
+    }
+
+    // the service choice is not encoded here because this notification can be sent
+    // as either a confirmed or an unconfirmed request
+    pub fn encode(&self, writer: &mut Writer) {
+        // process_id
+        encode_context_unsigned(writer, Self::TAG_PROCESS_ID, self.process_id);
+
+        // device_id
+        encode_context_object_id(writer, Self::TAG_DEVICE_ID, &self.device_id);
+
+        // lifetime
+        encode_context_unsigned(writer, Self::TAG_LIFETIME, self.time_remaining_seconds);
+
+        // timestamp
+        if let Some(timestamp) = &self.timestamp {
+            encode_opening_tag(writer, Self::TAG_TIMESTAMP);
+            timestamp.encode(writer);
+            encode_closing_tag(writer, Self::TAG_TIMESTAMP);
+        }
+
+        // list of cov notifications
+        encode_opening_tag(writer, Self::TAG_NOTIFICATIONS);
+        #[cfg(not(feature = "alloc"))]
+        encode_list(
+            writer,
+            self.buf,
+            self.notifications,
+            CovObjectNotification::encode,
+        );
+        #[cfg(feature = "alloc")]
+        for notification in self.notifications.iter() {
+            notification.encode(writer);
+        }
+        encode_closing_tag(writer, Self::TAG_NOTIFICATIONS);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // process_id
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_PROCESS_ID),
+            "CovNotificationMultiple decode process_id",
+        )?;
+        let process_id = decode_unsigned(tag.value, reader, buf)? as u32;
+
+        // device_id
+        let device_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_DEVICE_ID,
+            "CovNotificationMultiple decode device_id",
+        )?;
+        if device_id.object_type != ObjectType::ObjectDevice {
+            return Err(Error::InvalidValue(
+                "expected device object type for CovNotificationMultiple device_id field",
+            ));
+        }
+
+        // lifetime
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_LIFETIME),
+            "CovNotificationMultiple decode lifetime",
+        )?;
+        let time_remaining_seconds = decode_unsigned(tag.value, reader, buf)? as u32;
+
+        // timestamp
+        let mut timestamp = None;
+        if peek_tag(reader, buf)?.number == TagNumber::ContextSpecificOpening(Self::TAG_TIMESTAMP) {
+            Tag::decode(reader, buf)?;
+            timestamp = Some(DateTime::decode(reader, buf)?);
+            Tag::decode_expected(
+                reader,
+                buf,
+                TagNumber::ContextSpecificClosing(Self::TAG_TIMESTAMP),
+                "CovNotificationMultiple decode timestamp",
+            )?;
+        }
+
+        // list of cov notifications
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_NOTIFICATIONS,
+            "CovNotificationMultiple decode notifications",
+        )?;
+
+        #[cfg(not(feature = "alloc"))]
+        return Ok(Self {
+            process_id,
+            device_id,
+            time_remaining_seconds,
+            timestamp,
+            notifications: &[],
+            buf: inner_buf,
+        });
+
+        #[cfg(feature = "alloc")]
+        {
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            let mut notifications = Vec::new();
+            while !inner_reader.eof() {
+                let notification = CovObjectNotification::decode(&mut inner_reader, inner_buf)?;
+                notifications.push(notification);
+            }
+
+            Ok(Self {
+                process_id,
+                device_id,
+                time_remaining_seconds,
+                timestamp,
+                notifications,
+            })
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ CovNotificationMultiple<'a> {
+    type Item = Result<CovObjectNotification<'a>, Error>;
+    type IntoIter = CovObjectNotificationIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        CovObjectNotificationIter {
+            list: ListIter::new(self.notifications, self.buf),
+        }
+    }
+}
+
+pub struct CovObjectNotificationIter<'a> {
+    list: ListIter<'a, CovObjectNotification<'a>>,
+}
+
+impl<'a> Iterator for CovObjectNotificationIter<'a> {
+    type Item = Result<CovObjectNotification<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.next_with(CovObjectNotification::decode)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::vec;
+
+    use crate::{
+        application_protocol::primitives::data_value::{
+            ApplicationDataValue, Date, DateTime, Time,
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+    };
+
+    use super::{
+        CovNotificationMultiple, CovObjectNotification, CovPropertyValue, CovReference,
+        CovSubscriptionSpecification, SubscribeCovPropertyMultiple,
+    };
+
+    fn time(second: u8) -> Time {
+        Time {
+            hour: 12,
+            minute: 30,
+            second,
+            hundredths: 0,
+        }
+    }
+
+    #[test]
+    fn reversable_subscribe() {
+        let mut reference = CovReference::new(PropertyId::PropPresentValue, true);
+        reference.cov_increment = Some(0.5);
+        let subscriptions = vec![
+            CovSubscriptionSpecification::new(
+                ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+                vec![
+                    reference,
+                    CovReference::new(PropertyId::PropStatusFlags, false),
+                ],
+            ),
+            CovSubscriptionSpecification::new(
+                ObjectId::new(ObjectType::ObjectBinaryInput, 2),
+                vec![CovReference::new(PropertyId::PropPresentValue, false)],
+            ),
+        ];
+        let request = SubscribeCovPropertyMultiple::new(3, false, 600, subscriptions);
+
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        let decoded = SubscribeCovPropertyMultiple::decode(&mut reader, &buf[..len]).unwrap();
+        assert_eq!(decoded.process_id, 3);
+        assert_eq!(decoded.issue_confirmed_notifications, Some(false));
+        assert_eq!(decoded.lifetime_seconds, Some(600));
+        assert_eq!(decoded.max_notification_delay_seconds, None);
+        assert_eq!(decoded.subscriptions.len(), 2);
+        let references = &decoded.subscriptions[0].references;
+        assert_eq!(references.len(), 2);
+        assert_eq!(references[0].cov_increment, Some(0.5));
+        assert!(references[0].timestamped);
+        assert_eq!(references[1].property_id, PropertyId::PropStatusFlags);
+    }
+
+    #[test]
+    fn reversable_notification() {
+        let notifications = vec![
+            CovObjectNotification::new(
+                ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+                vec![CovPropertyValue::new(
+                    PropertyId::PropPresentValue,
+                    ApplicationDataValue::Real(21.5),
+                    Some(time(15)),
+                )],
+            ),
+            CovObjectNotification::new(
+                ObjectId::new(ObjectType::ObjectAnalogInput, 2),
+                vec![CovPropertyValue::new(
+                    PropertyId::PropPresentValue,
+                    ApplicationDataValue::Real(18.0),
+                    None,
+                )],
+            ),
+        ];
+        let date = Date {
+            year: 2024,
+            month: 5,
+            day: 17,
+            wday: 5,
+        };
+        let notification = CovNotificationMultiple::new(
+            3,
+            ObjectId::new(ObjectType::ObjectDevice, 79079),
+            120,
+            Some(DateTime::new(date, time(20))),
+            notifications,
+        );
+
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        notification.encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        let decoded = CovNotificationMultiple::decode(&mut reader, &buf[..len]).unwrap();
+        assert_eq!(decoded.process_id, 3);
+        assert_eq!(decoded.time_remaining_seconds, 120);
+        assert_eq!(decoded.timestamp.unwrap().time.second, 20);
+        assert_eq!(decoded.notifications.len(), 2);
+        assert_eq!(
+            decoded.notifications[0].values[0]
+                .time_of_change
+                .as_ref()
+                .unwrap()
+                .second,
+            15
+        );
+        assert!(decoded.notifications[1].values[0].time_of_change.is_none());
+    }
+}
+
+#[cfg(all(test, not(feature = "alloc")))]
+mod no_alloc_tests {
+    use crate::{
+        application_protocol::primitives::data_value::ApplicationDataValue,
+        common::{
+            helper::assert_encodes_to,
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+    };
+
+    use super::{
+        CovNotificationMultiple, CovObjectNotification, CovPropertyValue, CovReference,
+        CovSubscriptionSpecification, SubscribeCovPropertyMultiple,
+    };
+
+    #[test]
+    fn reversable_subscribe() {
+        let references = [
+            CovReference::new(PropertyId::PropPresentValue, true),
+            CovReference::new(PropertyId::PropStatusFlags, false),
+        ];
+        let subscriptions = [CovSubscriptionSpecification::new(
+            ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+            &references,
+        )];
+        let request = SubscribeCovPropertyMultiple::new(3, false, 600, &subscriptions);
+        assert_eq!((&request).into_iter().count(), 1);
+        assert_eq!((&subscriptions[0].references).into_iter().count(), 2);
+
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        request.encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        let decoded = SubscribeCovPropertyMultiple::decode(&mut reader, &buf[..len]).unwrap();
+        let subscription = (&decoded).into_iter().next().unwrap().unwrap();
+        let mut references = (&subscription.references).into_iter();
+        assert!(references.next().unwrap().unwrap().timestamped);
+        let reference = references.next().unwrap().unwrap();
+        assert_eq!(reference.property_id, PropertyId::PropStatusFlags);
+        assert!(references.next().is_none());
+
+        assert_encodes_to(&buf[..len], |writer| decoded.encode(writer));
+    }
+
+    #[test]
+    fn reversable_notification() {
+        let values = [CovPropertyValue::new(
+            PropertyId::PropPresentValue,
+            ApplicationDataValue::Real(21.5),
+            None,
+        )];
+        let notifications = [CovObjectNotification::new(
+            ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+            &values,
+        )];
+        let device_id = ObjectId::new(ObjectType::ObjectDevice, 79079);
+        let notification = CovNotificationMultiple::new(3, device_id, 120, None, &notifications);
+        assert_eq!((&notification).into_iter().count(), 1);
+
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        notification.encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        let decoded = CovNotificationMultiple::decode(&mut reader, &buf[..len]).unwrap();
+        let object = (&decoded).into_iter().next().unwrap().unwrap();
+        let value = (&object.values).into_iter().next().unwrap().unwrap();
+        assert!(matches!(value.value, ApplicationDataValue::Real(x) if x == 21.5));
+
+        assert_encodes_to(&buf[..len], |writer| decoded.encode(writer));
+    }
+}
@@ -0,0 +1,588 @@
+// create object (dynamically creates an object on a device that supports it)
+
+#[cfg(feature = "alloc")]
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
+
+#[cfg(not(feature = "alloc"))]
+use crate::common::helper::encode_list;
+
+use crate::{
+    application_protocol::{
+        confirmed::{ComplexAck, ComplexAckService, ConfirmedServiceChoice},
+        primitives::data_value::{ApplicationDataValue, OctetString},
+    },
+    common::{
+        error::Error,
+        helper::{
+            decode_context_property_id, decode_unsigned, encode_application_object_id,
+            encode_closing_tag, encode_context_enumerated, encode_context_object_id,
+            encode_context_unsigned, encode_enumerated, encode_opening_tag,
+            get_tagged_body_for_tag, peek_tag, ListIter,
+        },
+        io::{Reader, Writer},
+        object_id::{ObjectId, ObjectType},
+        property_id::PropertyId,
+        spec::BACNET_MAX_INSTANCE,
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+    network_protocol::data_link::DataLink,
+};
+
+// the device picks the instance number when only the object type is specified
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ObjectSpecifier {
+    Type(ObjectType),
+    Id(ObjectId),
+}
+
+impl ObjectSpecifier {
+    const TAG_OBJECT_TYPE: u8 = 0;
+    const TAG_OBJECT_ID: u8 = 1;
+
+    // used to decode the initial values (the instance number is unknown when only the type is specified)
+    pub fn object_id(&self) -> ObjectId {
+        match self {
+            Self::Type(object_type) => ObjectId::new(*object_type, BACNET_MAX_INSTANCE),
+            Self::Id(object_id) => *object_id,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Type(object_type) => {
+                encode_enumerated(writer, *object_type as u32, Some(Self::TAG_OBJECT_TYPE))
+            }
+            Self::Id(object_id) => encode_context_object_id(writer, Self::TAG_OBJECT_ID, object_id),
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode(reader, buf)?;
+        match tag.number {
+            TagNumber::ContextSpecific(Self::TAG_OBJECT_TYPE) => {
+                let value = decode_unsigned(tag.value, reader, buf)? as u32;
+                let object_type = ObjectType::try_from(value).map_err(|x| {
+                    Error::InvalidVariant(("ObjectSpecifier decode object_type", x))
+                })?;
+                Ok(Self::Type(object_type))
+            }
+            TagNumber::ContextSpecific(Self::TAG_OBJECT_ID) => {
+                let object_id = ObjectId::decode(tag.value, reader, buf)?;
+                Ok(Self::Id(object_id))
+            }
+            x => Err(Error::TagNotSupported(("ObjectSpecifier decode", x))),
+        }
+    }
+}
+
+// the value of a property to initialize when the object is created (a BACnetPropertyValue)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InitialValue<'a> {
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub value: InitialValueData<'a>,
+    pub priority: Option<u8>,
+}
+
+// constructed values (e.g. the BACnetDestination entries of a Recipient_List) cannot be represented as an
+// ApplicationDataValue so they are kept as the raw tagged data found between the value opening and closing tags
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum InitialValueData<'a> {
+    Value(ApplicationDataValue<'a>),
+    Constructed(OctetString<'a>),
+}
+
+impl<'a> InitialValueData<'a> {
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Value(x) => x.encode(writer),
+            Self::Constructed(x) => writer.extend_from_slice(x.as_slice()),
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(
+        object_id: &ObjectId,
+        property_id: &PropertyId,
+        buf: &'a [u8],
+    ) -> Result<Self, Error> {
+        // a single application tagged value that takes up the whole body
+        let mut reader = Reader::new_with_len(buf.len());
+        if let Ok(value) = ApplicationDataValue::decode(object_id, property_id, &mut reader, buf) {
+            if reader.eof() {
+                return Ok(Self::Value(value));
+            }
+        }
+
+        Ok(Self::Constructed(OctetString::new(buf)))
+    }
+}
+
+impl<'a> InitialValue<'a> {
+    const TAG_PROPERTY_ID: u8 = 0;
+    const TAG_ARRAY_INDEX: u8 = 1;
+    const TAG_VALUE: u8 = 2;
+    const TAG_PRIORITY: u8 = 3;
+
+    pub fn new(property_id: PropertyId, value: ApplicationDataValue<'a>) -> Self {
+        Self {
+            property_id,
+            array_index: None,
+            value: InitialValueData::Value(value),
+            priority: None,
+        }
+    }
+
+    // the data is encoded as is between the value opening and closing tags
+    pub fn new_constructed(property_id: PropertyId, data: OctetString<'a>) -> Self {
+        Self {
+            property_id,
+            array_index: None,
+            value: InitialValueData::Constructed(data),
+            priority: None,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        // property_id
+        encode_context_enumerated(writer, Self::TAG_PROPERTY_ID, &self.property_id);
+
+        // array_index
+        if let Some(array_index) = self.array_index {
+            encode_context_unsigned(writer, Self::TAG_ARRAY_INDEX, array_index);
+        }
+
+        // value
+        encode_opening_tag(writer, Self::TAG_VALUE);
+        self.value.encode(writer);
+        encode_closing_tag(writer, Self::TAG_VALUE);
+
+        // priority
+        if let Some(priority) = self.priority {
+            encode_context_unsigned(writer, Self::TAG_PRIORITY, priority);
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8], object_id: &ObjectId) -> Result<Self, Error> {
+        // property_id
+        let property_id = decode_context_property_id(
+            reader,
+            buf,
+            Self::TAG_PROPERTY_ID,
+            "InitialValue decode property_id",
+        )?;
+
+        // array_index
+        let mut array_index = None;
+        if peek_tag(reader, buf)?.number == TagNumber::ContextSpecific(Self::TAG_ARRAY_INDEX) {
+            let tag = Tag::decode(reader, buf)?;
+            array_index = Some(decode_unsigned(tag.value, reader, buf)? as u32);
+        }
+
+        // value
+        let inner_buf =
+            get_tagged_body_for_tag(reader, buf, Self::TAG_VALUE, "InitialValue decode value")?;
+        let value = InitialValueData::decode(object_id, &property_id, inner_buf)?;
+
+        // priority
+        let mut priority = None;
+        if !reader.eof()
+            && peek_tag(reader, buf)?.number == TagNumber::ContextSpecific(Self::TAG_PRIORITY)
+        {
+            let tag = Tag::decode(reader, buf)?;
+            priority = Some(decode_unsigned(tag.value, reader, buf)? as u8);
+        }
+
+        Ok(Self {
+            property_id,
+            array_index,
+            value,
+            priority,
+        })
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CreateObject<'a> {
+    pub object_specifier: ObjectSpecifier,
+    pub initial_values: &'a [InitialValue<'a>],
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CreateObject<'a> {
+    pub object_specifier: ObjectSpecifier,
+    pub initial_values: Vec<InitialValue<'a>>,
+    _phantom: &'a Phantom,
+}
+
+impl<'a> CreateObject<'a> {
+    const TAG_OBJECT_SPECIFIER: u8 = 0;
+    const TAG_INITIAL_VALUES: u8 = 1;
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(object_specifier: ObjectSpecifier, initial_values: &'a [InitialValue<'a>]) -> Self {
+        Self {
+            object_specifier,
+            initial_values,
+            buf: &[],
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(object_specifier: ObjectSpecifier, initial_values: Vec<InitialValue<'a>>) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            object_specifier,
+            initial_values,
+            _phantom: &PHANTOM,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        // object specifier
+        encode_opening_tag(writer, Self::TAG_OBJECT_SPECIFIER);
+        self.object_specifier.encode(writer);
+        encode_closing_tag(writer, Self::TAG_OBJECT_SPECIFIER);
+
+        // initial values (optional)
+        #[cfg(not(feature = "alloc"))]
+        let has_initial_values = !self.initial_values.is_empty() || !self.buf.is_empty();
+        #[cfg(feature = "alloc")]
+        let has_initial_values = !self.initial_values.is_empty();
+        if has_initial_values {
+            encode_opening_tag(writer, Self::TAG_INITIAL_VALUES);
+            #[cfg(not(feature = "alloc"))]
+            encode_list(writer, self.buf, self.initial_values, InitialValue::encode);
+            #[cfg(feature = "alloc")]
+            for value in self.initial_values.iter() {
+                value.encode(writer);
+            }
+            encode_closing_tag(writer, Self::TAG_INITIAL_VALUES);
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // object specifier
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecificOpening(Self::TAG_OBJECT_SPECIFIER),
+            "CreateObject decode object_specifier",
+        )?;
+        let object_specifier = ObjectSpecifier::decode(reader, buf)?;
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecificClosing(Self::TAG_OBJECT_SPECIFIER),
+            "CreateObject decode object_specifier",
+        )?;
+
+        // initial values (optional)
+        let inner_buf: &[u8] = if reader.eof() {
+            &[]
+        } else {
+            get_tagged_body_for_tag(
+                reader,
+                buf,
+                Self::TAG_INITIAL_VALUES,
+                "CreateObject decode initial_values",
+            )?
+        };
+
+        #[cfg(not(feature = "alloc"))]
+        return Ok(Self {
+            object_specifier,
+            initial_values: &[],
+            buf: inner_buf,
+        });
+
+        #[cfg(feature = "alloc")]
+        {
+            let object_id = object_specifier.object_id();
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            let mut initial_values = Vec::new();
+            while !inner_reader.eof() {
+                let value = InitialValue::decode(&mut inner_reader, inner_buf, &object_id)?;
+                initial_values.push(value);
+            }
+            Ok(Self::new(object_specifier, initial_values))
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ CreateObject<'a> {
+    type Item = Result<InitialValue<'a>, Error>;
+
+    type IntoIter = InitialValueIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        InitialValueIter {
+            list: ListIter::new(self.initial_values, self.buf),
+            object_id: self.object_specifier.object_id(),
+        }
+    }
+}
+
+pub struct InitialValueIter<'a> {
+    list: ListIter<'a, InitialValue<'a>>,
+    object_id: ObjectId,
+}
+
+impl<'a> Iterator for InitialValueIter<'a> {
+    type Item = Result<InitialValue<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let object_id = &self.object_id;
+        self.list
+            .next_with(|reader, buf| InitialValue::decode(reader, buf, object_id))
+    }
+}
+
+// the object id of the newly created object
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CreateObjectAck {
+    pub object_id: ObjectId,
+}
+
+impl CreateObjectAck {
+    pub fn new(object_id: ObjectId) -> Self {
+        Self { object_id }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(ConfirmedServiceChoice::CreateObject as u8);
+        encode_application_object_id(writer, &self.object_id);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::ObjectId),
+            "CreateObjectAck decode object_id",
+        )?;
+        let object_id = ObjectId::decode(tag.value, reader, buf)?;
+        Ok(Self { object_id })
+    }
+}
+
+impl<'a> TryFrom<DataLink<'a>> for CreateObjectAck {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        let ack: ComplexAck = value.try_into()?;
+        match ack.service {
+            ComplexAckService::CreateObject(ack) => Ok(ack),
+            _ => Err(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService CreateObjectAck",
+            )),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ConfirmedRequest, ConfirmedRequestService},
+            primitives::{
+                data_value::{ApplicationDataValue, CharacterString, OctetString, Time},
+                recipient::{Destination, Recipient},
+            },
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::EventTransitionBits,
+        },
+    };
+
+    use super::{CreateObject, InitialValue, InitialValueData, ObjectSpecifier};
+
+    fn encode_decode(request: CreateObject) -> CreateObject<'static> {
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        let service = ConfirmedRequestService::CreateObject(request);
+        ConfirmedRequest::new(4, service).encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ConfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        match decoded.service {
+            ConfirmedRequestService::CreateObject(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        }
+    }
+
+    #[test]
+    fn reversable() {
+        use alloc::vec;
+
+        let mut log_interval = InitialValue::new(
+            PropertyId::PropLogInterval,
+            ApplicationDataValue::UnsignedInt(6000),
+        );
+        log_interval.priority = Some(8);
+        let initial_values = vec![
+            InitialValue::new(
+                PropertyId::PropObjectName,
+                ApplicationDataValue::CharacterString(CharacterString::new("Zone Temp Log")),
+            ),
+            log_interval,
+        ];
+        let request = CreateObject::new(
+            ObjectSpecifier::Type(ObjectType::ObjectTrendlog),
+            initial_values,
+        );
+
+        let decoded = encode_decode(request);
+        assert!(matches!(
+            decoded.object_specifier,
+            ObjectSpecifier::Type(ObjectType::ObjectTrendlog)
+        ));
+        assert_eq!(decoded.initial_values.len(), 2);
+        assert!(matches!(
+            &decoded.initial_values[0].value,
+            InitialValueData::Value(ApplicationDataValue::CharacterString(x)) if x.inner == "Zone Temp Log"
+        ));
+        assert_eq!(
+            decoded.initial_values[1].property_id,
+            PropertyId::PropLogInterval
+        );
+        assert_eq!(decoded.initial_values[1].priority, Some(8));
+    }
+
+    #[test]
+    fn reversable_constructed() {
+        use alloc::vec;
+
+        // a notification class created with a Recipient_List of one destination
+        let destination = Destination {
+            valid_days: 0b1111_1110,
+            from_time: Time {
+                hour: 0,
+                minute: 0,
+                second: 0,
+                hundredths: 0,
+            },
+            to_time: Time {
+                hour: 23,
+                minute: 59,
+                second: 59,
+                hundredths: 99,
+            },
+            recipient: Recipient::Device(ObjectId::new(ObjectType::ObjectDevice, 8)),
+            process_id: 1,
+            issue_confirmed_notifications: false,
+            transitions: EventTransitionBits::new(0b1110_0000),
+        };
+        let mut data = [0; 32];
+        let mut writer = Writer::new(&mut data);
+        destination.encode(&mut writer);
+        let data_len = writer.index;
+
+        let initial_values = vec![
+            InitialValue::new(
+                PropertyId::PropNotificationClass,
+                ApplicationDataValue::UnsignedInt(5),
+            ),
+            InitialValue::new_constructed(
+                PropertyId::PropRecipientList,
+                OctetString::new(&data[..data_len]),
+            ),
+        ];
+        let request = CreateObject::new(
+            ObjectSpecifier::Type(ObjectType::ObjectNotificationClass),
+            initial_values,
+        );
+
+        let decoded = encode_decode(request);
+        assert_eq!(decoded.initial_values.len(), 2);
+        assert!(matches!(
+            decoded.initial_values[0].value,
+            InitialValueData::Value(ApplicationDataValue::UnsignedInt(5))
+        ));
+        let data = match &decoded.initial_values[1].value {
+            InitialValueData::Constructed(x) => x.as_slice(),
+            x => panic!("unexpected value {:?}", x),
+        };
+        let mut reader = Reader::new_with_len(data.len());
+        let decoded = Destination::decode(&mut reader, data).unwrap();
+        assert!(reader.eof());
+        assert_eq!(decoded.valid_days, 0b1111_1110);
+        assert_eq!(decoded.to_time.hour, 23);
+        assert!(matches!(decoded.recipient, Recipient::Device(x) if x.id == 8));
+        assert!(decoded.transitions.to_normal());
+    }
+}
+
+#[cfg(all(test, not(feature = "alloc")))]
+mod no_alloc_tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ConfirmedRequest, ConfirmedRequestService},
+            primitives::data_value::ApplicationDataValue,
+        },
+        common::{
+            helper::assert_encodes_to,
+            io::{Reader, Writer},
+            object_id::ObjectType,
+            property_id::PropertyId,
+        },
+    };
+
+    use super::{CreateObject, InitialValue, InitialValueData, ObjectSpecifier};
+
+    #[test]
+    fn reversable() {
+        let initial_values = [InitialValue::new(
+            PropertyId::PropLogInterval,
+            ApplicationDataValue::UnsignedInt(6000),
+        )];
+        let request = CreateObject::new(
+            ObjectSpecifier::Type(ObjectType::ObjectTrendlog),
+            &initial_values,
+        );
+        assert_eq!((&request).into_iter().count(), 1);
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        let service = ConfirmedRequestService::CreateObject(request);
+        ConfirmedRequest::new(4, service).encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ConfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        let request = match &decoded.service {
+            ConfirmedRequestService::CreateObject(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        let mut values = request.into_iter();
+        let value = values.next().unwrap().unwrap();
+        assert_eq!(value.property_id, PropertyId::PropLogInterval);
+        assert!(matches!(
+            value.value,
+            InitialValueData::Value(ApplicationDataValue::UnsignedInt(6000))
+        ));
+        assert!(values.next().is_none());
+
+        assert_encodes_to(&buf[..len], |writer| decoded.encode(writer));
+    }
+}
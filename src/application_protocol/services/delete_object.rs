@@ -0,0 +1,36 @@
+// delete object (deletes an object that was created dynamically)
+
+use crate::common::{
+    error::Error,
+    helper::encode_application_object_id,
+    io::{Reader, Writer},
+    object_id::ObjectId,
+    tag::{ApplicationTagNumber, Tag, TagNumber},
+};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeleteObject {
+    pub object_id: ObjectId,
+}
+
+impl DeleteObject {
+    pub fn new(object_id: ObjectId) -> Self {
+        Self { object_id }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_application_object_id(writer, &self.object_id);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::ObjectId),
+            "DeleteObject decode object_id",
+        )?;
+        let object_id = ObjectId::decode(tag.value, reader, buf)?;
+        Ok(Self { object_id })
+    }
+}
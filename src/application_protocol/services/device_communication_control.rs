@@ -0,0 +1,146 @@
+// device communication control (stops a device from initiating or responding to messages for a while)
+
+use crate::{
+    application_protocol::primitives::data_value::CharacterString,
+    common::{
+        error::Error,
+        helper::{
+            decode_unsigned, encode_context_character_string, encode_context_unsigned, peek_tag,
+        },
+        io::{Reader, Writer},
+        spec::CommunicationEnableDisable,
+        tag::{Tag, TagNumber},
+    },
+};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeviceCommunicationControl<'a> {
+    pub duration_minutes: Option<u16>, // indefinite if not specified
+    pub enable_disable: CommunicationEnableDisable,
+    pub password: Option<CharacterString<'a>>,
+}
+
+impl<'a> DeviceCommunicationControl<'a> {
+    const TAG_DURATION: u8 = 0;
+    const TAG_ENABLE_DISABLE: u8 = 1;
+    const TAG_PASSWORD: u8 = 2;
+
+    pub fn new(
+        duration_minutes: Option<u16>,
+        enable_disable: CommunicationEnableDisable,
+        password: Option<CharacterString<'a>>,
+    ) -> Self {
+        Self {
+            duration_minutes,
+            enable_disable,
+            password,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        // duration
+        if let Some(duration_minutes) = self.duration_minutes {
+            encode_context_unsigned(writer, Self::TAG_DURATION, duration_minutes);
+        }
+
+        // enable / disable
+        encode_context_unsigned(
+            writer,
+            Self::TAG_ENABLE_DISABLE,
+            self.enable_disable.clone() as u32,
+        );
+
+        // password
+        if let Some(password) = &self.password {
+            encode_context_character_string(writer, Self::TAG_PASSWORD, password.as_str());
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // duration
+        let mut duration_minutes = None;
+        if peek_tag(reader, buf)?.number == TagNumber::ContextSpecific(Self::TAG_DURATION) {
+            let tag = Tag::decode(reader, buf)?;
+            duration_minutes = Some(decode_unsigned(tag.value, reader, buf)? as u16);
+        }
+
+        // enable / disable
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_ENABLE_DISABLE),
+            "DeviceCommunicationControl decode enable_disable",
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let enable_disable = CommunicationEnableDisable::try_from(value).map_err(|x| {
+            Error::InvalidVariant(("DeviceCommunicationControl decode enable_disable", x))
+        })?;
+
+        // password
+        let mut password = None;
+        if !reader.eof() {
+            let tag = Tag::decode_expected(
+                reader,
+                buf,
+                TagNumber::ContextSpecific(Self::TAG_PASSWORD),
+                "DeviceCommunicationControl decode password",
+            )?;
+            password = Some(CharacterString::decode(tag.value, reader, buf)?);
+        }
+
+        Ok(Self {
+            duration_minutes,
+            enable_disable,
+            password,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ConfirmedRequest, ConfirmedRequestService},
+            primitives::data_value::CharacterString,
+        },
+        common::{
+            io::{Reader, Writer},
+            spec::CommunicationEnableDisable,
+        },
+    };
+
+    use super::DeviceCommunicationControl;
+
+    #[test]
+    fn reversable() {
+        let request = DeviceCommunicationControl::new(
+            Some(30),
+            CommunicationEnableDisable::DisableInitiation,
+            Some(CharacterString::new("secret")),
+        );
+
+        // encoding
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        let service = ConfirmedRequestService::DeviceCommunicationControl(request);
+        ConfirmedRequest::new(1, service).encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ConfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        let decoded = match decoded.service {
+            ConfirmedRequestService::DeviceCommunicationControl(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        assert_eq!(decoded.duration_minutes, Some(30));
+        assert!(matches!(
+            decoded.enable_disable,
+            CommunicationEnableDisable::DisableInitiation
+        ));
+        assert_eq!(decoded.password.unwrap().inner, "secret");
+    }
+}
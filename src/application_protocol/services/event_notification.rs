@@ -0,0 +1,811 @@
+// event notification
+
+use crate::{
+    application_protocol::primitives::data_value::{
+        BitString, CharacterString, CustomBitStream, OctetString, TimeStamp,
+    },
+    common::{
+        error::Error,
+        helper::{
+            decode_context_bool, decode_context_object_id, decode_context_real, decode_unsigned,
+            encode_closing_tag, encode_context_bool, encode_context_character_string,
+            encode_context_object_id, encode_context_real, encode_context_unsigned,
+            encode_opening_tag, get_tagged_body, get_tagged_body_for_tag, peek_tag,
+        },
+        io::{Reader, Writer},
+        object_id::{ObjectId, ObjectType},
+        spec::{Binary, EventState, EventType, NotifyType, Status},
+        tag::{Tag, TagNumber},
+    },
+};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EventNotification<'a> {
+    pub process_id: u32,
+    pub initiating_device_id: ObjectId,
+    pub event_object_id: ObjectId,
+    pub timestamp: TimeStamp,
+    pub notification_class: u32,
+    pub priority: u8,
+    pub event_type: EventType,
+    pub message_text: Option<CharacterString<'a>>,
+    pub notify_type: NotifyType,
+    pub ack_required: Option<bool>, // not used for ack notifications
+    pub from_state: Option<EventState>, // not used for ack notifications
+    pub to_state: EventState,
+    pub event_values: Option<NotificationParameters<'a>>, // not used for ack notifications
+}
+
+impl<'a> EventNotification<'a> {
+    const TAG_PROCESS_ID: u8 = 0;
+    const TAG_DEVICE_ID: u8 = 1;
+    const TAG_OBJECT_ID: u8 = 2;
+    const TAG_TIMESTAMP: u8 = 3;
+    const TAG_NOTIFICATION_CLASS: u8 = 4;
+    const TAG_PRIORITY: u8 = 5;
+    const TAG_EVENT_TYPE: u8 = 6;
+    const TAG_MESSAGE_TEXT: u8 = 7;
+    const TAG_NOTIFY_TYPE: u8 = 8;
+    const TAG_ACK_REQUIRED: u8 = 9;
+    const TAG_FROM_STATE: u8 = 10;
+    const TAG_TO_STATE: u8 = 11;
+    const TAG_EVENT_VALUES: u8 = 12;
+
+    // the service choice is not encoded here because this notification can be sent
+    // as either a confirmed or an unconfirmed request
+    pub fn encode(&self, writer: &mut Writer) {
+        // process_id
+        encode_context_unsigned(writer, Self::TAG_PROCESS_ID, self.process_id);
+
+        // initiating device_id
+        encode_context_object_id(writer, Self::TAG_DEVICE_ID, &self.initiating_device_id);
+
+        // event object_id
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.event_object_id);
+
+        // timestamp
+        encode_opening_tag(writer, Self::TAG_TIMESTAMP);
+        self.timestamp.encode(writer);
+        encode_closing_tag(writer, Self::TAG_TIMESTAMP);
+
+        // notification class
+        encode_context_unsigned(
+            writer,
+            Self::TAG_NOTIFICATION_CLASS,
+            self.notification_class,
+        );
+
+        // priority
+        encode_context_unsigned(writer, Self::TAG_PRIORITY, self.priority);
+
+        // event type
+        encode_context_unsigned(writer, Self::TAG_EVENT_TYPE, u32::from(&self.event_type));
+
+        // message text
+        if let Some(message_text) = &self.message_text {
+            encode_context_character_string(writer, Self::TAG_MESSAGE_TEXT, message_text.as_str());
+        }
+
+        // notify type
+        encode_context_unsigned(
+            writer,
+            Self::TAG_NOTIFY_TYPE,
+            self.notify_type.clone() as u32,
+        );
+
+        // ack required
+        if let Some(ack_required) = self.ack_required {
+            encode_context_bool(writer, Self::TAG_ACK_REQUIRED, ack_required);
+        }
+
+        // from state
+        if let Some(from_state) = &self.from_state {
+            encode_context_unsigned(writer, Self::TAG_FROM_STATE, u32::from(from_state));
+        }
+
+        // to state
+        encode_context_unsigned(writer, Self::TAG_TO_STATE, u32::from(&self.to_state));
+
+        // event values
+        if let Some(event_values) = &self.event_values {
+            encode_opening_tag(writer, Self::TAG_EVENT_VALUES);
+            event_values.encode(writer);
+            encode_closing_tag(writer, Self::TAG_EVENT_VALUES);
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // process_id
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_PROCESS_ID),
+            "EventNotification decode process_id",
+        )?;
+        let process_id = decode_unsigned(tag.value, reader, buf)? as u32;
+
+        // initiating device_id
+        let initiating_device_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_DEVICE_ID,
+            "EventNotification decode initiating_device_id",
+        )?;
+        if initiating_device_id.object_type != ObjectType::ObjectDevice {
+            return Err(Error::InvalidValue(
+                "expected device object type for EventNotification initiating_device_id field",
+            ));
+        }
+
+        // event object_id
+        let event_object_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_OBJECT_ID,
+            "EventNotification decode event_object_id",
+        )?;
+
+        // timestamp
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecificOpening(Self::TAG_TIMESTAMP),
+            "EventNotification decode timestamp",
+        )?;
+        let timestamp = TimeStamp::decode(reader, buf)?;
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecificClosing(Self::TAG_TIMESTAMP),
+            "EventNotification decode timestamp",
+        )?;
+
+        // notification class
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_NOTIFICATION_CLASS),
+            "EventNotification decode notification_class",
+        )?;
+        let notification_class = decode_unsigned(tag.value, reader, buf)? as u32;
+
+        // priority
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_PRIORITY),
+            "EventNotification decode priority",
+        )?;
+        let priority = decode_unsigned(tag.value, reader, buf)? as u8;
+
+        // event type
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_EVENT_TYPE),
+            "EventNotification decode event_type",
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let event_type = EventType::from(value);
+
+        // message text
+        let mut message_text = None;
+        let mut tag = Tag::decode(reader, buf)?;
+        if tag.number == TagNumber::ContextSpecific(Self::TAG_MESSAGE_TEXT) {
+            message_text = Some(CharacterString::decode(tag.value, reader, buf)?);
+            tag = Tag::decode(reader, buf)?;
+        }
+
+        // notify type
+        tag.expect_number(
+            "EventNotification decode notify_type",
+            TagNumber::ContextSpecific(Self::TAG_NOTIFY_TYPE),
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let notify_type = NotifyType::try_from(value)
+            .map_err(|x| Error::InvalidVariant(("EventNotification decode notify_type", x)))?;
+
+        // ack required
+        let mut ack_required = None;
+        if peek_tag(reader, buf)?.number == TagNumber::ContextSpecific(Self::TAG_ACK_REQUIRED) {
+            ack_required = Some(decode_context_bool(
+                reader,
+                buf,
+                Self::TAG_ACK_REQUIRED,
+                "EventNotification decode ack_required",
+            )?);
+        }
+
+        // from state
+        let mut from_state = None;
+        let mut tag = Tag::decode(reader, buf)?;
+        if tag.number == TagNumber::ContextSpecific(Self::TAG_FROM_STATE) {
+            let value = decode_unsigned(tag.value, reader, buf)? as u32;
+            from_state = Some(EventState::from(value));
+            tag = Tag::decode(reader, buf)?;
+        }
+
+        // to state
+        tag.expect_number(
+            "EventNotification decode to_state",
+            TagNumber::ContextSpecific(Self::TAG_TO_STATE),
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let to_state = EventState::from(value);
+
+        // event values
+        let mut event_values = None;
+        if !reader.eof()
+            && peek_tag(reader, buf)?.number
+                == TagNumber::ContextSpecificOpening(Self::TAG_EVENT_VALUES)
+        {
+            let inner_buf = get_tagged_body_for_tag(
+                reader,
+                buf,
+                Self::TAG_EVENT_VALUES,
+                "EventNotification decode event_values",
+            )?;
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            event_values = Some(NotificationParameters::decode(
+                &mut inner_reader,
+                inner_buf,
+            )?);
+        }
+
+        Ok(Self {
+            process_id,
+            initiating_device_id,
+            event_object_id,
+            timestamp,
+            notification_class,
+            priority,
+            event_type,
+            message_text,
+            notify_type,
+            ack_required,
+            from_state,
+            to_state,
+            event_values,
+        })
+    }
+}
+
+// BACnetNotificationParameters: the values that caused the event (depends on the event type)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum NotificationParameters<'a> {
+    ChangeOfBitstring {
+        referenced_bitstring: CustomBitStream<'a>,
+        status_flags: Status,
+    },
+    ChangeOfState {
+        new_state: PropertyStates,
+        status_flags: Status,
+    },
+    ChangeOfValue {
+        new_value: ChangeOfValueNewValue<'a>,
+        status_flags: Status,
+    },
+    FloatingLimit {
+        reference_value: f32,
+        status_flags: Status,
+        setpoint_value: f32,
+        error_limit: f32,
+    },
+    OutOfRange {
+        exceeding_value: f32,
+        status_flags: Status,
+        deadband: f32,
+        exceeded_limit: f32,
+    },
+    // parameters for other event types are not decoded, the choice tag number and raw body are kept
+    Other(u8, OctetString<'a>),
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ChangeOfValueNewValue<'a> {
+    ChangedBits(CustomBitStream<'a>),
+    ChangedValue(f32),
+}
+
+// BACnetPropertyStates: most choices are enumerations so only the common ones are mapped
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PropertyStates {
+    BooleanValue(bool),
+    BinaryValue(Binary),
+    EventType(EventType),
+    State(EventState),
+    UnsignedValue(u32),
+    Other { choice: u8, value: u32 },
+}
+
+impl PropertyStates {
+    const TAG_BOOLEAN_VALUE: u8 = 0;
+    const TAG_BINARY_VALUE: u8 = 1;
+    const TAG_EVENT_TYPE: u8 = 2;
+    const TAG_STATE: u8 = 8;
+    const TAG_UNSIGNED_VALUE: u8 = 11;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::BooleanValue(x) => encode_context_bool(writer, Self::TAG_BOOLEAN_VALUE, *x),
+            Self::BinaryValue(x) => {
+                encode_context_unsigned(writer, Self::TAG_BINARY_VALUE, x.clone() as u32)
+            }
+            Self::EventType(x) => {
+                encode_context_unsigned(writer, Self::TAG_EVENT_TYPE, u32::from(x))
+            }
+            Self::State(x) => encode_context_unsigned(writer, Self::TAG_STATE, u32::from(x)),
+            Self::UnsignedValue(x) => encode_context_unsigned(writer, Self::TAG_UNSIGNED_VALUE, *x),
+            Self::Other { choice, value } => encode_context_unsigned(writer, *choice, *value),
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode(reader, buf)?;
+        let choice = match tag.number {
+            TagNumber::ContextSpecific(x) => x,
+            x => return Err(Error::TagNotSupported(("PropertyStates decode", x))),
+        };
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+
+        let state = match choice {
+            Self::TAG_BOOLEAN_VALUE => Self::BooleanValue(value > 0),
+            Self::TAG_BINARY_VALUE => Self::BinaryValue(
+                Binary::try_from(value)
+                    .map_err(|x| Error::InvalidVariant(("PropertyStates decode binary", x)))?,
+            ),
+            Self::TAG_EVENT_TYPE => Self::EventType(EventType::from(value)),
+            Self::TAG_STATE => Self::State(EventState::from(value)),
+            Self::TAG_UNSIGNED_VALUE => Self::UnsignedValue(value),
+            choice => Self::Other { choice, value },
+        };
+
+        Ok(state)
+    }
+}
+
+impl<'a> NotificationParameters<'a> {
+    const TAG_CHANGE_OF_BITSTRING: u8 = 0;
+    const TAG_CHANGE_OF_STATE: u8 = 1;
+    const TAG_CHANGE_OF_VALUE: u8 = 2;
+    const TAG_FLOATING_LIMIT: u8 = 4;
+    const TAG_OUT_OF_RANGE: u8 = 5;
+
+    // all supported parameter types put the status flags in the second field
+    const TAG_STATUS_FLAGS: u8 = 1;
+
+    const TAG_CHANGED_BITS: u8 = 0;
+    const TAG_CHANGED_VALUE: u8 = 1;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::ChangeOfBitstring {
+                referenced_bitstring,
+                status_flags,
+            } => {
+                encode_opening_tag(writer, Self::TAG_CHANGE_OF_BITSTRING);
+                BitString::Custom(referenced_bitstring.clone()).encode_context(0, writer);
+                BitString::Status(status_flags.clone())
+                    .encode_context(Self::TAG_STATUS_FLAGS, writer);
+                encode_closing_tag(writer, Self::TAG_CHANGE_OF_BITSTRING);
+            }
+            Self::ChangeOfState {
+                new_state,
+                status_flags,
+            } => {
+                encode_opening_tag(writer, Self::TAG_CHANGE_OF_STATE);
+                encode_opening_tag(writer, 0);
+                new_state.encode(writer);
+                encode_closing_tag(writer, 0);
+                BitString::Status(status_flags.clone())
+                    .encode_context(Self::TAG_STATUS_FLAGS, writer);
+                encode_closing_tag(writer, Self::TAG_CHANGE_OF_STATE);
+            }
+            Self::ChangeOfValue {
+                new_value,
+                status_flags,
+            } => {
+                encode_opening_tag(writer, Self::TAG_CHANGE_OF_VALUE);
+                encode_opening_tag(writer, 0);
+                match new_value {
+                    ChangeOfValueNewValue::ChangedBits(x) => {
+                        BitString::Custom(x.clone()).encode_context(Self::TAG_CHANGED_BITS, writer)
+                    }
+                    ChangeOfValueNewValue::ChangedValue(x) => {
+                        encode_context_real(writer, Self::TAG_CHANGED_VALUE, *x)
+                    }
+                }
+                encode_closing_tag(writer, 0);
+                BitString::Status(status_flags.clone())
+                    .encode_context(Self::TAG_STATUS_FLAGS, writer);
+                encode_closing_tag(writer, Self::TAG_CHANGE_OF_VALUE);
+            }
+            Self::FloatingLimit {
+                reference_value,
+                status_flags,
+                setpoint_value,
+                error_limit,
+            } => {
+                encode_opening_tag(writer, Self::TAG_FLOATING_LIMIT);
+                encode_context_real(writer, 0, *reference_value);
+                BitString::Status(status_flags.clone())
+                    .encode_context(Self::TAG_STATUS_FLAGS, writer);
+                encode_context_real(writer, 2, *setpoint_value);
+                encode_context_real(writer, 3, *error_limit);
+                encode_closing_tag(writer, Self::TAG_FLOATING_LIMIT);
+            }
+            Self::OutOfRange {
+                exceeding_value,
+                status_flags,
+                deadband,
+                exceeded_limit,
+            } => {
+                encode_opening_tag(writer, Self::TAG_OUT_OF_RANGE);
+                encode_context_real(writer, 0, *exceeding_value);
+                BitString::Status(status_flags.clone())
+                    .encode_context(Self::TAG_STATUS_FLAGS, writer);
+                encode_context_real(writer, 2, *deadband);
+                encode_context_real(writer, 3, *exceeded_limit);
+                encode_closing_tag(writer, Self::TAG_OUT_OF_RANGE);
+            }
+            Self::Other(choice, body) => {
+                encode_opening_tag(writer, *choice);
+                writer.extend_from_slice(body.as_slice());
+                encode_closing_tag(writer, *choice);
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let (body, choice) = get_tagged_body(reader, buf)?;
+        let mut reader = Reader::new_with_len(body.len());
+        let reader = &mut reader;
+
+        let parameters = match choice {
+            Self::TAG_CHANGE_OF_BITSTRING => {
+                let referenced_bitstring = Self::decode_bit_string(reader, body, 0)?;
+                let status_flags = Self::decode_status_flags(reader, body)?;
+                Self::ChangeOfBitstring {
+                    referenced_bitstring,
+                    status_flags,
+                }
+            }
+            Self::TAG_CHANGE_OF_STATE => {
+                Tag::decode_expected(
+                    reader,
+                    body,
+                    TagNumber::ContextSpecificOpening(0),
+                    "NotificationParameters decode new_state",
+                )?;
+                let new_state = PropertyStates::decode(reader, body)?;
+                Tag::decode_expected(
+                    reader,
+                    body,
+                    TagNumber::ContextSpecificClosing(0),
+                    "NotificationParameters decode new_state",
+                )?;
+                let status_flags = Self::decode_status_flags(reader, body)?;
+                Self::ChangeOfState {
+                    new_state,
+                    status_flags,
+                }
+            }
+            Self::TAG_CHANGE_OF_VALUE => {
+                Tag::decode_expected(
+                    reader,
+                    body,
+                    TagNumber::ContextSpecificOpening(0),
+                    "NotificationParameters decode new_value",
+                )?;
+                let new_value = match peek_tag(reader, body)?.number {
+                    TagNumber::ContextSpecific(Self::TAG_CHANGED_BITS) => {
+                        ChangeOfValueNewValue::ChangedBits(Self::decode_bit_string(
+                            reader,
+                            body,
+                            Self::TAG_CHANGED_BITS,
+                        )?)
+                    }
+                    _ => ChangeOfValueNewValue::ChangedValue(decode_context_real(
+                        reader,
+                        body,
+                        Self::TAG_CHANGED_VALUE,
+                        "NotificationParameters decode changed_value",
+                    )?),
+                };
+                Tag::decode_expected(
+                    reader,
+                    body,
+                    TagNumber::ContextSpecificClosing(0),
+                    "NotificationParameters decode new_value",
+                )?;
+                let status_flags = Self::decode_status_flags(reader, body)?;
+                Self::ChangeOfValue {
+                    new_value,
+                    status_flags,
+                }
+            }
+            Self::TAG_FLOATING_LIMIT => {
+                let reference_value = decode_context_real(
+                    reader,
+                    body,
+                    0,
+                    "NotificationParameters decode reference_value",
+                )?;
+                let status_flags = Self::decode_status_flags(reader, body)?;
+                let setpoint_value = decode_context_real(
+                    reader,
+                    body,
+                    2,
+                    "NotificationParameters decode setpoint_value",
+                )?;
+                let error_limit = decode_context_real(
+                    reader,
+                    body,
+                    3,
+                    "NotificationParameters decode error_limit",
+                )?;
+                Self::FloatingLimit {
+                    reference_value,
+                    status_flags,
+                    setpoint_value,
+                    error_limit,
+                }
+            }
+            Self::TAG_OUT_OF_RANGE => {
+                let exceeding_value = decode_context_real(
+                    reader,
+                    body,
+                    0,
+                    "NotificationParameters decode exceeding_value",
+                )?;
+                let status_flags = Self::decode_status_flags(reader, body)?;
+                let deadband =
+                    decode_context_real(reader, body, 2, "NotificationParameters decode deadband")?;
+                let exceeded_limit = decode_context_real(
+                    reader,
+                    body,
+                    3,
+                    "NotificationParameters decode exceeded_limit",
+                )?;
+                Self::OutOfRange {
+                    exceeding_value,
+                    status_flags,
+                    deadband,
+                    exceeded_limit,
+                }
+            }
+            choice => Self::Other(choice, OctetString::new(body)),
+        };
+
+        Ok(parameters)
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    fn decode_bit_string(
+        reader: &mut Reader,
+        buf: &'a [u8],
+        expected_tag_number: u8,
+    ) -> Result<CustomBitStream<'a>, Error> {
+        const CONTEXT: &str = "NotificationParameters decode bit string";
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(expected_tag_number),
+            CONTEXT,
+        )?;
+        if tag.value == 0 {
+            return Err(Error::Length((CONTEXT, 0)));
+        }
+        let unused_bits = reader.read_byte(buf)?;
+        let bits = reader.read_slice(tag.value as usize - 1, buf)?;
+        Ok(CustomBitStream::new(unused_bits, bits))
+    }
+
+    fn decode_status_flags(reader: &mut Reader, buf: &[u8]) -> Result<Status, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_STATUS_FLAGS),
+            "NotificationParameters decode status_flags",
+        )?;
+        tag.expect_value("NotificationParameters decode status_flags", 2)?;
+        let _unused_bits = reader.read_byte(buf)?;
+        Ok(Status::new(reader.read_byte(buf)?))
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::{
+            primitives::data_value::{Date, DateTime, Time, TimeStamp},
+            unconfirmed::UnconfirmedRequest,
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            spec::{EventState, EventType, NotifyType, Status},
+        },
+    };
+
+    use super::{CharacterString, EventNotification, NotificationParameters};
+
+    #[test]
+    fn reversable() {
+        let date = Date {
+            year: 2024,
+            month: 3,
+            day: 14,
+            wday: 4,
+        };
+        let time = Time {
+            hour: 9,
+            minute: 30,
+            second: 0,
+            hundredths: 0,
+        };
+        let device_id = ObjectId::new(ObjectType::ObjectDevice, 79079);
+        let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, 3);
+        let notification = EventNotification {
+            process_id: 1,
+            initiating_device_id: device_id,
+            event_object_id: object_id,
+            timestamp: TimeStamp::DateTime(DateTime::new(date, time)),
+            notification_class: 10,
+            priority: 100,
+            event_type: EventType::OutOfRange,
+            message_text: Some(CharacterString::new("Zone temperature high")),
+            notify_type: NotifyType::Alarm,
+            ack_required: Some(true),
+            from_state: Some(EventState::Normal),
+            to_state: EventState::HighLimit,
+            event_values: Some(NotificationParameters::OutOfRange {
+                exceeding_value: 28.5,
+                status_flags: Status::new(0b1000_0000),
+                deadband: 1.0,
+                exceeded_limit: 26.0,
+            }),
+        };
+
+        // encoding
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        UnconfirmedRequest::EventNotification(notification).encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = match UnconfirmedRequest::decode(&mut reader, &buf[..len]).unwrap() {
+            UnconfirmedRequest::EventNotification(x) => x,
+            x => panic!("unexpected request {:?}", x),
+        };
+        assert_eq!(decoded.initiating_device_id, device_id);
+        assert_eq!(decoded.event_object_id, object_id);
+        assert_eq!(decoded.event_type, EventType::OutOfRange);
+        assert_eq!(decoded.message_text.unwrap().inner, "Zone temperature high");
+        assert_eq!(decoded.ack_required, Some(true));
+        assert!(matches!(decoded.to_state, EventState::HighLimit));
+        assert!(matches!(
+            decoded.timestamp,
+            TimeStamp::DateTime(DateTime {
+                time: Time { hour: 9, .. },
+                ..
+            })
+        ));
+        match decoded.event_values {
+            Some(NotificationParameters::OutOfRange {
+                exceeding_value,
+                status_flags,
+                ..
+            }) => {
+                assert_eq!(exceeding_value, 28.5);
+                assert!(status_flags.in_alarm());
+            }
+            x => panic!("unexpected event values {:?}", x),
+        }
+    }
+
+    #[test]
+    fn proprietary_event_type_and_life_safety_state() {
+        let device_id = ObjectId::new(ObjectType::ObjectDevice, 79079);
+        let object_id = ObjectId::new(ObjectType::ObjectLifeSafetyPoint, 1);
+        let notification = EventNotification {
+            process_id: 1,
+            initiating_device_id: device_id,
+            event_object_id: object_id,
+            timestamp: TimeStamp::SequenceNumber(7),
+            notification_class: 10,
+            priority: 100,
+            event_type: EventType::Proprietary(600),
+            message_text: None,
+            notify_type: NotifyType::Alarm,
+            ack_required: Some(false),
+            from_state: Some(EventState::Proprietary(300)),
+            to_state: EventState::LifeSafetyAlarm,
+            event_values: None,
+        };
+
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        UnconfirmedRequest::EventNotification(notification).encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = match UnconfirmedRequest::decode(&mut reader, &buf[..len]).unwrap() {
+            UnconfirmedRequest::EventNotification(x) => x,
+            x => panic!("unexpected request {:?}", x),
+        };
+        assert_eq!(decoded.event_type, EventType::Proprietary(600));
+        assert!(matches!(
+            decoded.from_state,
+            Some(EventState::Proprietary(300))
+        ));
+        assert!(matches!(decoded.to_state, EventState::LifeSafetyAlarm));
+
+        // deprecated and reserved codes still decode
+        assert_eq!(EventType::from(6), EventType::Reserved(6));
+        assert_eq!(EventType::from(12), EventType::Reserved(12));
+        assert!(matches!(EventState::from(6), EventState::Reserved(6)));
+    }
+
+    #[test]
+    fn decode_change_of_life_safety() {
+        // life safety point 1 of device 79079 goes from normal to life safety alarm
+        let input = [
+            0x03, 0x09, 0x01, 0x1C, 0x02, 0x01, 0x34, 0xE7, 0x2C, 0x05, 0x40, 0x00, 0x01, 0x3E,
+            0x19, 0x07, 0x3F, 0x49, 0x0A, 0x59, 0x64, 0x69, 0x08, 0x89, 0x00, 0x99, 0x00, 0xA9,
+            0x00, 0xB9, 0x05, 0xCE, 0x8E, 0x09, 0x05, 0x19, 0x02, 0x2A, 0x04, 0x00, 0x39, 0x00,
+            0x8F, 0xCF,
+        ];
+        let mut reader = Reader::new_with_len(input.len());
+        let decoded = match UnconfirmedRequest::decode(&mut reader, &input).unwrap() {
+            UnconfirmedRequest::EventNotification(x) => x,
+            x => panic!("unexpected request {:?}", x),
+        };
+
+        assert_eq!(
+            decoded.event_object_id,
+            ObjectId::new(ObjectType::ObjectLifeSafetyPoint, 1)
+        );
+        assert_eq!(decoded.event_type, EventType::ChangeOfLifeSafety);
+        assert!(matches!(decoded.from_state, Some(EventState::Normal)));
+        assert!(matches!(decoded.to_state, EventState::LifeSafetyAlarm));
+        match decoded.event_values {
+            Some(NotificationParameters::Other(8, body)) => {
+                assert_eq!(body.inner, &input[33..42])
+            }
+            x => panic!("unexpected event values {:?}", x),
+        }
+    }
+
+    #[test]
+    fn other_parameters_are_kept() {
+        // change of life safety (not decoded): new state, new mode, status flags and operation expected
+        let input = [
+            0x8E, 0x09, 0x05, 0x19, 0x02, 0x2A, 0x04, 0x00, 0x39, 0x00, 0x8F,
+        ];
+        let mut reader = Reader::new_with_len(input.len());
+        let decoded = NotificationParameters::decode(&mut reader, &input).unwrap();
+        match &decoded {
+            NotificationParameters::Other(choice, body) => {
+                assert_eq!(*choice, 8);
+                assert_eq!(body.inner, &input[1..10]);
+            }
+            x => panic!("unexpected event values {:?}", x),
+        }
+
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        decoded.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), &input);
+    }
+}
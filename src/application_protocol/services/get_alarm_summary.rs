@@ -0,0 +1,327 @@
+// get alarm summary (superseded by get event information but still widely supported)
+
+#[cfg(feature = "alloc")]
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
+
+#[cfg(not(feature = "alloc"))]
+use crate::common::helper::encode_list;
+
+use crate::{
+    application_protocol::{
+        confirmed::{ComplexAck, ComplexAckService, ConfirmedServiceChoice},
+        primitives::data_value::BitString,
+    },
+    common::{
+        error::Error,
+        helper::{
+            decode_unsigned, encode_application_enumerated, encode_application_object_id, ListIter,
+        },
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        property_id::PropertyId,
+        spec::{EventState, EventTransitionBits},
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+    network_protocol::data_link::DataLink,
+};
+
+// this request has no parameters
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GetAlarmSummary {}
+
+impl GetAlarmSummary {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn encode(&self, _writer: &mut Writer) {}
+
+    pub fn decode(_reader: &mut Reader, _buf: &[u8]) -> Result<Self, Error> {
+        Ok(Self {})
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GetAlarmSummaryAck<'a> {
+    pub alarm_summaries: &'a [AlarmSummary],
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GetAlarmSummaryAck<'a> {
+    pub alarm_summaries: Vec<AlarmSummary>,
+    _phantom: &'a Phantom,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AlarmSummary {
+    pub object_id: ObjectId,
+    pub alarm_state: EventState,
+    pub acknowledged_transitions: EventTransitionBits,
+}
+
+impl<'a> GetAlarmSummaryAck<'a> {
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(alarm_summaries: &'a [AlarmSummary]) -> Self {
+        Self {
+            alarm_summaries,
+            buf: &[],
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(alarm_summaries: Vec<AlarmSummary>) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            alarm_summaries,
+            _phantom: &PHANTOM,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(ConfirmedServiceChoice::GetAlarmSummary as u8);
+        #[cfg(not(feature = "alloc"))]
+        encode_list(writer, self.buf, self.alarm_summaries, AlarmSummary::encode);
+        #[cfg(feature = "alloc")]
+        for alarm_summary in self.alarm_summaries.iter() {
+            alarm_summary.encode(writer);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let mut alarm_summaries = Vec::new();
+        while !reader.eof() {
+            let alarm_summary = AlarmSummary::decode(reader, buf)?;
+            alarm_summaries.push(alarm_summary);
+        }
+
+        Ok(Self::new(alarm_summaries))
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let buf = &buf[reader.index..reader.end];
+        reader.index = reader.end;
+        Ok(Self {
+            alarm_summaries: &[],
+            buf,
+        })
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ GetAlarmSummaryAck<'a> {
+    type Item = Result<AlarmSummary, Error>;
+
+    type IntoIter = AlarmSummaryIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        AlarmSummaryIter {
+            list: ListIter::new(self.alarm_summaries, self.buf),
+        }
+    }
+}
+
+impl<'a> TryFrom<DataLink<'a>> for GetAlarmSummaryAck<'a> {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        let ack: ComplexAck = value.try_into()?;
+        match ack.service {
+            ComplexAckService::GetAlarmSummary(ack) => Ok(ack),
+            _ => Err(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService GetAlarmSummaryAck",
+            )),
+        }
+    }
+}
+
+pub struct AlarmSummaryIter<'a> {
+    list: ListIter<'a, AlarmSummary>,
+}
+
+impl<'a> Iterator for AlarmSummaryIter<'a> {
+    type Item = Result<AlarmSummary, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.next_with(AlarmSummary::decode)
+    }
+}
+
+impl AlarmSummary {
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_application_object_id(writer, &self.object_id);
+        encode_application_enumerated(writer, u32::from(&self.alarm_state));
+        BitString::EventTransitionBits(self.acknowledged_transitions.clone())
+            .encode_application(writer);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::ObjectId),
+            "AlarmSummary decode object_id",
+        )?;
+        let object_id = ObjectId::decode(tag.value, reader, buf)?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Enumerated),
+            "AlarmSummary decode alarm_state",
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let alarm_state = EventState::from(value);
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::BitString),
+            "AlarmSummary decode acknowledged_transitions",
+        )?;
+        let acknowledged_transitions =
+            match BitString::decode(&PropertyId::PropAckedTransitions, tag.value, reader, buf)? {
+                BitString::EventTransitionBits(x) => x,
+                _ => {
+                    return Err(Error::InvalidValue(
+                        "AlarmSummary decode expected event transition bits",
+                    ))
+                }
+            };
+
+        Ok(Self {
+            object_id,
+            alarm_state,
+            acknowledged_transitions,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::confirmed::{ComplexAck, ComplexAckService},
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            spec::{EventState, EventTransitionBits},
+        },
+    };
+
+    use super::{AlarmSummary, GetAlarmSummaryAck};
+
+    #[test]
+    fn reversable() {
+        use alloc::vec;
+
+        let alarm_summaries = vec![
+            AlarmSummary {
+                object_id: ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+                alarm_state: EventState::HighLimit,
+                acknowledged_transitions: EventTransitionBits::new(0b0110_0000),
+            },
+            AlarmSummary {
+                object_id: ObjectId::new(ObjectType::ObjectBinaryInput, 2),
+                alarm_state: EventState::OffNormal,
+                acknowledged_transitions: EventTransitionBits::new(0b1110_0000),
+            },
+        ];
+        let ack = GetAlarmSummaryAck::new(alarm_summaries);
+
+        // encoding
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        let ack = ComplexAck {
+            invoke_id: 4,
+            service: ComplexAckService::GetAlarmSummary(ack),
+        };
+        ack.encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = match ComplexAck::decode(&mut reader, &buf[..len])
+            .unwrap()
+            .service
+        {
+            ComplexAckService::GetAlarmSummary(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        assert_eq!(decoded.alarm_summaries.len(), 2);
+        let first = &decoded.alarm_summaries[0];
+        assert_eq!(
+            first.object_id,
+            ObjectId::new(ObjectType::ObjectAnalogInput, 1)
+        );
+        assert!(matches!(first.alarm_state, EventState::HighLimit));
+        assert!(!first.acknowledged_transitions.to_offnormal());
+        assert!(first.acknowledged_transitions.to_fault());
+        assert!(first.acknowledged_transitions.to_normal());
+        let second = &decoded.alarm_summaries[1];
+        assert!(matches!(second.alarm_state, EventState::OffNormal));
+        assert!(second.acknowledged_transitions.to_offnormal());
+    }
+}
+
+#[cfg(all(test, not(feature = "alloc")))]
+mod no_alloc_tests {
+    use crate::{
+        application_protocol::confirmed::{ComplexAck, ComplexAckService},
+        common::{
+            helper::assert_encodes_to,
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            spec::{EventState, EventTransitionBits},
+        },
+    };
+
+    use super::{AlarmSummary, GetAlarmSummaryAck};
+
+    #[test]
+    fn reversable() {
+        let alarm_summaries = [AlarmSummary {
+            object_id: ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+            alarm_state: EventState::HighLimit,
+            acknowledged_transitions: EventTransitionBits::new(0b0110_0000),
+        }];
+        let ack = GetAlarmSummaryAck::new(&alarm_summaries);
+        assert_eq!((&ack).into_iter().count(), 1);
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        ComplexAck {
+            invoke_id: 4,
+            service: ComplexAckService::GetAlarmSummary(ack),
+        }
+        .encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ComplexAck::decode(&mut reader, &buf[..len]).unwrap();
+        let ack = match &decoded.service {
+            ComplexAckService::GetAlarmSummary(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        let mut summaries = ack.into_iter();
+        let first = summaries.next().unwrap().unwrap();
+        assert_eq!(
+            first.object_id,
+            ObjectId::new(ObjectType::ObjectAnalogInput, 1)
+        );
+        assert!(matches!(first.alarm_state, EventState::HighLimit));
+        assert!(summaries.next().is_none());
+
+        assert_encodes_to(&buf[..len], |writer| decoded.encode(writer));
+    }
+}
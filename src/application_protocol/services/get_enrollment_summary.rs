@@ -0,0 +1,508 @@
+// get enrollment summary
+
+#[cfg(feature = "alloc")]
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
+
+#[cfg(not(feature = "alloc"))]
+use crate::common::helper::encode_list;
+
+use crate::{
+    application_protocol::{
+        confirmed::{ComplexAck, ComplexAckService, ConfirmedServiceChoice},
+        primitives::recipient::RecipientProcess,
+    },
+    common::{
+        error::Error,
+        helper::{
+            decode_unsigned, encode_application_enumerated, encode_application_object_id,
+            encode_application_unsigned, encode_closing_tag, encode_context_unsigned,
+            encode_opening_tag, peek_tag, ListIter,
+        },
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        spec::{AcknowledgmentFilter, EventState, EventType},
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+    network_protocol::data_link::DataLink,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u32)]
+pub enum EventStateFilter {
+    OffNormal = 0,
+    Fault = 1,
+    Normal = 2,
+    All = 3,
+    Active = 4,
+}
+
+impl TryFrom<u32> for EventStateFilter {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::OffNormal),
+            1 => Ok(Self::Fault),
+            2 => Ok(Self::Normal),
+            3 => Ok(Self::All),
+            4 => Ok(Self::Active),
+            x => Err(x),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PriorityFilter {
+    pub min_priority: u8,
+    pub max_priority: u8,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GetEnrollmentSummary<'a> {
+    pub acknowledgment_filter: AcknowledgmentFilter,
+    pub enrollment_filter: Option<RecipientProcess<'a>>,
+    pub event_state_filter: Option<EventStateFilter>,
+    pub event_type_filter: Option<EventType>,
+    pub priority_filter: Option<PriorityFilter>,
+    pub notification_class_filter: Option<u16>,
+}
+
+impl<'a> GetEnrollmentSummary<'a> {
+    const TAG_ACKNOWLEDGMENT_FILTER: u8 = 0;
+    const TAG_ENROLLMENT_FILTER: u8 = 1;
+    const TAG_EVENT_STATE_FILTER: u8 = 2;
+    const TAG_EVENT_TYPE_FILTER: u8 = 3;
+    const TAG_PRIORITY_FILTER: u8 = 4;
+    const TAG_NOTIFICATION_CLASS_FILTER: u8 = 5;
+
+    const TAG_MIN_PRIORITY: u8 = 0;
+    const TAG_MAX_PRIORITY: u8 = 1;
+
+    // use the public fields to set the optional filters
+    pub fn new(acknowledgment_filter: AcknowledgmentFilter) -> Self {
+        Self {
+            acknowledgment_filter,
+            enrollment_filter: None,
+            event_state_filter: None,
+            event_type_filter: None,
+            priority_filter: None,
+            notification_class_filter: None,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_unsigned(
+            writer,
+            Self::TAG_ACKNOWLEDGMENT_FILTER,
+            self.acknowledgment_filter.clone() as u32,
+        );
+
+        if let Some(enrollment_filter) = &self.enrollment_filter {
+            encode_opening_tag(writer, Self::TAG_ENROLLMENT_FILTER);
+            enrollment_filter.encode(writer);
+            encode_closing_tag(writer, Self::TAG_ENROLLMENT_FILTER);
+        }
+
+        if let Some(event_state_filter) = self.event_state_filter {
+            encode_context_unsigned(
+                writer,
+                Self::TAG_EVENT_STATE_FILTER,
+                event_state_filter as u32,
+            );
+        }
+
+        if let Some(event_type_filter) = self.event_type_filter {
+            encode_context_unsigned(
+                writer,
+                Self::TAG_EVENT_TYPE_FILTER,
+                u32::from(&event_type_filter),
+            );
+        }
+
+        if let Some(priority_filter) = &self.priority_filter {
+            encode_opening_tag(writer, Self::TAG_PRIORITY_FILTER);
+            encode_context_unsigned(writer, Self::TAG_MIN_PRIORITY, priority_filter.min_priority);
+            encode_context_unsigned(writer, Self::TAG_MAX_PRIORITY, priority_filter.max_priority);
+            encode_closing_tag(writer, Self::TAG_PRIORITY_FILTER);
+        }
+
+        if let Some(notification_class_filter) = self.notification_class_filter {
+            encode_context_unsigned(
+                writer,
+                Self::TAG_NOTIFICATION_CLASS_FILTER,
+                notification_class_filter,
+            );
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_ACKNOWLEDGMENT_FILTER),
+            "GetEnrollmentSummary decode acknowledgment_filter",
+        )?;
+        let acknowledgment_filter = match decode_unsigned(tag.value, reader, buf)? {
+            0 => AcknowledgmentFilter::All,
+            1 => AcknowledgmentFilter::Acked,
+            2 => AcknowledgmentFilter::NotAcked,
+            x => {
+                return Err(Error::InvalidVariant((
+                    "GetEnrollmentSummary decode acknowledgment_filter",
+                    x as u32,
+                )))
+            }
+        };
+
+        let mut request = Self::new(acknowledgment_filter);
+
+        // all other filters are optional
+        while !reader.eof() {
+            let tag = peek_tag(reader, buf)?;
+            match tag.number {
+                TagNumber::ContextSpecificOpening(Self::TAG_ENROLLMENT_FILTER) => {
+                    Tag::decode(reader, buf)?;
+                    request.enrollment_filter = Some(RecipientProcess::decode(reader, buf)?);
+                    Tag::decode_expected(
+                        reader,
+                        buf,
+                        TagNumber::ContextSpecificClosing(Self::TAG_ENROLLMENT_FILTER),
+                        "GetEnrollmentSummary decode enrollment_filter",
+                    )?;
+                }
+                TagNumber::ContextSpecific(Self::TAG_EVENT_STATE_FILTER) => {
+                    Tag::decode(reader, buf)?;
+                    let value = decode_unsigned(tag.value, reader, buf)? as u32;
+                    request.event_state_filter =
+                        Some(EventStateFilter::try_from(value).map_err(|x| {
+                            Error::InvalidVariant((
+                                "GetEnrollmentSummary decode event_state_filter",
+                                x,
+                            ))
+                        })?);
+                }
+                TagNumber::ContextSpecific(Self::TAG_EVENT_TYPE_FILTER) => {
+                    Tag::decode(reader, buf)?;
+                    let value = decode_unsigned(tag.value, reader, buf)? as u32;
+                    request.event_type_filter = Some(EventType::from(value));
+                }
+                TagNumber::ContextSpecificOpening(Self::TAG_PRIORITY_FILTER) => {
+                    Tag::decode(reader, buf)?;
+                    let tag = Tag::decode_expected(
+                        reader,
+                        buf,
+                        TagNumber::ContextSpecific(Self::TAG_MIN_PRIORITY),
+                        "GetEnrollmentSummary decode min_priority",
+                    )?;
+                    let min_priority = decode_unsigned(tag.value, reader, buf)? as u8;
+                    let tag = Tag::decode_expected(
+                        reader,
+                        buf,
+                        TagNumber::ContextSpecific(Self::TAG_MAX_PRIORITY),
+                        "GetEnrollmentSummary decode max_priority",
+                    )?;
+                    let max_priority = decode_unsigned(tag.value, reader, buf)? as u8;
+                    Tag::decode_expected(
+                        reader,
+                        buf,
+                        TagNumber::ContextSpecificClosing(Self::TAG_PRIORITY_FILTER),
+                        "GetEnrollmentSummary decode priority_filter",
+                    )?;
+                    request.priority_filter = Some(PriorityFilter {
+                        min_priority,
+                        max_priority,
+                    });
+                }
+                TagNumber::ContextSpecific(Self::TAG_NOTIFICATION_CLASS_FILTER) => {
+                    Tag::decode(reader, buf)?;
+                    let value = decode_unsigned(tag.value, reader, buf)? as u16;
+                    request.notification_class_filter = Some(value);
+                }
+                x => {
+                    return Err(Error::TagNotSupported((
+                        "GetEnrollmentSummary decode filter",
+                        x,
+                    )))
+                }
+            }
+        }
+
+        Ok(request)
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GetEnrollmentSummaryAck<'a> {
+    pub enrollment_summaries: &'a [EnrollmentSummary],
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GetEnrollmentSummaryAck<'a> {
+    pub enrollment_summaries: Vec<EnrollmentSummary>,
+    _phantom: &'a Phantom,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EnrollmentSummary {
+    pub object_id: ObjectId,
+    pub event_type: EventType,
+    pub event_state: EventState,
+    pub priority: u8,
+    pub notification_class: Option<u16>,
+}
+
+impl<'a> GetEnrollmentSummaryAck<'a> {
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(enrollment_summaries: &'a [EnrollmentSummary]) -> Self {
+        Self {
+            enrollment_summaries,
+            buf: &[],
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(enrollment_summaries: Vec<EnrollmentSummary>) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            enrollment_summaries,
+            _phantom: &PHANTOM,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(ConfirmedServiceChoice::GetEnrollmentSummary as u8);
+        #[cfg(not(feature = "alloc"))]
+        encode_list(
+            writer,
+            self.buf,
+            self.enrollment_summaries,
+            EnrollmentSummary::encode,
+        );
+        #[cfg(feature = "alloc")]
+        for enrollment_summary in self.enrollment_summaries.iter() {
+            enrollment_summary.encode(writer);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let mut enrollment_summaries = Vec::new();
+        while !reader.eof() {
+            let enrollment_summary = EnrollmentSummary::decode(reader, buf)?;
+            enrollment_summaries.push(enrollment_summary);
+        }
+
+        Ok(Self::new(enrollment_summaries))
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let buf = &buf[reader.index..reader.end];
+        reader.index = reader.end;
+        Ok(Self {
+            enrollment_summaries: &[],
+            buf,
+        })
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ GetEnrollmentSummaryAck<'a> {
+    type Item = Result<EnrollmentSummary, Error>;
+
+    type IntoIter = EnrollmentSummaryIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        EnrollmentSummaryIter {
+            list: ListIter::new(self.enrollment_summaries, self.buf),
+        }
+    }
+}
+
+impl<'a> TryFrom<DataLink<'a>> for GetEnrollmentSummaryAck<'a> {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        let ack: ComplexAck = value.try_into()?;
+        match ack.service {
+            ComplexAckService::GetEnrollmentSummary(ack) => Ok(ack),
+            _ => Err(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService GetEnrollmentSummaryAck",
+            )),
+        }
+    }
+}
+
+pub struct EnrollmentSummaryIter<'a> {
+    list: ListIter<'a, EnrollmentSummary>,
+}
+
+impl<'a> Iterator for EnrollmentSummaryIter<'a> {
+    type Item = Result<EnrollmentSummary, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.next_with(EnrollmentSummary::decode)
+    }
+}
+
+impl EnrollmentSummary {
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_application_object_id(writer, &self.object_id);
+        encode_application_enumerated(writer, u32::from(&self.event_type));
+        encode_application_enumerated(writer, u32::from(&self.event_state));
+        encode_application_unsigned(writer, self.priority);
+        if let Some(notification_class) = self.notification_class {
+            encode_application_unsigned(writer, notification_class);
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::ObjectId),
+            "EnrollmentSummary decode object_id",
+        )?;
+        let object_id = ObjectId::decode(tag.value, reader, buf)?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Enumerated),
+            "EnrollmentSummary decode event_type",
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let event_type = EventType::from(value);
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Enumerated),
+            "EnrollmentSummary decode event_state",
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let event_state = EventState::from(value);
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::UnsignedInt),
+            "EnrollmentSummary decode priority",
+        )?;
+        let priority = decode_unsigned(tag.value, reader, buf)? as u8;
+
+        // the notification class is optional and the next summary starts with an object id
+        let mut notification_class = None;
+        if !reader.eof()
+            && peek_tag(reader, buf)?.number
+                == TagNumber::Application(ApplicationTagNumber::UnsignedInt)
+        {
+            let tag = Tag::decode(reader, buf)?;
+            notification_class = Some(decode_unsigned(tag.value, reader, buf)? as u16);
+        }
+
+        Ok(Self {
+            object_id,
+            event_type,
+            event_state,
+            priority,
+            notification_class,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::confirmed::{ComplexAck, ComplexAckService},
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            spec::{EventState, EventType},
+        },
+    };
+
+    use super::{EnrollmentSummary, GetEnrollmentSummaryAck};
+
+    #[test]
+    fn reversable() {
+        use alloc::vec;
+
+        // the notification class is optional so mix summaries with and without one
+        let enrollment_summaries = vec![
+            EnrollmentSummary {
+                object_id: ObjectId::new(ObjectType::ObjectAnalogInput, 1),
+                event_type: EventType::OutOfRange,
+                event_state: EventState::Normal,
+                priority: 100,
+                notification_class: Some(4),
+            },
+            EnrollmentSummary {
+                object_id: ObjectId::new(ObjectType::ObjectBinaryInput, 2),
+                event_type: EventType::ChangeOfState,
+                event_state: EventState::OffNormal,
+                priority: 50,
+                notification_class: None,
+            },
+            EnrollmentSummary {
+                object_id: ObjectId::new(ObjectType::ObjectEventEnrollment, 3),
+                event_type: EventType::FloatingLimit,
+                event_state: EventState::LowLimit,
+                priority: 200,
+                notification_class: Some(7),
+            },
+        ];
+        let ack = GetEnrollmentSummaryAck::new(enrollment_summaries);
+
+        // encoding
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        let ack = ComplexAck {
+            invoke_id: 5,
+            service: ComplexAckService::GetEnrollmentSummary(ack),
+        };
+        ack.encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = match ComplexAck::decode(&mut reader, &buf[..len])
+            .unwrap()
+            .service
+        {
+            ComplexAckService::GetEnrollmentSummary(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        let summaries = &decoded.enrollment_summaries;
+        assert_eq!(summaries.len(), 3);
+        assert_eq!(
+            summaries[0].object_id,
+            ObjectId::new(ObjectType::ObjectAnalogInput, 1)
+        );
+        assert_eq!(summaries[0].event_type, EventType::OutOfRange);
+        assert_eq!(summaries[0].priority, 100);
+        assert_eq!(summaries[0].notification_class, Some(4));
+        assert_eq!(summaries[1].event_type, EventType::ChangeOfState);
+        assert!(matches!(summaries[1].event_state, EventState::OffNormal));
+        assert_eq!(summaries[1].notification_class, None);
+        assert_eq!(
+            summaries[2].object_id,
+            ObjectId::new(ObjectType::ObjectEventEnrollment, 3)
+        );
+        assert_eq!(summaries[2].notification_class, Some(7));
+    }
+}
@@ -0,0 +1,432 @@
+// get event information
+
+#[cfg(feature = "alloc")]
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
+
+#[cfg(not(feature = "alloc"))]
+use crate::common::helper::encode_list;
+
+use crate::{
+    application_protocol::{
+        confirmed::{ComplexAck, ComplexAckService, ConfirmedServiceChoice},
+        primitives::data_value::{BitString, TimeStamp},
+    },
+    common::{
+        error::Error,
+        helper::{
+            decode_context_bool, decode_context_object_id, decode_unsigned,
+            encode_application_unsigned, encode_closing_tag, encode_context_bool,
+            encode_context_object_id, encode_context_unsigned, encode_opening_tag,
+            get_tagged_body_for_tag, ListIter,
+        },
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        property_id::PropertyId,
+        spec::{EventState, EventTransitionBits, NotifyType},
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+    network_protocol::data_link::DataLink,
+};
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GetEventInformation {
+    pub last_received_object_id: Option<ObjectId>, // used to fetch the next page of event summaries
+}
+
+impl GetEventInformation {
+    const TAG_LAST_RECEIVED_OBJECT_ID: u8 = 0;
+
+    pub fn new(last_received_object_id: Option<ObjectId>) -> Self {
+        Self {
+            last_received_object_id,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        if let Some(object_id) = &self.last_received_object_id {
+            encode_context_object_id(writer, Self::TAG_LAST_RECEIVED_OBJECT_ID, object_id);
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let mut last_received_object_id = None;
+        if !reader.eof() {
+            last_received_object_id = Some(decode_context_object_id(
+                reader,
+                buf,
+                Self::TAG_LAST_RECEIVED_OBJECT_ID,
+                "GetEventInformation decode last_received_object_id",
+            )?);
+        }
+
+        Ok(Self {
+            last_received_object_id,
+        })
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GetEventInformationAck<'a> {
+    pub event_summaries: &'a [EventSummary],
+    pub more_events: bool,
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GetEventInformationAck<'a> {
+    pub event_summaries: Vec<EventSummary>,
+    pub more_events: bool,
+    _phantom: &'a Phantom,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EventSummary {
+    pub object_id: ObjectId,
+    pub event_state: EventState,
+    pub acknowledged_transitions: EventTransitionBits,
+    pub event_timestamps: [TimeStamp; 3], // to-offnormal, to-fault and to-normal
+    pub notify_type: NotifyType,
+    pub event_enable: EventTransitionBits,
+    pub event_priorities: [u32; 3], // to-offnormal, to-fault and to-normal
+}
+
+impl<'a> GetEventInformationAck<'a> {
+    const TAG_EVENT_SUMMARIES: u8 = 0;
+    const TAG_MORE_EVENTS: u8 = 1;
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(event_summaries: &'a [EventSummary], more_events: bool) -> Self {
+        Self {
+            event_summaries,
+            more_events,
+            buf: &[],
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(event_summaries: Vec<EventSummary>, more_events: bool) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            event_summaries,
+            more_events,
+            _phantom: &PHANTOM,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(ConfirmedServiceChoice::GetEventInformation as u8);
+
+        encode_opening_tag(writer, Self::TAG_EVENT_SUMMARIES);
+        #[cfg(not(feature = "alloc"))]
+        encode_list(writer, self.buf, self.event_summaries, EventSummary::encode);
+        #[cfg(feature = "alloc")]
+        for event_summary in self.event_summaries.iter() {
+            event_summary.encode(writer);
+        }
+        encode_closing_tag(writer, Self::TAG_EVENT_SUMMARIES);
+
+        encode_context_bool(writer, Self::TAG_MORE_EVENTS, self.more_events);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_EVENT_SUMMARIES,
+            "GetEventInformationAck decode event_summaries",
+        )?;
+
+        let more_events = decode_context_bool(
+            reader,
+            buf,
+            Self::TAG_MORE_EVENTS,
+            "GetEventInformationAck decode more_events",
+        )?;
+
+        #[cfg(not(feature = "alloc"))]
+        return Ok(Self {
+            event_summaries: &[],
+            more_events,
+            buf: inner_buf,
+        });
+
+        #[cfg(feature = "alloc")]
+        {
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            let mut event_summaries = Vec::new();
+            while !inner_reader.eof() {
+                let event_summary = EventSummary::decode(&mut inner_reader, inner_buf)?;
+                event_summaries.push(event_summary);
+            }
+            Ok(Self::new(event_summaries, more_events))
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ GetEventInformationAck<'a> {
+    type Item = Result<EventSummary, Error>;
+
+    type IntoIter = EventSummaryIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        EventSummaryIter {
+            list: ListIter::new(self.event_summaries, self.buf),
+        }
+    }
+}
+
+impl<'a> TryFrom<DataLink<'a>> for GetEventInformationAck<'a> {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        let ack: ComplexAck = value.try_into()?;
+        match ack.service {
+            ComplexAckService::GetEventInformation(ack) => Ok(ack),
+            _ => Err(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService GetEventInformationAck",
+            )),
+        }
+    }
+}
+
+pub struct EventSummaryIter<'a> {
+    list: ListIter<'a, EventSummary>,
+}
+
+impl<'a> Iterator for EventSummaryIter<'a> {
+    type Item = Result<EventSummary, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.next_with(EventSummary::decode)
+    }
+}
+
+impl EventSummary {
+    const TAG_OBJECT_ID: u8 = 0;
+    const TAG_EVENT_STATE: u8 = 1;
+    const TAG_ACKNOWLEDGED_TRANSITIONS: u8 = 2;
+    const TAG_EVENT_TIMESTAMPS: u8 = 3;
+    const TAG_NOTIFY_TYPE: u8 = 4;
+    const TAG_EVENT_ENABLE: u8 = 5;
+    const TAG_EVENT_PRIORITIES: u8 = 6;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
+        encode_context_unsigned(writer, Self::TAG_EVENT_STATE, u32::from(&self.event_state));
+        BitString::EventTransitionBits(self.acknowledged_transitions.clone())
+            .encode_context(Self::TAG_ACKNOWLEDGED_TRANSITIONS, writer);
+
+        encode_opening_tag(writer, Self::TAG_EVENT_TIMESTAMPS);
+        for timestamp in self.event_timestamps.iter() {
+            timestamp.encode(writer);
+        }
+        encode_closing_tag(writer, Self::TAG_EVENT_TIMESTAMPS);
+
+        encode_context_unsigned(
+            writer,
+            Self::TAG_NOTIFY_TYPE,
+            self.notify_type.clone() as u32,
+        );
+        BitString::EventTransitionBits(self.event_enable.clone())
+            .encode_context(Self::TAG_EVENT_ENABLE, writer);
+
+        encode_opening_tag(writer, Self::TAG_EVENT_PRIORITIES);
+        for priority in self.event_priorities {
+            encode_application_unsigned(writer, priority);
+        }
+        encode_closing_tag(writer, Self::TAG_EVENT_PRIORITIES);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let object_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_OBJECT_ID,
+            "EventSummary decode object_id",
+        )?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_EVENT_STATE),
+            "EventSummary decode event_state",
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let event_state = EventState::from(value);
+
+        let acknowledged_transitions = decode_transition_bits(
+            reader,
+            buf,
+            Self::TAG_ACKNOWLEDGED_TRANSITIONS,
+            &PropertyId::PropAckedTransitions,
+        )?;
+
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecificOpening(Self::TAG_EVENT_TIMESTAMPS),
+            "EventSummary decode event_timestamps",
+        )?;
+        let event_timestamps = [
+            TimeStamp::decode(reader, buf)?,
+            TimeStamp::decode(reader, buf)?,
+            TimeStamp::decode(reader, buf)?,
+        ];
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecificClosing(Self::TAG_EVENT_TIMESTAMPS),
+            "EventSummary decode event_timestamps",
+        )?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_NOTIFY_TYPE),
+            "EventSummary decode notify_type",
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let notify_type = NotifyType::try_from(value)
+            .map_err(|x| Error::InvalidVariant(("EventSummary decode notify_type", x)))?;
+
+        let event_enable = decode_transition_bits(
+            reader,
+            buf,
+            Self::TAG_EVENT_ENABLE,
+            &PropertyId::PropEventEnable,
+        )?;
+
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecificOpening(Self::TAG_EVENT_PRIORITIES),
+            "EventSummary decode event_priorities",
+        )?;
+        let mut event_priorities = [0; 3];
+        for priority in event_priorities.iter_mut() {
+            let tag = Tag::decode_expected(
+                reader,
+                buf,
+                TagNumber::Application(ApplicationTagNumber::UnsignedInt),
+                "EventSummary decode event_priorities",
+            )?;
+            *priority = decode_unsigned(tag.value, reader, buf)? as u32;
+        }
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecificClosing(Self::TAG_EVENT_PRIORITIES),
+            "EventSummary decode event_priorities",
+        )?;
+
+        Ok(Self {
+            object_id,
+            event_state,
+            acknowledged_transitions,
+            event_timestamps,
+            notify_type,
+            event_enable,
+            event_priorities,
+        })
+    }
+}
+
+fn decode_transition_bits(
+    reader: &mut Reader,
+    buf: &[u8],
+    tag_number: u8,
+    property_id: &PropertyId,
+) -> Result<EventTransitionBits, Error> {
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::ContextSpecific(tag_number),
+        "EventSummary decode transition bits",
+    )?;
+    match BitString::decode(property_id, tag.value, reader, buf)? {
+        BitString::EventTransitionBits(x) => Ok(x),
+        _ => Err(Error::InvalidValue(
+            "EventSummary decode expected event transition bits",
+        )),
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ComplexAck, ComplexAckService},
+            primitives::data_value::TimeStamp,
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            spec::{EventState, EventTransitionBits, NotifyType},
+        },
+    };
+
+    use super::{EventSummary, GetEventInformationAck};
+
+    #[test]
+    fn reversable() {
+        use alloc::vec;
+
+        let object_id = ObjectId::new(ObjectType::ObjectBinaryInput, 2);
+        let event_summary = EventSummary {
+            object_id,
+            event_state: EventState::OffNormal,
+            acknowledged_transitions: EventTransitionBits::new(0b0110_0000),
+            event_timestamps: [
+                TimeStamp::SequenceNumber(3),
+                TimeStamp::SequenceNumber(0),
+                TimeStamp::SequenceNumber(0),
+            ],
+            notify_type: NotifyType::Alarm,
+            event_enable: EventTransitionBits::new(0b1110_0000),
+            event_priorities: [10, 20, 30],
+        };
+        let ack = GetEventInformationAck::new(vec![event_summary], true);
+
+        // encoding
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        let ack = ComplexAck {
+            invoke_id: 3,
+            service: ComplexAckService::GetEventInformation(ack),
+        };
+        ack.encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = match ComplexAck::decode(&mut reader, &buf[..len])
+            .unwrap()
+            .service
+        {
+            ComplexAckService::GetEventInformation(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        assert!(decoded.more_events);
+        assert_eq!(decoded.event_summaries.len(), 1);
+        let summary = &decoded.event_summaries[0];
+        assert_eq!(summary.object_id, object_id);
+        assert!(!summary.acknowledged_transitions.to_offnormal());
+        assert!(summary.acknowledged_transitions.to_fault());
+        assert!(summary.event_enable.to_normal());
+        assert!(matches!(
+            summary.event_timestamps[0],
+            TimeStamp::SequenceNumber(3)
+        ));
+        assert_eq!(summary.event_priorities, [10, 20, 30]);
+    }
+}
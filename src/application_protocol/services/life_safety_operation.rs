@@ -0,0 +1,188 @@
+// life safety operation (silences or resets life safety objects, e.g. the points and zones of a fire panel)
+
+use crate::{
+    application_protocol::primitives::data_value::CharacterString,
+    common::{
+        error::Error,
+        helper::{
+            decode_context_object_id, decode_unsigned, encode_context_character_string,
+            encode_context_object_id, encode_context_unsigned, encode_enumerated,
+        },
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        spec::LifeSafetyOperation,
+        tag::{Tag, TagNumber},
+    },
+};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LifeSafetyOperationRequest<'a> {
+    pub process_id: u32,
+    pub requesting_source: CharacterString<'a>, // e.g. the name of the operator
+    pub operation: LifeSafetyOperation,
+    pub object_id: Option<ObjectId>, // all life safety objects in the device if not specified
+}
+
+impl<'a> LifeSafetyOperationRequest<'a> {
+    const TAG_PROCESS_ID: u8 = 0;
+    const TAG_REQUESTING_SOURCE: u8 = 1;
+    const TAG_OPERATION: u8 = 2;
+    const TAG_OBJECT_ID: u8 = 3;
+
+    pub fn new(
+        process_id: u32,
+        requesting_source: CharacterString<'a>,
+        operation: LifeSafetyOperation,
+        object_id: Option<ObjectId>,
+    ) -> Self {
+        Self {
+            process_id,
+            requesting_source,
+            operation,
+            object_id,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        // requesting process id
+        encode_context_unsigned(writer, Self::TAG_PROCESS_ID, self.process_id);
+
+        // requesting source
+        encode_context_character_string(
+            writer,
+            Self::TAG_REQUESTING_SOURCE,
+            self.requesting_source.as_str(),
+        );
+
+        // request
+        encode_enumerated(writer, self.operation as u32, Some(Self::TAG_OPERATION));
+
+        // object id
+        if let Some(object_id) = &self.object_id {
+            encode_context_object_id(writer, Self::TAG_OBJECT_ID, object_id);
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // requesting process id
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_PROCESS_ID),
+            "LifeSafetyOperationRequest decode process_id",
+        )?;
+        let process_id = decode_unsigned(tag.value, reader, buf)? as u32;
+
+        // requesting source
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_REQUESTING_SOURCE),
+            "LifeSafetyOperationRequest decode requesting_source",
+        )?;
+        let requesting_source = CharacterString::decode(tag.value, reader, buf)?;
+
+        // request
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_OPERATION),
+            "LifeSafetyOperationRequest decode operation",
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let operation = LifeSafetyOperation::try_from(value).map_err(|x| {
+            Error::InvalidVariant(("LifeSafetyOperationRequest decode operation", x))
+        })?;
+
+        // object id
+        let mut object_id = None;
+        if !reader.eof() {
+            object_id = Some(decode_context_object_id(
+                reader,
+                buf,
+                Self::TAG_OBJECT_ID,
+                "LifeSafetyOperationRequest decode object_id",
+            )?);
+        }
+
+        Ok(Self {
+            process_id,
+            requesting_source,
+            operation,
+            object_id,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ConfirmedRequest, ConfirmedRequestService},
+            primitives::data_value::{ApplicationDataValue, CharacterString, Enumerated},
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{LifeSafetyOperation, LifeSafetyState},
+        },
+    };
+
+    use super::LifeSafetyOperationRequest;
+
+    #[test]
+    fn reversable() {
+        let object_id = ObjectId::new(ObjectType::ObjectLifeSafetyZone, 3);
+        let request = LifeSafetyOperationRequest::new(
+            7,
+            CharacterString::new("operator 1"),
+            LifeSafetyOperation::SilenceAudible,
+            Some(object_id),
+        );
+
+        // encoding
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        let service = ConfirmedRequestService::LifeSafetyOperation(request);
+        ConfirmedRequest::new(1, service).encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ConfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        let decoded = match decoded.service {
+            ConfirmedRequestService::LifeSafetyOperation(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        assert_eq!(decoded.process_id, 7);
+        assert_eq!(decoded.requesting_source.inner, "operator 1");
+        assert_eq!(decoded.operation, LifeSafetyOperation::SilenceAudible);
+        assert_eq!(decoded.object_id, Some(object_id));
+    }
+
+    #[test]
+    fn decode_proprietary_life_safety_state() {
+        let object_id = ObjectId::new(ObjectType::ObjectLifeSafetyPoint, 1);
+        let decode = |buf: &[u8]| {
+            let mut reader = Reader::new_with_len(buf.len());
+            let property_id = PropertyId::PropPresentValue;
+            ApplicationDataValue::decode(&object_id, &property_id, &mut reader, buf).unwrap()
+        };
+
+        // enumerated 2 (alarm)
+        assert!(matches!(
+            decode(&[0x91, 0x02]),
+            ApplicationDataValue::Enumerated(Enumerated::LifeSafetyState(LifeSafetyState::Alarm))
+        ));
+
+        // enumerated 300 (proprietary)
+        assert!(matches!(
+            decode(&[0x92, 0x01, 0x2C]),
+            ApplicationDataValue::Enumerated(Enumerated::Unknown(300))
+        ));
+    }
+}
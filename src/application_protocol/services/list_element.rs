@@ -0,0 +1,531 @@
+// add list element and remove list element (edit a list property without overwriting entries added by others)
+
+#[cfg(feature = "alloc")]
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
+
+#[cfg(not(feature = "alloc"))]
+use crate::common::helper::encode_list;
+
+use crate::{
+    application_protocol::primitives::{
+        data_value::ApplicationDataValue,
+        property_reference::DeviceObjectPropertyReference,
+        recipient::{AddressBinding, Destination, Recipient},
+        special_event::SpecialEvent,
+    },
+    common::{
+        error::Error,
+        helper::{
+            decode_context_object_id, decode_context_property_id, decode_unsigned,
+            encode_closing_tag, encode_context_enumerated, encode_context_object_id,
+            encode_context_unsigned, encode_opening_tag, get_tagged_body_for_tag, peek_tag,
+            ListIter,
+        },
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        property_id::PropertyId,
+        spec::BACNET_ARRAY_ALL,
+        tag::{Tag, TagNumber},
+    },
+};
+
+// both services share the same request parameters
+pub type AddListElement<'a> = ListElementRequest<'a>;
+pub type RemoveListElement<'a> = ListElementRequest<'a>;
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ListElementRequest<'a> {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub elements: &'a [ListElement<'a>],
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ListElementRequest<'a> {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub elements: Vec<ListElement<'a>>,
+    _phantom: &'a Phantom,
+}
+
+impl<'a> ListElementRequest<'a> {
+    const TAG_OBJECT_ID: u8 = 0;
+    const TAG_PROPERTY_ID: u8 = 1;
+    const TAG_ARRAY_INDEX: u8 = 2;
+    const TAG_ELEMENTS: u8 = 3;
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(
+        object_id: ObjectId,
+        property_id: PropertyId,
+        array_index: Option<u32>,
+        elements: &'a [ListElement<'a>],
+    ) -> Self {
+        Self {
+            object_id,
+            property_id,
+            array_index,
+            elements,
+            buf: &[],
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(
+        object_id: ObjectId,
+        property_id: PropertyId,
+        array_index: Option<u32>,
+        elements: Vec<ListElement<'a>>,
+    ) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            object_id,
+            property_id,
+            array_index,
+            elements,
+            _phantom: &PHANTOM,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        // object_id
+        encode_context_object_id(writer, Self::TAG_OBJECT_ID, &self.object_id);
+
+        // property_id
+        encode_context_enumerated(writer, Self::TAG_PROPERTY_ID, &self.property_id);
+
+        // array_index
+        if let Some(array_index) = self.array_index {
+            encode_context_unsigned(writer, Self::TAG_ARRAY_INDEX, array_index);
+        }
+
+        // elements
+        encode_opening_tag(writer, Self::TAG_ELEMENTS);
+        #[cfg(not(feature = "alloc"))]
+        encode_list(writer, self.buf, self.elements, ListElement::encode);
+        #[cfg(feature = "alloc")]
+        for element in self.elements.iter() {
+            element.encode(writer);
+        }
+        encode_closing_tag(writer, Self::TAG_ELEMENTS);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let object_id = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_OBJECT_ID,
+            "ListElementRequest decode object_id",
+        )?;
+        let property_id = decode_context_property_id(
+            reader,
+            buf,
+            Self::TAG_PROPERTY_ID,
+            "ListElementRequest decode property_id",
+        )?;
+
+        // array_index
+        let mut array_index = None;
+        if peek_tag(reader, buf)?.number == TagNumber::ContextSpecific(Self::TAG_ARRAY_INDEX) {
+            let tag = Tag::decode(reader, buf)?;
+            let array_index_tmp = decode_unsigned(tag.value, reader, buf)? as u32;
+            if array_index_tmp != BACNET_ARRAY_ALL {
+                array_index = Some(array_index_tmp)
+            }
+        }
+
+        // elements
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_ELEMENTS,
+            "ListElementRequest decode elements",
+        )?;
+
+        #[cfg(not(feature = "alloc"))]
+        return Ok(Self {
+            object_id,
+            property_id,
+            array_index,
+            elements: &[],
+            buf: inner_buf,
+        });
+
+        #[cfg(feature = "alloc")]
+        {
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            let mut elements = Vec::new();
+            while !inner_reader.eof() {
+                let element =
+                    ListElement::decode(&object_id, &property_id, &mut inner_reader, inner_buf)?;
+                elements.push(element);
+            }
+            Ok(Self::new(object_id, property_id, array_index, elements))
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ ListElementRequest<'a> {
+    type Item = Result<ListElement<'a>, Error>;
+
+    type IntoIter = ListElementIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ListElementIter {
+            list: ListIter::new(self.elements, self.buf),
+            object_id: self.object_id,
+            property_id: self.property_id,
+        }
+    }
+}
+
+pub struct ListElementIter<'a> {
+    list: ListIter<'a, ListElement<'a>>,
+    object_id: ObjectId,
+    property_id: PropertyId,
+}
+
+impl<'a> Iterator for ListElementIter<'a> {
+    type Item = Result<ListElement<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (object_id, property_id) = (&self.object_id, &self.property_id);
+        self.list
+            .next_with(|reader, buf| ListElement::decode(object_id, property_id, reader, buf))
+    }
+}
+
+// the type of the elements of a list depends on the list property
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ListElement<'a> {
+    Destination(Destination<'a>),                     // Recipient_List
+    PropertyReference(DeviceObjectPropertyReference), // e.g. Log_DeviceObjectProperty
+    SpecialEvent(SpecialEvent<'a>),                   // Exception_Schedule
+    AddressBinding(AddressBinding<'a>),               // e.g. Manual_Slave_Address_Binding
+    Recipient(Recipient<'a>),                         // e.g. Time_Synchronization_Recipients
+    Value(ApplicationDataValue<'a>), // lists of application tagged values (e.g. Object_List)
+}
+
+impl<'a> ListElement<'a> {
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Destination(x) => x.encode(writer),
+            Self::PropertyReference(x) => x.encode(writer),
+            Self::SpecialEvent(x) => x.encode(writer),
+            Self::AddressBinding(x) => x.encode(writer),
+            Self::Recipient(x) => x.encode(writer),
+            Self::Value(x) => x.encode(writer),
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(
+        object_id: &ObjectId,
+        property_id: &PropertyId,
+        reader: &mut Reader,
+        buf: &'a [u8],
+    ) -> Result<Self, Error> {
+        match property_id {
+            PropertyId::PropRecipientList => {
+                Ok(Self::Destination(Destination::decode(reader, buf)?))
+            }
+            PropertyId::PropLogDeviceObjectProperty
+            | PropertyId::PropListOfObjectPropertyReferences => Ok(Self::PropertyReference(
+                DeviceObjectPropertyReference::decode(reader, buf)?,
+            )),
+            PropertyId::PropExceptionSchedule => {
+                Ok(Self::SpecialEvent(SpecialEvent::decode(reader, buf)?))
+            }
+            PropertyId::PropDeviceAddressBinding
+            | PropertyId::PropManualSlaveAddressBinding
+            | PropertyId::PropSlaveAddressBinding => {
+                Ok(Self::AddressBinding(AddressBinding::decode(reader, buf)?))
+            }
+            PropertyId::PropTimeSynchronizationRecipients
+            | PropertyId::PropUtcTimeSynchronizationRecipients
+            | PropertyId::PropRestartNotificationRecipients => {
+                Ok(Self::Recipient(Recipient::decode(reader, buf)?))
+            }
+            _ => Ok(Self::Value(ApplicationDataValue::decode(
+                object_id,
+                property_id,
+                reader,
+                buf,
+            )?)),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ConfirmedRequest, ConfirmedRequestService},
+            primitives::{
+                data_value::{ApplicationDataValue, Date, OctetString, Time},
+                property_reference::DeviceObjectPropertyReference,
+                recipient::{Address, Destination, Recipient},
+                special_event::{CalendarEntry, SpecialEvent, SpecialEventPeriod},
+            },
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::EventTransitionBits,
+            time_value::{SimpleApplicationDataValue, TimeValue},
+        },
+    };
+
+    use super::{AddListElement, ListElement, ListElementRequest, RemoveListElement};
+
+    fn encode_decode(service: ConfirmedRequestService) -> ListElementRequest<'static> {
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        ConfirmedRequest::new(9, service).encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ConfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        match decoded.service {
+            ConfirmedRequestService::AddListElement(x) => x,
+            ConfirmedRequestService::RemoveListElement(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        }
+    }
+
+    #[test]
+    fn reversable() {
+        use alloc::vec;
+
+        let object_id = ObjectId::new(ObjectType::ObjectDevice, 101);
+        let member = ObjectId::new(ObjectType::ObjectAnalogValue, 7);
+        let request = RemoveListElement::new(
+            object_id,
+            PropertyId::PropObjectList,
+            None,
+            vec![ListElement::Value(ApplicationDataValue::ObjectId(member))],
+        );
+
+        let decoded = encode_decode(ConfirmedRequestService::RemoveListElement(request));
+        assert_eq!(decoded.object_id, object_id);
+        assert_eq!(decoded.property_id, PropertyId::PropObjectList);
+        assert_eq!(decoded.array_index, None);
+        assert_eq!(decoded.elements.len(), 1);
+        assert!(matches!(
+            decoded.elements[0],
+            ListElement::Value(ApplicationDataValue::ObjectId(x)) if x == member
+        ));
+    }
+
+    #[test]
+    fn reversable_recipient_list() {
+        use alloc::vec;
+
+        let object_id = ObjectId::new(ObjectType::ObjectNotificationClass, 1);
+        let address = Address::new(0, OctetString::new(&[192, 168, 1, 249, 0xBA, 0xC0]));
+        let destination = Destination {
+            valid_days: 0b1111_1000,
+            from_time: Time {
+                hour: 6,
+                minute: 0,
+                second: 0,
+                hundredths: 0,
+            },
+            to_time: Time {
+                hour: 18,
+                minute: 0,
+                second: 0,
+                hundredths: 0,
+            },
+            recipient: Recipient::Address(address),
+            process_id: 3,
+            issue_confirmed_notifications: true,
+            transitions: EventTransitionBits::new(0b1110_0000),
+        };
+        let device = Destination {
+            recipient: Recipient::Device(ObjectId::new(ObjectType::ObjectDevice, 8)),
+            process_id: 4,
+            issue_confirmed_notifications: false,
+            ..destination.clone()
+        };
+        let request = AddListElement::new(
+            object_id,
+            PropertyId::PropRecipientList,
+            None,
+            vec![
+                ListElement::Destination(destination),
+                ListElement::Destination(device),
+            ],
+        );
+
+        let decoded = encode_decode(ConfirmedRequestService::AddListElement(request));
+        assert_eq!(decoded.property_id, PropertyId::PropRecipientList);
+        assert_eq!(decoded.elements.len(), 2);
+        let first = match &decoded.elements[0] {
+            ListElement::Destination(x) => x,
+            x => panic!("unexpected element {:?}", x),
+        };
+        assert_eq!(first.valid_days, 0b1111_1000);
+        assert_eq!(first.to_time.hour, 18);
+        assert_eq!(first.process_id, 3);
+        assert!(first.issue_confirmed_notifications);
+        assert!(matches!(
+            &first.recipient,
+            Recipient::Address(x) if x.mac_address.inner == [192, 168, 1, 249, 0xBA, 0xC0]
+        ));
+        let second = match &decoded.elements[1] {
+            ListElement::Destination(x) => x,
+            x => panic!("unexpected element {:?}", x),
+        };
+        assert!(matches!(second.recipient, Recipient::Device(x) if x.id == 8));
+        assert!(!second.issue_confirmed_notifications);
+    }
+
+    #[test]
+    fn reversable_constructed_elements() {
+        use alloc::vec;
+
+        // a trend log multiple member on another device
+        let mut reference = DeviceObjectPropertyReference::new(
+            ObjectId::new(ObjectType::ObjectAnalogInput, 2),
+            PropertyId::PropPresentValue,
+        );
+        reference.device_id = Some(ObjectId::new(ObjectType::ObjectDevice, 8));
+        let request = AddListElement::new(
+            ObjectId::new(ObjectType::ObjectTrendLogMultiple, 1),
+            PropertyId::PropLogDeviceObjectProperty,
+            None,
+            vec![ListElement::PropertyReference(reference.clone())],
+        );
+        let decoded = encode_decode(ConfirmedRequestService::AddListElement(request));
+        assert!(matches!(
+            &decoded.elements[0],
+            ListElement::PropertyReference(x) if *x == reference
+        ));
+
+        // a holiday in the exception schedule of a schedule object
+        let holiday = Date {
+            year: 2024,
+            month: 12,
+            day: 25,
+            wday: 3,
+        };
+        let time_values = vec![TimeValue {
+            time: Time {
+                hour: 0,
+                minute: 0,
+                second: 0,
+                hundredths: 0,
+            },
+            value: SimpleApplicationDataValue::Real(16.0),
+        }];
+        let event = SpecialEvent::new(
+            SpecialEventPeriod::CalendarEntry(CalendarEntry::Date(holiday)),
+            time_values,
+            10,
+        );
+        let request = AddListElement::new(
+            ObjectId::new(ObjectType::ObjectSchedule, 1),
+            PropertyId::PropExceptionSchedule,
+            None,
+            vec![ListElement::SpecialEvent(event)],
+        );
+        let decoded = encode_decode(ConfirmedRequestService::AddListElement(request));
+        let event = match &decoded.elements[0] {
+            ListElement::SpecialEvent(x) => x,
+            x => panic!("unexpected element {:?}", x),
+        };
+        assert!(matches!(
+            &event.period,
+            SpecialEventPeriod::CalendarEntry(CalendarEntry::Date(x)) if x.month == 12 && x.day == 25
+        ));
+        assert_eq!(event.time_values.len(), 1);
+        assert_eq!(event.priority, 10);
+    }
+}
+
+#[cfg(all(test, not(feature = "alloc")))]
+mod no_alloc_tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ConfirmedRequest, ConfirmedRequestService},
+            primitives::{
+                data_value::Time,
+                recipient::{Destination, Recipient},
+            },
+        },
+        common::{
+            helper::assert_encodes_to,
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::EventTransitionBits,
+        },
+    };
+
+    use super::{AddListElement, ListElement};
+
+    #[test]
+    fn reversable_recipient_list() {
+        let midnight = Time {
+            hour: 0,
+            minute: 0,
+            second: 0,
+            hundredths: 0,
+        };
+        let elements = [ListElement::Destination(Destination {
+            valid_days: 0b1111_1110,
+            from_time: midnight.clone(),
+            to_time: midnight,
+            recipient: Recipient::Device(ObjectId::new(ObjectType::ObjectDevice, 8)),
+            process_id: 1,
+            issue_confirmed_notifications: false,
+            transitions: EventTransitionBits::new(0b1110_0000),
+        })];
+        let request = AddListElement::new(
+            ObjectId::new(ObjectType::ObjectNotificationClass, 1),
+            PropertyId::PropRecipientList,
+            None,
+            &elements,
+        );
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        let service = ConfirmedRequestService::AddListElement(request);
+        ConfirmedRequest::new(9, service).encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ConfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        let request = match &decoded.service {
+            ConfirmedRequestService::AddListElement(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        let mut elements = request.into_iter();
+        match elements.next().unwrap().unwrap() {
+            ListElement::Destination(x) => {
+                assert_eq!(x.valid_days, 0b1111_1110);
+                assert!(matches!(x.recipient, Recipient::Device(x) if x.id == 8));
+            }
+            x => panic!("unexpected element {:?}", x),
+        }
+        assert!(elements.next().is_none());
+
+        assert_encodes_to(&buf[..len], |writer| decoded.encode(writer));
+    }
+}
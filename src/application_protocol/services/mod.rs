@@ -1,8 +1,29 @@
+pub mod acknowledge_alarm;
+pub mod atomic_read_file;
+pub mod atomic_write_file;
+pub mod audit_log_query;
+pub mod audit_notification;
 pub mod change_of_value;
+pub mod change_of_value_multiple;
+pub mod create_object;
+pub mod delete_object;
+pub mod device_communication_control;
+pub mod event_notification;
+pub mod get_alarm_summary;
+pub mod get_enrollment_summary;
+pub mod get_event_information;
 pub mod i_am;
+pub mod life_safety_operation;
+pub mod list_element;
+pub mod private_transfer;
 pub mod read_property;
 pub mod read_property_multiple;
 pub mod read_range;
+pub mod reinitialize_device;
+pub mod text_message;
 pub mod time_synchronization;
+pub mod virtual_terminal;
+pub mod who_am_i;
 pub mod who_is;
+pub mod write_group;
 pub mod write_property;
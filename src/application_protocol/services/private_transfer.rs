@@ -0,0 +1,290 @@
+// private transfer (vendor specific services identified by a vendor id and service number)
+
+#[cfg(feature = "alloc")]
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
+
+use crate::{
+    application_protocol::confirmed::{ComplexAck, ComplexAckService, ConfirmedServiceChoice},
+    common::{
+        error::Error,
+        helper::{
+            decode_unsigned, encode_closing_tag, encode_context_unsigned, encode_opening_tag,
+            get_tagged_body_for_tag,
+        },
+        io::{Reader, Writer},
+        tag::{Tag, TagNumber},
+    },
+    network_protocol::data_link::DataLink,
+};
+
+const TAG_VENDOR_ID: u8 = 0;
+const TAG_SERVICE_NUMBER: u8 = 1;
+const TAG_PARAMETERS: u8 = 2;
+
+// implemented by vendor specific payloads so they can be carried as service parameters or as a result block
+pub trait PrivateTransferData: Sized {
+    fn encode(&self, writer: &mut Writer);
+    fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error>;
+}
+
+// the encoded service parameters (or result block) found between the opening and closing tags
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ServiceParameters<'a> {
+    pub buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ServiceParameters<'a> {
+    pub buf: Vec<u8>,
+    _phantom: &'a Phantom,
+}
+
+impl<'a> ServiceParameters<'a> {
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(buf: &[u8]) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            buf: buf.into(),
+            _phantom: &PHANTOM,
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn as_slice(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf
+    }
+
+    pub fn decode_as<T: PrivateTransferData>(&self) -> Result<T, Error> {
+        let mut reader = Reader::new_with_len(self.buf.len());
+        T::decode(&mut reader, self.as_slice())
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PrivateTransfer<'a> {
+    pub vendor_id: u16,
+    pub service_number: u32,
+    pub parameters: Option<ServiceParameters<'a>>,
+}
+
+impl<'a> PrivateTransfer<'a> {
+    pub fn new(
+        vendor_id: u16,
+        service_number: u32,
+        parameters: Option<ServiceParameters<'a>>,
+    ) -> Self {
+        Self {
+            vendor_id,
+            service_number,
+            parameters,
+        }
+    }
+
+    // the service choice is not encoded here because this request can be sent
+    // as either a confirmed or an unconfirmed request
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_private_transfer(
+            writer,
+            self.vendor_id,
+            self.service_number,
+            &self.parameters,
+        );
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let (vendor_id, service_number, parameters) = decode_private_transfer(reader, buf)?;
+        Ok(Self {
+            vendor_id,
+            service_number,
+            parameters,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PrivateTransferAck<'a> {
+    pub vendor_id: u16,
+    pub service_number: u32,
+    pub result_block: Option<ServiceParameters<'a>>,
+}
+
+impl<'a> PrivateTransferAck<'a> {
+    pub fn new(
+        vendor_id: u16,
+        service_number: u32,
+        result_block: Option<ServiceParameters<'a>>,
+    ) -> Self {
+        Self {
+            vendor_id,
+            service_number,
+            result_block,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(ConfirmedServiceChoice::PrivateTransfer as u8);
+        encode_private_transfer(
+            writer,
+            self.vendor_id,
+            self.service_number,
+            &self.result_block,
+        );
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let (vendor_id, service_number, result_block) = decode_private_transfer(reader, buf)?;
+        Ok(Self {
+            vendor_id,
+            service_number,
+            result_block,
+        })
+    }
+}
+
+impl<'a> TryFrom<DataLink<'a>> for PrivateTransferAck<'a> {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        let ack: ComplexAck = value.try_into()?;
+        match ack.service {
+            ComplexAckService::PrivateTransfer(ack) => Ok(ack),
+            _ => Err(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService PrivateTransferAck",
+            )),
+        }
+    }
+}
+
+// the request and the ack share the same layout
+fn encode_private_transfer(
+    writer: &mut Writer,
+    vendor_id: u16,
+    service_number: u32,
+    parameters: &Option<ServiceParameters>,
+) {
+    encode_context_unsigned(writer, TAG_VENDOR_ID, vendor_id);
+    encode_context_unsigned(writer, TAG_SERVICE_NUMBER, service_number);
+    if let Some(parameters) = parameters {
+        encode_opening_tag(writer, TAG_PARAMETERS);
+        writer.extend_from_slice(parameters.as_slice());
+        encode_closing_tag(writer, TAG_PARAMETERS);
+    }
+}
+
+#[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+fn decode_private_transfer<'a>(
+    reader: &mut Reader,
+    buf: &'a [u8],
+) -> Result<(u16, u32, Option<ServiceParameters<'a>>), Error> {
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::ContextSpecific(TAG_VENDOR_ID),
+        "PrivateTransfer decode vendor_id",
+    )?;
+    let vendor_id = decode_unsigned(tag.value, reader, buf)? as u16;
+
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::ContextSpecific(TAG_SERVICE_NUMBER),
+        "PrivateTransfer decode service_number",
+    )?;
+    let service_number = decode_unsigned(tag.value, reader, buf)? as u32;
+
+    let mut parameters = None;
+    if !reader.eof() {
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            TAG_PARAMETERS,
+            "PrivateTransfer decode parameters",
+        )?;
+        parameters = Some(ServiceParameters::new(inner_buf));
+    }
+
+    Ok((vendor_id, service_number, parameters))
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::confirmed::{ConfirmedRequest, ConfirmedRequestService},
+        common::{
+            error::Error,
+            helper::{decode_unsigned, encode_application_unsigned},
+            io::{Reader, Writer},
+            tag::{ApplicationTagNumber, Tag, TagNumber},
+        },
+    };
+
+    use super::{PrivateTransfer, PrivateTransferData, ServiceParameters};
+
+    #[derive(Debug, PartialEq)]
+    struct Setpoint(u64);
+
+    impl PrivateTransferData for Setpoint {
+        fn encode(&self, writer: &mut Writer) {
+            encode_application_unsigned(writer, self.0);
+        }
+
+        fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+            let tag = Tag::decode_expected(
+                reader,
+                buf,
+                TagNumber::Application(ApplicationTagNumber::UnsignedInt),
+                "Setpoint decode",
+            )?;
+            Ok(Setpoint(decode_unsigned(tag.value, reader, buf)?))
+        }
+    }
+
+    #[test]
+    fn reversable() {
+        let mut parameters = [0; 16];
+        let mut writer = Writer::new(&mut parameters);
+        Setpoint(2150).encode(&mut writer);
+        let len = writer.index;
+        let parameters = ServiceParameters::new(&parameters[..len]);
+        let request = PrivateTransfer::new(260, 3, Some(parameters));
+
+        // encoding
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        let service = ConfirmedRequestService::PrivateTransfer(request);
+        ConfirmedRequest::new(6, service).encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ConfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        let decoded = match decoded.service {
+            ConfirmedRequestService::PrivateTransfer(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        assert_eq!(decoded.vendor_id, 260);
+        assert_eq!(decoded.service_number, 3);
+        let setpoint: Setpoint = decoded.parameters.unwrap().decode_as().unwrap();
+        assert_eq!(setpoint, Setpoint(2150));
+    }
+}
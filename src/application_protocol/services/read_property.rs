@@ -1,14 +1,17 @@
+#[cfg(not(feature = "alloc"))]
+use crate::common::helper::encode_list;
+
 use crate::{
     application_protocol::{
         confirmed::{ComplexAck, ComplexAckService, ConfirmedServiceChoice},
-        primitives::data_value::ApplicationDataValue,
+        primitives::{data_value::ApplicationDataValue, recipient::Recipient},
     },
     common::{
         error::Error,
         helper::{
             decode_context_object_id, decode_context_property_id, encode_closing_tag,
             encode_context_enumerated, encode_context_object_id, encode_context_unsigned,
-            encode_opening_tag, get_tagged_body_for_tag,
+            encode_opening_tag, get_tagged_body_for_tag, ListIter,
         },
         io::{Reader, Writer},
         object_id::ObjectId,
@@ -28,6 +31,7 @@ use {
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum ReadPropertyValue<'a> {
     ObjectIdList(ObjectIdList<'a>),
+    RecipientList(RecipientList<'a>),
     ApplicationDataValue(ApplicationDataValue<'a>),
 }
 
@@ -147,6 +151,109 @@ impl<'a> Iterator for ObjectIdIter<'a> {
     }
 }
 
+// a list of BACnetRecipient (e.g. the devices that a time master synchronizes)
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RecipientList<'a> {
+    recipients: &'a [Recipient<'a>],
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RecipientList<'a> {
+    pub recipients: Vec<Recipient<'a>>,
+    _phantom: &'a Phantom,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RecipientIter<'a> {
+    list: ListIter<'a, Recipient<'a>>,
+}
+
+impl<'a> RecipientList<'a> {
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(recipients: &'a [Recipient<'a>]) -> Self {
+        Self {
+            recipients,
+            buf: &[],
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn new_from_buf(buf: &'a [u8]) -> Self {
+        Self {
+            recipients: &[],
+            buf,
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(recipients: Vec<Recipient<'a>>) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            recipients,
+            _phantom: &PHANTOM,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        #[cfg(not(feature = "alloc"))]
+        encode_list(writer, self.buf, self.recipients, Recipient::encode);
+        #[cfg(feature = "alloc")]
+        for recipient in self.recipients.iter() {
+            recipient.encode(writer);
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn decode(_reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        Ok(Self::new_from_buf(buf))
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn decode(_reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::new_with_len(buf.len());
+        let mut recipients = Vec::new();
+        while !reader.eof() {
+            recipients.push(Recipient::decode(&mut reader, buf)?);
+        }
+        Ok(Self::new(recipients))
+    }
+}
+
+impl<'a> RecipientIter<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self {
+            list: ListIter::new(&[], buf),
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ RecipientList<'a> {
+    type Item = Result<Recipient<'a>, Error>;
+    type IntoIter = RecipientIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        RecipientIter {
+            list: ListIter::new(self.recipients, self.buf),
+        }
+    }
+}
+
+impl<'a> Iterator for RecipientIter<'a> {
+    type Item = Result<Recipient<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.next_with(Recipient::decode)
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ReadPropertyAck<'a> {
@@ -182,6 +289,9 @@ impl<'a> ReadPropertyAck<'a> {
             ReadPropertyValue::ObjectIdList(value) => {
                 value.encode(writer);
             }
+            ReadPropertyValue::RecipientList(value) => {
+                value.encode(writer);
+            }
         }
         encode_closing_tag(writer, 3);
     }
@@ -197,7 +307,7 @@ impl<'a> ReadPropertyAck<'a> {
         let mut reader = Reader::new_with_len(buf.len());
 
         match property_id {
-            PropertyId::PropObjectList => {
+            PropertyId::PropObjectList | PropertyId::PropConfigurationFiles => {
                 let object_ids = ObjectIdList::decode(&mut reader, buf)?;
                 let property_value = ReadPropertyValue::ObjectIdList(object_ids);
 
@@ -207,6 +317,18 @@ impl<'a> ReadPropertyAck<'a> {
                     property_value,
                 })
             }
+            PropertyId::PropTimeSynchronizationRecipients
+            | PropertyId::PropUtcTimeSynchronizationRecipients
+            | PropertyId::PropRestartNotificationRecipients => {
+                let recipients = RecipientList::decode(&mut reader, buf)?;
+                let property_value = ReadPropertyValue::RecipientList(recipients);
+
+                Ok(Self {
+                    object_id,
+                    property_id,
+                    property_value,
+                })
+            }
             property_id => {
                 let value =
                     ApplicationDataValue::decode(&object_id, &property_id, &mut reader, buf)?;
@@ -267,3 +389,138 @@ impl ReadProperty {
         })
     }
 }
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::vec;
+
+    use crate::{
+        application_protocol::{
+            confirmed::{ComplexAck, ComplexAckService},
+            primitives::{
+                data_value::OctetString,
+                recipient::{Address, Recipient},
+            },
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+    };
+
+    use super::{ReadPropertyAck, ReadPropertyValue, RecipientList};
+
+    #[test]
+    fn reversable_recipient_list() {
+        let device_id = ObjectId::new(ObjectType::ObjectDevice, 79079);
+        let mac_address = [192, 168, 1, 10, 0xBA, 0xC0];
+        let recipients = vec![
+            Recipient::Device(ObjectId::new(ObjectType::ObjectDevice, 12)),
+            Recipient::Address(Address::new(0, OctetString::new(&mac_address))),
+        ];
+        let ack = ReadPropertyAck {
+            object_id: device_id,
+            property_id: PropertyId::PropTimeSynchronizationRecipients,
+            property_value: ReadPropertyValue::RecipientList(RecipientList::new(recipients)),
+        };
+
+        // encoding
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        ComplexAck {
+            invoke_id: 2,
+            service: ComplexAckService::ReadProperty(ack),
+        }
+        .encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = match ComplexAck::decode(&mut reader, &buf[..len])
+            .unwrap()
+            .service
+        {
+            ComplexAckService::ReadProperty(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        let recipients = match decoded.property_value {
+            ReadPropertyValue::RecipientList(x) => x.recipients,
+            x => panic!("unexpected value {:?}", x),
+        };
+        assert_eq!(recipients.len(), 2);
+        assert!(matches!(
+            recipients[0],
+            Recipient::Device(ObjectId { id: 12, .. })
+        ));
+        match &recipients[1] {
+            Recipient::Address(x) => {
+                assert_eq!(x.network_number, 0);
+                assert_eq!(x.mac_address.as_slice(), mac_address);
+            }
+            x => panic!("unexpected recipient {:?}", x),
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "alloc")))]
+mod no_alloc_tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ComplexAck, ComplexAckService},
+            primitives::recipient::Recipient,
+        },
+        common::{
+            helper::assert_encodes_to,
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+    };
+
+    use super::{ReadPropertyAck, ReadPropertyValue, RecipientList};
+
+    #[test]
+    fn reversable_recipient_list() {
+        let recipients = [Recipient::Device(ObjectId::new(
+            ObjectType::ObjectDevice,
+            12,
+        ))];
+        let list = RecipientList::new(&recipients);
+        assert_eq!((&list).into_iter().count(), 1);
+        let ack = ReadPropertyAck {
+            object_id: ObjectId::new(ObjectType::ObjectDevice, 79079),
+            property_id: PropertyId::PropTimeSynchronizationRecipients,
+            property_value: ReadPropertyValue::RecipientList(list),
+        };
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        ComplexAck {
+            invoke_id: 2,
+            service: ComplexAckService::ReadProperty(ack),
+        }
+        .encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ComplexAck::decode(&mut reader, &buf[..len]).unwrap();
+        let list = match &decoded.service {
+            ComplexAckService::ReadProperty(ReadPropertyAck {
+                property_value: ReadPropertyValue::RecipientList(x),
+                ..
+            }) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        let mut recipients = list.into_iter();
+        assert!(matches!(
+            recipients.next().unwrap().unwrap(),
+            Recipient::Device(ObjectId { id: 12, .. })
+        ));
+        assert!(recipients.next().is_none());
+
+        assert_encodes_to(&buf[..len], |writer| decoded.encode(writer));
+    }
+}
@@ -10,8 +10,9 @@ use crate::{
         error::Error,
         helper::{
             decode_context_object_id, decode_context_property_id, decode_unsigned,
-            encode_closing_tag, encode_context_enumerated, encode_context_object_id,
-            encode_context_unsigned, encode_opening_tag, get_tagged_body, get_tagged_body_for_tag,
+            encode_application_character_string, encode_closing_tag, encode_context_enumerated,
+            encode_context_object_id, encode_context_unsigned, encode_enumerated,
+            encode_opening_tag, get_tagged_body, get_tagged_body_for_tag,
         },
         io::{Reader, Writer},
         object_id::{ObjectId, ObjectType},
@@ -180,37 +181,6 @@ impl<'a> Iterator for PropertyResultIter<'a> {
     }
 }
 
-fn read_error(reader: &mut Reader, buf: &[u8]) -> Result<PropertyAccessError, Error> {
-    // error class enumerated
-    let tag = Tag::decode_expected(
-        reader,
-        buf,
-        TagNumber::Application(ApplicationTagNumber::Enumerated),
-        "read_error error_class",
-    )?;
-    let value = decode_unsigned(tag.value, reader, buf)? as u32;
-    let error_class = value
-        .try_into()
-        .map_err(|x| Error::InvalidVariant(("ErrorClass", x)))?;
-
-    // error code enumerated
-    let tag = Tag::decode_expected(
-        reader,
-        buf,
-        TagNumber::Application(ApplicationTagNumber::Enumerated),
-        "read_error error code",
-    )?;
-    let value = decode_unsigned(tag.value, reader, buf)? as u32;
-    let error_code = value
-        .try_into()
-        .map_err(|x| Error::InvalidVariant(("ErrorCode", x)))?;
-
-    Ok(PropertyAccessError {
-        error_class,
-        error_code,
-    })
-}
-
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct PropertyResultList<'a> {
@@ -263,9 +233,22 @@ impl<'a> PropertyResult<'a> {
                 val.encode(writer);
                 encode_closing_tag(writer, Self::PROPERTY_VALUE_TAG);
             }
-            PropertyValue::PropError(_) => todo!(),
-            PropertyValue::PropObjectName(_) => todo!(),
-            PropertyValue::PropDescription(_) => todo!(),
+            PropertyValue::PropError(error) => {
+                encode_opening_tag(writer, Self::PROPERTY_VALUE_ERROR_TAG);
+                error.encode(writer);
+                encode_closing_tag(writer, Self::PROPERTY_VALUE_ERROR_TAG);
+            }
+            // these are plain character strings on the wire (they decode as PropValue)
+            PropertyValue::PropObjectName(name) => {
+                encode_opening_tag(writer, Self::PROPERTY_VALUE_TAG);
+                encode_application_character_string(writer, name);
+                encode_closing_tag(writer, Self::PROPERTY_VALUE_TAG);
+            }
+            PropertyValue::PropDescription(description) => {
+                encode_opening_tag(writer, Self::PROPERTY_VALUE_TAG);
+                encode_application_character_string(writer, description);
+                encode_closing_tag(writer, Self::PROPERTY_VALUE_TAG);
+            }
         }
     }
 
@@ -327,7 +310,7 @@ impl<'a> PropertyResult<'a> {
             }
         } else if tag_number == Self::PROPERTY_VALUE_ERROR_TAG {
             // property read error
-            let error = read_error(reader, buf)?;
+            let error = PropertyAccessError::decode(reader, buf)?;
             Ok(PropertyValue::PropError(error))
         } else {
             Err(Error::TagNotSupported((
@@ -367,6 +350,51 @@ pub struct PropertyAccessError {
     pub error_code: ErrorCode,
 }
 
+impl PropertyAccessError {
+    pub fn new(error_class: ErrorClass, error_code: ErrorCode) -> Self {
+        Self {
+            error_class,
+            error_code,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_enumerated(writer, u32::from(&self.error_class), None);
+        encode_enumerated(writer, u32::from(&self.error_code), None);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        // error class enumerated
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Enumerated),
+            "PropertyAccessError decode error_class",
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let error_class = value
+            .try_into()
+            .map_err(|x| Error::InvalidVariant(("ErrorClass", x)))?;
+
+        // error code enumerated
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Enumerated),
+            "PropertyAccessError decode error_code",
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let error_code = value
+            .try_into()
+            .map_err(|x| Error::InvalidVariant(("ErrorCode", x)))?;
+
+        Ok(Self {
+            error_class,
+            error_code,
+        })
+    }
+}
+
 impl<'a> Display for PropertyValue<'a> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match &self {
@@ -429,6 +457,58 @@ impl<'a> ReadPropertyMultipleAck<'a> {
     }
 }
 
+/// Builds an ack one result at a time, e.g. when a server responds to a ReadPropertyMultiple request
+/// Results are added to the most recent object so a mix of values and errors can be returned for each object.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReadPropertyMultipleAckBuilder<'a> {
+    objects_with_results: Vec<ObjectWithResults<'a>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> ReadPropertyMultipleAckBuilder<'a> {
+    /// Starts with the results of the first object
+    pub fn new(object_id: ObjectId) -> Self {
+        Self {
+            objects_with_results: alloc::vec![ObjectWithResults::new(object_id, Vec::new())],
+        }
+    }
+
+    /// Results added after this belong to `object_id`
+    pub fn object(mut self, object_id: ObjectId) -> Self {
+        self.objects_with_results
+            .push(ObjectWithResults::new(object_id, Vec::new()));
+        self
+    }
+
+    pub fn value(self, id: PropertyId, value: ApplicationDataValue<'a>) -> Self {
+        self.result(PropertyResult {
+            id,
+            value: PropertyValue::PropValue(value),
+        })
+    }
+
+    pub fn error(self, id: PropertyId, error_class: ErrorClass, error_code: ErrorCode) -> Self {
+        self.result(PropertyResult {
+            id,
+            value: PropertyValue::PropError(PropertyAccessError::new(error_class, error_code)),
+        })
+    }
+
+    pub fn result(mut self, result: PropertyResult<'a>) -> Self {
+        // never empty because we start with an object
+        if let Some(object) = self.objects_with_results.last_mut() {
+            object.property_results.push(result);
+        }
+        self
+    }
+
+    pub fn build(self) -> ReadPropertyMultipleAck<'a> {
+        ReadPropertyMultipleAck::new(self.objects_with_results)
+    }
+}
+
 pub struct ObjectWithResultsIter<'a> {
     buf: &'a [u8],
     reader: Reader,
@@ -744,3 +824,98 @@ impl<'a> Iterator for ReadPropertyMultipleIter<'a> {
         Some(object_with_property_ids)
     }
 }
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::string::ToString;
+
+    use crate::{
+        application_protocol::{
+            confirmed::{ComplexAck, ComplexAckService},
+            primitives::data_value::ApplicationDataValue,
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{ErrorClass, ErrorCode},
+        },
+    };
+
+    use super::{PropertyResult, PropertyValue, ReadPropertyMultipleAckBuilder};
+
+    #[test]
+    fn reversable() {
+        let analog_input = ObjectId::new(ObjectType::ObjectAnalogInput, 1);
+        let binary_value = ObjectId::new(ObjectType::ObjectBinaryValue, 2);
+        let ack = ReadPropertyMultipleAckBuilder::new(analog_input)
+            .value(
+                PropertyId::PropPresentValue,
+                ApplicationDataValue::Real(21.5),
+            )
+            .error(
+                PropertyId::PropDescription,
+                ErrorClass::Property,
+                ErrorCode::UnknownProperty,
+            )
+            .result(PropertyResult {
+                id: PropertyId::PropObjectName,
+                value: PropertyValue::PropObjectName("Room Temp".to_string()),
+            })
+            .object(binary_value)
+            .result(PropertyResult {
+                id: PropertyId::PropDescription,
+                value: PropertyValue::PropDescription("Fan".to_string()),
+            })
+            .build();
+
+        let mut buf = [0; 256];
+        let mut writer = Writer::new(&mut buf);
+        ComplexAck {
+            invoke_id: 1,
+            service: ComplexAckService::ReadPropertyMultiple(ack),
+        }
+        .encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ComplexAck::decode(&mut reader, &buf[..len]).unwrap();
+        let ack = match decoded.service {
+            ComplexAckService::ReadPropertyMultiple(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+
+        assert_eq!(ack.objects_with_results.len(), 2);
+        let first = &ack.objects_with_results[0];
+        assert_eq!(first.object_id, analog_input);
+        assert_eq!(first.property_results.len(), 3);
+        assert!(matches!(
+            first.property_results[0].value,
+            PropertyValue::PropValue(ApplicationDataValue::Real(x)) if x == 21.5
+        ));
+        assert!(matches!(
+            &first.property_results[1].value,
+            PropertyValue::PropError(x)
+                if matches!(x.error_class, ErrorClass::Property)
+                    && matches!(x.error_code, ErrorCode::UnknownProperty)
+        ));
+        // names and descriptions are decoded as character string values
+        match &first.property_results[2].value {
+            PropertyValue::PropValue(ApplicationDataValue::CharacterString(x)) => {
+                assert_eq!(x.inner, "Room Temp")
+            }
+            x => panic!("unexpected value {:?}", x),
+        }
+
+        let second = &ack.objects_with_results[1];
+        assert_eq!(second.object_id, binary_value);
+        assert_eq!(second.property_results[0].id, PropertyId::PropDescription);
+        match &second.property_results[0].value {
+            PropertyValue::PropValue(ApplicationDataValue::CharacterString(x)) => {
+                assert_eq!(x.inner, "Fan")
+            }
+            x => panic!("unexpected value {:?}", x),
+        }
+    }
+}
@@ -1,20 +1,34 @@
+#[cfg(not(feature = "alloc"))]
+use crate::common::helper::encode_list;
+
 use crate::{
     application_protocol::{
         confirmed::ConfirmedServiceChoice,
-        primitives::data_value::{BitString, Date, Time},
+        primitives::{
+            data_value::{
+                ApplicationDataValue, BitString, CustomBitStream, Date, DateTime, OctetString, Time,
+            },
+            recipient::{AddressBinding, Destination, Recipient},
+        },
+        services::{
+            change_of_value::CovSubscription, event_notification::EventNotification,
+            read_property_multiple::PropertyAccessError,
+        },
     },
     common::{
-        error::{Error, Unimplemented},
+        error::Error,
         helper::{
-            decode_context_object_id, decode_context_property_id, decode_signed, decode_unsigned,
-            encode_application_signed, encode_application_unsigned, encode_closing_tag,
-            encode_context_enumerated, encode_context_object_id, encode_context_unsigned,
-            encode_opening_tag, get_tagged_body_for_tag,
+            decode_bit_string, decode_context_object_id, decode_context_property_id,
+            decode_context_real, decode_signed, decode_unsigned, encode_application_signed,
+            encode_application_unsigned, encode_closing_tag, encode_context_bit_string,
+            encode_context_bool, encode_context_enumerated, encode_context_object_id,
+            encode_context_real, encode_context_unsigned, encode_opening_tag, encode_signed,
+            get_tagged_body_for_tag, is_next_context_tag, peek_tag, ListIter,
         },
         io::{Reader, Writer},
-        object_id::ObjectId,
+        object_id::{ObjectId, ObjectType},
         property_id::PropertyId,
-        spec::BACNET_ARRAY_ALL,
+        spec::{LogStatusBits, BACNET_ARRAY_ALL},
         tag::{ApplicationTagNumber, Tag, TagNumber},
     },
 };
@@ -71,6 +85,7 @@ pub struct ReadRangeAck<'a> {
     pub result_flags: BitString<'a>,
     pub item_count: usize,
     pub item_data: ReadRangeItems<'a>,
+    pub first_sequence_number: Option<u32>, // only for requests by sequence number or time
 }
 
 impl<'a> ReadRangeAck<'a> {
@@ -80,6 +95,7 @@ impl<'a> ReadRangeAck<'a> {
     const RESULT_FLAGS_TAG: u8 = 3;
     const ITEM_COUNT_TAG: u8 = 4;
     const ITEM_DATA_TAG: u8 = 5;
+    const FIRST_SEQUENCE_NUMBER_TAG: u8 = 6;
 
     pub fn encode(&self, writer: &mut Writer) {
         writer.push(ConfirmedServiceChoice::ReadRange as u8);
@@ -96,6 +112,15 @@ impl<'a> ReadRangeAck<'a> {
         encode_opening_tag(writer, Self::ITEM_DATA_TAG);
         self.item_data.encode(writer);
         encode_closing_tag(writer, Self::ITEM_DATA_TAG);
+
+        // first sequence number
+        if let Some(first_sequence_number) = self.first_sequence_number {
+            encode_context_unsigned(
+                writer,
+                Self::FIRST_SEQUENCE_NUMBER_TAG,
+                first_sequence_number,
+            );
+        }
     }
 
     #[cfg_attr(feature = "alloc", remove_lifetimes_from_fn_args)]
@@ -132,7 +157,8 @@ impl<'a> ReadRangeAck<'a> {
             "ReadRangeAck decode result_flag",
             TagNumber::ContextSpecific(Self::RESULT_FLAGS_TAG),
         )?;
-        let result_flags = BitString::decode(&property_id, tag.value, reader, buf)?;
+        // the result flags are the same whatever property was read
+        let result_flags = BitString::decode(&PropertyId::PropLogBuffer, tag.value, reader, buf)?;
 
         // item_count
         let tag = Tag::decode_expected(
@@ -144,7 +170,7 @@ impl<'a> ReadRangeAck<'a> {
         let item_count = decode_unsigned(tag.value, reader, buf)? as usize;
 
         // item_data
-        let buf = if reader.eof() {
+        let item_buf = if reader.eof() {
             &[]
         } else {
             get_tagged_body_for_tag(
@@ -154,7 +180,21 @@ impl<'a> ReadRangeAck<'a> {
                 "ReadRangeAck decode item_data",
             )?
         };
-        let item_data = ReadRangeItems::decode(buf)?;
+        let item_data = ReadRangeItems::decode(object_id, property_id, item_buf)?;
+
+        // first_sequence_number
+        let first_sequence_number =
+            if is_next_context_tag(reader, buf, Self::FIRST_SEQUENCE_NUMBER_TAG)? {
+                let tag = Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::ContextSpecific(Self::FIRST_SEQUENCE_NUMBER_TAG),
+                    "ReadRangeAck decode first_sequence_number",
+                )?;
+                Some(decode_unsigned(tag.value, reader, buf)? as u32)
+            } else {
+                None
+            };
 
         Ok(Self {
             object_id,
@@ -163,6 +203,7 @@ impl<'a> ReadRangeAck<'a> {
             result_flags,
             item_count,
             item_data,
+            first_sequence_number,
         })
     }
 }
@@ -172,6 +213,8 @@ impl<'a> ReadRangeAck<'a> {
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ReadRangeItems<'a> {
     items: &'a [ReadRangeItem<'a>],
+    object_id: ObjectId,
+    property_id: PropertyId,
     buf: &'a [u8],
 }
 
@@ -182,21 +225,21 @@ pub struct ReadRangeItems<'a> {
     pub items: Vec<ReadRangeItem<'a>>,
 }
 
+// BACnetLogRecord log-datum
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub enum ReadRangeValue {
-    Status,
+pub enum ReadRangeValue<'a> {
+    Status(LogStatusBits),
     Bool(bool),
     Real(f32),
     Enum(u32),
     Unsigned(u32),
     Signed(i32),
-    Bits,
+    Bits(CustomBitStream<'a>),
     Null,
-    Error,
-    Delta,
-    Any,
+    Error(PropertyAccessError),
+    Delta(f32),           // the number of seconds the clock was changed by
+    Any(OctetString<'a>), // the encoded application tagged values of a constructed value
 }
 
 #[derive(Debug, Clone)]
@@ -240,13 +283,28 @@ impl TryFrom<u8> for ReadRangeValueType {
 
 impl<'a> ReadRangeItems<'a> {
     #[cfg(not(feature = "alloc"))]
-    pub fn new_from_buf(buf: &'a [u8]) -> Self {
-        Self { items: &[], buf }
+    pub fn new_from_buf(object_id: ObjectId, property_id: PropertyId, buf: &'a [u8]) -> Self {
+        Self {
+            items: &[],
+            object_id,
+            property_id,
+            buf,
+        }
     }
 
+    // the object and property the items were read from determine how they are decoded
     #[cfg(not(feature = "alloc"))]
-    pub fn new(items: &'a [ReadRangeItem<'a>]) -> Self {
-        Self { items, buf: &[] }
+    pub fn new(
+        object_id: ObjectId,
+        property_id: PropertyId,
+        items: &'a [ReadRangeItem<'a>],
+    ) -> Self {
+        Self {
+            items,
+            object_id,
+            property_id,
+            buf: &[],
+        }
     }
 
     #[cfg(feature = "alloc")]
@@ -255,23 +313,31 @@ impl<'a> ReadRangeItems<'a> {
     }
 
     pub fn encode(&self, writer: &mut Writer) {
+        #[cfg(not(feature = "alloc"))]
+        encode_list(writer, self.buf, self.items, ReadRangeItem::encode);
+        #[cfg(feature = "alloc")]
         for item in self.items.iter() {
             item.encode(writer)
         }
     }
 
     #[cfg(not(feature = "alloc"))]
-    pub fn decode(buf: &'a [u8]) -> Result<Self, Error> {
-        Ok(Self::new_from_buf(buf))
+    pub fn decode(
+        object_id: ObjectId,
+        property_id: PropertyId,
+        buf: &'a [u8],
+    ) -> Result<Self, Error> {
+        Ok(Self::new_from_buf(object_id, property_id, buf))
     }
 
+    // the layout of the items depends on the property and the type of object they were read from
     #[cfg(feature = "alloc")]
-    pub fn decode(buf: &[u8]) -> Result<Self, Error> {
+    pub fn decode(object_id: ObjectId, property_id: PropertyId, buf: &[u8]) -> Result<Self, Error> {
         let mut reader = Reader::new_with_len(buf.len());
         let mut items = Vec::new();
 
         while !reader.eof() {
-            let item = ReadRangeItem::decode(&mut reader, buf)?;
+            let item = ReadRangeItem::decode(&object_id, &property_id, &mut reader, buf)?;
             items.push(item);
         }
 
@@ -287,40 +353,237 @@ impl<'a> IntoIterator for &'_ ReadRangeItems<'a> {
 
     fn into_iter(self) -> Self::IntoIter {
         ReadRangeItemsIter {
-            buf: self.buf,
-            reader: Reader::new_with_len(self.buf.len()),
+            list: ListIter::new(self.items, self.buf),
+            object_id: self.object_id,
+            property_id: self.property_id,
         }
     }
 }
 
 pub struct ReadRangeItemsIter<'a> {
-    reader: Reader,
-    buf: &'a [u8],
+    list: ListIter<'a, ReadRangeItem<'a>>,
+    object_id: ObjectId,
+    property_id: PropertyId,
 }
 
 impl<'a> Iterator for ReadRangeItemsIter<'a> {
     type Item = Result<ReadRangeItem<'a>, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.reader.eof() {
-            return None;
+        let object_id = &self.object_id;
+        let property_id = &self.property_id;
+        self.list
+            .next_with(|reader, buf| ReadRangeItem::decode(object_id, property_id, reader, buf))
+    }
+}
+
+impl<'a> ReadRangeValue<'a> {
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            // 3 bits used (5 unused)
+            Self::Status(x) => {
+                encode_context_bit_string(writer, ReadRangeValueType::Status as u8, 5, &[x.inner])
+            }
+            Self::Bool(x) => encode_context_bool(writer, ReadRangeValueType::Bool as u8, *x),
+            Self::Real(x) => encode_context_real(writer, ReadRangeValueType::Real as u8, *x),
+            Self::Enum(x) => encode_context_unsigned(writer, ReadRangeValueType::Enum as u8, *x),
+            Self::Unsigned(x) => {
+                encode_context_unsigned(writer, ReadRangeValueType::Unsigned as u8, *x)
+            }
+            Self::Signed(x) => encode_signed(writer, Some(ReadRangeValueType::Signed as u8), *x),
+            Self::Bits(x) => encode_context_bit_string(
+                writer,
+                ReadRangeValueType::Bits as u8,
+                x.unused_bits,
+                x.as_slice(),
+            ),
+            Self::Null => Tag::new(
+                TagNumber::ContextSpecific(ReadRangeValueType::Null as u8),
+                0,
+            )
+            .encode(writer),
+            Self::Error(x) => {
+                encode_opening_tag(writer, ReadRangeValueType::Error as u8);
+                x.encode(writer);
+                encode_closing_tag(writer, ReadRangeValueType::Error as u8);
+            }
+            Self::Delta(x) => encode_context_real(writer, ReadRangeValueType::Delta as u8, *x),
+            Self::Any(x) => {
+                encode_opening_tag(writer, ReadRangeValueType::Any as u8);
+                writer.extend_from_slice(x.as_slice());
+                encode_closing_tag(writer, ReadRangeValueType::Any as u8);
+            }
         }
+    }
 
-        let item = ReadRangeItem::decode(&mut self.reader, self.buf);
-        Some(item)
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // constructed choices
+        if is_next_context_tag(reader, buf, ReadRangeValueType::Error as u8)? {
+            let buf = get_tagged_body_for_tag(
+                reader,
+                buf,
+                ReadRangeValueType::Error as u8,
+                "ReadRangeValue decode error",
+            )?;
+            let mut reader = Reader::new_with_len(buf.len());
+            let error = PropertyAccessError::decode(&mut reader, buf)?;
+            return Ok(Self::Error(error));
+        }
+        if is_next_context_tag(reader, buf, ReadRangeValueType::Any as u8)? {
+            let buf = get_tagged_body_for_tag(
+                reader,
+                buf,
+                ReadRangeValueType::Any as u8,
+                "ReadRangeValue decode any",
+            )?;
+            return Ok(Self::Any(OctetString::new(buf)));
+        }
+
+        // primitive choices
+        let tag = Tag::decode(reader, buf)?;
+        let value_type: ReadRangeValueType = match tag.number {
+            TagNumber::ContextSpecific(tag_number) => tag_number
+                .try_into()
+                .map_err(|x| Error::InvalidVariant(("ReadRangeValueType", x as u32)))?,
+            x => return Err(Error::TagNotSupported(("ReadRangeValue decode", x))),
+        };
+        let value = match value_type {
+            ReadRangeValueType::Status => {
+                let [bits] = decode_bit_string::<1>(tag.value, reader, buf)?;
+                Self::Status(LogStatusBits::new(bits))
+            }
+            ReadRangeValueType::Bool => {
+                tag.expect_value("ReadRangeValue decode bool", 1)?;
+                Self::Bool(reader.read_byte(buf)? > 0)
+            }
+            ReadRangeValueType::Real => {
+                tag.expect_value("ReadRangeValue decode real", 4)?;
+                Self::Real(f32::from_be_bytes(reader.read_bytes(buf)?))
+            }
+            ReadRangeValueType::Enum => Self::Enum(decode_unsigned(tag.value, reader, buf)? as u32),
+            ReadRangeValueType::Unsigned => {
+                Self::Unsigned(decode_unsigned(tag.value, reader, buf)? as u32)
+            }
+            ReadRangeValueType::Signed => {
+                Self::Signed(decode_signed(tag.value, reader, buf)? as i32)
+            }
+            ReadRangeValueType::Bits => {
+                if tag.value == 0 {
+                    return Err(Error::Length((
+                        "bit string has no unused bits byte",
+                        tag.value,
+                    )));
+                }
+                let unused_bits = reader.read_byte(buf)?;
+                let bits = reader.read_slice(tag.value as usize - 1, buf)?;
+                Self::Bits(CustomBitStream::new(unused_bits, bits))
+            }
+            ReadRangeValueType::Null => Self::Null,
+            ReadRangeValueType::Delta => {
+                tag.expect_value("ReadRangeValue decode delta", 4)?;
+                Self::Delta(f32::from_be_bytes(reader.read_bytes(buf)?))
+            }
+            ReadRangeValueType::Error | ReadRangeValueType::Any => {
+                return Err(Error::ExpectedOpeningTag(tag.number))
+            }
+        };
+
+        Ok(value)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ReadRangeItem<'a> {
+    Log(LogRecord<'a>),                   // Log_Buffer of an ObjectTrendlog
+    LogMultiple(LogMultipleRecord<'a>),   // Log_Buffer of an ObjectTrendLogMultiple
+    EventLog(EventLogRecord<'a>),         // Log_Buffer of an ObjectEventLog
+    AddressBinding(AddressBinding<'a>),   // e.g. Device_Address_Binding
+    Destination(Destination<'a>),         // Recipient_List
+    CovSubscription(CovSubscription<'a>), // Active_COV_Subscriptions
+    Recipient(Recipient<'a>),             // e.g. Time_Synchronization_Recipients
+    Value(ApplicationDataValue<'a>),      // lists of application tagged values
+    Any(OctetString<'a>), // the remaining encoded items of a list of constructed values we do not decode
+}
+
+impl<'a> ReadRangeItem<'a> {
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Log(x) => x.encode(writer),
+            Self::LogMultiple(x) => x.encode(writer),
+            Self::EventLog(x) => x.encode(writer),
+            Self::AddressBinding(x) => x.encode(writer),
+            Self::Destination(x) => x.encode(writer),
+            Self::CovSubscription(x) => x.encode(writer),
+            Self::Recipient(x) => x.encode(writer),
+            Self::Value(x) => x.encode(writer),
+            Self::Any(x) => writer.extend_from_slice(x.as_slice()),
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(
+        object_id: &ObjectId,
+        property_id: &PropertyId,
+        reader: &mut Reader,
+        buf: &'a [u8],
+    ) -> Result<Self, Error> {
+        match property_id {
+            PropertyId::PropLogBuffer => match object_id.object_type {
+                ObjectType::ObjectTrendLogMultiple => {
+                    Ok(Self::LogMultiple(LogMultipleRecord::decode(reader, buf)?))
+                }
+                ObjectType::ObjectEventLog => {
+                    Ok(Self::EventLog(EventLogRecord::decode(reader, buf)?))
+                }
+                _ => Ok(Self::Log(LogRecord::decode(reader, buf)?)),
+            },
+            PropertyId::PropDeviceAddressBinding
+            | PropertyId::PropManualSlaveAddressBinding
+            | PropertyId::PropSlaveAddressBinding => {
+                Ok(Self::AddressBinding(AddressBinding::decode(reader, buf)?))
+            }
+            PropertyId::PropRecipientList => {
+                Ok(Self::Destination(Destination::decode(reader, buf)?))
+            }
+            PropertyId::PropActiveCovSubscriptions => {
+                Ok(Self::CovSubscription(CovSubscription::decode(reader, buf)?))
+            }
+            PropertyId::PropTimeSynchronizationRecipients
+            | PropertyId::PropUtcTimeSynchronizationRecipients
+            | PropertyId::PropRestartNotificationRecipients => {
+                Ok(Self::Recipient(Recipient::decode(reader, buf)?))
+            }
+            _ => match peek_tag(reader, buf)?.number {
+                TagNumber::Application(_) => Ok(Self::Value(ApplicationDataValue::decode(
+                    object_id,
+                    property_id,
+                    reader,
+                    buf,
+                )?)),
+                // the length of a constructed item is not known without knowing its type
+                _ => {
+                    let items = OctetString::new(&buf[reader.index..reader.end]);
+                    reader.index = reader.end;
+                    Ok(Self::Any(items))
+                }
+            },
+        }
     }
 }
 
+// BACnetLogRecord (trend log)
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub struct ReadRangeItem<'a> {
+pub struct LogRecord<'a> {
     pub date: Date,
     pub time: Time,
-    pub value: ReadRangeValue,
-    pub status_flags: BitString<'a>,
+    pub value: ReadRangeValue<'a>,
+    pub status_flags: Option<BitString<'a>>,
 }
 
-impl<'a> ReadRangeItem<'a> {
+impl<'a> LogRecord<'a> {
     const DATE_TIME_TAG: u8 = 0;
     const VALUE_TAG: u8 = 1;
     const STATUS_FLAGS_TAG: u8 = 2;
@@ -344,22 +607,13 @@ impl<'a> ReadRangeItem<'a> {
 
         // value
         Tag::new(TagNumber::ContextSpecificOpening(Self::VALUE_TAG), 0).encode(writer);
-        match self.value {
-            ReadRangeValue::Real(value) => {
-                Tag::new(
-                    TagNumber::ContextSpecific(ReadRangeValueType::Real as u8),
-                    4,
-                )
-                .encode(writer);
-                writer.extend_from_slice(&value.to_be_bytes());
-            }
-            _ => todo!("{:?}", self.value),
-        }
+        self.value.encode(writer);
         Tag::new(TagNumber::ContextSpecificClosing(Self::VALUE_TAG), 0).encode(writer);
 
-        // status
-        self.status_flags
-            .encode_context(Self::STATUS_FLAGS_TAG, writer);
+        // status (optional)
+        if let Some(status_flags) = &self.status_flags {
+            status_flags.encode_context(Self::STATUS_FLAGS_TAG, writer);
+        }
     }
 
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
@@ -369,27 +623,27 @@ impl<'a> ReadRangeItem<'a> {
             reader,
             buf,
             TagNumber::ContextSpecificOpening(Self::DATE_TIME_TAG),
-            "ReadRangeItem decode",
+            "LogRecord decode",
         )?;
         Tag::decode_expected(
             reader,
             buf,
             TagNumber::Application(ApplicationTagNumber::Date),
-            "ReadRangeItem decode",
+            "LogRecord decode",
         )?;
         let date = Date::decode(reader, buf)?;
         Tag::decode_expected(
             reader,
             buf,
             TagNumber::Application(ApplicationTagNumber::Time),
-            "ReadRangeItem decode",
+            "LogRecord decode",
         )?;
         let time = Time::decode(reader, buf)?;
         Tag::decode_expected(
             reader,
             buf,
             TagNumber::ContextSpecificClosing(Self::DATE_TIME_TAG),
-            "ReadRangeItem decode",
+            "LogRecord decode",
         )?;
 
         // value
@@ -397,47 +651,484 @@ impl<'a> ReadRangeItem<'a> {
             reader,
             buf,
             TagNumber::ContextSpecificOpening(Self::VALUE_TAG),
-            "ReadRangeItem decode",
+            "LogRecord decode",
+        )?;
+        let value = ReadRangeValue::decode(reader, buf)?;
+        Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecificClosing(Self::VALUE_TAG),
+            "LogRecord decode",
+        )?;
+
+        // status flags (optional)
+        let status_flags = if is_next_context_tag(reader, buf, Self::STATUS_FLAGS_TAG)? {
+            let tag = Tag::decode_expected(
+                reader,
+                buf,
+                TagNumber::ContextSpecific(Self::STATUS_FLAGS_TAG),
+                "LogRecord decode status_flags",
+            )?;
+            Some(BitString::decode(
+                &PropertyId::PropStatusFlags,
+                tag.value,
+                reader,
+                buf,
+            )?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            date,
+            time,
+            value,
+            status_flags,
+        })
+    }
+}
+
+// BACnetLogMultipleRecord (trend log multiple)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LogMultipleRecord<'a> {
+    pub timestamp: DateTime,
+    pub log_data: LogMultipleData<'a>,
+}
+
+impl<'a> LogMultipleRecord<'a> {
+    const TAG_TIMESTAMP: u8 = 0;
+    const TAG_LOG_DATA: u8 = 1;
+
+    pub fn new(timestamp: DateTime, log_data: LogMultipleData<'a>) -> Self {
+        Self {
+            timestamp,
+            log_data,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_opening_tag(writer, Self::TAG_TIMESTAMP);
+        self.timestamp.encode(writer);
+        encode_closing_tag(writer, Self::TAG_TIMESTAMP);
+
+        encode_opening_tag(writer, Self::TAG_LOG_DATA);
+        self.log_data.encode(writer);
+        encode_closing_tag(writer, Self::TAG_LOG_DATA);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let timestamp = decode_timestamp(
+            reader,
+            buf,
+            Self::TAG_TIMESTAMP,
+            "LogMultipleRecord decode timestamp",
+        )?;
+
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_LOG_DATA,
+            "LogMultipleRecord decode log_data",
+        )?;
+        let mut inner_reader = Reader::new_with_len(inner_buf.len());
+        let log_data = LogMultipleData::decode(&mut inner_reader, inner_buf)?;
+
+        Ok(Self {
+            timestamp,
+            log_data,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LogMultipleData<'a> {
+    Status(LogStatusBits),
+    Values(LogMultipleValues<'a>), // one value per logged property
+    TimeChange(f32),               // the number of seconds the clock was changed by
+}
+
+impl<'a> LogMultipleData<'a> {
+    const TAG_LOG_STATUS: u8 = 0;
+    const TAG_LOG_DATA: u8 = 1;
+    const TAG_TIME_CHANGE: u8 = 2;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            // 3 bits used (5 unused)
+            Self::Status(x) => {
+                encode_context_bit_string(writer, Self::TAG_LOG_STATUS, 5, &[x.inner])
+            }
+            Self::Values(x) => {
+                encode_opening_tag(writer, Self::TAG_LOG_DATA);
+                x.encode(writer);
+                encode_closing_tag(writer, Self::TAG_LOG_DATA);
+            }
+            Self::TimeChange(x) => encode_context_real(writer, Self::TAG_TIME_CHANGE, *x),
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        if is_next_context_tag(reader, buf, Self::TAG_LOG_DATA)? {
+            let inner_buf = get_tagged_body_for_tag(
+                reader,
+                buf,
+                Self::TAG_LOG_DATA,
+                "LogMultipleData decode log_data",
+            )?;
+            return Ok(Self::Values(LogMultipleValues::decode(inner_buf)?));
+        }
+
+        if is_next_context_tag(reader, buf, Self::TAG_TIME_CHANGE)? {
+            let seconds = decode_context_real(
+                reader,
+                buf,
+                Self::TAG_TIME_CHANGE,
+                "LogMultipleData decode time_change",
+            )?;
+            return Ok(Self::TimeChange(seconds));
+        }
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_LOG_STATUS),
+            "LogMultipleData decode log_status",
         )?;
+        let [bits] = decode_bit_string::<1>(tag.value, reader, buf)?;
+        Ok(Self::Status(LogStatusBits::new(bits)))
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LogMultipleValues<'a> {
+    pub values: &'a [LogMultipleValue<'a>],
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LogMultipleValues<'a> {
+    pub values: Vec<LogMultipleValue<'a>>,
+}
+
+impl<'a> LogMultipleValues<'a> {
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(values: &'a [LogMultipleValue<'a>]) -> Self {
+        Self { values, buf: &[] }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(values: Vec<LogMultipleValue<'a>>) -> Self {
+        Self { values }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        #[cfg(not(feature = "alloc"))]
+        encode_list(writer, self.buf, self.values, LogMultipleValue::encode);
+        #[cfg(feature = "alloc")]
+        for value in self.values.iter() {
+            value.encode(writer)
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn decode(buf: &'a [u8]) -> Result<Self, Error> {
+        Ok(Self { values: &[], buf })
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn decode(buf: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::new_with_len(buf.len());
+        let mut values = Vec::new();
+
+        while !reader.eof() {
+            let value = LogMultipleValue::decode(&mut reader, buf)?;
+            values.push(value);
+        }
+
+        Ok(Self::new(values))
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ LogMultipleValues<'a> {
+    type Item = Result<LogMultipleValue<'a>, Error>;
+
+    type IntoIter = LogMultipleValuesIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        LogMultipleValuesIter {
+            list: ListIter::new(self.values, self.buf),
+        }
+    }
+}
+
+pub struct LogMultipleValuesIter<'a> {
+    list: ListIter<'a, LogMultipleValue<'a>>,
+}
+
+impl<'a> Iterator for LogMultipleValuesIter<'a> {
+    type Item = Result<LogMultipleValue<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.next_with(LogMultipleValue::decode)
+    }
+}
+
+// the value of a single logged property in a BACnetLogMultipleRecord
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LogMultipleValue<'a> {
+    Bool(bool),
+    Real(f32),
+    Enum(u32),
+    Unsigned(u32),
+    Signed(i32),
+    Bits(CustomBitStream<'a>),
+    Null,
+    Failure(PropertyAccessError),
+    Any(OctetString<'a>), // the encoded application tagged values of a constructed value
+}
+
+impl<'a> LogMultipleValue<'a> {
+    const TAG_BOOL: u8 = 0;
+    const TAG_REAL: u8 = 1;
+    const TAG_ENUM: u8 = 2;
+    const TAG_UNSIGNED: u8 = 3;
+    const TAG_SIGNED: u8 = 4;
+    const TAG_BITS: u8 = 5;
+    const TAG_NULL: u8 = 6;
+    const TAG_FAILURE: u8 = 7;
+    const TAG_ANY: u8 = 8;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Bool(x) => encode_context_bool(writer, Self::TAG_BOOL, *x),
+            Self::Real(x) => encode_context_real(writer, Self::TAG_REAL, *x),
+            Self::Enum(x) => encode_context_unsigned(writer, Self::TAG_ENUM, *x),
+            Self::Unsigned(x) => encode_context_unsigned(writer, Self::TAG_UNSIGNED, *x),
+            Self::Signed(x) => encode_signed(writer, Some(Self::TAG_SIGNED), *x),
+            Self::Bits(x) => {
+                encode_context_bit_string(writer, Self::TAG_BITS, x.unused_bits, x.as_slice())
+            }
+            Self::Null => Tag::new(TagNumber::ContextSpecific(Self::TAG_NULL), 0).encode(writer),
+            Self::Failure(x) => {
+                encode_opening_tag(writer, Self::TAG_FAILURE);
+                x.encode(writer);
+                encode_closing_tag(writer, Self::TAG_FAILURE);
+            }
+            Self::Any(x) => {
+                encode_opening_tag(writer, Self::TAG_ANY);
+                writer.extend_from_slice(x.as_slice());
+                encode_closing_tag(writer, Self::TAG_ANY);
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // constructed choices
+        if is_next_context_tag(reader, buf, Self::TAG_FAILURE)? {
+            let buf = get_tagged_body_for_tag(
+                reader,
+                buf,
+                Self::TAG_FAILURE,
+                "LogMultipleValue decode failure",
+            )?;
+            let mut reader = Reader::new_with_len(buf.len());
+            let error = PropertyAccessError::decode(&mut reader, buf)?;
+            return Ok(Self::Failure(error));
+        }
+        if is_next_context_tag(reader, buf, Self::TAG_ANY)? {
+            let buf =
+                get_tagged_body_for_tag(reader, buf, Self::TAG_ANY, "LogMultipleValue decode any")?;
+            return Ok(Self::Any(OctetString::new(buf)));
+        }
+
+        // primitive choices
         let tag = Tag::decode(reader, buf)?;
-        let value_type: ReadRangeValueType = match tag.number {
-            TagNumber::ContextSpecific(tag_number) => tag_number
-                .try_into()
-                .map_err(|x| Error::InvalidVariant(("ReadRangeValueType", x as u32)))?,
-            x => return Err(Error::TagNotSupported(("ReadRangeItems next value", x))),
+        let tag_number = match tag.number {
+            TagNumber::ContextSpecific(x) => x,
+            x => return Err(Error::TagNotSupported(("LogMultipleValue decode", x))),
         };
-        let value = match value_type {
-            ReadRangeValueType::Real => {
-                let value = f32::from_be_bytes(reader.read_bytes(buf)?);
-                ReadRangeValue::Real(value)
+        let value = match tag_number {
+            Self::TAG_BOOL => {
+                tag.expect_value("LogMultipleValue decode bool", 1)?;
+                Self::Bool(reader.read_byte(buf)? > 0)
+            }
+            Self::TAG_REAL => {
+                tag.expect_value("LogMultipleValue decode real", 4)?;
+                Self::Real(f32::from_be_bytes(reader.read_bytes(buf)?))
+            }
+            Self::TAG_ENUM => Self::Enum(decode_unsigned(tag.value, reader, buf)? as u32),
+            Self::TAG_UNSIGNED => Self::Unsigned(decode_unsigned(tag.value, reader, buf)? as u32),
+            Self::TAG_SIGNED => Self::Signed(decode_signed(tag.value, reader, buf)? as i32),
+            Self::TAG_BITS => {
+                if tag.value == 0 {
+                    return Err(Error::Length((
+                        "bit string has no unused bits byte",
+                        tag.value,
+                    )));
+                }
+                let unused_bits = reader.read_byte(buf)?;
+                let bits = reader.read_slice(tag.value as usize - 1, buf)?;
+                Self::Bits(CustomBitStream::new(unused_bits, bits))
             }
-            x => return Err(Error::Unimplemented(Unimplemented::ReadRangeValueType(x))),
+            Self::TAG_NULL => Self::Null,
+            x => return Err(Error::InvalidVariant(("LogMultipleValue", x as u32))),
         };
-        Tag::decode_expected(
+
+        Ok(value)
+    }
+}
+
+// BACnetEventLogRecord (event log)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EventLogRecord<'a> {
+    pub timestamp: DateTime,
+    pub log_datum: EventLogDatum<'a>,
+}
+
+impl<'a> EventLogRecord<'a> {
+    const TAG_TIMESTAMP: u8 = 0;
+    const TAG_LOG_DATUM: u8 = 1;
+
+    pub fn new(timestamp: DateTime, log_datum: EventLogDatum<'a>) -> Self {
+        Self {
+            timestamp,
+            log_datum,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_opening_tag(writer, Self::TAG_TIMESTAMP);
+        self.timestamp.encode(writer);
+        encode_closing_tag(writer, Self::TAG_TIMESTAMP);
+
+        encode_opening_tag(writer, Self::TAG_LOG_DATUM);
+        self.log_datum.encode(writer);
+        encode_closing_tag(writer, Self::TAG_LOG_DATUM);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let timestamp = decode_timestamp(
             reader,
             buf,
-            TagNumber::ContextSpecificClosing(Self::VALUE_TAG),
-            "ReadRangeItem decode",
+            Self::TAG_TIMESTAMP,
+            "EventLogRecord decode timestamp",
         )?;
 
-        // status flags
-        Tag::decode_expected(
+        let inner_buf = get_tagged_body_for_tag(
             reader,
             buf,
-            TagNumber::ContextSpecific(Self::STATUS_FLAGS_TAG),
-            "ReadRangeItem decode",
+            Self::TAG_LOG_DATUM,
+            "EventLogRecord decode log_datum",
         )?;
-        let status_flags = BitString::decode(&PropertyId::PropStatusFlags, tag.value, reader, buf)?;
+        let mut inner_reader = Reader::new_with_len(inner_buf.len());
+        let log_datum = EventLogDatum::decode(&mut inner_reader, inner_buf)?;
 
-        Ok(ReadRangeItem {
-            date,
-            time,
-            value,
-            status_flags,
+        Ok(Self {
+            timestamp,
+            log_datum,
         })
     }
 }
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EventLogDatum<'a> {
+    Status(LogStatusBits),
+    Notification(EventNotification<'a>),
+    TimeChange(f32), // the number of seconds the clock was changed by
+}
+
+impl<'a> EventLogDatum<'a> {
+    const TAG_LOG_STATUS: u8 = 0;
+    const TAG_NOTIFICATION: u8 = 1;
+    const TAG_TIME_CHANGE: u8 = 2;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            // 3 bits used (5 unused)
+            Self::Status(x) => {
+                encode_context_bit_string(writer, Self::TAG_LOG_STATUS, 5, &[x.inner])
+            }
+            Self::Notification(x) => {
+                encode_opening_tag(writer, Self::TAG_NOTIFICATION);
+                x.encode(writer);
+                encode_closing_tag(writer, Self::TAG_NOTIFICATION);
+            }
+            Self::TimeChange(x) => encode_context_real(writer, Self::TAG_TIME_CHANGE, *x),
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        if is_next_context_tag(reader, buf, Self::TAG_NOTIFICATION)? {
+            let inner_buf = get_tagged_body_for_tag(
+                reader,
+                buf,
+                Self::TAG_NOTIFICATION,
+                "EventLogDatum decode notification",
+            )?;
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            let notification = EventNotification::decode(&mut inner_reader, inner_buf)?;
+            return Ok(Self::Notification(notification));
+        }
+
+        if is_next_context_tag(reader, buf, Self::TAG_TIME_CHANGE)? {
+            let seconds = decode_context_real(
+                reader,
+                buf,
+                Self::TAG_TIME_CHANGE,
+                "EventLogDatum decode time_change",
+            )?;
+            return Ok(Self::TimeChange(seconds));
+        }
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_LOG_STATUS),
+            "EventLogDatum decode log_status",
+        )?;
+        let [bits] = decode_bit_string::<1>(tag.value, reader, buf)?;
+        Ok(Self::Status(LogStatusBits::new(bits)))
+    }
+}
+
+// a BACnetDateTime wrapped in a context tag
+fn decode_timestamp(
+    reader: &mut Reader,
+    buf: &[u8],
+    tag_number: u8,
+    context: &'static str,
+) -> Result<DateTime, Error> {
+    Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::ContextSpecificOpening(tag_number),
+        context,
+    )?;
+    let timestamp = DateTime::decode(reader, buf)?;
+    Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::ContextSpecificClosing(tag_number),
+        context,
+    )?;
+    Ok(timestamp)
+}
+
 impl ReadRange {
     const OBJECT_ID_TAG: u8 = 0;
     const PROPERTY_ID_TAG: u8 = 1;
@@ -493,27 +1184,7 @@ impl ReadRange {
                 )?;
                 let index = decode_unsigned(index_tag.value, reader, buf)? as u32;
 
-                // count
-                let count_tag = Tag::decode(reader, buf)?;
-                let count = match count_tag.number {
-                    TagNumber::Application(ApplicationTagNumber::UnsignedInt) => {
-                        decode_unsigned(count_tag.value, reader, buf)? as u32
-                    }
-                    TagNumber::Application(ApplicationTagNumber::SignedInt) => {
-                        let count = decode_signed(count_tag.value, reader, buf)?;
-                        if count < 0 {
-                            return Err(Error::InvalidValue("ReadRange count cannot be negative"));
-                        }
-
-                        count as u32
-                    }
-                    _ => {
-                        return Err(Error::TagNotSupported((
-                            "ReadRange count tag",
-                            count_tag.number,
-                        )))
-                    }
-                };
+                let count = Self::decode_count(reader, buf)?;
 
                 // closing tag
                 Tag::decode_expected(
@@ -523,9 +1194,30 @@ impl ReadRange {
                     "ReadRange decode closing position",
                 )?;
 
-                ReadRangeRequestType::ByPosition(ReadRangeByPosition {
-                    count: count as u32,
-                    index,
+                ReadRangeRequestType::ByPosition(ReadRangeByPosition { count, index })
+            }
+            TagNumber::ContextSpecificOpening(Self::BY_SEQUENCE_TAG) => {
+                // sequence number
+                let sequence_tag = Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::Application(ApplicationTagNumber::UnsignedInt),
+                    "ReadRange decode sequence_num",
+                )?;
+                let sequence_num = decode_unsigned(sequence_tag.value, reader, buf)? as u32;
+                let count = Self::decode_count(reader, buf)?;
+
+                // closing tag
+                Tag::decode_expected(
+                    reader,
+                    buf,
+                    TagNumber::ContextSpecificClosing(Self::BY_SEQUENCE_TAG),
+                    "ReadRange decode closing sequence",
+                )?;
+
+                ReadRangeRequestType::BySequence(ReadRangeBySequence {
+                    sequence_num,
+                    count,
                 })
             }
             number => return Err(Error::TagNotSupported(("ReadRange opening tag", number))),
@@ -539,6 +1231,28 @@ impl ReadRange {
         })
     }
 
+    // the count is signed (a negative count reads backwards) but we only support reading forwards
+    fn decode_count(reader: &mut Reader, buf: &[u8]) -> Result<u32, Error> {
+        let count_tag = Tag::decode(reader, buf)?;
+        match count_tag.number {
+            TagNumber::Application(ApplicationTagNumber::UnsignedInt) => {
+                Ok(decode_unsigned(count_tag.value, reader, buf)? as u32)
+            }
+            TagNumber::Application(ApplicationTagNumber::SignedInt) => {
+                let count = decode_signed(count_tag.value, reader, buf)?;
+                if count < 0 {
+                    return Err(Error::InvalidValue("ReadRange count cannot be negative"));
+                }
+
+                Ok(count as u32)
+            }
+            _ => Err(Error::TagNotSupported((
+                "ReadRange count tag",
+                count_tag.number,
+            ))),
+        }
+    }
+
     pub fn encode(&self, writer: &mut Writer) {
         // object_id
         encode_context_object_id(writer, Self::OBJECT_ID_TAG, &self.object_id);
@@ -577,3 +1291,817 @@ impl ReadRange {
         }
     }
 }
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ComplexAck, ComplexAckService},
+            primitives::{
+                data_value::{
+                    ApplicationDataValue, BitString, CharacterString, CustomBitStream, Date,
+                    DateTime, OctetString, Time, TimeStamp,
+                },
+                recipient::{Address, AddressBinding, Destination, Recipient, RecipientProcess},
+            },
+            services::{
+                change_of_value::CovSubscription, event_notification::EventNotification,
+                read_property_multiple::PropertyAccessError,
+            },
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{
+                ErrorClass, ErrorCode, EventState, EventTransitionBits, EventType, LogBufferResult,
+                LogStatusBits, NotifyType, Status, BACNET_ARRAY_ALL,
+            },
+        },
+    };
+
+    use super::{
+        EventLogDatum, EventLogRecord, LogMultipleData, LogMultipleRecord, LogMultipleValue,
+        LogMultipleValues, LogRecord, ReadRangeAck, ReadRangeItem, ReadRangeItems, ReadRangeValue,
+    };
+
+    fn date_time(second: u8) -> DateTime {
+        let date = Date {
+            year: 2024,
+            month: 3,
+            day: 14,
+            wday: 4,
+        };
+        let time = Time {
+            hour: 10,
+            minute: 15,
+            second,
+            hundredths: 0,
+        };
+        DateTime::new(date, time)
+    }
+
+    fn item<'a>(second: u8, value: ReadRangeValue<'a>) -> LogRecord<'a> {
+        let DateTime { date, time } = date_time(second);
+        LogRecord {
+            date,
+            time,
+            value,
+            status_flags: Some(BitString::Status(Status::new(0b0100_0000))),
+        }
+    }
+
+    // encodes the ack as a complex ack and decodes it again
+    fn round_trip<'a>(
+        object_id: ObjectId,
+        property_id: PropertyId,
+        items: alloc::vec::Vec<ReadRangeItem<'_>>,
+        buf: &'a mut [u8],
+    ) -> ReadRangeAck<'a> {
+        let ack = ReadRangeAck {
+            object_id,
+            property_id,
+            array_index: BACNET_ARRAY_ALL,
+            result_flags: BitString::LogBufferResult(LogBufferResult::new(0b1000_0000)),
+            item_count: items.len(),
+            item_data: ReadRangeItems::new(items),
+            first_sequence_number: Some(42),
+        };
+
+        let mut writer = Writer::new(buf);
+        ComplexAck {
+            invoke_id: 1,
+            service: ComplexAckService::ReadRange(ack),
+        }
+        .encode(&mut writer);
+        let len = writer.index;
+
+        let buf: &'a [u8] = buf;
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ComplexAck::decode(&mut reader, &buf[..len]).unwrap();
+        match decoded.service {
+            ComplexAckService::ReadRange(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        }
+    }
+
+    #[test]
+    fn reversable() {
+        let mut no_flags = item(10, ReadRangeValue::Null);
+        no_flags.status_flags = None;
+        let items = [
+            item(0, ReadRangeValue::Status(LogStatusBits::new(0b0100_0000))),
+            item(1, ReadRangeValue::Bool(true)),
+            item(2, ReadRangeValue::Real(21.5)),
+            item(3, ReadRangeValue::Enum(3)),
+            item(4, ReadRangeValue::Unsigned(70000)),
+            item(5, ReadRangeValue::Signed(-12)),
+            item(
+                6,
+                ReadRangeValue::Bits(CustomBitStream::new(4, &[0b1010_0000])),
+            ),
+            item(
+                7,
+                ReadRangeValue::Error(PropertyAccessError {
+                    error_class: ErrorClass::Property,
+                    error_code: ErrorCode::UnknownProperty,
+                }),
+            ),
+            item(8, ReadRangeValue::Delta(-3.5)),
+            // application tagged unsigned 5 and boolean true
+            item(
+                9,
+                ReadRangeValue::Any(OctetString::new(&[0x21, 0x05, 0x11])),
+            ),
+            no_flags,
+        ];
+        let items = items.into_iter().map(ReadRangeItem::Log).collect();
+
+        let mut buf = [0; 512];
+        let decoded = round_trip(
+            ObjectId::new(ObjectType::ObjectTrendlog, 1),
+            PropertyId::PropLogBuffer,
+            items,
+            &mut buf,
+        );
+        assert_eq!(decoded.item_count, 11);
+        assert_eq!(decoded.first_sequence_number, Some(42));
+        let items: alloc::vec::Vec<_> = decoded
+            .item_data
+            .items
+            .into_iter()
+            .map(|x| match x {
+                ReadRangeItem::Log(x) => x,
+                x => panic!("unexpected item {:?}", x),
+            })
+            .collect();
+        assert_eq!(items.len(), 11);
+        assert!(matches!(&items[0].value, ReadRangeValue::Status(x) if x.buffer_purged()));
+        assert!(matches!(items[1].value, ReadRangeValue::Bool(true)));
+        assert!(matches!(items[2].value, ReadRangeValue::Real(x) if x == 21.5));
+        assert!(matches!(items[3].value, ReadRangeValue::Enum(3)));
+        assert!(matches!(items[4].value, ReadRangeValue::Unsigned(70000)));
+        assert!(matches!(items[5].value, ReadRangeValue::Signed(-12)));
+        assert!(matches!(
+            &items[6].value,
+            ReadRangeValue::Bits(x) if x.unused_bits == 4 && x.bits == [0b1010_0000]
+        ));
+        assert!(matches!(
+            &items[7].value,
+            ReadRangeValue::Error(PropertyAccessError {
+                error_class: ErrorClass::Property,
+                error_code: ErrorCode::UnknownProperty,
+            })
+        ));
+        assert!(matches!(items[8].value, ReadRangeValue::Delta(x) if x == -3.5));
+        assert!(matches!(
+            &items[9].value,
+            ReadRangeValue::Any(x) if x.inner == [0x21, 0x05, 0x11]
+        ));
+        assert!(matches!(items[10].value, ReadRangeValue::Null));
+        assert!(items[10].status_flags.is_none());
+        assert!(matches!(
+            &items[0].status_flags,
+            Some(BitString::Status(x)) if x.fault()
+        ));
+    }
+
+    // a trend log read by position (two records)
+    const TREND_LOG_ACK: [u8; 61] = [
+        0x30, 0x01, 0x1A, // complex ack, invoke id 1, ReadRange
+        0x0C, 0x05, 0x00, 0x00, 0x01, // trend log 1
+        0x19, 0x83, // Log_Buffer
+        0x3A, 0x05, 0xC0, // result flags: first item and last item
+        0x49, 0x02, // item count
+        0x5E, // item data
+        0x0E, 0xA4, 0x62, 0x03, 0x17, 0x01, 0xB4, 0x13, 0x36, 0x1B, 0x00,
+        0x0F, // 1998-03-23 19:54:27
+        0x1E, 0x2C, 0x41, 0x90, 0x00, 0x00, 0x1F, // real 18.0
+        0x2A, 0x04, 0x00, // status flags
+        0x0E, 0xA4, 0x62, 0x03, 0x17, 0x01, 0xB4, 0x13, 0x38, 0x1B, 0x00,
+        0x0F, // 1998-03-23 19:56:27
+        0x1E, 0x2C, 0x41, 0x90, 0xCC, 0xCD, 0x1F, // real 18.1
+        0x2A, 0x04, 0x80, // status flags: in alarm
+        0x5F,
+    ];
+
+    fn decode_ack(buf: &[u8]) -> ReadRangeAck<'_> {
+        let mut reader = Reader::new_with_len(buf.len());
+        reader.index = 1;
+        let decoded = ComplexAck::decode(&mut reader, buf).unwrap();
+        assert!(reader.eof());
+        match decoded.service {
+            ComplexAckService::ReadRange(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        }
+    }
+
+    #[test]
+    fn decode_log_records() {
+        let decoded = decode_ack(&TREND_LOG_ACK);
+        assert_eq!(
+            decoded.object_id,
+            ObjectId::new(ObjectType::ObjectTrendlog, 1)
+        );
+        assert!(matches!(
+            &decoded.result_flags,
+            BitString::LogBufferResult(x) if x.first_item() && x.last_item() && !x.more_items()
+        ));
+        assert_eq!(decoded.item_count, 2);
+        assert_eq!(decoded.first_sequence_number, None);
+
+        let items = decoded.item_data.items;
+        let first = match &items[0] {
+            ReadRangeItem::Log(x) => x,
+            x => panic!("unexpected item {:?}", x),
+        };
+        assert_eq!(
+            (first.date.year, first.date.month, first.date.day),
+            (1998, 3, 23)
+        );
+        assert_eq!(
+            (first.time.hour, first.time.minute, first.time.second),
+            (19, 54, 27)
+        );
+        assert!(matches!(first.value, ReadRangeValue::Real(x) if x == 18.0));
+        assert!(matches!(&first.status_flags, Some(BitString::Status(x)) if !x.in_alarm()));
+        let second = match &items[1] {
+            ReadRangeItem::Log(x) => x,
+            x => panic!("unexpected item {:?}", x),
+        };
+        assert_eq!(second.time.minute, 56);
+        assert!(matches!(second.value, ReadRangeValue::Real(x) if x == 18.1));
+        assert!(matches!(&second.status_flags, Some(BitString::Status(x)) if x.in_alarm()));
+    }
+
+    #[test]
+    fn encode_log_record() {
+        let DateTime { date, time } = date_time(30);
+        let record = LogRecord {
+            date,
+            time,
+            value: ReadRangeValue::Unsigned(300),
+            status_flags: None,
+        };
+
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        record.encode(&mut writer);
+        let expected = [
+            0x0E, 0xA4, 0x7C, 0x03, 0x0E, 0x04, 0xB4, 0x0A, 0x0F, 0x1E, 0x00,
+            0x0F, // 2024-03-14 10:15:30
+            0x1E, 0x4A, 0x01, 0x2C, 0x1F, // unsigned 300
+        ];
+        assert_eq!(writer.to_bytes(), expected);
+    }
+
+    // the item data of a trend log multiple logging three properties
+    const LOG_MULTIPLE_ITEMS: [u8; 65] = [
+        0x0E, 0xA4, 0x7C, 0x03, 0x0E, 0x04, 0xB4, 0x0A, 0x0F, 0x00, 0x00,
+        0x0F, // 2024-03-14 10:15:00
+        0x1E, 0x1E, // log data
+        0x1C, 0x41, 0xAC, 0x00, 0x00, // real 21.5
+        0x29, 0x03, // enumerated 3
+        0x3A, 0x01, 0x2C, // unsigned 300
+        0x5A, 0x04, 0xA0, // bit string 1010
+        0x1F, 0x1F, //
+        0x0E, 0xA4, 0x7C, 0x03, 0x0E, 0x04, 0xB4, 0x0A, 0x10, 0x00, 0x00, 0x0F, // 10:16:00
+        0x1E, 0x2C, 0x42, 0x70, 0x00, 0x00, 0x1F, // clock changed by 60 seconds
+        0x0E, 0xA4, 0x7C, 0x03, 0x0E, 0x04, 0xB4, 0x0A, 0x11, 0x00, 0x00, 0x0F, // 10:17:00
+        0x1E, 0x0A, 0x05, 0x40, 0x1F, // log status: buffer purged
+    ];
+
+    // the item data of an event log
+    const EVENT_LOG_ITEMS: [u8; 36] = [
+        0x0E, 0xA4, 0x7C, 0x03, 0x0E, 0x04, 0xB4, 0x0A, 0x0F, 0x00, 0x00,
+        0x0F, // 2024-03-14 10:15:00
+        0x1E, 0x0A, 0x05, 0x80, 0x1F, // log status: log disabled
+        0x0E, 0xA4, 0x7C, 0x03, 0x0E, 0x04, 0xB4, 0x0A, 0x0F, 0x01, 0x00, 0x0F, // 10:15:01
+        0x1E, 0x2C, 0xBF, 0x80, 0x00, 0x00, 0x1F, // clock changed by -1 second
+    ];
+
+    #[test]
+    fn decode_log_multiple_records() {
+        let object_id = ObjectId::new(ObjectType::ObjectTrendLogMultiple, 1);
+        let decoded =
+            ReadRangeItems::decode(object_id, PropertyId::PropLogBuffer, &LOG_MULTIPLE_ITEMS)
+                .unwrap();
+        let records: [&LogMultipleRecord; 3] = core::array::from_fn(|i| match &decoded.items[i] {
+            ReadRangeItem::LogMultiple(x) => x,
+            x => panic!("unexpected item {:?}", x),
+        });
+        assert_eq!(decoded.items.len(), 3);
+
+        assert_eq!(records[0].timestamp.time.minute, 15);
+        let values = match &records[0].log_data {
+            LogMultipleData::Values(x) => &x.values,
+            x => panic!("unexpected log data {:?}", x),
+        };
+        assert_eq!(values.len(), 4);
+        assert!(matches!(values[0], LogMultipleValue::Real(x) if x == 21.5));
+        assert!(matches!(values[1], LogMultipleValue::Enum(3)));
+        assert!(matches!(values[2], LogMultipleValue::Unsigned(300)));
+        assert!(
+            matches!(&values[3], LogMultipleValue::Bits(x) if x.unused_bits == 4 && x.as_slice() == [0xA0])
+        );
+        assert!(matches!(records[1].log_data, LogMultipleData::TimeChange(x) if x == 60.0));
+        assert!(
+            matches!(&records[2].log_data, LogMultipleData::Status(x) if x.buffer_purged() && !x.log_disabled())
+        );
+
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        decoded.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), LOG_MULTIPLE_ITEMS);
+    }
+
+    #[test]
+    fn decode_event_log_records() {
+        let object_id = ObjectId::new(ObjectType::ObjectEventLog, 1);
+        let decoded =
+            ReadRangeItems::decode(object_id, PropertyId::PropLogBuffer, &EVENT_LOG_ITEMS).unwrap();
+        assert_eq!(decoded.items.len(), 2);
+        assert!(matches!(
+            &decoded.items[0],
+            ReadRangeItem::EventLog(EventLogRecord {
+                log_datum: EventLogDatum::Status(x),
+                ..
+            }) if x.log_disabled() && !x.buffer_purged()
+        ));
+        assert!(matches!(
+            &decoded.items[1],
+            ReadRangeItem::EventLog(EventLogRecord {
+                timestamp,
+                log_datum: EventLogDatum::TimeChange(x),
+            }) if *x == -1.0 && timestamp.time.second == 1
+        ));
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        decoded.encode(&mut writer);
+        assert_eq!(writer.to_bytes(), EVENT_LOG_ITEMS);
+    }
+
+    #[test]
+    fn reversable_log_multiple() {
+        use alloc::vec;
+
+        let values = vec![
+            LogMultipleValue::Real(21.5),
+            LogMultipleValue::Bool(false),
+            LogMultipleValue::Bits(CustomBitStream::new(6, &[0b1100_0000])),
+            LogMultipleValue::Failure(PropertyAccessError {
+                error_class: ErrorClass::Object,
+                error_code: ErrorCode::UnknownObject,
+            }),
+            LogMultipleValue::Null,
+        ];
+        let items = vec![
+            ReadRangeItem::LogMultiple(LogMultipleRecord::new(
+                date_time(0),
+                LogMultipleData::Values(LogMultipleValues::new(values)),
+            )),
+            ReadRangeItem::LogMultiple(LogMultipleRecord::new(
+                date_time(1),
+                LogMultipleData::TimeChange(60.0),
+            )),
+            ReadRangeItem::LogMultiple(LogMultipleRecord::new(
+                date_time(2),
+                LogMultipleData::Status(LogStatusBits::new(0b1000_0000)),
+            )),
+        ];
+
+        let mut buf = [0; 256];
+        let decoded = round_trip(
+            ObjectId::new(ObjectType::ObjectTrendLogMultiple, 1),
+            PropertyId::PropLogBuffer,
+            items,
+            &mut buf,
+        );
+        let items = decoded.item_data.items;
+        assert_eq!(items.len(), 3);
+        let values = match &items[0] {
+            ReadRangeItem::LogMultiple(LogMultipleRecord {
+                log_data: LogMultipleData::Values(x),
+                ..
+            }) => &x.values,
+            x => panic!("unexpected item {:?}", x),
+        };
+        assert_eq!(values.len(), 5);
+        assert!(matches!(values[0], LogMultipleValue::Real(x) if x == 21.5));
+        assert!(matches!(values[1], LogMultipleValue::Bool(false)));
+        assert!(matches!(&values[2], LogMultipleValue::Bits(x) if x.bits == [0b1100_0000]));
+        assert!(matches!(
+            &values[3],
+            LogMultipleValue::Failure(PropertyAccessError {
+                error_code: ErrorCode::UnknownObject,
+                ..
+            })
+        ));
+        assert!(matches!(values[4], LogMultipleValue::Null));
+        assert!(matches!(
+            &items[1],
+            ReadRangeItem::LogMultiple(LogMultipleRecord {
+                log_data: LogMultipleData::TimeChange(x),
+                ..
+            }) if *x == 60.0
+        ));
+        assert!(matches!(
+            &items[2],
+            ReadRangeItem::LogMultiple(LogMultipleRecord {
+                log_data: LogMultipleData::Status(x),
+                timestamp,
+            }) if x.log_disabled() && timestamp.time.second == 2
+        ));
+    }
+
+    #[test]
+    fn reversable_event_log() {
+        use alloc::vec;
+
+        let notification = EventNotification {
+            process_id: 1,
+            initiating_device_id: ObjectId::new(ObjectType::ObjectDevice, 79079),
+            event_object_id: ObjectId::new(ObjectType::ObjectAnalogInput, 3),
+            timestamp: TimeStamp::DateTime(date_time(0)),
+            notification_class: 10,
+            priority: 100,
+            event_type: EventType::OutOfRange,
+            message_text: Some(CharacterString::new("Chiller supply temperature high")),
+            notify_type: NotifyType::Alarm,
+            ack_required: Some(true),
+            from_state: Some(EventState::Normal),
+            to_state: EventState::HighLimit,
+            event_values: None,
+        };
+        let items = vec![
+            ReadRangeItem::EventLog(EventLogRecord::new(
+                date_time(0),
+                EventLogDatum::Notification(notification),
+            )),
+            ReadRangeItem::EventLog(EventLogRecord::new(
+                date_time(1),
+                EventLogDatum::Status(LogStatusBits::new(0b0010_0000)),
+            )),
+        ];
+
+        let mut buf = [0; 256];
+        let decoded = round_trip(
+            ObjectId::new(ObjectType::ObjectEventLog, 1),
+            PropertyId::PropLogBuffer,
+            items,
+            &mut buf,
+        );
+        let items = decoded.item_data.items;
+        assert_eq!(items.len(), 2);
+        let notification = match &items[0] {
+            ReadRangeItem::EventLog(EventLogRecord {
+                log_datum: EventLogDatum::Notification(x),
+                ..
+            }) => x,
+            x => panic!("unexpected item {:?}", x),
+        };
+        assert_eq!(notification.event_object_id.id, 3);
+        assert_eq!(
+            notification.message_text.as_ref().unwrap().inner,
+            "Chiller supply temperature high"
+        );
+        assert!(matches!(notification.to_state, EventState::HighLimit));
+        assert!(matches!(
+            &items[1],
+            ReadRangeItem::EventLog(EventLogRecord {
+                log_datum: EventLogDatum::Status(x),
+                ..
+            }) if x.log_interrupted()
+        ));
+    }
+
+    #[test]
+    fn reversable_list() {
+        use alloc::vec;
+
+        let device_id = ObjectId::new(ObjectType::ObjectDevice, 79079);
+        let address = Address::new(0, OctetString::new(&[192, 168, 1, 249, 0xBA, 0xC0]));
+        let destination = Destination {
+            valid_days: 0b1111_1000,
+            from_time: Time {
+                hour: 6,
+                minute: 0,
+                second: 0,
+                hundredths: 0,
+            },
+            to_time: Time {
+                hour: 18,
+                minute: 0,
+                second: 0,
+                hundredths: 0,
+            },
+            recipient: Recipient::Address(address.clone()),
+            process_id: 3,
+            issue_confirmed_notifications: true,
+            transitions: EventTransitionBits::new(0b1110_0000),
+        };
+        let subscription = CovSubscription {
+            recipient: RecipientProcess::new(Recipient::Device(device_id), 7),
+            object_id: ObjectId::new(ObjectType::ObjectAnalogInput, 3),
+            property_id: PropertyId::PropPresentValue,
+            array_index: None,
+            issue_confirmed_notifications: false,
+            time_remaining_seconds: 300,
+            cov_increment: Some(0.5),
+        };
+
+        // address binding
+        let items = vec![ReadRangeItem::AddressBinding(AddressBinding::new(
+            device_id, address,
+        ))];
+        let mut buf = [0; 128];
+        let decoded = round_trip(
+            device_id,
+            PropertyId::PropDeviceAddressBinding,
+            items,
+            &mut buf,
+        );
+        assert!(matches!(
+            &decoded.item_data.items[0],
+            ReadRangeItem::AddressBinding(x)
+                if x.device_id == device_id && x.address.mac_address.inner == [192, 168, 1, 249, 0xBA, 0xC0]
+        ));
+
+        // destination
+        let notification_class = ObjectId::new(ObjectType::ObjectNotificationClass, 1);
+        let items = vec![ReadRangeItem::Destination(destination)];
+        let mut buf = [0; 128];
+        let decoded = round_trip(
+            notification_class,
+            PropertyId::PropRecipientList,
+            items,
+            &mut buf,
+        );
+        let destination = match &decoded.item_data.items[0] {
+            ReadRangeItem::Destination(x) => x,
+            x => panic!("unexpected item {:?}", x),
+        };
+        assert_eq!(destination.valid_days, 0b1111_1000);
+        assert_eq!(destination.to_time.hour, 18);
+        assert!(matches!(&destination.recipient, Recipient::Address(x) if x.network_number == 0));
+        assert_eq!(destination.process_id, 3);
+        assert!(destination.issue_confirmed_notifications);
+        assert!(destination.transitions.to_normal());
+
+        // cov subscription
+        let items = vec![ReadRangeItem::CovSubscription(subscription)];
+        let mut buf = [0; 128];
+        let decoded = round_trip(
+            device_id,
+            PropertyId::PropActiveCovSubscriptions,
+            items,
+            &mut buf,
+        );
+        let subscription = match &decoded.item_data.items[0] {
+            ReadRangeItem::CovSubscription(x) => x,
+            x => panic!("unexpected item {:?}", x),
+        };
+        assert!(matches!(subscription.recipient.recipient, Recipient::Device(x) if x == device_id));
+        assert_eq!(subscription.recipient.process_id, 7);
+        assert_eq!(subscription.object_id.id, 3);
+        assert_eq!(subscription.property_id, PropertyId::PropPresentValue);
+        assert_eq!(subscription.time_remaining_seconds, 300);
+        assert_eq!(subscription.cov_increment, Some(0.5));
+
+        // application tagged values
+        let items = vec![
+            ReadRangeItem::Value(ApplicationDataValue::ObjectId(device_id)),
+            ReadRangeItem::Value(ApplicationDataValue::ObjectId(notification_class)),
+        ];
+        let mut buf = [0; 128];
+        let decoded = round_trip(device_id, PropertyId::PropObjectList, items, &mut buf);
+        assert_eq!(decoded.item_data.items.len(), 2);
+        assert!(matches!(
+            &decoded.item_data.items[1],
+            ReadRangeItem::Value(ApplicationDataValue::ObjectId(x)) if *x == notification_class
+        ));
+
+        // constructed items of other lists (two BACnetDeviceObjectPropertyReferences)
+        let raw = [
+            0x0C, 0x00, 0x00, 0x00, 0x01, 0x19, 0x55, // analog input 1 present value
+            0x0C, 0x00, 0x00, 0x00, 0x02, 0x19, 0x55, // analog input 2 present value
+        ];
+        let items = vec![ReadRangeItem::Any(OctetString::new(&raw))];
+        let mut buf = [0; 128];
+        let group = ObjectId::new(ObjectType::ObjectGlobalGroup, 1);
+        let decoded = round_trip(group, PropertyId::PropGroupMembers, items, &mut buf);
+        assert_eq!(decoded.item_data.items.len(), 1);
+        assert!(matches!(
+            &decoded.item_data.items[0],
+            ReadRangeItem::Any(x) if x.as_slice() == raw
+        ));
+    }
+}
+
+#[cfg(all(test, not(feature = "alloc")))]
+mod no_alloc_tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ComplexAck, ComplexAckService},
+            primitives::data_value::{Date, DateTime, Time},
+        },
+        common::{
+            helper::assert_encodes_to,
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::LogStatusBits,
+        },
+    };
+
+    use super::{
+        EventLogDatum, EventLogRecord, LogMultipleData, LogMultipleRecord, LogMultipleValue,
+        LogMultipleValues, ReadRangeItem, ReadRangeItems, ReadRangeValue,
+    };
+
+    // a trend log read by position (two records)
+    const TREND_LOG_ACK: [u8; 61] = [
+        0x30, 0x01, 0x1A, // complex ack, invoke id 1, ReadRange
+        0x0C, 0x05, 0x00, 0x00, 0x01, // trend log 1
+        0x19, 0x83, // Log_Buffer
+        0x3A, 0x05, 0xC0, // result flags: first item and last item
+        0x49, 0x02, // item count
+        0x5E, // item data
+        0x0E, 0xA4, 0x62, 0x03, 0x17, 0x01, 0xB4, 0x13, 0x36, 0x1B, 0x00,
+        0x0F, // 1998-03-23 19:54:27
+        0x1E, 0x2C, 0x41, 0x90, 0x00, 0x00, 0x1F, // real 18.0
+        0x2A, 0x04, 0x00, // status flags
+        0x0E, 0xA4, 0x62, 0x03, 0x17, 0x01, 0xB4, 0x13, 0x38, 0x1B, 0x00,
+        0x0F, // 1998-03-23 19:56:27
+        0x1E, 0x2C, 0x41, 0x90, 0xCC, 0xCD, 0x1F, // real 18.1
+        0x2A, 0x04, 0x80, // status flags: in alarm
+        0x5F,
+    ];
+
+    #[test]
+    fn decode_log_records() {
+        let buf = &TREND_LOG_ACK;
+        let mut reader = Reader::new_with_len(buf.len());
+        reader.index = 1;
+        let decoded = ComplexAck::decode(&mut reader, buf).unwrap();
+        assert!(reader.eof());
+        assert_encodes_to(buf, |writer| decoded.encode(writer));
+        let ack = match decoded.service {
+            ComplexAckService::ReadRange(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        assert_eq!(ack.object_id, ObjectId::new(ObjectType::ObjectTrendlog, 1));
+        assert_eq!(ack.item_count, 2);
+
+        let mut values = [0.0; 2];
+        let mut count = 0;
+        for item in &ack.item_data {
+            match item.unwrap() {
+                ReadRangeItem::Log(x) => match x.value {
+                    ReadRangeValue::Real(value) => values[count] = value,
+                    x => panic!("unexpected value {:?}", x),
+                },
+                x => panic!("unexpected item {:?}", x),
+            }
+            count += 1;
+        }
+        assert_eq!(count, 2);
+        assert_eq!(values, [18.0, 18.1]);
+    }
+
+    #[test]
+    fn decode_log_multiple_records() {
+        let buf = [
+            0x0E, 0xA4, 0x7C, 0x03, 0x0E, 0x04, 0xB4, 0x0A, 0x0F, 0x00, 0x00,
+            0x0F, // 2024-03-14 10:15:00
+            0x1E, 0x1E, // log data
+            0x1C, 0x41, 0xAC, 0x00, 0x00, // real 21.5
+            0x3A, 0x01, 0x2C, // unsigned 300
+            0x1F, 0x1F,
+        ];
+        let object_id = ObjectId::new(ObjectType::ObjectTrendLogMultiple, 1);
+        let items = ReadRangeItems::decode(object_id, PropertyId::PropLogBuffer, &buf).unwrap();
+
+        let mut iter = (&items).into_iter();
+        let record = match iter.next().unwrap().unwrap() {
+            ReadRangeItem::LogMultiple(x) => x,
+            x => panic!("unexpected item {:?}", x),
+        };
+        assert!(iter.next().is_none());
+        let values = match &record.log_data {
+            LogMultipleData::Values(x) => x,
+            x => panic!("unexpected log data {:?}", x),
+        };
+        let mut values = values.into_iter();
+        assert!(matches!(values.next(), Some(Ok(LogMultipleValue::Real(x))) if x == 21.5));
+        assert!(matches!(
+            values.next(),
+            Some(Ok(LogMultipleValue::Unsigned(300)))
+        ));
+        assert!(values.next().is_none());
+
+        assert_encodes_to(&buf, |writer| record.encode(writer));
+    }
+
+    #[test]
+    fn reversable_log_multiple() {
+        let values = [LogMultipleValue::Real(21.5), LogMultipleValue::Null];
+        let values = LogMultipleValues::new(&values);
+        assert_eq!((&values).into_iter().count(), 2);
+        let record = LogMultipleRecord {
+            timestamp: DateTime::new(
+                Date {
+                    year: 2024,
+                    month: 3,
+                    day: 14,
+                    wday: 4,
+                },
+                Time {
+                    hour: 10,
+                    minute: 15,
+                    second: 0,
+                    hundredths: 0,
+                },
+            ),
+            log_data: LogMultipleData::Values(values),
+        };
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        record.encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        let decoded = LogMultipleRecord::decode(&mut reader, &buf[..len]).unwrap();
+        let values = match &decoded.log_data {
+            LogMultipleData::Values(x) => x,
+            x => panic!("unexpected log data {:?}", x),
+        };
+        let mut values = values.into_iter();
+        assert!(matches!(values.next(), Some(Ok(LogMultipleValue::Real(x))) if x == 21.5));
+        assert!(matches!(values.next(), Some(Ok(LogMultipleValue::Null))));
+        assert!(values.next().is_none());
+
+        assert_encodes_to(&buf[..len], |writer| decoded.encode(writer));
+    }
+
+    #[test]
+    fn reversable_event_log_records() {
+        let timestamp = DateTime::new(
+            Date {
+                year: 2024,
+                month: 3,
+                day: 14,
+                wday: 4,
+            },
+            Time {
+                hour: 10,
+                minute: 15,
+                second: 0,
+                hundredths: 0,
+            },
+        );
+        let records = [
+            ReadRangeItem::EventLog(EventLogRecord {
+                timestamp: timestamp.clone(),
+                log_datum: EventLogDatum::Status(LogStatusBits::new(0b1000_0000)),
+            }),
+            ReadRangeItem::EventLog(EventLogRecord {
+                timestamp,
+                log_datum: EventLogDatum::TimeChange(-1.0),
+            }),
+        ];
+        let object_id = ObjectId::new(ObjectType::ObjectEventLog, 1);
+        let items = ReadRangeItems::new(object_id, PropertyId::PropLogBuffer, &records);
+        assert_eq!((&items).into_iter().count(), 2);
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        items.encode(&mut writer);
+        let len = writer.index;
+
+        // the items are decoded as event log records because of the object they were read from
+        let decoded =
+            ReadRangeItems::decode(object_id, PropertyId::PropLogBuffer, &buf[..len]).unwrap();
+        let mut iter = (&decoded).into_iter();
+        assert!(matches!(
+            iter.next(),
+            Some(Ok(ReadRangeItem::EventLog(EventLogRecord {
+                log_datum: EventLogDatum::Status(x),
+                ..
+            }))) if x.log_disabled()
+        ));
+        assert!(matches!(
+            iter.next(),
+            Some(Ok(ReadRangeItem::EventLog(EventLogRecord {
+                log_datum: EventLogDatum::TimeChange(x),
+                ..
+            }))) if x == -1.0
+        ));
+        assert!(iter.next().is_none());
+
+        assert_encodes_to(&buf[..len], |writer| decoded.encode(writer));
+    }
+}
@@ -0,0 +1,102 @@
+// reinitialize device (restarts a device or drives the backup and restore procedure)
+
+use crate::{
+    application_protocol::primitives::data_value::CharacterString,
+    common::{
+        error::Error,
+        helper::{decode_unsigned, encode_context_character_string, encode_context_unsigned},
+        io::{Reader, Writer},
+        spec::ReinitializedState,
+        tag::{Tag, TagNumber},
+    },
+};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReinitializeDevice<'a> {
+    pub state: ReinitializedState,
+    pub password: Option<CharacterString<'a>>,
+}
+
+impl<'a> ReinitializeDevice<'a> {
+    const TAG_STATE: u8 = 0;
+    const TAG_PASSWORD: u8 = 1;
+
+    pub fn new(state: ReinitializedState, password: Option<CharacterString<'a>>) -> Self {
+        Self { state, password }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        // reinitialized state of device
+        encode_context_unsigned(writer, Self::TAG_STATE, self.state as u32);
+
+        // password
+        if let Some(password) = &self.password {
+            encode_context_character_string(writer, Self::TAG_PASSWORD, password.as_str());
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // reinitialized state of device
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_STATE),
+            "ReinitializeDevice decode state",
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let state = ReinitializedState::try_from(value)
+            .map_err(|x| Error::InvalidVariant(("ReinitializeDevice decode state", x)))?;
+
+        // password
+        let mut password = None;
+        if !reader.eof() {
+            let tag = Tag::decode_expected(
+                reader,
+                buf,
+                TagNumber::ContextSpecific(Self::TAG_PASSWORD),
+                "ReinitializeDevice decode password",
+            )?;
+            password = Some(CharacterString::decode(tag.value, reader, buf)?);
+        }
+
+        Ok(Self { state, password })
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::confirmed::{ConfirmedRequest, ConfirmedRequestService},
+        common::{
+            io::{Reader, Writer},
+            spec::ReinitializedState,
+        },
+    };
+
+    use super::ReinitializeDevice;
+
+    #[test]
+    fn reversable() {
+        let request = ReinitializeDevice::new(ReinitializedState::StartBackup, None);
+
+        // encoding
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        let service = ConfirmedRequestService::ReinitializeDevice(request);
+        ConfirmedRequest::new(1, service).encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ConfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        let decoded = match decoded.service {
+            ConfirmedRequestService::ReinitializeDevice(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        assert_eq!(decoded.state, ReinitializedState::StartBackup);
+        assert!(decoded.password.is_none());
+    }
+}
@@ -0,0 +1,207 @@
+// text message (sends a text message to an operator or another device)
+
+use crate::{
+    application_protocol::primitives::data_value::CharacterString,
+    common::{
+        error::Error,
+        helper::{
+            decode_context_object_id, decode_unsigned, encode_closing_tag,
+            encode_context_character_string, encode_context_object_id, encode_context_unsigned,
+            encode_enumerated, encode_opening_tag, get_tagged_body_for_tag, peek_tag,
+        },
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        spec::TextMessagePriority,
+        tag::{Tag, TagNumber},
+    },
+};
+
+// classifies the message so that the receiver can decide how to handle it
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MessageClass<'a> {
+    Numeric(u32),
+    Text(CharacterString<'a>),
+}
+
+impl<'a> MessageClass<'a> {
+    const TAG_NUMERIC: u8 = 0;
+    const TAG_TEXT: u8 = 1;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Numeric(x) => encode_context_unsigned(writer, Self::TAG_NUMERIC, *x),
+            Self::Text(x) => encode_context_character_string(writer, Self::TAG_TEXT, x.as_str()),
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let tag = Tag::decode(reader, buf)?;
+        match tag.number {
+            TagNumber::ContextSpecific(Self::TAG_NUMERIC) => {
+                let value = decode_unsigned(tag.value, reader, buf)? as u32;
+                Ok(Self::Numeric(value))
+            }
+            TagNumber::ContextSpecific(Self::TAG_TEXT) => {
+                let value = CharacterString::decode(tag.value, reader, buf)?;
+                Ok(Self::Text(value))
+            }
+            number => Err(Error::TagNotSupported(("MessageClass decode", number))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TextMessage<'a> {
+    pub source_device: ObjectId,
+    pub message_class: Option<MessageClass<'a>>,
+    pub priority: TextMessagePriority,
+    pub message: CharacterString<'a>,
+}
+
+impl<'a> TextMessage<'a> {
+    const TAG_SOURCE_DEVICE: u8 = 0;
+    const TAG_MESSAGE_CLASS: u8 = 1;
+    const TAG_PRIORITY: u8 = 2;
+    const TAG_MESSAGE: u8 = 3;
+
+    pub fn new(
+        source_device: ObjectId,
+        message_class: Option<MessageClass<'a>>,
+        priority: TextMessagePriority,
+        message: CharacterString<'a>,
+    ) -> Self {
+        Self {
+            source_device,
+            message_class,
+            priority,
+            message,
+        }
+    }
+
+    // the service choice is not encoded here because this request can be sent
+    // as either a confirmed or an unconfirmed request
+    pub fn encode(&self, writer: &mut Writer) {
+        // source device
+        encode_context_object_id(writer, Self::TAG_SOURCE_DEVICE, &self.source_device);
+
+        // message class
+        if let Some(message_class) = &self.message_class {
+            encode_opening_tag(writer, Self::TAG_MESSAGE_CLASS);
+            message_class.encode(writer);
+            encode_closing_tag(writer, Self::TAG_MESSAGE_CLASS);
+        }
+
+        // message priority
+        encode_enumerated(writer, self.priority as u32, Some(Self::TAG_PRIORITY));
+
+        // message
+        encode_context_character_string(writer, Self::TAG_MESSAGE, self.message.as_str());
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // source device
+        let source_device = decode_context_object_id(
+            reader,
+            buf,
+            Self::TAG_SOURCE_DEVICE,
+            "TextMessage decode source_device",
+        )?;
+
+        // message class
+        let mut message_class = None;
+        if peek_tag(reader, buf)?.number
+            == TagNumber::ContextSpecificOpening(Self::TAG_MESSAGE_CLASS)
+        {
+            let inner_buf = get_tagged_body_for_tag(
+                reader,
+                buf,
+                Self::TAG_MESSAGE_CLASS,
+                "TextMessage decode message_class",
+            )?;
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            message_class = Some(MessageClass::decode(&mut inner_reader, inner_buf)?);
+        }
+
+        // message priority
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_PRIORITY),
+            "TextMessage decode priority",
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let priority = TextMessagePriority::try_from(value)
+            .map_err(|x| Error::InvalidVariant(("TextMessage decode priority", x)))?;
+
+        // message
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_MESSAGE),
+            "TextMessage decode message",
+        )?;
+        let message = CharacterString::decode(tag.value, reader, buf)?;
+
+        Ok(Self {
+            source_device,
+            message_class,
+            priority,
+            message,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::{
+            primitives::data_value::CharacterString, unconfirmed::UnconfirmedRequest,
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+            spec::TextMessagePriority,
+        },
+    };
+
+    use super::{MessageClass, TextMessage};
+
+    #[test]
+    fn reversable() {
+        let request = TextMessage::new(
+            ObjectId::new(ObjectType::ObjectDevice, 1234),
+            Some(MessageClass::Text(CharacterString::new("maintenance"))),
+            TextMessagePriority::Urgent,
+            CharacterString::new("Filter change required on AHU-1"),
+        );
+
+        // encoding
+        let mut buf = [0; 128];
+        let mut writer = Writer::new(&mut buf);
+        UnconfirmedRequest::TextMessage(request).encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = UnconfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        let decoded = match decoded {
+            UnconfirmedRequest::TextMessage(x) => x,
+            x => panic!("unexpected request {:?}", x),
+        };
+        assert_eq!(
+            decoded.source_device,
+            ObjectId::new(ObjectType::ObjectDevice, 1234)
+        );
+        assert!(matches!(
+            decoded.message_class,
+            Some(MessageClass::Text(x)) if x.inner == "maintenance"
+        ));
+        assert_eq!(decoded.priority, TextMessagePriority::Urgent);
+        assert_eq!(decoded.message.inner, "Filter change required on AHU-1");
+    }
+}
@@ -1,14 +1,16 @@
 use crate::{
     application_protocol::{
-        primitives::data_value::{Date, Time},
+        primitives::data_value::{Date, DateTime, Time},
         unconfirmed::UnconfirmedServiceChoice,
     },
     common::{
-        io::Writer,
+        error::Error,
+        io::{Reader, Writer},
         tag::{ApplicationTagNumber, Tag, TagNumber},
     },
 };
 
+// the date and time are local to the receiving device
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct TimeSynchronization {
@@ -19,15 +21,118 @@ pub struct TimeSynchronization {
 impl TimeSynchronization {
     pub fn encode(&self, writer: &mut Writer) {
         writer.push(UnconfirmedServiceChoice::TimeSynchronization as u8);
+        encode_date_time(writer, &self.date, &self.time);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let DateTime { date, time } = DateTime::decode(reader, buf)?;
+        Ok(Self { date, time })
+    }
+}
+
+// the date and time are in UTC and the receiving device converts them to local time
+// using its own UTC_Offset and Daylight_Savings_Status properties
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct UtcTimeSynchronization {
+    pub date: Date,
+    pub time: Time,
+}
+
+impl UtcTimeSynchronization {
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(UnconfirmedServiceChoice::UtcTimeSynchronization as u8);
+        encode_date_time(writer, &self.date, &self.time);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let DateTime { date, time } = DateTime::decode(reader, buf)?;
+        Ok(Self { date, time })
+    }
+}
+
+fn encode_date_time(writer: &mut Writer, date: &Date, time: &Time) {
+    // date
+    let tag = Tag::new(TagNumber::Application(ApplicationTagNumber::Date), 4);
+    tag.encode(writer);
+    date.encode(writer);
+
+    // time
+    let tag = Tag::new(TagNumber::Application(ApplicationTagNumber::Time), 4);
+    tag.encode(writer);
+    time.encode(writer);
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::{
+            primitives::data_value::{Date, Time},
+            unconfirmed::UnconfirmedRequest,
+        },
+        common::io::{Reader, Writer},
+    };
+
+    use super::UtcTimeSynchronization;
+
+    #[test]
+    fn reversable() {
+        let date = Date {
+            year: 2024,
+            month: 3,
+            day: 31,
+            wday: 7,
+        };
+        let time = Time {
+            hour: 1,
+            minute: 59,
+            second: 30,
+            hundredths: 0,
+        };
+        let request = UtcTimeSynchronization { date, time };
+
+        // encoding
+        let mut buf = [0; 32];
+        let mut writer = Writer::new(&mut buf);
+        UnconfirmedRequest::UtcTimeSynchronization(request).encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = UnconfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        let decoded = match decoded {
+            UnconfirmedRequest::UtcTimeSynchronization(x) => x,
+            x => panic!("unexpected request {:?}", x),
+        };
+        assert_eq!(decoded.date.year, 2024);
+        assert_eq!(decoded.date.day, 31);
+        assert_eq!(decoded.time.minute, 59);
+        assert_eq!(decoded.time.second, 30);
+    }
 
-        // date
-        let tag = Tag::new(TagNumber::Application(ApplicationTagNumber::Date), 4);
-        tag.encode(writer);
-        self.date.encode(writer);
+    #[test]
+    fn decode_time_synchronization() {
+        // unconfirmed request, TimeSynchronization, application date and time
+        let buf = [
+            0x10, 0x06, 0xA4, 0x7C, 0x03, 0x1F, 0x07, 0xB4, 0x01, 0x3B, 0x1E, 0x00,
+        ];
 
-        // time
-        let tag = Tag::new(TagNumber::Application(ApplicationTagNumber::Time), 4);
-        tag.encode(writer);
-        self.time.encode(writer);
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(buf.len());
+        reader.index = 1;
+        let decoded = UnconfirmedRequest::decode(&mut reader, &buf).unwrap();
+        let decoded = match decoded {
+            UnconfirmedRequest::TimeSynchronization(x) => x,
+            x => panic!("unexpected request {:?}", x),
+        };
+        assert_eq!(decoded.date.year, 2024);
+        assert_eq!(decoded.date.month, 3);
+        assert_eq!(decoded.date.day, 31);
+        assert_eq!(decoded.date.wday, 7);
+        assert_eq!(decoded.time.hour, 1);
+        assert_eq!(decoded.time.minute, 59);
+        assert_eq!(decoded.time.second, 30);
+        assert!(reader.eof());
     }
 }
@@ -0,0 +1,476 @@
+// virtual terminal (vt-open, vt-data and vt-close)
+// opens a character based terminal session on a remote device (e.g. the operator interface of an older controller)
+
+#[cfg(feature = "alloc")]
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
+
+#[cfg(not(feature = "alloc"))]
+use crate::common::helper::encode_list;
+
+use crate::{
+    application_protocol::{
+        confirmed::{ComplexAck, ComplexAckService, ConfirmedServiceChoice},
+        primitives::data_value::OctetString,
+    },
+    common::{
+        error::Error,
+        helper::{
+            decode_context_bool, decode_unsigned, encode_application_enumerated,
+            encode_application_unsigned, encode_context_bool, encode_context_unsigned, ListIter,
+        },
+        io::{Reader, Writer},
+        spec::VtClass,
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+    network_protocol::data_link::DataLink,
+};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct VtOpen {
+    pub vt_class: VtClass,
+    pub local_session_id: u8, // the id the remote device uses when it sends data back to us
+}
+
+impl VtOpen {
+    pub fn new(vt_class: VtClass, local_session_id: u8) -> Self {
+        Self {
+            vt_class,
+            local_session_id,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_application_enumerated(writer, self.vt_class as u32);
+        encode_application_unsigned(writer, self.local_session_id);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::Enumerated),
+            "VtOpen decode vt_class",
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let vt_class = VtClass::try_from(value)
+            .map_err(|x| Error::InvalidVariant(("VtOpen decode vt_class", x)))?;
+
+        let local_session_id = decode_session_id(reader, buf, "VtOpen decode local_session_id")?;
+
+        Ok(Self {
+            vt_class,
+            local_session_id,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct VtOpenAck {
+    pub remote_session_id: u8, // the id we use when we send data to the remote device
+}
+
+impl VtOpenAck {
+    pub fn new(remote_session_id: u8) -> Self {
+        Self { remote_session_id }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(ConfirmedServiceChoice::VtOpen as u8);
+        encode_application_unsigned(writer, self.remote_session_id);
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let remote_session_id =
+            decode_session_id(reader, buf, "VtOpenAck decode remote_session_id")?;
+        Ok(Self { remote_session_id })
+    }
+}
+
+impl<'a> TryFrom<DataLink<'a>> for VtOpenAck {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        let ack: ComplexAck = value.try_into()?;
+        match ack.service {
+            ComplexAckService::VtOpen(ack) => Ok(ack),
+            _ => Err(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService VtOpenAck",
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct VtData<'a> {
+    pub session_id: u8, // the session id of the receiving device
+    pub data: OctetString<'a>,
+    pub data_flag: bool, // set when the data completes a message that the receiver should act on as a whole
+}
+
+impl<'a> VtData<'a> {
+    pub fn new(session_id: u8, data: OctetString<'a>, data_flag: bool) -> Self {
+        Self {
+            session_id,
+            data,
+            data_flag,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_application_unsigned(writer, self.session_id);
+        Tag::new(
+            TagNumber::Application(ApplicationTagNumber::OctetString),
+            self.data.inner.len() as u32,
+        )
+        .encode(writer);
+        writer.extend_from_slice(self.data.as_slice());
+        encode_application_unsigned(writer, self.data_flag as u8);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let session_id = decode_session_id(reader, buf, "VtData decode session_id")?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::OctetString),
+            "VtData decode data",
+        )?;
+        let data = OctetString::decode(tag.value, reader, buf)?;
+
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::Application(ApplicationTagNumber::UnsignedInt),
+            "VtData decode data_flag",
+        )?;
+        let data_flag = decode_unsigned(tag.value, reader, buf)? > 0;
+
+        Ok(Self {
+            session_id,
+            data,
+            data_flag,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct VtDataAck {
+    pub all_new_data_accepted: bool,
+    pub accepted_octet_count: Option<u32>, // only present when some of the data was not accepted
+}
+
+impl VtDataAck {
+    const TAG_ALL_NEW_DATA_ACCEPTED: u8 = 0;
+    const TAG_ACCEPTED_OCTET_COUNT: u8 = 1;
+
+    pub fn new(all_new_data_accepted: bool, accepted_octet_count: Option<u32>) -> Self {
+        Self {
+            all_new_data_accepted,
+            accepted_octet_count,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        writer.push(ConfirmedServiceChoice::VtData as u8);
+        encode_context_bool(
+            writer,
+            Self::TAG_ALL_NEW_DATA_ACCEPTED,
+            self.all_new_data_accepted,
+        );
+        if let Some(count) = self.accepted_octet_count {
+            encode_context_unsigned(writer, Self::TAG_ACCEPTED_OCTET_COUNT, count);
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let all_new_data_accepted = decode_context_bool(
+            reader,
+            buf,
+            Self::TAG_ALL_NEW_DATA_ACCEPTED,
+            "VtDataAck decode all_new_data_accepted",
+        )?;
+
+        let mut accepted_octet_count = None;
+        if !reader.eof() {
+            let tag = Tag::decode_expected(
+                reader,
+                buf,
+                TagNumber::ContextSpecific(Self::TAG_ACCEPTED_OCTET_COUNT),
+                "VtDataAck decode accepted_octet_count",
+            )?;
+            accepted_octet_count = Some(decode_unsigned(tag.value, reader, buf)? as u32);
+        }
+
+        Ok(Self {
+            all_new_data_accepted,
+            accepted_octet_count,
+        })
+    }
+}
+
+impl<'a> TryFrom<DataLink<'a>> for VtDataAck {
+    type Error = Error;
+
+    fn try_from(value: DataLink<'a>) -> Result<Self, Self::Error> {
+        let ack: ComplexAck = value.try_into()?;
+        match ack.service {
+            ComplexAckService::VtData(ack) => Ok(ack),
+            _ => Err(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService VtDataAck",
+            )),
+        }
+    }
+}
+
+// closes one or more sessions (the ids are the session ids of the remote device)
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct VtClose<'a> {
+    pub remote_session_ids: &'a [u8],
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct VtClose<'a> {
+    pub remote_session_ids: Vec<u8>,
+    _phantom: &'a Phantom,
+}
+
+impl<'a> VtClose<'a> {
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(remote_session_ids: &'a [u8]) -> Self {
+        Self {
+            remote_session_ids,
+            buf: &[],
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(remote_session_ids: Vec<u8>) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            remote_session_ids,
+            _phantom: &PHANTOM,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        #[cfg(not(feature = "alloc"))]
+        encode_list(writer, self.buf, self.remote_session_ids, encode_session_id);
+        #[cfg(feature = "alloc")]
+        for session_id in self.remote_session_ids.iter() {
+            encode_session_id(session_id, writer);
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        #[cfg(not(feature = "alloc"))]
+        {
+            let buf = &buf[reader.index..reader.end];
+            reader.index = reader.end;
+            Ok(Self {
+                remote_session_ids: &[],
+                buf,
+            })
+        }
+
+        #[cfg(feature = "alloc")]
+        {
+            let mut remote_session_ids = Vec::new();
+            while !reader.eof() {
+                remote_session_ids.push(decode_session_id(
+                    reader,
+                    buf,
+                    "VtClose decode remote_session_id",
+                )?);
+            }
+            Ok(Self::new(remote_session_ids))
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ VtClose<'a> {
+    type Item = Result<u8, Error>;
+
+    type IntoIter = VtSessionIdIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        VtSessionIdIter {
+            list: ListIter::new(self.remote_session_ids, self.buf),
+        }
+    }
+}
+
+pub struct VtSessionIdIter<'a> {
+    list: ListIter<'a, u8>,
+}
+
+impl<'a> Iterator for VtSessionIdIter<'a> {
+    type Item = Result<u8, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.next_with(|reader, buf| {
+            decode_session_id(reader, buf, "VtClose decode remote_session_id")
+        })
+    }
+}
+
+fn encode_session_id(session_id: &u8, writer: &mut Writer) {
+    encode_application_unsigned(writer, *session_id);
+}
+
+fn decode_session_id(reader: &mut Reader, buf: &[u8], context: &'static str) -> Result<u8, Error> {
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::Application(ApplicationTagNumber::UnsignedInt),
+        context,
+    )?;
+    let value = decode_unsigned(tag.value, reader, buf)?;
+    if value > u8::MAX as u64 {
+        return Err(Error::InvalidValue("vt session id out of range"));
+    }
+    Ok(value as u8)
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::{
+            confirmed::{ComplexAck, ComplexAckService, ConfirmedRequest, ConfirmedRequestService},
+            primitives::data_value::OctetString,
+        },
+        common::{
+            io::{Reader, Writer},
+            spec::VtClass,
+        },
+    };
+
+    use super::{VtClose, VtData, VtDataAck, VtOpen, VtOpenAck};
+
+    fn encode_decode_request(service: ConfirmedRequestService) -> ConfirmedRequestService {
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        ConfirmedRequest::new(1, service).encode(&mut writer);
+        let len = writer.index;
+
+        // skip the pdu type byte
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        ConfirmedRequest::decode(&mut reader, &buf[..len])
+            .unwrap()
+            .service
+    }
+
+    fn encode_decode_ack(service: ComplexAckService) -> ComplexAckService {
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        ComplexAck {
+            invoke_id: 1,
+            service,
+        }
+        .encode(&mut writer);
+        let len = writer.index;
+
+        // skip the pdu type byte
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        ComplexAck::decode(&mut reader, &buf[..len])
+            .unwrap()
+            .service
+    }
+
+    #[test]
+    fn reversable() {
+        use alloc::vec;
+
+        let request = VtOpen::new(VtClass::DecVt100, 5);
+        match encode_decode_request(ConfirmedRequestService::VtOpen(request)) {
+            ConfirmedRequestService::VtOpen(x) => {
+                assert_eq!(x.vt_class, VtClass::DecVt100);
+                assert_eq!(x.local_session_id, 5);
+            }
+            x => panic!("unexpected service {:?}", x),
+        }
+
+        match encode_decode_ack(ComplexAckService::VtOpen(VtOpenAck::new(29))) {
+            ComplexAckService::VtOpen(x) => assert_eq!(x.remote_session_id, 29),
+            x => panic!("unexpected service {:?}", x),
+        }
+
+        let request = VtData::new(29, OctetString::new(b"login\r\n"), true);
+        match encode_decode_request(ConfirmedRequestService::VtData(request)) {
+            ConfirmedRequestService::VtData(x) => {
+                assert_eq!(x.session_id, 29);
+                assert_eq!(x.data.inner, b"login\r\n");
+                assert!(x.data_flag);
+            }
+            x => panic!("unexpected service {:?}", x),
+        }
+
+        match encode_decode_ack(ComplexAckService::VtData(VtDataAck::new(false, Some(3)))) {
+            ComplexAckService::VtData(x) => {
+                assert!(!x.all_new_data_accepted);
+                assert_eq!(x.accepted_octet_count, Some(3));
+            }
+            x => panic!("unexpected service {:?}", x),
+        }
+
+        let request = VtClose::new(vec![29, 30]);
+        match encode_decode_request(ConfirmedRequestService::VtClose(request)) {
+            ConfirmedRequestService::VtClose(x) => assert_eq!(x.remote_session_ids, [29, 30]),
+            x => panic!("unexpected service {:?}", x),
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "alloc")))]
+mod no_alloc_tests {
+    use crate::{
+        application_protocol::confirmed::{ConfirmedRequest, ConfirmedRequestService},
+        common::{
+            helper::assert_encodes_to,
+            io::{Reader, Writer},
+        },
+    };
+
+    use super::VtClose;
+
+    #[test]
+    fn vt_close() {
+        let session_ids = [29, 30];
+        let request = VtClose::new(&session_ids);
+        assert_eq!((&request).into_iter().count(), 2);
+
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        ConfirmedRequest::new(1, ConfirmedRequestService::VtClose(request)).encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = ConfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        let request = match &decoded.service {
+            ConfirmedRequestService::VtClose(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        let mut session_ids = request.into_iter();
+        assert_eq!(session_ids.next().unwrap().unwrap(), 29);
+        assert_eq!(session_ids.next().unwrap().unwrap(), 30);
+        assert!(session_ids.next().is_none());
+
+        assert_encodes_to(&buf[..len], |writer| decoded.encode(writer));
+    }
+}
@@ -0,0 +1,262 @@
+// who am i and you are (addendum 2016-bz)
+// an unconfigured device asks for its device instance and mac address by identifying itself with its vendor id, model name and serial number
+
+use crate::{
+    application_protocol::primitives::data_value::{CharacterString, OctetString},
+    common::{
+        error::Error,
+        helper::{
+            decode_unsigned, encode_application_character_string, encode_application_object_id,
+            encode_application_unsigned,
+        },
+        io::{Reader, Writer},
+        object_id::ObjectId,
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WhoAmI<'a> {
+    pub vendor_id: u16,
+    pub model_name: CharacterString<'a>,
+    pub serial_number: CharacterString<'a>,
+}
+
+impl<'a> WhoAmI<'a> {
+    pub fn new(
+        vendor_id: u16,
+        model_name: CharacterString<'a>,
+        serial_number: CharacterString<'a>,
+    ) -> Self {
+        Self {
+            vendor_id,
+            model_name,
+            serial_number,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_identification(
+            writer,
+            self.vendor_id,
+            &self.model_name,
+            &self.serial_number,
+        );
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let (vendor_id, model_name, serial_number) = decode_identification(reader, buf)?;
+        Ok(Self {
+            vendor_id,
+            model_name,
+            serial_number,
+        })
+    }
+}
+
+// the device id and mac address are left out to tell the device to forget its assigned identity
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct YouAre<'a> {
+    pub vendor_id: u16,
+    pub model_name: CharacterString<'a>,
+    pub serial_number: CharacterString<'a>,
+    pub device_id: Option<ObjectId>,
+    pub mac_address: Option<OctetString<'a>>,
+}
+
+impl<'a> YouAre<'a> {
+    pub fn new(
+        vendor_id: u16,
+        model_name: CharacterString<'a>,
+        serial_number: CharacterString<'a>,
+        device_id: Option<ObjectId>,
+        mac_address: Option<OctetString<'a>>,
+    ) -> Self {
+        Self {
+            vendor_id,
+            model_name,
+            serial_number,
+            device_id,
+            mac_address,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_identification(
+            writer,
+            self.vendor_id,
+            &self.model_name,
+            &self.serial_number,
+        );
+
+        // device id
+        if let Some(device_id) = &self.device_id {
+            encode_application_object_id(writer, device_id);
+        }
+
+        // mac address
+        if let Some(mac_address) = &self.mac_address {
+            Tag::new(
+                TagNumber::Application(ApplicationTagNumber::OctetString),
+                mac_address.inner.len() as u32,
+            )
+            .encode(writer);
+            writer.extend_from_slice(mac_address.as_slice());
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let (vendor_id, model_name, serial_number) = decode_identification(reader, buf)?;
+
+        // both optional fields are application tagged so the tag tells them apart
+        let mut device_id = None;
+        let mut mac_address = None;
+        while !reader.eof() {
+            let tag = Tag::decode(reader, buf)?;
+            match tag.number {
+                TagNumber::Application(ApplicationTagNumber::ObjectId) => {
+                    device_id = Some(ObjectId::decode(tag.value, reader, buf)?);
+                }
+                TagNumber::Application(ApplicationTagNumber::OctetString) => {
+                    mac_address = Some(OctetString::decode(tag.value, reader, buf)?);
+                }
+                number => return Err(Error::TagNotSupported(("YouAre decode", number))),
+            }
+        }
+
+        Ok(Self {
+            vendor_id,
+            model_name,
+            serial_number,
+            device_id,
+            mac_address,
+        })
+    }
+}
+
+fn encode_identification(
+    writer: &mut Writer,
+    vendor_id: u16,
+    model_name: &CharacterString,
+    serial_number: &CharacterString,
+) {
+    encode_application_unsigned(writer, vendor_id);
+    encode_application_character_string(writer, model_name.as_str());
+    encode_application_character_string(writer, serial_number.as_str());
+}
+
+#[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+fn decode_identification<'a>(
+    reader: &mut Reader,
+    buf: &'a [u8],
+) -> Result<(u16, CharacterString<'a>, CharacterString<'a>), Error> {
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::Application(ApplicationTagNumber::UnsignedInt),
+        "WhoAmI or YouAre decode vendor_id",
+    )?;
+    let vendor_id = decode_unsigned(tag.value, reader, buf)?;
+    if vendor_id > u16::MAX as u64 {
+        return Err(Error::InvalidValue("vendor_id out of range"));
+    }
+
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::Application(ApplicationTagNumber::CharacterString),
+        "WhoAmI or YouAre decode model_name",
+    )?;
+    let model_name = CharacterString::decode(tag.value, reader, buf)?;
+
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::Application(ApplicationTagNumber::CharacterString),
+        "WhoAmI or YouAre decode serial_number",
+    )?;
+    let serial_number = CharacterString::decode(tag.value, reader, buf)?;
+
+    Ok((vendor_id as u16, model_name, serial_number))
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::{
+            primitives::data_value::{CharacterString, OctetString},
+            unconfirmed::UnconfirmedRequest,
+        },
+        common::{
+            io::{Reader, Writer},
+            object_id::{ObjectId, ObjectType},
+        },
+    };
+
+    use super::{WhoAmI, YouAre};
+
+    #[test]
+    fn reversable_who_am_i() {
+        let request = WhoAmI::new(
+            260,
+            CharacterString::new("VAV-100"),
+            CharacterString::new("SN-000123"),
+        );
+
+        // encoding
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        UnconfirmedRequest::WhoAmI(request).encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = UnconfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        let decoded = match decoded {
+            UnconfirmedRequest::WhoAmI(x) => x,
+            x => panic!("unexpected request {:?}", x),
+        };
+        assert_eq!(decoded.vendor_id, 260);
+        assert_eq!(decoded.model_name.inner, "VAV-100");
+        assert_eq!(decoded.serial_number.inner, "SN-000123");
+    }
+
+    #[test]
+    fn reversable() {
+        let request = YouAre::new(
+            260,
+            CharacterString::new("VAV-100"),
+            CharacterString::new("SN-000123"),
+            Some(ObjectId::new(ObjectType::ObjectDevice, 10123)),
+            Some(OctetString::new(&[0x2a])),
+        );
+
+        // encoding
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        UnconfirmedRequest::YouAre(request).encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = UnconfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        let decoded = match decoded {
+            UnconfirmedRequest::YouAre(x) => x,
+            x => panic!("unexpected request {:?}", x),
+        };
+        assert_eq!(decoded.vendor_id, 260);
+        assert_eq!(decoded.model_name.inner, "VAV-100");
+        assert_eq!(decoded.serial_number.inner, "SN-000123");
+        assert_eq!(
+            decoded.device_id,
+            Some(ObjectId::new(ObjectType::ObjectDevice, 10123))
+        );
+        assert_eq!(decoded.mac_address.unwrap().inner, [0x2a]);
+    }
+}
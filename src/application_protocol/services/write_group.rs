@@ -0,0 +1,546 @@
+// write group (addendum 2010-aa)
+// writes values to the channel objects of many devices at once, usually for lighting control
+
+#[cfg(feature = "alloc")]
+use {crate::common::spooky::Phantom, alloc::vec::Vec};
+
+#[cfg(not(feature = "alloc"))]
+use crate::common::helper::encode_list;
+
+use crate::{
+    application_protocol::primitives::data_value::ApplicationDataValue,
+    common::{
+        error::Error,
+        helper::{
+            decode_context_bool, decode_context_real, decode_unsigned, encode_closing_tag,
+            encode_context_bool, encode_context_real, encode_context_unsigned, encode_enumerated,
+            encode_opening_tag, get_tagged_body_for_tag, peek_tag, ListIter,
+        },
+        io::{Reader, Writer},
+        object_id::{ObjectId, ObjectType},
+        property_id::PropertyId,
+        spec::{LightingOperation, BACNET_MAX_INSTANCE},
+        tag::{ApplicationTagNumber, Tag, TagNumber},
+    },
+};
+
+// BACnetLightingCommand
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LightingCommand {
+    pub operation: LightingOperation,
+    pub target_level: Option<f32>, // percent
+    pub ramp_rate: Option<f32>,    // percent per second
+    pub step_increment: Option<f32>,
+    pub fade_time_ms: Option<u32>,
+    pub priority: Option<u8>,
+}
+
+impl LightingCommand {
+    const TAG_OPERATION: u8 = 0;
+    const TAG_TARGET_LEVEL: u8 = 1;
+    const TAG_RAMP_RATE: u8 = 2;
+    const TAG_STEP_INCREMENT: u8 = 3;
+    const TAG_FADE_TIME: u8 = 4;
+    const TAG_PRIORITY: u8 = 5;
+
+    pub fn new(operation: LightingOperation) -> Self {
+        Self {
+            operation,
+            target_level: None,
+            ramp_rate: None,
+            step_increment: None,
+            fade_time_ms: None,
+            priority: None,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_enumerated(writer, self.operation as u32, Some(Self::TAG_OPERATION));
+        if let Some(target_level) = self.target_level {
+            encode_context_real(writer, Self::TAG_TARGET_LEVEL, target_level);
+        }
+        if let Some(ramp_rate) = self.ramp_rate {
+            encode_context_real(writer, Self::TAG_RAMP_RATE, ramp_rate);
+        }
+        if let Some(step_increment) = self.step_increment {
+            encode_context_real(writer, Self::TAG_STEP_INCREMENT, step_increment);
+        }
+        if let Some(fade_time_ms) = self.fade_time_ms {
+            encode_context_unsigned(writer, Self::TAG_FADE_TIME, fade_time_ms);
+        }
+        if let Some(priority) = self.priority {
+            encode_context_unsigned(writer, Self::TAG_PRIORITY, priority);
+        }
+    }
+
+    pub fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_OPERATION),
+            "LightingCommand decode operation",
+        )?;
+        let value = decode_unsigned(tag.value, reader, buf)? as u32;
+        let operation = LightingOperation::try_from(value)
+            .map_err(|x| Error::InvalidVariant(("LightingCommand decode operation", x)))?;
+        let mut command = Self::new(operation);
+
+        while !reader.eof() {
+            match peek_tag(reader, buf)?.number {
+                TagNumber::ContextSpecific(Self::TAG_TARGET_LEVEL) => {
+                    command.target_level = Some(decode_context_real(
+                        reader,
+                        buf,
+                        Self::TAG_TARGET_LEVEL,
+                        "LightingCommand decode target_level",
+                    )?);
+                }
+                TagNumber::ContextSpecific(Self::TAG_RAMP_RATE) => {
+                    command.ramp_rate = Some(decode_context_real(
+                        reader,
+                        buf,
+                        Self::TAG_RAMP_RATE,
+                        "LightingCommand decode ramp_rate",
+                    )?);
+                }
+                TagNumber::ContextSpecific(Self::TAG_STEP_INCREMENT) => {
+                    command.step_increment = Some(decode_context_real(
+                        reader,
+                        buf,
+                        Self::TAG_STEP_INCREMENT,
+                        "LightingCommand decode step_increment",
+                    )?);
+                }
+                TagNumber::ContextSpecific(Self::TAG_FADE_TIME) => {
+                    let tag = Tag::decode(reader, buf)?;
+                    command.fade_time_ms = Some(decode_unsigned(tag.value, reader, buf)? as u32);
+                }
+                TagNumber::ContextSpecific(Self::TAG_PRIORITY) => {
+                    let tag = Tag::decode(reader, buf)?;
+                    command.priority = Some(decode_unsigned(tag.value, reader, buf)? as u8);
+                }
+                number => return Err(Error::TagNotSupported(("LightingCommand decode", number))),
+            }
+        }
+
+        Ok(command)
+    }
+}
+
+// BACnetChannelValue: any primitive value or a lighting command
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ChannelValue<'a> {
+    Null, // relinquishes the value written at the write priority
+    Value(ApplicationDataValue<'a>),
+    LightingCommand(LightingCommand),
+}
+
+impl<'a> ChannelValue<'a> {
+    const TAG_LIGHTING_COMMAND: u8 = 0;
+
+    pub fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Null => {
+                Tag::new(TagNumber::Application(ApplicationTagNumber::Null), 0).encode(writer)
+            }
+            Self::Value(x) => x.encode(writer),
+            Self::LightingCommand(x) => {
+                encode_opening_tag(writer, Self::TAG_LIGHTING_COMMAND);
+                x.encode(writer);
+                encode_closing_tag(writer, Self::TAG_LIGHTING_COMMAND);
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        match peek_tag(reader, buf)?.number {
+            TagNumber::Application(ApplicationTagNumber::Null) => {
+                Tag::decode(reader, buf)?;
+                Ok(Self::Null)
+            }
+            TagNumber::ContextSpecificOpening(Self::TAG_LIGHTING_COMMAND) => {
+                let inner_buf = get_tagged_body_for_tag(
+                    reader,
+                    buf,
+                    Self::TAG_LIGHTING_COMMAND,
+                    "ChannelValue decode lighting_command",
+                )?;
+                let mut inner_reader = Reader::new_with_len(inner_buf.len());
+                let command = LightingCommand::decode(&mut inner_reader, inner_buf)?;
+                Ok(Self::LightingCommand(command))
+            }
+            _ => {
+                // a channel value is not tied to a particular object so enumerations are left undecoded
+                let object_id = ObjectId::new(ObjectType::ObjectChannel, BACNET_MAX_INSTANCE);
+                let value = ApplicationDataValue::decode(
+                    &object_id,
+                    &PropertyId::PropPresentValue,
+                    reader,
+                    buf,
+                )?;
+                Ok(Self::Value(value))
+            }
+        }
+    }
+}
+
+// BACnetGroupChannelValue
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GroupChannelValue<'a> {
+    pub channel: u16,
+    pub overriding_priority: Option<u8>, // the write priority of the request is used if not specified
+    pub value: ChannelValue<'a>,
+}
+
+impl<'a> GroupChannelValue<'a> {
+    const TAG_CHANNEL: u8 = 0;
+    const TAG_OVERRIDING_PRIORITY: u8 = 1;
+
+    pub fn new(channel: u16, overriding_priority: Option<u8>, value: ChannelValue<'a>) -> Self {
+        Self {
+            channel,
+            overriding_priority,
+            value,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        encode_context_unsigned(writer, Self::TAG_CHANNEL, self.channel);
+        if let Some(overriding_priority) = self.overriding_priority {
+            encode_context_unsigned(writer, Self::TAG_OVERRIDING_PRIORITY, overriding_priority);
+        }
+        self.value.encode(writer);
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_CHANNEL),
+            "GroupChannelValue decode channel",
+        )?;
+        let channel = decode_unsigned(tag.value, reader, buf)? as u16;
+
+        let mut overriding_priority = None;
+        if peek_tag(reader, buf)?.number
+            == TagNumber::ContextSpecific(Self::TAG_OVERRIDING_PRIORITY)
+        {
+            let tag = Tag::decode(reader, buf)?;
+            overriding_priority = Some(decode_unsigned(tag.value, reader, buf)? as u8);
+        }
+
+        let value = ChannelValue::decode(reader, buf)?;
+
+        Ok(Self {
+            channel,
+            overriding_priority,
+            value,
+        })
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WriteGroup<'a> {
+    pub group_number: u32,
+    pub write_priority: u8,
+    pub change_list: &'a [GroupChannelValue<'a>],
+    pub inhibit_delay: Option<bool>,
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WriteGroup<'a> {
+    pub group_number: u32,
+    pub write_priority: u8,
+    pub change_list: Vec<GroupChannelValue<'a>>,
+    pub inhibit_delay: Option<bool>,
+    _phantom: &'a Phantom,
+}
+
+impl<'a> WriteGroup<'a> {
+    const TAG_GROUP_NUMBER: u8 = 0;
+    const TAG_WRITE_PRIORITY: u8 = 1;
+    const TAG_CHANGE_LIST: u8 = 2;
+    const TAG_INHIBIT_DELAY: u8 = 3;
+
+    #[cfg(not(feature = "alloc"))]
+    pub fn new(
+        group_number: u32,
+        write_priority: u8,
+        change_list: &'a [GroupChannelValue<'a>],
+        inhibit_delay: Option<bool>,
+    ) -> Self {
+        Self {
+            group_number,
+            write_priority,
+            change_list,
+            inhibit_delay,
+            buf: &[],
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn new(
+        group_number: u32,
+        write_priority: u8,
+        change_list: Vec<GroupChannelValue<'a>>,
+        inhibit_delay: Option<bool>,
+    ) -> Self {
+        use crate::common::spooky::PHANTOM;
+
+        Self {
+            group_number,
+            write_priority,
+            change_list,
+            inhibit_delay,
+            _phantom: &PHANTOM,
+        }
+    }
+
+    pub fn encode(&self, writer: &mut Writer) {
+        // group number
+        encode_context_unsigned(writer, Self::TAG_GROUP_NUMBER, self.group_number);
+
+        // write priority
+        encode_context_unsigned(writer, Self::TAG_WRITE_PRIORITY, self.write_priority);
+
+        // change list
+        encode_opening_tag(writer, Self::TAG_CHANGE_LIST);
+        #[cfg(not(feature = "alloc"))]
+        encode_list(
+            writer,
+            self.buf,
+            self.change_list,
+            GroupChannelValue::encode,
+        );
+        #[cfg(feature = "alloc")]
+        for item in self.change_list.iter() {
+            item.encode(writer);
+        }
+        encode_closing_tag(writer, Self::TAG_CHANGE_LIST);
+
+        // inhibit delay
+        if let Some(inhibit_delay) = self.inhibit_delay {
+            encode_context_bool(writer, Self::TAG_INHIBIT_DELAY, inhibit_delay);
+        }
+    }
+
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub fn decode(reader: &mut Reader, buf: &'a [u8]) -> Result<Self, Error> {
+        // group number
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_GROUP_NUMBER),
+            "WriteGroup decode group_number",
+        )?;
+        let group_number = decode_unsigned(tag.value, reader, buf)? as u32;
+
+        // write priority
+        let tag = Tag::decode_expected(
+            reader,
+            buf,
+            TagNumber::ContextSpecific(Self::TAG_WRITE_PRIORITY),
+            "WriteGroup decode write_priority",
+        )?;
+        let write_priority = decode_unsigned(tag.value, reader, buf)? as u8;
+
+        // change list
+        let inner_buf = get_tagged_body_for_tag(
+            reader,
+            buf,
+            Self::TAG_CHANGE_LIST,
+            "WriteGroup decode change_list",
+        )?;
+
+        // inhibit delay
+        let mut inhibit_delay = None;
+        if !reader.eof() {
+            inhibit_delay = Some(decode_context_bool(
+                reader,
+                buf,
+                Self::TAG_INHIBIT_DELAY,
+                "WriteGroup decode inhibit_delay",
+            )?);
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        return Ok(Self {
+            group_number,
+            write_priority,
+            change_list: &[],
+            inhibit_delay,
+            buf: inner_buf,
+        });
+
+        #[cfg(feature = "alloc")]
+        {
+            let mut inner_reader = Reader::new_with_len(inner_buf.len());
+            let mut change_list = Vec::new();
+            while !inner_reader.eof() {
+                change_list.push(GroupChannelValue::decode(&mut inner_reader, inner_buf)?);
+            }
+            Ok(Self::new(
+                group_number,
+                write_priority,
+                change_list,
+                inhibit_delay,
+            ))
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'a> IntoIterator for &'_ WriteGroup<'a> {
+    type Item = Result<GroupChannelValue<'a>, Error>;
+
+    type IntoIter = GroupChannelValueIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        GroupChannelValueIter {
+            list: ListIter::new(self.change_list, self.buf),
+        }
+    }
+}
+
+pub struct GroupChannelValueIter<'a> {
+    list: ListIter<'a, GroupChannelValue<'a>>,
+}
+
+impl<'a> Iterator for GroupChannelValueIter<'a> {
+    type Item = Result<GroupChannelValue<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.next_with(GroupChannelValue::decode)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        application_protocol::{
+            primitives::data_value::ApplicationDataValue, unconfirmed::UnconfirmedRequest,
+        },
+        common::{
+            io::{Reader, Writer},
+            spec::LightingOperation,
+        },
+    };
+
+    use super::{ChannelValue, GroupChannelValue, LightingCommand, WriteGroup};
+
+    #[test]
+    fn reversable() {
+        use alloc::vec;
+
+        let mut command = LightingCommand::new(LightingOperation::FadeTo);
+        command.target_level = Some(75.0);
+        command.fade_time_ms = Some(2000);
+        let change_list = vec![
+            GroupChannelValue::new(1, None, ChannelValue::LightingCommand(command)),
+            GroupChannelValue::new(
+                2,
+                Some(8),
+                ChannelValue::Value(ApplicationDataValue::Real(50.0)),
+            ),
+            GroupChannelValue::new(3, None, ChannelValue::Null),
+        ];
+        let request = WriteGroup::new(100, 12, change_list, Some(true));
+
+        // encoding
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        UnconfirmedRequest::WriteGroup(request).encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the pdu type byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = UnconfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        let decoded = match decoded {
+            UnconfirmedRequest::WriteGroup(x) => x,
+            x => panic!("unexpected request {:?}", x),
+        };
+        assert_eq!(decoded.group_number, 100);
+        assert_eq!(decoded.write_priority, 12);
+        assert_eq!(decoded.inhibit_delay, Some(true));
+        assert_eq!(decoded.change_list.len(), 3);
+
+        match &decoded.change_list[0].value {
+            ChannelValue::LightingCommand(x) => {
+                assert_eq!(x.operation, LightingOperation::FadeTo);
+                assert_eq!(x.target_level, Some(75.0));
+                assert_eq!(x.fade_time_ms, Some(2000));
+                assert_eq!(x.ramp_rate, None);
+            }
+            x => panic!("unexpected value {:?}", x),
+        }
+        assert_eq!(decoded.change_list[1].overriding_priority, Some(8));
+        assert!(matches!(
+            decoded.change_list[1].value,
+            ChannelValue::Value(ApplicationDataValue::Real(x)) if x == 50.0
+        ));
+        assert!(matches!(decoded.change_list[2].value, ChannelValue::Null));
+    }
+}
+
+#[cfg(all(test, not(feature = "alloc")))]
+mod no_alloc_tests {
+    use crate::{
+        application_protocol::{
+            primitives::data_value::ApplicationDataValue, unconfirmed::UnconfirmedRequest,
+        },
+        common::{
+            helper::assert_encodes_to,
+            io::{Reader, Writer},
+        },
+    };
+
+    use super::{ChannelValue, GroupChannelValue, WriteGroup};
+
+    #[test]
+    fn reversable() {
+        let change_list = [
+            GroupChannelValue::new(
+                2,
+                Some(8),
+                ChannelValue::Value(ApplicationDataValue::Real(50.0)),
+            ),
+            GroupChannelValue::new(3, None, ChannelValue::Null),
+        ];
+        let request = WriteGroup::new(100, 12, &change_list, None);
+        assert_eq!((&request).into_iter().count(), 2);
+
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        UnconfirmedRequest::WriteGroup(request).encode(&mut writer);
+        let len = writer.index;
+
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = UnconfirmedRequest::decode(&mut reader, &buf[..len]).unwrap();
+        let request = match &decoded {
+            UnconfirmedRequest::WriteGroup(x) => x,
+            x => panic!("unexpected request {:?}", x),
+        };
+        let mut change_list = request.into_iter();
+        let first = change_list.next().unwrap().unwrap();
+        assert_eq!(first.channel, 2);
+        assert_eq!(first.overriding_priority, Some(8));
+        let second = change_list.next().unwrap().unwrap();
+        assert!(matches!(second.value, ChannelValue::Null));
+        assert!(change_list.next().is_none());
+
+        assert_encodes_to(&buf[..len], |writer| decoded.encode(writer));
+    }
+}
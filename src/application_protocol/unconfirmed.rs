@@ -2,8 +2,12 @@ use crate::{
     application_protocol::{
         application_pdu::ApduType,
         services::{
-            change_of_value::CovNotification, i_am::IAm, time_synchronization::TimeSynchronization,
-            who_is::WhoIs,
+            audit_notification::AuditNotificationRequest, change_of_value::CovNotification,
+            change_of_value_multiple::CovNotificationMultiple,
+            event_notification::EventNotification, i_am::IAm, private_transfer::PrivateTransfer,
+            text_message::TextMessage, time_synchronization::TimeSynchronization,
+            time_synchronization::UtcTimeSynchronization, who_am_i::WhoAmI, who_am_i::YouAre,
+            who_is::WhoIs, write_group::WriteGroup,
         },
     },
     common::{
@@ -19,6 +23,15 @@ pub enum UnconfirmedRequest<'a> {
     IAm(IAm),
     CovNotification(CovNotification<'a>),
     TimeSynchronization(TimeSynchronization),
+    CovNotificationMultiple(CovNotificationMultiple<'a>),
+    EventNotification(EventNotification<'a>),
+    PrivateTransfer(PrivateTransfer<'a>),
+    TextMessage(TextMessage<'a>),
+    UtcTimeSynchronization(UtcTimeSynchronization),
+    WhoAmI(WhoAmI<'a>),
+    YouAre(YouAre<'a>),
+    WriteGroup(WriteGroup<'a>),
+    AuditNotification(AuditNotificationRequest<'a>),
 }
 
 impl<'a> UnconfirmedRequest<'a> {
@@ -28,8 +41,44 @@ impl<'a> UnconfirmedRequest<'a> {
         match &self {
             Self::IAm(payload) => payload.encode(writer),
             Self::WhoIs(payload) => payload.encode(writer),
-            Self::CovNotification(_) => todo!(),
+            Self::CovNotification(payload) => {
+                writer.push(UnconfirmedServiceChoice::CovNotification as u8);
+                payload.encode(writer)
+            }
             Self::TimeSynchronization(payload) => payload.encode(writer),
+            Self::CovNotificationMultiple(payload) => {
+                writer.push(UnconfirmedServiceChoice::CovNotificationMultiple as u8);
+                payload.encode(writer)
+            }
+            Self::EventNotification(payload) => {
+                writer.push(UnconfirmedServiceChoice::EventNotification as u8);
+                payload.encode(writer)
+            }
+            Self::PrivateTransfer(payload) => {
+                writer.push(UnconfirmedServiceChoice::PrivateTransfer as u8);
+                payload.encode(writer)
+            }
+            Self::TextMessage(payload) => {
+                writer.push(UnconfirmedServiceChoice::TextMessage as u8);
+                payload.encode(writer)
+            }
+            Self::UtcTimeSynchronization(payload) => payload.encode(writer),
+            Self::WhoAmI(payload) => {
+                writer.push(UnconfirmedServiceChoice::WhoAmI as u8);
+                payload.encode(writer)
+            }
+            Self::YouAre(payload) => {
+                writer.push(UnconfirmedServiceChoice::YouAre as u8);
+                payload.encode(writer)
+            }
+            Self::WriteGroup(payload) => {
+                writer.push(UnconfirmedServiceChoice::WriteGroup as u8);
+                payload.encode(writer)
+            }
+            Self::AuditNotification(payload) => {
+                writer.push(UnconfirmedServiceChoice::AuditNotification as u8);
+                payload.encode(writer)
+            }
         }
     }
 
@@ -52,6 +101,46 @@ impl<'a> UnconfirmedRequest<'a> {
                 let apdu = CovNotification::decode(reader, buf)?;
                 Ok(Self::CovNotification(apdu))
             }
+            UnconfirmedServiceChoice::CovNotificationMultiple => {
+                let apdu = CovNotificationMultiple::decode(reader, buf)?;
+                Ok(Self::CovNotificationMultiple(apdu))
+            }
+            UnconfirmedServiceChoice::EventNotification => {
+                let apdu = EventNotification::decode(reader, buf)?;
+                Ok(Self::EventNotification(apdu))
+            }
+            UnconfirmedServiceChoice::PrivateTransfer => {
+                let apdu = PrivateTransfer::decode(reader, buf)?;
+                Ok(Self::PrivateTransfer(apdu))
+            }
+            UnconfirmedServiceChoice::TextMessage => {
+                let apdu = TextMessage::decode(reader, buf)?;
+                Ok(Self::TextMessage(apdu))
+            }
+            UnconfirmedServiceChoice::TimeSynchronization => {
+                let apdu = TimeSynchronization::decode(reader, buf)?;
+                Ok(Self::TimeSynchronization(apdu))
+            }
+            UnconfirmedServiceChoice::UtcTimeSynchronization => {
+                let apdu = UtcTimeSynchronization::decode(reader, buf)?;
+                Ok(Self::UtcTimeSynchronization(apdu))
+            }
+            UnconfirmedServiceChoice::WhoAmI => {
+                let apdu = WhoAmI::decode(reader, buf)?;
+                Ok(Self::WhoAmI(apdu))
+            }
+            UnconfirmedServiceChoice::YouAre => {
+                let apdu = YouAre::decode(reader, buf)?;
+                Ok(Self::YouAre(apdu))
+            }
+            UnconfirmedServiceChoice::WriteGroup => {
+                let apdu = WriteGroup::decode(reader, buf)?;
+                Ok(Self::WriteGroup(apdu))
+            }
+            UnconfirmedServiceChoice::AuditNotification => {
+                let apdu = AuditNotificationRequest::decode(reader, buf)?;
+                Ok(Self::AuditNotification(apdu))
+            }
             x => Err(Error::Unimplemented(
                 Unimplemented::UnconfirmedServiceChoice(x),
             )),
@@ -43,6 +43,7 @@ fn get_tagged_body_internal<'a>(
     let index = reader.index;
     let mut counter = 0;
     loop {
+        let tag_index = reader.index;
         let tag = Tag::decode(reader, buf)?;
 
         // keep track of nested tags and when we reach our last closing tag then we are done
@@ -50,9 +51,8 @@ fn get_tagged_body_internal<'a>(
             TagNumber::ContextSpecificOpening(x) if x == opening_tag_number => counter += 1,
             TagNumber::ContextSpecificClosing(x) if x == opening_tag_number => {
                 if counter == 0 {
-                    //  let len = reader.index - index - 1;
-                    let end = reader.index - 1; // -1 to ignore the last closing tag
-                    return Ok(&buf[index..end]);
+                    // ignore the last closing tag (two bytes for tag numbers above 14)
+                    return Ok(&buf[index..tag_index]);
                 } else {
                     counter -= 1;
                 }
@@ -98,6 +98,119 @@ pub fn encode_context_bool(writer: &mut Writer, tag_number: u8, value: bool) {
     writer.push(item);
 }
 
+pub fn decode_context_bool(
+    reader: &mut Reader,
+    buf: &[u8],
+    expected_tag_number: u8,
+    context: &'static str,
+) -> Result<bool, Error> {
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::ContextSpecific(expected_tag_number),
+        context,
+    )?;
+    tag.expect_value(context, 1)?;
+    Ok(reader.read_byte(buf)? > 0)
+}
+
+pub fn encode_context_real(writer: &mut Writer, tag_number: u8, value: f32) {
+    let bytes = value.to_be_bytes();
+    Tag::new(TagNumber::ContextSpecific(tag_number), bytes.len() as u32).encode(writer);
+    writer.extend_from_slice(&bytes);
+}
+
+pub fn encode_context_character_string(writer: &mut Writer, tag_number: u8, value: &str) {
+    let utf8_encoded = value.as_bytes(); // strings in rust are utf8 encoded already
+    Tag::new(
+        TagNumber::ContextSpecific(tag_number),
+        utf8_encoded.len() as u32 + 1, // keep space for encoding byte
+    )
+    .encode(writer);
+    writer.push(0); // utf8 encoding
+    writer.extend_from_slice(utf8_encoded);
+}
+
+pub fn encode_application_character_string(writer: &mut Writer, value: &str) {
+    let utf8_encoded = value.as_bytes(); // strings in rust are utf8 encoded already
+    Tag::new(
+        TagNumber::Application(ApplicationTagNumber::CharacterString),
+        utf8_encoded.len() as u32 + 1, // keep space for encoding byte
+    )
+    .encode(writer);
+    writer.push(0); // utf8 encoding
+    writer.extend_from_slice(utf8_encoded);
+}
+
+pub fn decode_context_real(
+    reader: &mut Reader,
+    buf: &[u8],
+    expected_tag_number: u8,
+    context: &'static str,
+) -> Result<f32, Error> {
+    let tag = Tag::decode_expected(
+        reader,
+        buf,
+        TagNumber::ContextSpecific(expected_tag_number),
+        context,
+    )?;
+    tag.expect_value(context, 4)?;
+    Ok(f32::from_be_bytes(reader.read_bytes(buf)?))
+}
+
+// bit strings start with a byte that holds the number of unused bits in the last byte
+pub fn encode_context_bit_string(
+    writer: &mut Writer,
+    tag_number: u8,
+    unused_bits: u8,
+    bytes: &[u8],
+) {
+    Tag::new(
+        TagNumber::ContextSpecific(tag_number),
+        bytes.len() as u32 + 1,
+    )
+    .encode(writer);
+    writer.push(unused_bits);
+    writer.extend_from_slice(bytes);
+}
+
+// decodes the bits into a fixed size array, missing bytes are left as zeros and extra bytes are ignored
+pub fn decode_bit_string<const COUNT: usize>(
+    len: u32,
+    reader: &mut Reader,
+    buf: &[u8],
+) -> Result<[u8; COUNT], Error> {
+    if len == 0 {
+        return Err(Error::Length(("bit string has no unused bits byte", len)));
+    }
+
+    let _unused_bits = reader.read_byte(buf)?;
+    let slice = reader.read_slice(len as usize - 1, buf)?;
+    let mut bytes = [0; COUNT];
+    let count = slice.len().min(COUNT);
+    bytes[..count].copy_from_slice(&slice[..count]);
+    Ok(bytes)
+}
+
+// reads the next tag without advancing the reader (useful for optional fields)
+pub fn peek_tag(reader: &Reader, buf: &[u8]) -> Result<Tag, Error> {
+    let mut reader = reader.clone();
+    Tag::decode(&mut reader, buf)
+}
+
+// optional fields are identified by their context tag number (primitive or constructed)
+pub fn is_next_context_tag(reader: &Reader, buf: &[u8], tag_number: u8) -> Result<bool, Error> {
+    if reader.eof() {
+        return Ok(false);
+    }
+
+    let is_next = match peek_tag(reader, buf)?.number {
+        TagNumber::ContextSpecific(x) | TagNumber::ContextSpecificOpening(x) => x == tag_number,
+        _ => false,
+    };
+    Ok(is_next)
+}
+
 pub fn encode_opening_tag(writer: &mut Writer, tag_number: u8) {
     if tag_number <= 14 {
         let byte = 0b0001000 | (tag_number << 4) | 6;
@@ -155,9 +268,11 @@ pub fn encode_unsigned(writer: &mut Writer, context_tag: Option<u8>, value: impl
 
 pub fn encode_signed(writer: &mut Writer, context_tag: Option<u8>, value: impl Into<i64>) {
     let value = value.into();
-    let skip = match u64::try_from(value) {
-        Ok(non_negative) => count_skippable_zero_bytes(non_negative),
-        Err(_) => count_skippable_sign_bytes(value),
+    let skip = if value >= 0 {
+        // we must leave at least 1 sign bit (e.g. 128 takes 2 bytes)
+        (value.leading_zeros() as usize - 1) / 8
+    } else {
+        count_skippable_sign_bytes(value)
     };
     let data = value.to_be_bytes();
     let data = &data[skip..];
@@ -254,6 +369,65 @@ fn count_skippable_sign_bytes(data: i64) -> usize {
     }
 }
 
+// In no-alloc builds a list is either built with new() from a slice of items or left in the buffer it was decoded from
+// and decoded lazily. Both are kept so that a list encodes and iterates the same way whichever way it was made:
+// the buffer is written back as it was received followed by the items, and iteration yields the items followed by
+// those decoded from the buffer.
+pub fn encode_list<T>(
+    writer: &mut Writer,
+    buf: &[u8],
+    items: &[T],
+    encode: impl Fn(&T, &mut Writer),
+) {
+    writer.extend_from_slice(buf);
+    for item in items {
+        encode(item, writer);
+    }
+}
+
+// iterates a no-alloc list (see encode_list)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ListIter<'a, T> {
+    items: core::slice::Iter<'a, T>,
+    reader: Reader,
+    buf: &'a [u8],
+}
+
+impl<'a, T: Clone> ListIter<'a, T> {
+    pub fn new(items: &'a [T], buf: &'a [u8]) -> Self {
+        Self {
+            items: items.iter(),
+            reader: Reader::new_with_len(buf.len()),
+            buf,
+        }
+    }
+
+    pub fn next_with(
+        &mut self,
+        decode: impl FnOnce(&mut Reader, &'a [u8]) -> Result<T, Error>,
+    ) -> Option<Result<T, Error>> {
+        if let Some(item) = self.items.next() {
+            return Some(Ok(item.clone()));
+        }
+
+        if self.reader.eof() {
+            return None;
+        }
+
+        Some(decode(&mut self.reader, self.buf))
+    }
+}
+
+// checks that `encode` writes exactly `expected` (e.g. that a decoded no-alloc list is written back as it was received)
+#[cfg(all(test, not(feature = "alloc")))]
+pub fn assert_encodes_to(expected: &[u8], encode: impl FnOnce(&mut Writer)) {
+    let mut buf = [0; 512];
+    let mut writer = Writer::new(&mut buf);
+    encode(&mut writer);
+    assert_eq!(writer.to_bytes(), expected);
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -306,4 +480,49 @@ mod test {
         assert_eq!(count_skippable_sign_bytes(0x7FFF_FFFF_FFFF_FFFF_u64 as i64), 0);
         assert_eq!(count_skippable_sign_bytes(0x0000_FFFF_FFFF_FFFF_u64 as i64), 0);
     }
+
+    #[test]
+    fn test_encode_signed() {
+        for (value, len) in [
+            (0_i64, 1),
+            (127, 1),
+            (128, 2),
+            (142, 2),
+            (32767, 2),
+            (32768, 3),
+            (-1, 1),
+            (-128, 1),
+            (-129, 2),
+            (i64::MAX, 8),
+            (i64::MIN, 8),
+        ] {
+            let mut buf = [0; 16];
+            let mut writer = Writer::new(&mut buf);
+            encode_signed(&mut writer, None, value);
+            let end = writer.index;
+
+            let mut reader = Reader::new_with_len(end);
+            let tag = Tag::decode(&mut reader, &buf[..end]).unwrap();
+            assert_eq!(tag.value, len, "length of {}", value);
+            assert_eq!(
+                decode_signed(tag.value, &mut reader, &buf[..end]).unwrap(),
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_tagged_body_extended_tag_number() {
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        encode_opening_tag(&mut writer, 15);
+        encode_application_unsigned(&mut writer, 42_u32);
+        encode_closing_tag(&mut writer, 15);
+        let end = writer.index;
+
+        let mut reader = Reader::new_with_len(end);
+        let body = get_tagged_body_for_tag(&mut reader, &buf[..end], 15, "test").unwrap();
+        assert_eq!(body, [0x21, 42]);
+        assert!(reader.eof());
+    }
 }
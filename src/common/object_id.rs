@@ -101,6 +101,8 @@ pub enum ObjectType {
     ObjectLightingOutput = 54,        // Addendum 2010-i
     ObjectBinaryLightingOutput = 55,  // Addendum 135-2012az
     ObjectNetworkPort = 56,           // Addendum 135-2012az
+    ObjectAuditLog = 61,              // Addendum 2016-bi
+    ObjectAuditReporter = 62,         // Addendum 2016-bi
     // Enumerated values 0-127 are reserved for definition by ASHRAE.
     // Enumerated values 128-1023 may be used by others subject to
     // the procedures and constraints described in Clause 23.
@@ -174,6 +176,8 @@ impl TryFrom<u32> for ObjectType {
             54 => Ok(Self::ObjectLightingOutput),
             55 => Ok(Self::ObjectBinaryLightingOutput),
             56 => Ok(Self::ObjectNetworkPort),
+            61 => Ok(Self::ObjectAuditLog),
+            62 => Ok(Self::ObjectAuditReporter),
             57..=127 => Ok(Self::Reserved),
             128..=1023 => Ok(Self::Proprietary),
             x => Err(x),
@@ -110,6 +110,82 @@ impl TryFrom<u32> for ErrorClass {
     }
 }
 
+impl From<&ErrorClass> for u32 {
+    fn from(value: &ErrorClass) -> Self {
+        match value {
+            ErrorClass::Device => 0,
+            ErrorClass::Object => 1,
+            ErrorClass::Property => 2,
+            ErrorClass::Resources => 3,
+            ErrorClass::Security => 4,
+            ErrorClass::Services => 5,
+            ErrorClass::Vt => 6,
+            ErrorClass::Communication => 7,
+            ErrorClass::Proprietary(x) => *x as u32,
+        }
+    }
+}
+
+// the reason a confirmed request was rejected (sent in a Reject pdu)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum RejectReason {
+    Other = 0,
+    BufferOverflow = 1,
+    InconsistentParameters = 2,
+    InvalidParameterDataType = 3,
+    InvalidTag = 4,
+    MissingRequiredParameter = 5,
+    ParameterOutOfRange = 6,
+    TooManyArguments = 7,
+    UndefinedEnumeration = 8,
+    UnrecognizedService = 9,
+    // codes 64 and above
+    Proprietary(u8),
+}
+
+impl TryFrom<u8> for RejectReason {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Other),
+            1 => Ok(Self::BufferOverflow),
+            2 => Ok(Self::InconsistentParameters),
+            3 => Ok(Self::InvalidParameterDataType),
+            4 => Ok(Self::InvalidTag),
+            5 => Ok(Self::MissingRequiredParameter),
+            6 => Ok(Self::ParameterOutOfRange),
+            7 => Ok(Self::TooManyArguments),
+            8 => Ok(Self::UndefinedEnumeration),
+            9 => Ok(Self::UnrecognizedService),
+            // codes 64 and above
+            x if x > 63 => Ok(Self::Proprietary(x)),
+            x => Err(x),
+        }
+    }
+}
+
+impl From<&RejectReason> for u8 {
+    fn from(value: &RejectReason) -> Self {
+        match value {
+            RejectReason::Other => 0,
+            RejectReason::BufferOverflow => 1,
+            RejectReason::InconsistentParameters => 2,
+            RejectReason::InvalidParameterDataType => 3,
+            RejectReason::InvalidTag => 4,
+            RejectReason::MissingRequiredParameter => 5,
+            RejectReason::ParameterOutOfRange => 6,
+            RejectReason::TooManyArguments => 7,
+            RejectReason::UndefinedEnumeration => 8,
+            RejectReason::UnrecognizedService => 9,
+            RejectReason::Proprietary(x) => *x,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -564,6 +640,220 @@ impl TryFrom<u32> for ErrorCode {
     }
 }
 
+impl From<&ErrorCode> for u32 {
+    fn from(value: &ErrorCode) -> Self {
+        match value {
+            ErrorCode::Other => 0,
+            ErrorCode::DeviceBusy => 3,
+            ErrorCode::ConfigurationInProgress => 2,
+            ErrorCode::OperationalProblem => 25,
+            ErrorCode::DynamicCreationNotSupported => 4,
+            ErrorCode::NoObjectsOfSpecifiedType => 17,
+            ErrorCode::ObjectDeletionNotPermitted => 23,
+            ErrorCode::ObjectIdentifierAlreadyExists => 24,
+            ErrorCode::ReadAccessDenied => 27,
+            ErrorCode::UnknownObject => 31,
+            ErrorCode::UnsupportedObjectType => 36,
+            ErrorCode::CharacterSetNotSupported => 41,
+            ErrorCode::DatatypeNotSupported => 47,
+            ErrorCode::InconsistentSelectionCriterion => 8,
+            ErrorCode::InvalidArrayIndex => 42,
+            ErrorCode::InvalidDataType => 9,
+            ErrorCode::NotCovProperty => 44,
+            ErrorCode::OptionalFunctionalityNotSupported => 45,
+            ErrorCode::PropertyIsNotAnArray => 50,
+            ErrorCode::UnknownProperty => 32,
+            ErrorCode::ValueOutOfRange => 37,
+            ErrorCode::WriteAccessDenied => 40,
+            ErrorCode::NoSpaceForObject => 18,
+            ErrorCode::NoSpaceToAddListElement => 19,
+            ErrorCode::NoSpaceToWriteProperty => 20,
+            ErrorCode::AuthenticationFailed => 1,
+            ErrorCode::IncompatibleSecurityLevels => 6,
+            ErrorCode::InvalidOperatorName => 12,
+            ErrorCode::KeyGenerationError => 15,
+            ErrorCode::PasswordFailure => 26,
+            ErrorCode::SecurityNotSupported => 28,
+            ErrorCode::Timeout => 30,
+            ErrorCode::CovSubscriptionFailed => 43,
+            ErrorCode::DuplicateName => 48,
+            ErrorCode::DuplicateObjectId => 49,
+            ErrorCode::FileAccessDenied => 5,
+            ErrorCode::InconsistentParameters => 7,
+            ErrorCode::InvalidConfigurationData => 46,
+            ErrorCode::InvalidFileAccessMethod => 10,
+            ErrorCode::InvalidFileStartPosition => 11,
+            ErrorCode::InvalidParameterDataType => 13,
+            ErrorCode::InvalidTimeStamp => 14,
+            ErrorCode::MissingRequiredParameter => 16,
+            ErrorCode::PropertyIsNotAList => 22,
+            ErrorCode::ServiceRequestDenied => 29,
+            ErrorCode::UnknownVtClass => 34,
+            ErrorCode::UnknownVtSession => 35,
+            ErrorCode::NoVtSessionsAvailable => 21,
+            ErrorCode::VtSessionAlreadyClosed => 38,
+            ErrorCode::VtSessionTerminationFailure => 39,
+            ErrorCode::Reserved1 => 33,
+            ErrorCode::AbortBufferOverflow => 51,
+            ErrorCode::AbortInvalidApduInThisState => 52,
+            ErrorCode::AbortPreemptedByHigherPriorityTask => 53,
+            ErrorCode::AbortSegmentationNotSupported => 54,
+            ErrorCode::AbortProprietary => 55,
+            ErrorCode::AbortOther => 56,
+            ErrorCode::InvalidTag => 57,
+            ErrorCode::NetworkDown => 58,
+            ErrorCode::RejectBufferOverflow => 59,
+            ErrorCode::RejectInconsistentParameters => 60,
+            ErrorCode::RejectInvalidParameterDataType => 61,
+            ErrorCode::RejectInvalidTag => 62,
+            ErrorCode::RejectMissingRequiredParameter => 63,
+            ErrorCode::RejectParameterOutOfRange => 64,
+            ErrorCode::RejectTooManyArguments => 65,
+            ErrorCode::RejectUndefinedEnumeration => 66,
+            ErrorCode::RejectUnrecognizedService => 67,
+            ErrorCode::RejectProprietary => 68,
+            ErrorCode::RejectOther => 69,
+            ErrorCode::UnknownDevice => 70,
+            ErrorCode::UnknownRoute => 71,
+            ErrorCode::ValueNotInitialized => 72,
+            ErrorCode::InvalidEventState => 73,
+            ErrorCode::NoAlarmConfigured => 74,
+            ErrorCode::LogBufferFull => 75,
+            ErrorCode::LoggedValuePurged => 76,
+            ErrorCode::NoPropertySpecified => 77,
+            ErrorCode::NotConfiguredForTriggeredLogging => 78,
+            ErrorCode::UnknownSubscription => 79,
+            ErrorCode::ParameterOutOfRange => 80,
+            ErrorCode::ListElementNotFound => 81,
+            ErrorCode::Busy => 82,
+            ErrorCode::CommunicationDisabled => 83,
+            ErrorCode::Success => 84,
+            ErrorCode::AccessDenied => 85,
+            ErrorCode::BadDestinationAddress => 86,
+            ErrorCode::BadDestinationDeviceId => 87,
+            ErrorCode::BadSignature => 88,
+            ErrorCode::BadSourceAddress => 89,
+            ErrorCode::BadTimestamp => 90,
+            ErrorCode::CannotUseKey => 91,
+            ErrorCode::CannotVerifyMessageId => 92,
+            ErrorCode::CorrectKeyRevision => 93,
+            ErrorCode::DestinationDeviceIdRequired => 94,
+            ErrorCode::DuplicateMessage => 95,
+            ErrorCode::EncryptionNotConfigured => 96,
+            ErrorCode::EncryptionRequired => 97,
+            ErrorCode::IncorrectKey => 98,
+            ErrorCode::InvalidKeyData => 99,
+            ErrorCode::KeyUpdateInProgress => 100,
+            ErrorCode::MalformedMessage => 101,
+            ErrorCode::NotKeyServer => 102,
+            ErrorCode::SecurityNotConfigured => 103,
+            ErrorCode::SourceSecurityRequired => 104,
+            ErrorCode::TooManyKeys => 105,
+            ErrorCode::UnknownAuthenticationType => 106,
+            ErrorCode::UnknownKey => 107,
+            ErrorCode::UnknownKeyRevision => 108,
+            ErrorCode::UnknownSourceMessage => 109,
+            ErrorCode::NotRouterToDnet => 110,
+            ErrorCode::RouterBusy => 111,
+            ErrorCode::UnknownNetworkMessage => 112,
+            ErrorCode::MessageTooLong => 113,
+            ErrorCode::SecurityError => 114,
+            ErrorCode::AddressingError => 115,
+            ErrorCode::WriteBdtFailed => 116,
+            ErrorCode::ReadBdtFailed => 117,
+            ErrorCode::RegisterForeignDeviceFailed => 118,
+            ErrorCode::ReadFdtFailed => 119,
+            ErrorCode::DeleteFdtEntryFailed => 120,
+            ErrorCode::DistributeBroadcastFailed => 121,
+            ErrorCode::UnknownFileSize => 122,
+            ErrorCode::AbortApduTooLong => 123,
+            ErrorCode::AbortApplicationExceededReplyTime => 124,
+            ErrorCode::AbortOutOfResources => 125,
+            ErrorCode::AbortTsmTimeout => 126,
+            ErrorCode::AbortWindowSizeOutOfRange => 127,
+            ErrorCode::FileFull => 128,
+            ErrorCode::InconsistentConfiguration => 129,
+            ErrorCode::InconsistentObjectType => 130,
+            ErrorCode::InternalError => 131,
+            ErrorCode::NotConfigured => 132,
+            ErrorCode::OutOfMemory => 133,
+            ErrorCode::ValueTooLong => 134,
+            ErrorCode::AbortInsufficientSecurity => 135,
+            ErrorCode::AbortSecurityError => 136,
+            ErrorCode::DuplicateEntry => 137,
+            ErrorCode::InvalidValueInThisState => 138,
+            ErrorCode::InvalidOperationInThisState => 139,
+            ErrorCode::ListItemNotNumbered => 140,
+            ErrorCode::ListItemNotTimestamped => 141,
+            ErrorCode::InvalidDataEncoding => 142,
+            ErrorCode::BvlcFunctionUnknown => 143,
+            ErrorCode::BvlcProprietaryFunctionUnknown => 144,
+            ErrorCode::HeaderEncodingError => 145,
+            ErrorCode::HeaderNotUnderstood => 146,
+            ErrorCode::MessageIncomplete => 147,
+            ErrorCode::NotABacnetScHub => 148,
+            ErrorCode::PayloadExpected => 149,
+            ErrorCode::UnexpectedData => 150,
+            ErrorCode::NodeDuplicateVmac => 151,
+            ErrorCode::HttpUnexpectedResponseCode => 152,
+            ErrorCode::HttpNoUpgrade => 153,
+            ErrorCode::HttpResourceNotLocal => 154,
+            ErrorCode::HttpProxyAuthenticationFailed => 155,
+            ErrorCode::HttpResponseTimeout => 156,
+            ErrorCode::HttpResponseSyntaxError => 157,
+            ErrorCode::HttpResponseValueError => 158,
+            ErrorCode::HttpResponseMissingHeader => 159,
+            ErrorCode::HttpWebsocketHeaderError => 160,
+            ErrorCode::HttpUpgradeRequired => 161,
+            ErrorCode::HttpUpgradeError => 162,
+            ErrorCode::HttpTemporaryUnavailable => 163,
+            ErrorCode::HttpNotAServer => 164,
+            ErrorCode::HttpError => 165,
+            ErrorCode::WebsocketSchemeNotSupported => 166,
+            ErrorCode::WebsocketUnknownControlMessage => 167,
+            ErrorCode::WebsocketCloseError => 168,
+            ErrorCode::WebsocketClosedByPeer => 169,
+            ErrorCode::WebsocketEndpointLeaves => 170,
+            ErrorCode::WebsocketProtocolError => 171,
+            ErrorCode::WebsocketDataNotAccepted => 172,
+            ErrorCode::WebsocketClosedAbnormally => 173,
+            ErrorCode::WebsocketDataInconsistent => 174,
+            ErrorCode::WebsocketDataAgainstPolicy => 175,
+            ErrorCode::WebsocketFrameTooLong => 176,
+            ErrorCode::WebsocketExtensionMissing => 177,
+            ErrorCode::WebsocketRequestUnavailable => 178,
+            ErrorCode::WebsocketError => 179,
+            ErrorCode::TlsClientCertificateError => 180,
+            ErrorCode::TlsServerCertificateError => 181,
+            ErrorCode::TlsClientAuthenticationFailed => 182,
+            ErrorCode::TlsServerAuthenticationFailed => 183,
+            ErrorCode::TlsClientCertificateExpired => 184,
+            ErrorCode::TlsServerCertificateExpired => 185,
+            ErrorCode::TlsClientCertificateRevoked => 186,
+            ErrorCode::TlsServerCertificateRevoked => 187,
+            ErrorCode::TlsError => 188,
+            ErrorCode::DnsUnavailable => 189,
+            ErrorCode::DnsNameResolutionFailed => 190,
+            ErrorCode::DnsResolverFailure => 191,
+            ErrorCode::DnsError => 192,
+            ErrorCode::TcpConnectTimeout => 193,
+            ErrorCode::TcpConnectionRefused => 194,
+            ErrorCode::TcpClosedByLocal => 195,
+            ErrorCode::TcpClosedOther => 196,
+            ErrorCode::TcpError => 197,
+            ErrorCode::IpAddressNotReachable => 198,
+            ErrorCode::IpError => 199,
+            ErrorCode::CertificateExpired => 200,
+            ErrorCode::CertificateInvalid => 201,
+            ErrorCode::CertificateMalformed => 202,
+            ErrorCode::CertificateRevoked => 203,
+            ErrorCode::UnknownSecurityKey => 204,
+            ErrorCode::ReferencedPortInError => 205,
+            ErrorCode::Proprietary(x) => *x as u32,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -1382,6 +1672,40 @@ impl Status {
     }
 }
 
+#[repr(u8)]
+pub enum EventTransitionFlags {
+    ToOffnormal = 0b1000_0000,
+    ToFault = 0b0100_0000,
+    ToNormal = 0b0010_0000,
+}
+
+// used for the acked transitions and event enable properties
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventTransitionBits {
+    pub inner: u8,
+}
+
+impl EventTransitionBits {
+    pub fn new(inner: u8) -> Self {
+        Self { inner }
+    }
+
+    pub const fn to_offnormal(&self) -> bool {
+        self.inner & EventTransitionFlags::ToOffnormal as u8
+            == EventTransitionFlags::ToOffnormal as u8
+    }
+
+    pub const fn to_fault(&self) -> bool {
+        self.inner & EventTransitionFlags::ToFault as u8 == EventTransitionFlags::ToFault as u8
+    }
+
+    pub const fn to_normal(&self) -> bool {
+        self.inner & EventTransitionFlags::ToNormal as u8 == EventTransitionFlags::ToNormal as u8
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -1416,6 +1740,39 @@ pub enum LogStatus {
     LogInterrupted = 2,
 }
 
+#[repr(u8)]
+pub enum LogStatusFlags {
+    LogDisabled = 0b1000_0000,
+    BufferPurged = 0b0100_0000,
+    LogInterrupted = 0b0010_0000,
+}
+
+// the log status records found in trend log and audit log buffers
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogStatusBits {
+    pub inner: u8,
+}
+
+impl LogStatusBits {
+    pub fn new(inner: u8) -> Self {
+        Self { inner }
+    }
+
+    pub const fn log_disabled(&self) -> bool {
+        self.inner & LogStatusFlags::LogDisabled as u8 == LogStatusFlags::LogDisabled as u8
+    }
+
+    pub const fn buffer_purged(&self) -> bool {
+        self.inner & LogStatusFlags::BufferPurged as u8 == LogStatusFlags::BufferPurged as u8
+    }
+
+    pub const fn log_interrupted(&self) -> bool {
+        self.inner & LogStatusFlags::LogInterrupted as u8 == LogStatusFlags::LogInterrupted as u8
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -1435,19 +1792,40 @@ pub enum EventState {
     OffNormal = 2,
     HighLimit = 3,
     LowLimit = 4,
+    LifeSafetyAlarm = 5,
+    // codes 64 to 65535
+    Proprietary(u16),
+    // codes not assigned by the standard yet
+    Reserved(u32),
 }
 
-impl TryFrom<u32> for EventState {
-    type Error = u32;
+impl From<u32> for EventState {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Normal,
+            1 => Self::Fault,
+            2 => Self::OffNormal,
+            3 => Self::HighLimit,
+            4 => Self::LowLimit,
+            5 => Self::LifeSafetyAlarm,
+            // codes 64 to 65535
+            x @ 64..=65535 => Self::Proprietary(x as u16),
+            x => Self::Reserved(x),
+        }
+    }
+}
 
-    fn try_from(value: u32) -> Result<Self, Self::Error> {
+impl From<&EventState> for u32 {
+    fn from(value: &EventState) -> Self {
         match value {
-            0 => Ok(Self::Normal),
-            1 => Ok(Self::Fault),
-            2 => Ok(Self::OffNormal),
-            3 => Ok(Self::HighLimit),
-            4 => Ok(Self::LowLimit),
-            x => Err(x),
+            EventState::Normal => 0,
+            EventState::Fault => 1,
+            EventState::OffNormal => 2,
+            EventState::HighLimit => 3,
+            EventState::LowLimit => 4,
+            EventState::LifeSafetyAlarm => 5,
+            EventState::Proprietary(x) => *x as u32,
+            EventState::Reserved(x) => *x,
         }
     }
 }
@@ -1475,6 +1853,98 @@ impl TryFrom<u32> for NotifyType {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u32)]
+pub enum EventType {
+    ChangeOfBitstring = 0,
+    ChangeOfState = 1,
+    ChangeOfValue = 2,
+    CommandFailure = 3,
+    FloatingLimit = 4,
+    OutOfRange = 5,
+    // 6 and 7 are deprecated
+    ChangeOfLifeSafety = 8,
+    Extended = 9,
+    BufferReady = 10,
+    UnsignedRange = 11,
+    // 12 is reserved
+    AccessEvent = 13,
+    DoubleOutOfRange = 14,
+    SignedOutOfRange = 15,
+    UnsignedOutOfRange = 16,
+    ChangeOfCharacterstring = 17,
+    ChangeOfStatusFlags = 18,
+    ChangeOfReliability = 19,
+    None = 20,
+    ChangeOfDiscreteValue = 21,
+    ChangeOfTimer = 22,
+    // codes 64 to 65535
+    Proprietary(u16),
+    // codes not assigned by the standard (including the deprecated ones)
+    Reserved(u32),
+}
+
+impl From<u32> for EventType {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::ChangeOfBitstring,
+            1 => Self::ChangeOfState,
+            2 => Self::ChangeOfValue,
+            3 => Self::CommandFailure,
+            4 => Self::FloatingLimit,
+            5 => Self::OutOfRange,
+            8 => Self::ChangeOfLifeSafety,
+            9 => Self::Extended,
+            10 => Self::BufferReady,
+            11 => Self::UnsignedRange,
+            13 => Self::AccessEvent,
+            14 => Self::DoubleOutOfRange,
+            15 => Self::SignedOutOfRange,
+            16 => Self::UnsignedOutOfRange,
+            17 => Self::ChangeOfCharacterstring,
+            18 => Self::ChangeOfStatusFlags,
+            19 => Self::ChangeOfReliability,
+            20 => Self::None,
+            21 => Self::ChangeOfDiscreteValue,
+            22 => Self::ChangeOfTimer,
+            // codes 64 to 65535
+            x @ 64..=65535 => Self::Proprietary(x as u16),
+            x => Self::Reserved(x),
+        }
+    }
+}
+
+impl From<&EventType> for u32 {
+    fn from(value: &EventType) -> Self {
+        match value {
+            EventType::ChangeOfBitstring => 0,
+            EventType::ChangeOfState => 1,
+            EventType::ChangeOfValue => 2,
+            EventType::CommandFailure => 3,
+            EventType::FloatingLimit => 4,
+            EventType::OutOfRange => 5,
+            EventType::ChangeOfLifeSafety => 8,
+            EventType::Extended => 9,
+            EventType::BufferReady => 10,
+            EventType::UnsignedRange => 11,
+            EventType::AccessEvent => 13,
+            EventType::DoubleOutOfRange => 14,
+            EventType::SignedOutOfRange => 15,
+            EventType::UnsignedOutOfRange => 16,
+            EventType::ChangeOfCharacterstring => 17,
+            EventType::ChangeOfStatusFlags => 18,
+            EventType::ChangeOfReliability => 19,
+            EventType::None => 20,
+            EventType::ChangeOfDiscreteValue => 21,
+            EventType::ChangeOfTimer => 22,
+            EventType::Proprietary(x) => *x as u32,
+            EventType::Reserved(x) => *x,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -1498,6 +1968,319 @@ impl TryFrom<u32> for LoggingType {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u32)]
+pub enum BackupState {
+    Idle = 0,
+    PreparingForBackup = 1,
+    PreparingForRestore = 2,
+    PerformingABackup = 3,
+    PerformingARestore = 4,
+    BackupFailure = 5,
+    RestoreFailure = 6,
+}
+
+impl TryFrom<u32> for BackupState {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Idle),
+            1 => Ok(Self::PreparingForBackup),
+            2 => Ok(Self::PreparingForRestore),
+            3 => Ok(Self::PerformingABackup),
+            4 => Ok(Self::PerformingARestore),
+            5 => Ok(Self::BackupFailure),
+            6 => Ok(Self::RestoreFailure),
+            x => Err(x),
+        }
+    }
+}
+
+// the state of a life safety point or zone
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LifeSafetyState {
+    Quiet = 0,
+    PreAlarm = 1,
+    Alarm = 2,
+    Fault = 3,
+    FaultPreAlarm = 4,
+    FaultAlarm = 5,
+    NotReady = 6,
+    Active = 7,
+    Tamper = 8,
+    TestAlarm = 9,
+    TestActive = 10,
+    TestFault = 11,
+    TestFaultAlarm = 12,
+    Holdup = 13,
+    Duress = 14,
+    TamperAlarm = 15,
+    Abnormal = 16,
+    EmergencyPower = 17,
+    Delayed = 18,
+    Blocked = 19,
+    LocalAlarm = 20,
+    GeneralAlarm = 21,
+    Supervisory = 22,
+    TestSupervisory = 23,
+}
+
+impl TryFrom<u32> for LifeSafetyState {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Quiet),
+            1 => Ok(Self::PreAlarm),
+            2 => Ok(Self::Alarm),
+            3 => Ok(Self::Fault),
+            4 => Ok(Self::FaultPreAlarm),
+            5 => Ok(Self::FaultAlarm),
+            6 => Ok(Self::NotReady),
+            7 => Ok(Self::Active),
+            8 => Ok(Self::Tamper),
+            9 => Ok(Self::TestAlarm),
+            10 => Ok(Self::TestActive),
+            11 => Ok(Self::TestFault),
+            12 => Ok(Self::TestFaultAlarm),
+            13 => Ok(Self::Holdup),
+            14 => Ok(Self::Duress),
+            15 => Ok(Self::TamperAlarm),
+            16 => Ok(Self::Abnormal),
+            17 => Ok(Self::EmergencyPower),
+            18 => Ok(Self::Delayed),
+            19 => Ok(Self::Blocked),
+            20 => Ok(Self::LocalAlarm),
+            21 => Ok(Self::GeneralAlarm),
+            22 => Ok(Self::Supervisory),
+            23 => Ok(Self::TestSupervisory),
+            x => Err(x),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LifeSafetyMode {
+    Off = 0,
+    On = 1,
+    Test = 2,
+    Manned = 3,
+    Unmanned = 4,
+    Armed = 5,
+    Disarmed = 6,
+    Prearmed = 7,
+    Slow = 8,
+    Fast = 9,
+    Disconnected = 10,
+    Enabled = 11,
+    Disabled = 12,
+    AutomaticReleaseDisabled = 13,
+    Default = 14,
+    ActivatedOeoAlarm = 15,
+    ActivatedOeoEvacuate = 16,
+    ActivatedOeoPhase1Recall = 17,
+    ActivatedOeoUnavailable = 18,
+    Deactivated = 19,
+}
+
+impl TryFrom<u32> for LifeSafetyMode {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Off),
+            1 => Ok(Self::On),
+            2 => Ok(Self::Test),
+            3 => Ok(Self::Manned),
+            4 => Ok(Self::Unmanned),
+            5 => Ok(Self::Armed),
+            6 => Ok(Self::Disarmed),
+            7 => Ok(Self::Prearmed),
+            8 => Ok(Self::Slow),
+            9 => Ok(Self::Fast),
+            10 => Ok(Self::Disconnected),
+            11 => Ok(Self::Enabled),
+            12 => Ok(Self::Disabled),
+            13 => Ok(Self::AutomaticReleaseDisabled),
+            14 => Ok(Self::Default),
+            15 => Ok(Self::ActivatedOeoAlarm),
+            16 => Ok(Self::ActivatedOeoEvacuate),
+            17 => Ok(Self::ActivatedOeoPhase1Recall),
+            18 => Ok(Self::ActivatedOeoUnavailable),
+            19 => Ok(Self::Deactivated),
+            x => Err(x),
+        }
+    }
+}
+
+// the operation requested of a life safety object by an operator
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LifeSafetyOperation {
+    None = 0,
+    Silence = 1,
+    SilenceAudible = 2,
+    SilenceVisual = 3,
+    Reset = 4,
+    ResetAlarm = 5,
+    ResetFault = 6,
+    Unsilence = 7,
+    UnsilenceAudible = 8,
+    UnsilenceVisual = 9,
+}
+
+impl TryFrom<u32> for LifeSafetyOperation {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Silence),
+            2 => Ok(Self::SilenceAudible),
+            3 => Ok(Self::SilenceVisual),
+            4 => Ok(Self::Reset),
+            5 => Ok(Self::ResetAlarm),
+            6 => Ok(Self::ResetFault),
+            7 => Ok(Self::Unsilence),
+            8 => Ok(Self::UnsilenceAudible),
+            9 => Ok(Self::UnsilenceVisual),
+            x => Err(x),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SilencedState {
+    Unsilenced = 0,
+    AudibleSilenced = 1,
+    VisuallySilenced = 2,
+    AllSilenced = 3,
+}
+
+impl TryFrom<u32> for SilencedState {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Unsilenced),
+            1 => Ok(Self::AudibleSilenced),
+            2 => Ok(Self::VisuallySilenced),
+            3 => Ok(Self::AllSilenced),
+            x => Err(x),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AuditOperation {
+    Read = 0,
+    Write = 1,
+    Create = 2,
+    Delete = 3,
+    LifeSafety = 4,
+    AcknowledgeAlarm = 5,
+    DeviceDisableComm = 6,
+    DeviceEnableComm = 7,
+    DeviceReset = 8,
+    DeviceBackup = 9,
+    DeviceRestore = 10,
+    Subscription = 11,
+    Notification = 12,
+    AuditingFailure = 13,
+    NetworkChanges = 14,
+    General = 15,
+}
+
+impl TryFrom<u32> for AuditOperation {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Read),
+            1 => Ok(Self::Write),
+            2 => Ok(Self::Create),
+            3 => Ok(Self::Delete),
+            4 => Ok(Self::LifeSafety),
+            5 => Ok(Self::AcknowledgeAlarm),
+            6 => Ok(Self::DeviceDisableComm),
+            7 => Ok(Self::DeviceEnableComm),
+            8 => Ok(Self::DeviceReset),
+            9 => Ok(Self::DeviceBackup),
+            10 => Ok(Self::DeviceRestore),
+            11 => Ok(Self::Subscription),
+            12 => Ok(Self::Notification),
+            13 => Ok(Self::AuditingFailure),
+            14 => Ok(Self::NetworkChanges),
+            15 => Ok(Self::General),
+            x => Err(x),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SuccessFilter {
+    All = 0,
+    SuccessesOnly = 1,
+    FailuresOnly = 2,
+}
+
+impl TryFrom<u32> for SuccessFilter {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::All),
+            1 => Ok(Self::SuccessesOnly),
+            2 => Ok(Self::FailuresOnly),
+            x => Err(x),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VtClass {
+    DefaultTerminal = 0,
+    AnsiX364 = 1,
+    DecVt52 = 2,
+    DecVt100 = 3,
+    DecVt220 = 4,
+    Hp70094 = 5,
+    Ibm3130 = 6,
+}
+
+impl TryFrom<u32> for VtClass {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::DefaultTerminal),
+            1 => Ok(Self::AnsiX364),
+            2 => Ok(Self::DecVt52),
+            3 => Ok(Self::DecVt100),
+            4 => Ok(Self::DecVt220),
+            5 => Ok(Self::Hp70094),
+            6 => Ok(Self::Ibm3130),
+            x => Err(x),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -1528,6 +2311,51 @@ pub enum CommunicationEnableDisable {
     DisableInitiation = 2,
 }
 
+impl TryFrom<u32> for CommunicationEnableDisable {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Enable),
+            1 => Ok(Self::Disable),
+            2 => Ok(Self::DisableInitiation),
+            x => Err(x),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReinitializedState {
+    Coldstart = 0,
+    Warmstart = 1,
+    StartBackup = 2,
+    EndBackup = 3,
+    StartRestore = 4,
+    EndRestore = 5,
+    AbortRestore = 6,
+    ActivateChanges = 7,
+}
+
+impl TryFrom<u32> for ReinitializedState {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Coldstart),
+            1 => Ok(Self::Warmstart),
+            2 => Ok(Self::StartBackup),
+            3 => Ok(Self::EndBackup),
+            4 => Ok(Self::StartRestore),
+            5 => Ok(Self::EndRestore),
+            6 => Ok(Self::AbortRestore),
+            7 => Ok(Self::ActivateChanges),
+            x => Err(x),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -1538,4 +2366,64 @@ pub enum MessagePriority {
     LifeSafety = 3,
 }
 
+// BACnetLightingOperation: the operation carried out by a lighting command
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LightingOperation {
+    None = 0,
+    FadeTo = 1,
+    RampTo = 2,
+    StepUp = 3,
+    StepDown = 4,
+    StepOn = 5,
+    StepOff = 6,
+    Warn = 7,
+    WarnOff = 8,
+    WarnRelinquish = 9,
+    Stop = 10,
+}
+
+impl TryFrom<u32> for LightingOperation {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::FadeTo),
+            2 => Ok(Self::RampTo),
+            3 => Ok(Self::StepUp),
+            4 => Ok(Self::StepDown),
+            5 => Ok(Self::StepOn),
+            6 => Ok(Self::StepOff),
+            7 => Ok(Self::Warn),
+            8 => Ok(Self::WarnOff),
+            9 => Ok(Self::WarnRelinquish),
+            10 => Ok(Self::Stop),
+            x => Err(x),
+        }
+    }
+}
+
+// the priority of a text message (not to be confused with the network layer message priority)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextMessagePriority {
+    Normal = 0,
+    Urgent = 1,
+}
+
+impl TryFrom<u32> for TextMessagePriority {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Normal),
+            1 => Ok(Self::Urgent),
+            x => Err(x),
+        }
+    }
+}
+
 // end of bit string enumerations
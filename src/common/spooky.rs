@@ -13,7 +13,7 @@ impl defmt::Format for Phantom {
 }
 
 #[cfg(feature = "serde")]
-impl<'a, 'de> serde::Deserialize<'de> for &'a Phantom {
+impl<'de> serde::Deserialize<'de> for &Phantom {
     fn deserialize<D>(_deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
@@ -22,7 +22,7 @@ impl<'a, 'de> serde::Deserialize<'de> for &'a Phantom {
     }
 }
 
-impl<'a> Default for &'a Phantom {
+impl Default for &Phantom {
     fn default() -> Self {
         &PHANTOM
     }
@@ -236,19 +236,19 @@ fn decode_tag_number(reader: &mut Reader, buf: &[u8]) -> Result<(TagNumber, u8),
     let byte0 = reader.read_byte(buf)?;
 
     let value = if is_context_specific(byte0) {
-        // context specific tag num
-        if is_extended_tag_number(byte0) {
-            let num = reader.read_byte(buf)?;
-            (TagNumber::ContextSpecific(num), byte0)
+        // context specific tag num (tag numbers above 14 are in the next byte)
+        let num = if is_extended_tag_number(byte0) {
+            reader.read_byte(buf)?
         } else {
-            let num = byte0 >> 4;
-            if is_opening_tag(byte0) {
-                (TagNumber::ContextSpecificOpening(num), 0)
-            } else if is_closing_tag(byte0) {
-                (TagNumber::ContextSpecificClosing(num), 0)
-            } else {
-                (TagNumber::ContextSpecific(num), byte0)
-            }
+            byte0 >> 4
+        };
+
+        if is_opening_tag(byte0) {
+            (TagNumber::ContextSpecificOpening(num), 0)
+        } else if is_closing_tag(byte0) {
+            (TagNumber::ContextSpecificClosing(num), 0)
+        } else {
+            (TagNumber::ContextSpecific(num), byte0)
         }
     } else {
         // application tag num
@@ -278,3 +278,23 @@ fn is_opening_tag(byte0: u8) -> bool {
 fn is_closing_tag(byte0: u8) -> bool {
     byte0 & 0x07 == 0x07
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode_extended_opening_and_closing_tags() {
+        // tag numbers above 14 follow the first byte
+        let buf = [0xFE, 0x0F, 0xFF, 0x0F];
+        let mut reader = Reader::new_with_len(buf.len());
+
+        let tag = Tag::decode(&mut reader, &buf).unwrap();
+        assert_eq!(tag.number, TagNumber::ContextSpecificOpening(15));
+        assert_eq!(reader.index, 2);
+
+        let tag = Tag::decode(&mut reader, &buf).unwrap();
+        assert_eq!(tag.number, TagNumber::ContextSpecificClosing(15));
+        assert!(reader.eof());
+    }
+}
@@ -0,0 +1,510 @@
+/// Backs up and restores the configuration of a device using the procedure described in clause 19.1 of the BACnet standard
+/// A backup puts the device into backup mode with ReinitializeDevice, reads the list of configuration files from the device object,
+/// downloads each file and then takes the device out of backup mode again. A restore does the reverse by uploading the files.
+/// While the device is preparing it reports its progress in the Backup_And_Restore_State property which is polled until the device is ready.
+/// The device abandons the procedure if it does not hear from the client for Backup_Failure_Timeout seconds so this can be set up front.
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use maybe_async::maybe_async;
+
+use crate::{
+    application_protocol::{
+        primitives::data_value::{ApplicationDataValue, CharacterString, Enumerated},
+        services::{
+            read_property::{ReadProperty, ReadPropertyValue},
+            reinitialize_device::ReinitializeDevice,
+            write_property::WriteProperty,
+        },
+    },
+    common::{
+        error::Error,
+        object_id::ObjectId,
+        property_id::PropertyId,
+        spec::{BackupState, ErrorCode, ReinitializedState},
+    },
+};
+
+use super::{Bacnet, BacnetError, Delay, NetworkIo};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BackupFile {
+    pub file_id: ObjectId,
+    pub contents: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BackupRestore<'a> {
+    pub device_id: ObjectId,
+    pub password: Option<&'a str>,
+    pub backup_failure_timeout_seconds: Option<u16>, // written to the device before the procedure starts
+    pub poll_interval_ms: u32,
+    pub max_polls: u32, // the procedure fails if the device is still preparing after this many polls
+}
+
+impl<'a> BackupRestore<'a> {
+    pub fn new(device_id: ObjectId, password: Option<&'a str>) -> Self {
+        Self {
+            device_id,
+            password,
+            backup_failure_timeout_seconds: None,
+            poll_interval_ms: 1000,
+            max_polls: 60,
+        }
+    }
+
+    /// Downloads all the configuration files of the device
+    /// If anything goes wrong after the device entered backup mode then the device is told that the backup has ended
+    #[maybe_async()]
+    pub async fn backup<T, D>(
+        &self,
+        bacnet: &Bacnet<T>,
+        delay: &D,
+        buf: &mut [u8],
+    ) -> Result<Vec<BackupFile>, BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+        D: Delay,
+    {
+        self.write_backup_failure_timeout(bacnet, buf).await?;
+        self.reinitialize(bacnet, buf, ReinitializedState::StartBackup)
+            .await?;
+
+        match self.download_files(bacnet, delay, buf).await {
+            Ok(files) => {
+                self.reinitialize(bacnet, buf, ReinitializedState::EndBackup)
+                    .await?;
+                Ok(files)
+            }
+            Err(e) => {
+                // best effort, the device times out of backup mode on its own anyway
+                let _ = self
+                    .reinitialize(bacnet, buf, ReinitializedState::EndBackup)
+                    .await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Uploads previously backed up configuration files to the device
+    /// If anything goes wrong after the device entered restore mode then the restore is aborted
+    #[maybe_async()]
+    pub async fn restore<T, D>(
+        &self,
+        bacnet: &Bacnet<T>,
+        delay: &D,
+        buf: &mut [u8],
+        files: &[BackupFile],
+    ) -> Result<(), BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+        D: Delay,
+    {
+        self.write_backup_failure_timeout(bacnet, buf).await?;
+        self.reinitialize(bacnet, buf, ReinitializedState::StartRestore)
+            .await?;
+
+        match self.upload_files(bacnet, delay, buf, files).await {
+            Ok(()) => {
+                self.reinitialize(bacnet, buf, ReinitializedState::EndRestore)
+                    .await
+            }
+            Err(e) => {
+                let _ = self
+                    .reinitialize(bacnet, buf, ReinitializedState::AbortRestore)
+                    .await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Reads the Backup_And_Restore_State property of the device
+    #[maybe_async()]
+    pub async fn read_state<T>(
+        &self,
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+    ) -> Result<BackupState, BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        let request = ReadProperty::new(self.device_id, PropertyId::PropBackupAndRestoreState);
+        let ack = bacnet.read_property(buf, request).await?;
+        match ack.property_value {
+            ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::Enumerated(
+                Enumerated::BackupState(state),
+            )) => Ok(state),
+            _ => Err(BacnetError::Codec(Error::InvalidValue(
+                "expected an enumerated Backup_And_Restore_State",
+            ))),
+        }
+    }
+
+    #[maybe_async()]
+    async fn download_files<T, D>(
+        &self,
+        bacnet: &Bacnet<T>,
+        delay: &D,
+        buf: &mut [u8],
+    ) -> Result<Vec<BackupFile>, BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+        D: Delay,
+    {
+        self.wait_while_preparing(bacnet, delay, buf, BackupState::PreparingForBackup)
+            .await?;
+
+        let request = ReadProperty::new(self.device_id, PropertyId::PropConfigurationFiles);
+        let ack = bacnet.read_property(buf, request).await?;
+        let file_ids = match ack.property_value {
+            ReadPropertyValue::ObjectIdList(x) => x.object_ids,
+            _ => {
+                return Err(BacnetError::Codec(Error::InvalidValue(
+                    "expected a list of object ids for Configuration_Files",
+                )))
+            }
+        };
+
+        let mut files = Vec::with_capacity(file_ids.len());
+        for file_id in file_ids {
+            let contents = bacnet.read_file(buf, file_id).await?;
+            files.push(BackupFile { file_id, contents });
+        }
+
+        Ok(files)
+    }
+
+    #[maybe_async()]
+    async fn upload_files<T, D>(
+        &self,
+        bacnet: &Bacnet<T>,
+        delay: &D,
+        buf: &mut [u8],
+        files: &[BackupFile],
+    ) -> Result<(), BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+        D: Delay,
+    {
+        self.wait_while_preparing(bacnet, delay, buf, BackupState::PreparingForRestore)
+            .await?;
+
+        let max_apdu = bacnet.read_max_apdu(buf, self.device_id).await?;
+        for file in files {
+            bacnet
+                .write_file(buf, file.file_id, &file.contents, max_apdu)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    // devices that predate the Backup_And_Restore_State property are assumed to be ready straight away
+    #[maybe_async()]
+    async fn wait_while_preparing<T, D>(
+        &self,
+        bacnet: &Bacnet<T>,
+        delay: &D,
+        buf: &mut [u8],
+        preparing: BackupState,
+    ) -> Result<(), BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+        D: Delay,
+    {
+        for _ in 0..self.max_polls {
+            let state = match self.read_state(bacnet, buf).await {
+                Ok(state) => state,
+                Err(BacnetError::Bacnet(e))
+                    if matches!(e.error_code, ErrorCode::UnknownProperty) =>
+                {
+                    return Ok(())
+                }
+                Err(e) => return Err(e),
+            };
+
+            match state {
+                BackupState::BackupFailure | BackupState::RestoreFailure => {
+                    return Err(BacnetError::Codec(Error::InvalidValue(
+                        "device reported a backup or restore failure",
+                    )))
+                }
+                x if x == preparing => delay.delay_ms(self.poll_interval_ms).await,
+                _ => return Ok(()),
+            }
+        }
+
+        Err(BacnetError::Codec(Error::InvalidValue(
+            "device took too long to prepare for backup or restore",
+        )))
+    }
+
+    #[maybe_async()]
+    async fn write_backup_failure_timeout<T>(
+        &self,
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+    ) -> Result<(), BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        if let Some(timeout) = self.backup_failure_timeout_seconds {
+            let request = WriteProperty::new(
+                self.device_id,
+                PropertyId::PropBackupFailureTimeout,
+                None,
+                None,
+                ApplicationDataValue::UnsignedInt(timeout as u64),
+            );
+            bacnet.write_property(buf, request).await?;
+        }
+
+        Ok(())
+    }
+
+    #[maybe_async()]
+    async fn reinitialize<T>(
+        &self,
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+        state: ReinitializedState,
+    ) -> Result<(), BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        let password = self.password.map(CharacterString::new);
+        let request = ReinitializeDevice::new(state, password);
+        bacnet.reinitialize_device(buf, request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::{
+        application_protocol::{
+            confirmed::{ComplexAckService, ConfirmedRequestService, ConfirmedServiceChoice},
+            primitives::data_value::{ApplicationDataValue, Enumerated, OctetString},
+            services::{
+                atomic_read_file::{AtomicReadFileAck, FileData},
+                atomic_write_file::AtomicWriteFileAck,
+                read_property::{ObjectIdList, ReadPropertyAck, ReadPropertyValue},
+            },
+        },
+        common::{
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{BackupState, ErrorClass, ErrorCode, ReinitializedState},
+        },
+        simple::{
+            fake_network::{FakeDelay, FakeNetwork, Reply},
+            Bacnet, BacnetError,
+        },
+    };
+
+    use super::{BackupFile, BackupRestore};
+
+    const DEVICE_ID: ObjectId = ObjectId {
+        object_type: ObjectType::ObjectDevice,
+        id: 79079,
+    };
+
+    fn property(property_id: PropertyId, property_value: ReadPropertyValue<'static>) -> Reply {
+        Reply::ComplexAck(ComplexAckService::ReadProperty(ReadPropertyAck {
+            object_id: DEVICE_ID,
+            property_id,
+            property_value,
+        }))
+    }
+
+    fn state(state: BackupState) -> Reply {
+        property(
+            PropertyId::PropBackupAndRestoreState,
+            ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::Enumerated(
+                Enumerated::BackupState(state),
+            )),
+        )
+    }
+
+    fn file(file_id: ObjectId, contents: &[u8]) -> BackupFile {
+        BackupFile {
+            file_id,
+            contents: contents.to_vec(),
+        }
+    }
+
+    fn reinitialized_state(bacnet: &Bacnet<FakeNetwork>, index: usize) -> ReinitializedState {
+        match bacnet.io.sent_request(index) {
+            ConfirmedRequestService::ReinitializeDevice(x) => x.state,
+            x => panic!("unexpected request {:?}", x),
+        }
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn backup() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let delay = FakeDelay::default();
+        let mut buf = vec![0; 1500];
+        let file_1 = ObjectId::new(ObjectType::ObjectFile, 1);
+        let file_2 = ObjectId::new(ObjectType::ObjectFile, 2);
+
+        bacnet
+            .io
+            .push(Reply::SimpleAck(ConfirmedServiceChoice::ReinitializeDevice));
+        bacnet.io.push(state(BackupState::PreparingForBackup));
+        bacnet.io.push(state(BackupState::PerformingABackup));
+        bacnet.io.push(property(
+            PropertyId::PropConfigurationFiles,
+            ReadPropertyValue::ObjectIdList(ObjectIdList::new(vec![file_1, file_2])),
+        ));
+        for contents in [&[1, 2, 3][..], &[4, 5][..]] {
+            let data = FileData::new_stream(0, OctetString::new(contents));
+            let ack = AtomicReadFileAck::new(true, data);
+            bacnet
+                .io
+                .push(Reply::ComplexAck(ComplexAckService::AtomicReadFile(ack)));
+        }
+        bacnet
+            .io
+            .push(Reply::SimpleAck(ConfirmedServiceChoice::ReinitializeDevice));
+
+        let backup = BackupRestore::new(DEVICE_ID, Some("secret"));
+        let files = backup.backup(&bacnet, &delay, &mut buf).await.unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].file_id, file_1);
+        assert_eq!(files[0].contents, [1, 2, 3]);
+        assert_eq!(files[1].file_id, file_2);
+        assert_eq!(files[1].contents, [4, 5]);
+
+        assert_eq!(
+            bacnet.io.sent_confirmed(),
+            vec![
+                ConfirmedServiceChoice::ReinitializeDevice,
+                ConfirmedServiceChoice::ReadProperty,
+                ConfirmedServiceChoice::ReadProperty,
+                ConfirmedServiceChoice::ReadProperty,
+                ConfirmedServiceChoice::AtomicReadFile,
+                ConfirmedServiceChoice::AtomicReadFile,
+                ConfirmedServiceChoice::ReinitializeDevice,
+            ]
+        );
+        assert!(matches!(
+            reinitialized_state(&bacnet, 0),
+            ReinitializedState::StartBackup
+        ));
+        assert!(matches!(
+            reinitialized_state(&bacnet, 6),
+            ReinitializedState::EndBackup
+        ));
+        assert_eq!(delay.total_ms.get(), backup.poll_interval_ms);
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn restore_is_aborted_when_an_upload_fails() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let delay = FakeDelay::default();
+        let mut buf = vec![0; 1500];
+        let file_id = ObjectId::new(ObjectType::ObjectFile, 1);
+
+        bacnet
+            .io
+            .push(Reply::SimpleAck(ConfirmedServiceChoice::ReinitializeDevice));
+        bacnet.io.push(state(BackupState::PerformingARestore));
+        // the file is uploaded in three chunks because the device only accepts small apdus
+        bacnet.io.push(property(
+            PropertyId::PropMaxApduLengthAccepted,
+            ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::UnsignedInt(206)),
+        ));
+        bacnet
+            .io
+            .push(Reply::ComplexAck(ComplexAckService::AtomicWriteFile(
+                AtomicWriteFileAck::StartPosition(0),
+            )));
+        bacnet.io.push(Reply::Error(
+            ConfirmedServiceChoice::AtomicWriteFile,
+            ErrorClass::Object,
+            ErrorCode::FileAccessDenied,
+        ));
+        bacnet
+            .io
+            .push(Reply::SimpleAck(ConfirmedServiceChoice::ReinitializeDevice));
+
+        let restore = BackupRestore::new(DEVICE_ID, None);
+        let files = [file(file_id, &[7; 300])];
+        let result = restore.restore(&bacnet, &delay, &mut buf, &files).await;
+        assert!(matches!(
+            result,
+            Err(BacnetError::Bacnet(e)) if matches!(e.error_code, ErrorCode::FileAccessDenied)
+        ));
+
+        assert_eq!(
+            bacnet.io.sent_confirmed(),
+            vec![
+                ConfirmedServiceChoice::ReinitializeDevice,
+                ConfirmedServiceChoice::ReadProperty,
+                ConfirmedServiceChoice::ReadProperty,
+                ConfirmedServiceChoice::AtomicWriteFile,
+                ConfirmedServiceChoice::AtomicWriteFile,
+                ConfirmedServiceChoice::ReinitializeDevice,
+            ]
+        );
+        assert!(matches!(
+            reinitialized_state(&bacnet, 0),
+            ReinitializedState::StartRestore
+        ));
+        assert!(matches!(
+            reinitialized_state(&bacnet, 5),
+            ReinitializedState::AbortRestore
+        ));
+        match bacnet.io.sent_request(4) {
+            ConfirmedRequestService::AtomicWriteFile(x) => match x.data {
+                FileData::Stream {
+                    start_position,
+                    data,
+                } => {
+                    assert_eq!(start_position, 142);
+                    assert_eq!(data.inner.len(), 142);
+                }
+                x => panic!("unexpected file data {:?}", x),
+            },
+            x => panic!("unexpected request {:?}", x),
+        }
+        assert_eq!(delay.total_ms.get(), 0);
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn only_an_unknown_state_property_means_ready() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let delay = FakeDelay::default();
+        let mut buf = vec![0; 1500];
+        let restore = BackupRestore::new(DEVICE_ID, None);
+
+        // a device that predates Backup_And_Restore_State
+        bacnet.io.push(Reply::Error(
+            ConfirmedServiceChoice::ReadProperty,
+            ErrorClass::Property,
+            ErrorCode::UnknownProperty,
+        ));
+        let result = restore
+            .wait_while_preparing(&bacnet, &delay, &mut buf, BackupState::PreparingForRestore)
+            .await;
+        assert!(result.is_ok());
+
+        // any other error is passed on
+        bacnet.io.push(Reply::Error(
+            ConfirmedServiceChoice::ReadProperty,
+            ErrorClass::Security,
+            ErrorCode::PasswordFailure,
+        ));
+        let result = restore
+            .wait_while_preparing(&bacnet, &delay, &mut buf, BackupState::PreparingForRestore)
+            .await;
+        assert!(matches!(
+            result,
+            Err(BacnetError::Bacnet(e)) if matches!(e.error_code, ErrorCode::PasswordFailure)
+        ));
+    }
+}
@@ -0,0 +1,210 @@
+/// A server side Channel object (addendum 2010-aa) that fans values out to the object properties it references
+/// A channel is a member of one or more control groups and is identified within them by its channel number.
+/// When a WriteGroup request for one of its control groups contains a value for its channel number the value becomes the present value
+/// of the channel and is written to every member of its List_Of_Object_Property_References.
+/// Writing the members is left to the application (through a callback) because the members can be local objects or properties on other devices.
+use alloc::vec::Vec;
+
+use crate::{
+    application_protocol::{
+        primitives::property_reference::DeviceObjectPropertyReference,
+        services::write_group::{ChannelValue, WriteGroup},
+    },
+    common::{error::Error, object_id::ObjectId},
+};
+
+// the lowest write priority, used when a write does not specify one
+const LOWEST_PRIORITY: u8 = 16;
+
+// BACnetWriteStatus
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WriteStatus {
+    Idle = 0,
+    InProgress = 1,
+    Successful = 2,
+    Failed = 3,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Channel<'a> {
+    pub object_id: ObjectId,
+    pub channel_number: u16,
+    pub control_groups: Vec<u32>,
+    pub references: Vec<DeviceObjectPropertyReference>,
+    pub present_value: ChannelValue<'a>,
+    pub last_priority: u8,
+    pub write_status: WriteStatus,
+}
+
+impl<'a> Channel<'a> {
+    pub fn new(object_id: ObjectId, channel_number: u16) -> Self {
+        Self {
+            object_id,
+            channel_number,
+            control_groups: Vec::new(),
+            references: Vec::new(),
+            present_value: ChannelValue::Null,
+            last_priority: LOWEST_PRIORITY,
+            write_status: WriteStatus::Idle,
+        }
+    }
+
+    pub fn is_member_of(&self, group_number: u32) -> bool {
+        self.control_groups.contains(&group_number)
+    }
+
+    /// Applies a WriteGroup request to the channel
+    /// Returns false if the request does not address this channel (wrong control group or channel number), otherwise see `write`
+    pub fn write_group<F>(&mut self, request: &WriteGroup<'a>, write: F) -> bool
+    where
+        F: FnMut(&DeviceObjectPropertyReference, &ChannelValue<'a>, u8) -> Result<(), Error>,
+    {
+        if !self.is_member_of(request.group_number) {
+            return false;
+        }
+
+        // if the channel appears more than once in the change list then the last value wins
+        let item = request
+            .change_list
+            .iter()
+            .rev()
+            .find(|x| x.channel == self.channel_number);
+
+        match item {
+            Some(item) => {
+                let priority = item.overriding_priority.unwrap_or(request.write_priority);
+                self.write(item.value.clone(), priority, write);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sets the present value of the channel and writes it to every member at the given priority
+    /// Members that fail to be written do not stop the others from being written but the write status is set to failed.
+    pub fn write<F>(&mut self, value: ChannelValue<'a>, priority: u8, mut write: F)
+    where
+        F: FnMut(&DeviceObjectPropertyReference, &ChannelValue<'a>, u8) -> Result<(), Error>,
+    {
+        self.write_status = WriteStatus::InProgress;
+        self.present_value = value;
+        self.last_priority = priority;
+
+        let mut failed = false;
+        for reference in self.references.iter() {
+            failed |= write(reference, &self.present_value, priority).is_err();
+        }
+
+        self.write_status = if failed {
+            WriteStatus::Failed
+        } else {
+            WriteStatus::Successful
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use crate::{
+        application_protocol::{
+            primitives::{
+                data_value::ApplicationDataValue, property_reference::DeviceObjectPropertyReference,
+            },
+            services::write_group::{ChannelValue, GroupChannelValue, WriteGroup},
+        },
+        common::{
+            error::Error,
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+        },
+    };
+
+    use super::{Channel, WriteStatus};
+
+    fn channel() -> Channel<'static> {
+        let mut channel = Channel::new(ObjectId::new(ObjectType::ObjectChannel, 1), 5);
+        channel.control_groups.push(100);
+        for id in [1, 2] {
+            let object_id = ObjectId::new(ObjectType::ObjectAnalogOutput, id);
+            let reference =
+                DeviceObjectPropertyReference::new(object_id, PropertyId::PropPresentValue);
+            channel.references.push(reference);
+        }
+        channel
+    }
+
+    fn request(group_number: u32, channel: u16) -> WriteGroup<'static> {
+        let change_list = vec![
+            GroupChannelValue::new(4, None, ChannelValue::Null),
+            GroupChannelValue::new(
+                channel,
+                Some(8),
+                ChannelValue::Value(ApplicationDataValue::Real(50.0)),
+            ),
+        ];
+        WriteGroup::new(group_number, 12, change_list, None)
+    }
+
+    #[test]
+    fn writes_its_value_from_a_matching_write_group() {
+        let mut channel = channel();
+        let mut written = Vec::new();
+
+        let applied = channel.write_group(&request(100, 5), |reference, value, priority| {
+            assert!(matches!(
+                value,
+                ChannelValue::Value(ApplicationDataValue::Real(x)) if *x == 50.0
+            ));
+            written.push((reference.object_id.id, priority));
+            Ok(())
+        });
+
+        assert!(applied);
+        assert_eq!(written, vec![(1, 8), (2, 8)]); // the overriding priority wins
+        assert_eq!(channel.last_priority, 8);
+        assert_eq!(channel.write_status, WriteStatus::Successful);
+    }
+
+    #[test]
+    fn ignores_a_write_group_for_another_channel_or_group() {
+        let mut channel = channel();
+        let mut written = 0;
+
+        assert!(!channel.write_group(&request(100, 6), |_, _, _| {
+            written += 1;
+            Ok(())
+        }));
+        assert!(!channel.write_group(&request(101, 5), |_, _, _| {
+            written += 1;
+            Ok(())
+        }));
+
+        assert_eq!(written, 0);
+        assert!(matches!(channel.present_value, ChannelValue::Null));
+        assert_eq!(channel.write_status, WriteStatus::Idle);
+    }
+
+    #[test]
+    fn keeps_writing_after_a_member_fails() {
+        let mut channel = channel();
+        let mut written = Vec::new();
+
+        channel.write(ChannelValue::Null, 10, |reference, _, _| {
+            written.push(reference.object_id.id);
+            if reference.object_id.id == 1 {
+                Err(Error::InvalidValue("write access denied"))
+            } else {
+                Ok(())
+            }
+        });
+
+        assert_eq!(written, vec![1, 2]);
+        assert_eq!(channel.last_priority, 10);
+        assert_eq!(channel.write_status, WriteStatus::Failed);
+    }
+}
@@ -0,0 +1,160 @@
+/// A scripted stand-in for a device on the network, used to test the procedures in this module
+/// Each read returns the next scripted reply. Acks and errors are addressed to the invoke_id of the last confirmed request written.
+/// Everything written is recorded so that tests can check which requests were sent and in what order.
+use alloc::{collections::VecDeque, vec::Vec};
+use core::cell::{Cell, RefCell};
+
+use maybe_async::maybe_async;
+
+use crate::{
+    application_protocol::{
+        application_pdu::{ApduType, ApplicationPdu},
+        confirmed::{
+            ComplexAck, ComplexAckService, ConfirmedBacnetError, ConfirmedRequestService,
+            ConfirmedServiceChoice, SimpleAck,
+        },
+    },
+    common::{
+        io::{Reader, Writer},
+        spec::{ErrorClass, ErrorCode},
+    },
+    network_protocol::{
+        data_link::{DataLink, DataLinkFunction},
+        network_pdu::{MessagePriority, NetworkMessage, NetworkPdu},
+    },
+};
+
+use super::{Delay, NetworkIo};
+
+// bvlc header (4 bytes) followed by an npdu without addresses (2 bytes)
+const APDU_OFFSET: usize = 6;
+
+#[derive(Debug)]
+pub enum Reply {
+    ComplexAck(ComplexAckService<'static>),
+    SimpleAck(ConfirmedServiceChoice),
+    Error(ConfirmedServiceChoice, ErrorClass, ErrorCode),
+    Message(ApplicationPdu<'static>), // a message the device sends on its own (e.g. a notification)
+}
+
+#[derive(Debug, Default)]
+pub struct FakeNetwork {
+    replies: RefCell<VecDeque<Reply>>,
+    sent: RefCell<Vec<Vec<u8>>>,
+}
+
+impl FakeNetwork {
+    pub fn push(&self, reply: Reply) {
+        self.replies.borrow_mut().push_back(reply);
+    }
+
+    pub fn remaining_replies(&self) -> usize {
+        self.replies.borrow().len()
+    }
+
+    /// The service choice of every confirmed request written so far
+    pub fn sent_confirmed(&self) -> Vec<ConfirmedServiceChoice> {
+        self.sent
+            .borrow()
+            .iter()
+            .filter(|x| x[APDU_OFFSET] >> 4 == ApduType::ConfirmedServiceRequest as u8)
+            .map(|x| x[APDU_OFFSET + 3].try_into().unwrap())
+            .collect()
+    }
+
+    /// Decodes the confirmed request at `index` of the confirmed requests written so far
+    pub fn sent_request(&self, index: usize) -> ConfirmedRequestService<'static> {
+        let buf = self
+            .sent
+            .borrow()
+            .iter()
+            .filter(|x| x[APDU_OFFSET] >> 4 == ApduType::ConfirmedServiceRequest as u8)
+            .nth(index)
+            .unwrap()
+            .clone();
+        let data_link = DataLink::decode(&mut Reader::new_with_len(buf.len()), &buf).unwrap();
+        match data_link.npdu.unwrap().network_message {
+            NetworkMessage::Apdu(ApplicationPdu::ConfirmedRequest(x)) => x.service,
+            x => panic!("unexpected message {:?}", x),
+        }
+    }
+
+    /// Decodes the message at `index` of everything written so far (e.g. an ack sent in reply to a request)
+    pub fn sent_apdu(&self, index: usize) -> ApplicationPdu<'static> {
+        let buf = self.sent.borrow()[index].clone();
+        let data_link = DataLink::decode(&mut Reader::new_with_len(buf.len()), &buf).unwrap();
+        match data_link.npdu.unwrap().network_message {
+            NetworkMessage::Apdu(x) => x,
+            x => panic!("unexpected message {:?}", x),
+        }
+    }
+
+    pub fn sent_count(&self) -> usize {
+        self.sent.borrow().len()
+    }
+
+    // the invoke_id of the last confirmed request written
+    fn invoke_id(&self) -> u8 {
+        self.sent
+            .borrow()
+            .iter()
+            .rev()
+            .find(|x| x[APDU_OFFSET] >> 4 == ApduType::ConfirmedServiceRequest as u8)
+            .map(|x| x[APDU_OFFSET + 2])
+            .unwrap_or(0)
+    }
+}
+
+#[maybe_async(AFIT)]
+impl NetworkIo for FakeNetwork {
+    type Error = ();
+
+    async fn read(&self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        // running out of replies looks like a timeout
+        let reply = self.replies.borrow_mut().pop_front().ok_or(())?;
+        let invoke_id = self.invoke_id();
+        let apdu = match reply {
+            Reply::ComplexAck(service) => {
+                ApplicationPdu::ComplexAck(ComplexAck { invoke_id, service })
+            }
+            Reply::SimpleAck(service_choice) => ApplicationPdu::SimpleAck(SimpleAck {
+                invoke_id,
+                service_choice,
+            }),
+            Reply::Error(service_choice, error_class, error_code) => {
+                ApplicationPdu::Error(ConfirmedBacnetError {
+                    invoke_id,
+                    service_choice,
+                    error_class,
+                    error_code,
+                })
+            }
+            Reply::Message(apdu) => apdu,
+        };
+
+        let message = NetworkMessage::Apdu(apdu);
+        let npdu = NetworkPdu::new(None, None, false, MessagePriority::Normal, message);
+        let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
+        let mut writer = Writer::new(buf);
+        data_link.encode(&mut writer);
+        Ok(writer.index)
+    }
+
+    async fn write(&self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.sent.borrow_mut().push(buf.to_vec());
+        Ok(buf.len())
+    }
+}
+
+// adds up how long a procedure asked to wait instead of waiting
+#[derive(Debug, Default)]
+pub struct FakeDelay {
+    pub total_ms: Cell<u32>,
+}
+
+#[maybe_async(AFIT)]
+impl Delay for FakeDelay {
+    async fn delay_ms(&self, ms: u32) {
+        self.total_ms.set(self.total_ms.get() + ms);
+    }
+}
@@ -12,22 +12,49 @@ use core::{
     sync::atomic::{AtomicU8, Ordering},
 };
 
+use alloc::vec::Vec;
 use maybe_async::maybe_async;
 
 use crate::{
     application_protocol::{
         application_pdu::ApplicationPdu,
         confirmed::{
-            ComplexAck, ComplexAckService, ConfirmedRequest, ConfirmedRequestService, SimpleAck,
+            ComplexAck, ComplexAckService, ConfirmedBacnetError, ConfirmedRequest,
+            ConfirmedRequestService, ConfirmedServiceChoice, Reject, SimpleAck,
         },
+        primitives::data_value::{ApplicationDataValue, BitString, OctetString},
         services::{
+            acknowledge_alarm::AcknowledgeAlarm,
+            atomic_read_file::{AtomicReadFile, AtomicReadFileAck, FileData, FileRecords},
+            atomic_write_file::{AtomicWriteFile, AtomicWriteFileAck},
+            audit_log_query::{
+                AuditLogQuery, AuditLogQueryAck, AuditLogQueryParameters, AuditLogRecord,
+            },
+            audit_notification::AuditNotificationRequest,
             change_of_value::{CovNotification, SubscribeCov},
+            change_of_value_multiple::{CovNotificationMultiple, SubscribeCovPropertyMultiple},
+            create_object::CreateObject,
+            delete_object::DeleteObject,
+            device_communication_control::DeviceCommunicationControl,
+            get_alarm_summary::{GetAlarmSummary, GetAlarmSummaryAck},
+            get_enrollment_summary::{GetEnrollmentSummary, GetEnrollmentSummaryAck},
+            get_event_information::{EventSummary, GetEventInformation, GetEventInformationAck},
             i_am::IAm,
-            read_property::{ReadProperty, ReadPropertyAck},
+            life_safety_operation::LifeSafetyOperationRequest,
+            list_element::{AddListElement, RemoveListElement},
+            private_transfer::{PrivateTransfer, PrivateTransferAck},
+            read_property::{ReadProperty, ReadPropertyAck, ReadPropertyValue},
             read_property_multiple::{ReadPropertyMultiple, ReadPropertyMultipleAck},
-            read_range::{ReadRange, ReadRangeAck},
-            time_synchronization::TimeSynchronization,
+            read_range::{
+                ReadRange, ReadRangeAck, ReadRangeByPosition, ReadRangeItem, ReadRangeRequestType,
+            },
+            reinitialize_device::ReinitializeDevice,
+            text_message::TextMessage,
+            time_synchronization::{TimeSynchronization, UtcTimeSynchronization},
+            virtual_terminal::{VtClose, VtData, VtDataAck, VtOpen, VtOpenAck},
+            who_am_i::{WhoAmI, YouAre},
             who_is::WhoIs,
+            write_group::WriteGroup,
             write_property::WriteProperty,
         },
         unconfirmed::UnconfirmedRequest,
@@ -35,6 +62,8 @@ use crate::{
     common::{
         error::Error,
         io::{Reader, Writer},
+        object_id::ObjectId,
+        property_id::PropertyId,
     },
     network_protocol::{
         data_link::{DataLink, DataLinkFunction},
@@ -42,6 +71,24 @@ use crate::{
     },
 };
 
+pub mod backup_restore;
+pub mod channel;
+pub mod private_transfer_registry;
+pub mod provisioning;
+pub mod subscription_manager;
+pub mod time_master;
+pub mod trend_log;
+pub mod vt_session;
+
+#[cfg(test)]
+mod fake_network;
+
+// the largest unsegmented apdu we ask for in our confirmed requests
+const MAX_APDU: usize = 1476;
+
+// room for the network headers and the tags surrounding file data in an atomic read or write file message
+const FILE_CHUNK_OVERHEAD: usize = 64;
+
 #[derive(Debug)]
 pub struct Bacnet<T>
 where
@@ -70,6 +117,13 @@ pub trait NetworkIo {
     async fn write(&self, buf: &[u8]) -> Result<usize, Self::Error>;
 }
 
+// used by long running procedures that have to wait for a device between requests
+#[allow(async_fn_in_trait)]
+#[maybe_async(AFIT)]
+pub trait Delay {
+    async fn delay_ms(&self, ms: u32);
+}
+
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum BacnetError<T>
@@ -79,6 +133,7 @@ where
     Io(T::Error),
     Codec(Error),
     InvokeId(InvokeIdError),
+    Bacnet(ConfirmedBacnetError),
 }
 
 impl<T: NetworkIo> From<Error> for BacnetError<T> {
@@ -213,177 +268,1061 @@ where
         Ok(None)
     }
 
+    #[maybe_async()]
+    pub async fn subscribe_change_of_value_property_multiple(
+        &self,
+        buf: &mut [u8],
+        request: SubscribeCovPropertyMultiple<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::SubscribeCovPropertyMultiple(request);
+        let _ack = self.send_and_receive_simple_ack(buf, service).await?;
+        Ok(())
+    }
+
     #[maybe_async()]
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
-    pub async fn read_range<'a>(
+    pub async fn read_change_of_value_multiple<'a>(
         &self,
         buf: &'a mut [u8],
-        request: ReadRange,
-    ) -> Result<ReadRangeAck<'a>, BacnetError<T>> {
-        let service = ConfirmedRequestService::ReadRange(request);
-        let ack = self.send_and_receive_complex_ack(buf, service).await?;
-        match ack.service {
-            ComplexAckService::ReadRange(ack) => Ok(ack),
-            _ => Err(BacnetError::Codec(Error::ConvertDataLink(
-                "apdu message is not a ComplexAckService ReadRangeAck",
-            ))),
-        }
+    ) -> Result<Option<CovNotificationMultiple<'a>>, BacnetError<T>> {
+        let n = self.io.read(buf).await.map_err(BacnetError::Io)?;
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, &buf[..n])?;
+
+        if let Some(npdu) = message.npdu {
+            if let NetworkMessage::Apdu(ApplicationPdu::UnconfirmedRequest(
+                UnconfirmedRequest::CovNotificationMultiple(x),
+            )) = npdu.network_message
+            {
+                return Ok(Some(x));
+            }
+        };
+
+        Ok(None)
     }
 
+    /// Sends a change of value notification to a subscriber without waiting for a reply
     #[maybe_async()]
-    pub async fn write_property<'a>(
+    pub async fn notify_change_of_value(
         &self,
         buf: &mut [u8],
-        request: WriteProperty<'_>,
+        notification: CovNotification<'_>,
     ) -> Result<(), BacnetError<T>> {
-        let service = ConfirmedRequestService::WriteProperty(request);
+        let service = UnconfirmedRequest::CovNotification(notification);
+        self.send_unconfirmed(buf, service).await
+    }
+
+    /// Sends a change of value notification to a subscriber and waits for it to be acknowledged
+    #[maybe_async()]
+    pub async fn notify_change_of_value_confirmed(
+        &self,
+        buf: &mut [u8],
+        notification: CovNotification<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::CovNotification(notification);
         let _ack = self.send_and_receive_simple_ack(buf, service).await?;
         Ok(())
     }
 
+    /// Sends a change of value notification for many objects without waiting for a reply
     #[maybe_async()]
-    pub async fn time_sync(
+    pub async fn notify_change_of_value_multiple(
         &self,
         buf: &mut [u8],
-        request: TimeSynchronization,
+        notification: CovNotificationMultiple<'_>,
     ) -> Result<(), BacnetError<T>> {
-        let service = UnconfirmedRequest::TimeSynchronization(request);
+        let service = UnconfirmedRequest::CovNotificationMultiple(notification);
         self.send_unconfirmed(buf, service).await
     }
 
+    /// Sends a change of value notification for many objects and waits for it to be acknowledged
+    #[maybe_async()]
+    pub async fn notify_change_of_value_multiple_confirmed(
+        &self,
+        buf: &mut [u8],
+        notification: CovNotificationMultiple<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::CovNotificationMultiple(notification);
+        let _ack = self.send_and_receive_simple_ack(buf, service).await?;
+        Ok(())
+    }
+
+    /// Acknowledges an alarm or event notification on the device that raised it
+    #[maybe_async()]
+    pub async fn acknowledge_alarm(
+        &self,
+        buf: &mut [u8],
+        request: AcknowledgeAlarm<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::AcknowledgeAlarm(request);
+        let _ack = self.send_and_receive_simple_ack(buf, service).await?;
+        Ok(())
+    }
+
     #[maybe_async()]
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
-    async fn send_and_receive_complex_ack<'a>(
+    pub async fn get_event_information<'a>(
         &self,
         buf: &'a mut [u8],
-        service: ConfirmedRequestService<'_>,
-    ) -> Result<ComplexAck<'a>, BacnetError<T>> {
-        let invoke_id = self.send_confirmed(buf, service).await?;
-
-        loop {
-            // receive reply
-            let n = self.io.read(buf).await.map_err(BacnetError::Io)?;
-            let buf = &buf[..n];
+        request: GetEventInformation,
+    ) -> Result<GetEventInformationAck<'a>, BacnetError<T>> {
+        let service = ConfirmedRequestService::GetEventInformation(request);
+        let ack = self.send_and_receive_complex_ack(buf, service).await?;
+        match ack.service {
+            ComplexAckService::GetEventInformation(ack) => Ok(ack),
+            _ => Err(BacnetError::Codec(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService GetEventInformationAck",
+            ))),
+        }
+    }
 
-            // use the DataLink codec to decode the bytes
-            let mut reader = Reader::default();
-            let message = DataLink::decode(&mut reader, buf).map_err(BacnetError::Codec)?;
+    /// Fetches the event summaries of all objects on the device that are in alarm or have unacknowledged transitions
+    /// The device may only return a page of summaries at a time so this keeps asking for more until there are none left
+    #[maybe_async()]
+    pub async fn get_all_event_information(
+        &self,
+        buf: &mut [u8],
+    ) -> Result<Vec<EventSummary>, BacnetError<T>> {
+        let mut event_summaries: Vec<EventSummary> = Vec::new();
 
-            match message.npdu {
-                Some(x) => match x.network_message {
-                    NetworkMessage::Apdu(ApplicationPdu::ComplexAck(ack)) => {
-                        // ignore earier messages
-                        if ack.invoke_id < invoke_id {
-                            continue;
-                        }
+        loop {
+            let last_received_object_id = event_summaries.last().map(|x| x.object_id);
+            let request = GetEventInformation::new(last_received_object_id);
+            let ack = self.get_event_information(buf, request).await?;
+            let count = ack.event_summaries.len();
+            event_summaries.extend(ack.event_summaries);
 
-                        // return message is expected to have the same invoke_id as the request (return error if later invoke id)
-                        Self::check_invoke_id(invoke_id, ack.invoke_id)?;
-                        return Ok(ack);
-                    }
-                    _ => continue,
-                },
-                _ => continue,
+            // guard against a device that keeps saying there is more without returning anything
+            if !ack.more_events || count == 0 {
+                return Ok(event_summaries);
             }
         }
     }
 
-    /*
-
-        #[maybe_async()]
+    #[maybe_async()]
     #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
-    async fn send_and_receive_complex_ack<'a>(
+    pub async fn get_alarm_summary<'a>(
         &self,
         buf: &'a mut [u8],
-        service: ConfirmedRequestService<'_>,
-    ) -> Result<ComplexAck<'a>, BacnetError<T>> {
-        let invoke_id = self.send_confirmed(buf, service).await?;
+    ) -> Result<GetAlarmSummaryAck<'a>, BacnetError<T>> {
+        let service = ConfirmedRequestService::GetAlarmSummary(GetAlarmSummary::new());
+        let ack = self.send_and_receive_complex_ack(buf, service).await?;
+        match ack.service {
+            ComplexAckService::GetAlarmSummary(ack) => Ok(ack),
+            _ => Err(BacnetError::Codec(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService GetAlarmSummaryAck",
+            ))),
+        }
+    }
 
-        for _ in 1..5 {
-            // receive reply
-            let n = self.io.read(buf).await.map_err(BacnetError::Io)?;
-            let buf = &buf[..n];
+    #[maybe_async()]
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub async fn get_enrollment_summary<'a>(
+        &self,
+        buf: &'a mut [u8],
+        request: GetEnrollmentSummary<'_>,
+    ) -> Result<GetEnrollmentSummaryAck<'a>, BacnetError<T>> {
+        let service = ConfirmedRequestService::GetEnrollmentSummary(request);
+        let ack = self.send_and_receive_complex_ack(buf, service).await?;
+        match ack.service {
+            ComplexAckService::GetEnrollmentSummary(ack) => Ok(ack),
+            _ => Err(BacnetError::Codec(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService GetEnrollmentSummaryAck",
+            ))),
+        }
+    }
 
-            // use the DataLink codec to decode the bytes
-            let mut reader = Reader::default();
-            let message = DataLink::decode(&mut reader, buf).map_err(BacnetError::Codec)?;
+    #[maybe_async()]
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub async fn atomic_read_file<'a>(
+        &self,
+        buf: &'a mut [u8],
+        request: AtomicReadFile,
+    ) -> Result<AtomicReadFileAck<'a>, BacnetError<T>> {
+        let service = ConfirmedRequestService::AtomicReadFile(request);
+        let ack = self.send_and_receive_complex_ack(buf, service).await?;
+        match ack.service {
+            ComplexAckService::AtomicReadFile(ack) => Ok(ack),
+            _ => Err(BacnetError::Codec(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService AtomicReadFileAck",
+            ))),
+        }
+    }
 
-            match message.npdu {
-                Some(x) => match x.network_message {
-                    NetworkMessage::Apdu(ApplicationPdu::ComplexAck(ack)) => {
-                        // return message is expected to have the same invoke_id as the request
-                        Self::check_invoke_id(invoke_id, ack.invoke_id)?;
-                        return Ok(ack),
-                    }
-                    NetworkMessage::Apdu(ApplicationPdu::UnconfirmedRequest()) => {
-                        continue;
-                    }
-                    _ => return Err(Error::ConvertDataLink(
-                        "npdu message is not an apdu complex ack",
-                    )),
-                },
-                _ => return Err(Error::ConvertDataLink("no npdu defined in message")),
-            }
+    #[maybe_async()]
+    pub async fn atomic_write_file(
+        &self,
+        buf: &mut [u8],
+        request: AtomicWriteFile<'_>,
+    ) -> Result<AtomicWriteFileAck, BacnetError<T>> {
+        let service = ConfirmedRequestService::AtomicWriteFile(request);
+        let ack = self.send_and_receive_complex_ack(buf, service).await?;
+        match ack.service {
+            ComplexAckService::AtomicWriteFile(ack) => Ok(ack),
+            _ => Err(BacnetError::Codec(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService AtomicWriteFileAck",
+            ))),
         }
+    }
 
-        Ok(ack)
+    /// Creates an object on the device and returns the object id the device assigned to it
+    #[maybe_async()]
+    pub async fn create_object(
+        &self,
+        buf: &mut [u8],
+        request: CreateObject<'_>,
+    ) -> Result<ObjectId, BacnetError<T>> {
+        let service = ConfirmedRequestService::CreateObject(request);
+        let ack = self.send_and_receive_complex_ack(buf, service).await?;
+        match ack.service {
+            ComplexAckService::CreateObject(ack) => Ok(ack.object_id),
+            _ => Err(BacnetError::Codec(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService CreateObjectAck",
+            ))),
+        }
     }
-     */
 
     #[maybe_async()]
-    async fn send_and_receive_simple_ack<'a>(
+    pub async fn delete_object(
         &self,
         buf: &mut [u8],
-        service: ConfirmedRequestService<'_>,
-    ) -> Result<SimpleAck, BacnetError<T>> {
-        let invoke_id = self.send_confirmed(buf, service).await?;
+        request: DeleteObject,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::DeleteObject(request);
+        let _ack = self.send_and_receive_simple_ack(buf, service).await?;
+        Ok(())
+    }
 
-        // receive reply
-        let n = self.io.read(buf).await.map_err(BacnetError::Io)?;
-        let buf = &buf[..n];
+    #[maybe_async()]
+    pub async fn add_list_element(
+        &self,
+        buf: &mut [u8],
+        request: AddListElement<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::AddListElement(request);
+        let _ack = self.send_and_receive_simple_ack(buf, service).await?;
+        Ok(())
+    }
 
-        // use the DataLink codec to decode the bytes
-        let mut reader = Reader::default();
-        let message = DataLink::decode(&mut reader, buf).map_err(BacnetError::Codec)?;
+    #[maybe_async()]
+    pub async fn remove_list_element(
+        &self,
+        buf: &mut [u8],
+        request: RemoveListElement<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::RemoveListElement(request);
+        let _ack = self.send_and_receive_simple_ack(buf, service).await?;
+        Ok(())
+    }
 
-        // TODO: return bacnet error if the server returns one
-        // return message is expected to be a ComplexAck
-        let ack: SimpleAck = message.try_into().map_err(BacnetError::Codec)?;
+    #[maybe_async()]
+    pub async fn device_communication_control(
+        &self,
+        buf: &mut [u8],
+        request: DeviceCommunicationControl<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::DeviceCommunicationControl(request);
+        let _ack = self.send_and_receive_simple_ack(buf, service).await?;
+        Ok(())
+    }
 
-        // return message is expected to have the same invoke_id as the request
-        Self::check_invoke_id(invoke_id, ack.invoke_id)?;
+    #[maybe_async()]
+    pub async fn reinitialize_device(
+        &self,
+        buf: &mut [u8],
+        request: ReinitializeDevice<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::ReinitializeDevice(request);
+        let _ack = self.send_and_receive_simple_ack(buf, service).await?;
+        Ok(())
+    }
 
-        Ok(ack)
+    /// Sends a vendor specific request and waits for the result
+    #[maybe_async()]
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub async fn private_transfer<'a>(
+        &self,
+        buf: &'a mut [u8],
+        request: PrivateTransfer<'_>,
+    ) -> Result<PrivateTransferAck<'a>, BacnetError<T>> {
+        let service = ConfirmedRequestService::PrivateTransfer(request);
+        let ack = self.send_and_receive_complex_ack(buf, service).await?;
+        match ack.service {
+            ComplexAckService::PrivateTransfer(ack) => Ok(ack),
+            _ => Err(BacnetError::Codec(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService PrivateTransferAck",
+            ))),
+        }
     }
 
+    /// Sends a vendor specific request without waiting for a reply
     #[maybe_async()]
-    async fn send_unconfirmed(
+    pub async fn private_transfer_unconfirmed(
         &self,
         buf: &mut [u8],
-        service: UnconfirmedRequest<'_>,
+        request: PrivateTransfer<'_>,
     ) -> Result<(), BacnetError<T>> {
-        let apdu = ApplicationPdu::UnconfirmedRequest(service);
-        let message = NetworkMessage::Apdu(apdu);
-        let npdu = NetworkPdu::new(None, None, true, MessagePriority::Normal, message);
-        let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
-
-        let mut writer = Writer::new(buf);
-        data_link.encode(&mut writer);
+        let service = UnconfirmedRequest::PrivateTransfer(request);
+        self.send_unconfirmed(buf, service).await
+    }
 
-        // send packet
-        let buffer = writer.to_bytes();
-        self.io.write(buffer).await.map_err(BacnetError::Io)?;
+    /// Silences or resets life safety objects (all of them in the device if the request has no object id)
+    #[maybe_async()]
+    pub async fn life_safety_operation(
+        &self,
+        buf: &mut [u8],
+        request: LifeSafetyOperationRequest<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::LifeSafetyOperation(request);
+        let _ack = self.send_and_receive_simple_ack(buf, service).await?;
         Ok(())
     }
 
+    /// Sends a text message and waits for the receiving device to acknowledge it
     #[maybe_async()]
-    async fn send_confirmed(
+    pub async fn text_message(
         &self,
         buf: &mut [u8],
-        service: ConfirmedRequestService<'_>,
-    ) -> Result<u8, BacnetError<T>> {
-        let invoke_id = self.get_then_inc_invoke_id();
-        let apdu = ApplicationPdu::ConfirmedRequest(ConfirmedRequest::new(invoke_id, service));
+        request: TextMessage<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::TextMessage(request);
+        let _ack = self.send_and_receive_simple_ack(buf, service).await?;
+        Ok(())
+    }
+
+    #[maybe_async()]
+    pub async fn text_message_unconfirmed(
+        &self,
+        buf: &mut [u8],
+        request: TextMessage<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = UnconfirmedRequest::TextMessage(request);
+        self.send_unconfirmed(buf, service).await
+    }
+
+    /// Reports operations carried out on (or requested by) this device to an audit reporter or audit log
+    #[maybe_async()]
+    pub async fn audit_notification(
+        &self,
+        buf: &mut [u8],
+        request: AuditNotificationRequest<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::AuditNotification(request);
+        let _ack = self.send_and_receive_simple_ack(buf, service).await?;
+        Ok(())
+    }
+
+    #[maybe_async()]
+    pub async fn audit_notification_unconfirmed(
+        &self,
+        buf: &mut [u8],
+        request: AuditNotificationRequest<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = UnconfirmedRequest::AuditNotification(request);
+        self.send_unconfirmed(buf, service).await
+    }
+
+    #[maybe_async()]
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub async fn audit_log_query<'a>(
+        &self,
+        buf: &'a mut [u8],
+        request: AuditLogQuery<'_>,
+    ) -> Result<AuditLogQueryAck<'a>, BacnetError<T>> {
+        let service = ConfirmedRequestService::AuditLogQuery(request);
+        let ack = self.send_and_receive_complex_ack(buf, service).await?;
+        match ack.service {
+            ComplexAckService::AuditLogQuery(ack) => Ok(ack),
+            _ => Err(BacnetError::Codec(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService AuditLogQueryAck",
+            ))),
+        }
+    }
+
+    /// Collects all the records of an Audit Log object that match the query parameters
+    /// The records are requested `page_size` at a time, each page starting after the last sequence number received
+    #[maybe_async()]
+    pub async fn read_audit_log(
+        &self,
+        buf: &mut [u8],
+        audit_log: ObjectId,
+        parameters: AuditLogQueryParameters<'_>,
+        page_size: u16,
+    ) -> Result<Vec<AuditLogRecord<'static>>, BacnetError<T>> {
+        let mut records: Vec<AuditLogRecord> = Vec::new();
+
+        loop {
+            let start_at_sequence_number = records.last().map(|x| x.sequence_number + 1);
+            let request = AuditLogQuery::new(
+                audit_log,
+                parameters.clone(),
+                start_at_sequence_number,
+                page_size,
+            );
+            let ack = self.audit_log_query(buf, request).await?;
+            let count = ack.records.len();
+            records.extend(ack.records);
+
+            // guard against a device that keeps saying there is more without returning anything
+            if ack.no_more_items || count == 0 {
+                return Ok(records);
+            }
+        }
+    }
+
+    /// Opens a virtual terminal session on the remote device (see `vt_session` for a higher level interface)
+    #[maybe_async()]
+    pub async fn vt_open(
+        &self,
+        buf: &mut [u8],
+        request: VtOpen,
+    ) -> Result<VtOpenAck, BacnetError<T>> {
+        let service = ConfirmedRequestService::VtOpen(request);
+        let ack = self.send_and_receive_complex_ack(buf, service).await?;
+        match ack.service {
+            ComplexAckService::VtOpen(ack) => Ok(ack),
+            _ => Err(BacnetError::Codec(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService VtOpenAck",
+            ))),
+        }
+    }
+
+    #[maybe_async()]
+    pub async fn vt_data(
+        &self,
+        buf: &mut [u8],
+        request: VtData<'_>,
+    ) -> Result<VtDataAck, BacnetError<T>> {
+        let service = ConfirmedRequestService::VtData(request);
+        let ack = self.send_and_receive_complex_ack(buf, service).await?;
+        match ack.service {
+            ComplexAckService::VtData(ack) => Ok(ack),
+            _ => Err(BacnetError::Codec(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService VtDataAck",
+            ))),
+        }
+    }
+
+    #[maybe_async()]
+    pub async fn vt_close(
+        &self,
+        buf: &mut [u8],
+        request: VtClose<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::VtClose(request);
+        let _ack = self.send_and_receive_simple_ack(buf, service).await?;
+        Ok(())
+    }
+
+    /// Downloads the entire contents of a File object using stream access
+    /// The file is read in chunks small enough to fit into a single unsegmented apdu and into `buf`
+    #[maybe_async()]
+    pub async fn read_file(
+        &self,
+        buf: &mut [u8],
+        file_id: ObjectId,
+    ) -> Result<Vec<u8>, BacnetError<T>> {
+        let chunk_size = Self::file_chunk_size(buf, MAX_APDU)?;
+        let mut contents: Vec<u8> = Vec::new();
+
+        loop {
+            let request = AtomicReadFile::new_stream(file_id, contents.len() as i32, chunk_size);
+            let ack = self.atomic_read_file(buf, request).await?;
+            let count = match ack.data {
+                FileData::Stream { data, .. } => {
+                    let count = data.inner.len();
+                    contents.extend(data.inner);
+                    count
+                }
+                FileData::Record { .. } => {
+                    return Err(BacnetError::Codec(Error::InvalidValue(
+                        "read_file expected stream access file data",
+                    )))
+                }
+            };
+
+            // guard against a device that never reports the end of the file
+            if ack.end_of_file || count == 0 {
+                return Ok(contents);
+            }
+        }
+    }
+
+    /// Uploads the entire contents of a File object using stream access
+    /// The file is written in chunks small enough to fit into `buf` and into a single unsegmented apdu of at most `max_apdu` bytes.
+    /// Set `max_apdu` to the Max_APDU_Length_Accepted of the device (from its I-Am or from `read_max_apdu`)
+    #[maybe_async()]
+    pub async fn write_file(
+        &self,
+        buf: &mut [u8],
+        file_id: ObjectId,
+        contents: &[u8],
+        max_apdu: usize,
+    ) -> Result<(), BacnetError<T>> {
+        let chunk_size = Self::file_chunk_size(buf, max_apdu)? as usize;
+
+        for (index, chunk) in contents.chunks(chunk_size).enumerate() {
+            let start_position = (index * chunk_size) as i32;
+            let data = FileData::new_stream(start_position, OctetString::new(chunk));
+            let request = AtomicWriteFile::new(file_id, data);
+            self.atomic_write_file(buf, request).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Downloads every record of a File object using record access
+    /// Records are requested `records_per_request` at a time so pick a number whose records fit into a single unsegmented apdu and into `buf`
+    #[maybe_async()]
+    pub async fn read_file_records(
+        &self,
+        buf: &mut [u8],
+        file_id: ObjectId,
+        records_per_request: u32,
+    ) -> Result<Vec<Vec<u8>>, BacnetError<T>> {
+        let mut records: Vec<Vec<u8>> = Vec::new();
+
+        loop {
+            let request =
+                AtomicReadFile::new_record(file_id, records.len() as i32, records_per_request);
+            let ack = self.atomic_read_file(buf, request).await?;
+            let count = match ack.data {
+                FileData::Record { records: chunk, .. } => {
+                    let count = chunk.records.len();
+                    records.extend(chunk.records.into_iter().map(|record| record.inner));
+                    count
+                }
+                FileData::Stream { .. } => {
+                    return Err(BacnetError::Codec(Error::InvalidValue(
+                        "read_file_records expected record access file data",
+                    )))
+                }
+            };
+
+            // guard against a device that never reports the end of the file
+            if ack.end_of_file || count == 0 {
+                return Ok(records);
+            }
+        }
+    }
+
+    /// Writes records to a File object using record access starting at `start_record` (-1 appends them to the end of the file)
+    /// As many whole records as fit into `buf` and into a single unsegmented apdu of at most `max_apdu` bytes are written with each request.
+    /// Set `max_apdu` to the Max_APDU_Length_Accepted of the device (from its I-Am or from `read_max_apdu`)
+    #[maybe_async()]
+    pub async fn write_file_records(
+        &self,
+        buf: &mut [u8],
+        file_id: ObjectId,
+        start_record: i32,
+        records: &[&[u8]],
+        max_apdu: usize,
+    ) -> Result<(), BacnetError<T>> {
+        let chunk_size = Self::file_chunk_size(buf, max_apdu)? as usize;
+        let mut start = 0;
+
+        while start < records.len() {
+            // each record is sent as an octet string with a tag of up to 5 bytes
+            let mut end = start;
+            let mut size = 0;
+            while end < records.len() && size + records[end].len() + 5 <= chunk_size {
+                size += records[end].len() + 5;
+                end += 1;
+            }
+            if end == start {
+                return Err(BacnetError::Codec(Error::Length((
+                    "file record too large to fit into a single message",
+                    records[start].len() as u32,
+                ))));
+            }
+
+            let chunk = records[start..end]
+                .iter()
+                .map(|record| OctetString::new(record))
+                .collect();
+            let position = if start_record < 0 {
+                start_record
+            } else {
+                start_record + start as i32
+            };
+            let data = FileData::new_record(position, FileRecords::new(chunk));
+            let request = AtomicWriteFile::new(file_id, data);
+            self.atomic_write_file(buf, request).await?;
+            start = end;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the Max_APDU_Length_Accepted property of a device
+    #[maybe_async()]
+    pub async fn read_max_apdu(
+        &self,
+        buf: &mut [u8],
+        device_id: ObjectId,
+    ) -> Result<usize, BacnetError<T>> {
+        let request = ReadProperty::new(device_id, PropertyId::PropMaxApduLengthAccepted);
+        let ack = self.read_property(buf, request).await?;
+        match ack.property_value {
+            ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::UnsignedInt(x)) => {
+                Ok(x as usize)
+            }
+            _ => Err(BacnetError::Codec(Error::InvalidValue(
+                "expected an unsigned Max_APDU_Length_Accepted",
+            ))),
+        }
+    }
+
+    // the number of file octets that can be sent or received in a single message
+    // we accept apdus up to MAX_APDU when reading but must stay within what the device accepts when writing
+    fn file_chunk_size(buf: &[u8], max_apdu: usize) -> Result<u32, BacnetError<T>> {
+        let len = buf.len().min(max_apdu).min(MAX_APDU);
+        if len <= FILE_CHUNK_OVERHEAD {
+            return Err(BacnetError::Codec(Error::Length((
+                "buffer or max apdu too small to transfer a file",
+                len as u32,
+            ))));
+        }
+
+        Ok((len - FILE_CHUNK_OVERHEAD) as u32)
+    }
+
+    #[maybe_async()]
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub async fn read_range<'a>(
+        &self,
+        buf: &'a mut [u8],
+        request: ReadRange,
+    ) -> Result<ReadRangeAck<'a>, BacnetError<T>> {
+        let service = ConfirmedRequestService::ReadRange(request);
+        let ack = self.send_and_receive_complex_ack(buf, service).await?;
+        match ack.service {
+            ComplexAckService::ReadRange(ack) => Ok(ack),
+            _ => Err(BacnetError::Codec(Error::ConvertDataLink(
+                "apdu message is not a ComplexAckService ReadRangeAck",
+            ))),
+        }
+    }
+
+    /// Collects all the items of a list property (e.g. Device_Address_Binding or Active_COV_Subscriptions)
+    /// The items are requested by position `page_size` at a time until the device says there are no more
+    #[maybe_async()]
+    pub async fn read_list(
+        &self,
+        buf: &mut [u8],
+        object_id: ObjectId,
+        property_id: PropertyId,
+        page_size: u32,
+    ) -> Result<Vec<ReadRangeItem<'static>>, BacnetError<T>> {
+        let mut items: Vec<ReadRangeItem> = Vec::new();
+
+        loop {
+            // positions start at 1
+            let request_type = ReadRangeRequestType::ByPosition(ReadRangeByPosition {
+                index: items.len() as u32 + 1,
+                count: page_size,
+            });
+            let request = ReadRange::new(object_id, property_id, request_type);
+            let ack = self.read_range(buf, request).await?;
+            let more_items =
+                matches!(&ack.result_flags, BitString::LogBufferResult(x) if x.more_items());
+            let count = ack.item_data.items.len();
+            items.extend(ack.item_data.items);
+
+            // guard against a device that keeps saying there is more without returning anything
+            if !more_items || count == 0 {
+                return Ok(items);
+            }
+        }
+    }
+
+    #[maybe_async()]
+    pub async fn write_property(
+        &self,
+        buf: &mut [u8],
+        request: WriteProperty<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = ConfirmedRequestService::WriteProperty(request);
+        let _ack = self.send_and_receive_simple_ack(buf, service).await?;
+        Ok(())
+    }
+
+    #[maybe_async()]
+    pub async fn time_sync(
+        &self,
+        buf: &mut [u8],
+        request: TimeSynchronization,
+    ) -> Result<(), BacnetError<T>> {
+        let service = UnconfirmedRequest::TimeSynchronization(request);
+        self.send_unconfirmed(buf, service).await
+    }
+
+    #[maybe_async()]
+    pub async fn utc_time_sync(
+        &self,
+        buf: &mut [u8],
+        request: UtcTimeSynchronization,
+    ) -> Result<(), BacnetError<T>> {
+        let service = UnconfirmedRequest::UtcTimeSynchronization(request);
+        self.send_unconfirmed(buf, service).await
+    }
+
+    /// Sends a TimeSynchronization to every device on every network (a global broadcast)
+    #[maybe_async()]
+    pub async fn broadcast_time_sync(
+        &self,
+        buf: &mut [u8],
+        request: TimeSynchronization,
+    ) -> Result<(), BacnetError<T>> {
+        let service = UnconfirmedRequest::TimeSynchronization(request);
+        let dst = Some(DestinationAddress::new(0xffff, None));
+        self.send_unconfirmed_broadcast(buf, service, dst).await
+    }
+
+    /// Sends a UTCTimeSynchronization to every device on every network (a global broadcast)
+    #[maybe_async()]
+    pub async fn broadcast_utc_time_sync(
+        &self,
+        buf: &mut [u8],
+        request: UtcTimeSynchronization,
+    ) -> Result<(), BacnetError<T>> {
+        let service = UnconfirmedRequest::UtcTimeSynchronization(request);
+        let dst = Some(DestinationAddress::new(0xffff, None));
+        self.send_unconfirmed_broadcast(buf, service, dst).await
+    }
+
+    /// Writes values to the channel objects of all devices in a control group
+    #[maybe_async()]
+    pub async fn write_group(
+        &self,
+        buf: &mut [u8],
+        request: WriteGroup<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = UnconfirmedRequest::WriteGroup(request);
+        self.send_unconfirmed(buf, service).await
+    }
+
+    /// Asks a supervisor for a device id and mac address (used by unconfigured devices)
+    #[maybe_async()]
+    pub async fn who_am_i(
+        &self,
+        buf: &mut [u8],
+        request: WhoAmI<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = UnconfirmedRequest::WhoAmI(request);
+        self.send_unconfirmed(buf, service).await
+    }
+
+    /// Assigns a device id and mac address to an unconfigured device
+    /// The device has no address yet so the request is broadcast on its network (the local network if `network_number` is None)
+    #[maybe_async()]
+    pub async fn you_are(
+        &self,
+        buf: &mut [u8],
+        request: YouAre<'_>,
+        network_number: Option<u16>,
+    ) -> Result<(), BacnetError<T>> {
+        let service = UnconfirmedRequest::YouAre(request);
+        let dst = network_number.map(|net| DestinationAddress::new(net, None));
+        self.send_unconfirmed_broadcast(buf, service, dst).await
+    }
+
+    #[maybe_async()]
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    async fn send_and_receive_complex_ack<'a>(
+        &self,
+        buf: &'a mut [u8],
+        service: ConfirmedRequestService<'_>,
+    ) -> Result<ComplexAck<'a>, BacnetError<T>> {
+        let invoke_id = self.send_confirmed(buf, service).await?;
+
+        loop {
+            // receive reply
+            let n = self.io.read(buf).await.map_err(BacnetError::Io)?;
+            let buf = &buf[..n];
+
+            // use the DataLink codec to decode the bytes
+            let mut reader = Reader::default();
+            let message = DataLink::decode(&mut reader, buf).map_err(BacnetError::Codec)?;
+
+            match message.npdu {
+                Some(x) => match x.network_message {
+                    NetworkMessage::Apdu(ApplicationPdu::ComplexAck(ack)) => {
+                        // ignore earier messages
+                        if ack.invoke_id < invoke_id {
+                            continue;
+                        }
+
+                        // return message is expected to have the same invoke_id as the request (return error if later invoke id)
+                        Self::check_invoke_id(invoke_id, ack.invoke_id)?;
+                        return Ok(ack);
+                    }
+                    NetworkMessage::Apdu(ApplicationPdu::Error(error))
+                        if error.invoke_id == invoke_id =>
+                    {
+                        return Err(BacnetError::Bacnet(error));
+                    }
+                    _ => continue,
+                },
+                _ => continue,
+            }
+        }
+    }
+
+    /*
+
+        #[maybe_async()]
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    async fn send_and_receive_complex_ack<'a>(
+        &self,
+        buf: &'a mut [u8],
+        service: ConfirmedRequestService<'_>,
+    ) -> Result<ComplexAck<'a>, BacnetError<T>> {
+        let invoke_id = self.send_confirmed(buf, service).await?;
+
+        for _ in 1..5 {
+            // receive reply
+            let n = self.io.read(buf).await.map_err(BacnetError::Io)?;
+            let buf = &buf[..n];
+
+            // use the DataLink codec to decode the bytes
+            let mut reader = Reader::default();
+            let message = DataLink::decode(&mut reader, buf).map_err(BacnetError::Codec)?;
+
+            match message.npdu {
+                Some(x) => match x.network_message {
+                    NetworkMessage::Apdu(ApplicationPdu::ComplexAck(ack)) => {
+                        // return message is expected to have the same invoke_id as the request
+                        Self::check_invoke_id(invoke_id, ack.invoke_id)?;
+                        return Ok(ack),
+                    }
+                    NetworkMessage::Apdu(ApplicationPdu::UnconfirmedRequest()) => {
+                        continue;
+                    }
+                    _ => return Err(Error::ConvertDataLink(
+                        "npdu message is not an apdu complex ack",
+                    )),
+                },
+                _ => return Err(Error::ConvertDataLink("no npdu defined in message")),
+            }
+        }
+
+        Ok(ack)
+    }
+     */
+
+    #[maybe_async()]
+    async fn send_and_receive_simple_ack(
+        &self,
+        buf: &mut [u8],
+        service: ConfirmedRequestService<'_>,
+    ) -> Result<SimpleAck, BacnetError<T>> {
+        let invoke_id = self.send_confirmed(buf, service).await?;
+
+        // receive reply
+        let n = self.io.read(buf).await.map_err(BacnetError::Io)?;
+        let buf = &buf[..n];
+
+        // use the DataLink codec to decode the bytes
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, buf).map_err(BacnetError::Codec)?;
+
+        // the server responds with an error if it could not process the request
+        if let Some(npdu) = &message.npdu {
+            if let NetworkMessage::Apdu(ApplicationPdu::Error(error)) = &npdu.network_message {
+                Self::check_invoke_id(invoke_id, error.invoke_id)?;
+                return Err(BacnetError::Bacnet(error.clone()));
+            }
+        }
+
+        // return message is expected to be a SimpleAck
+        let ack: SimpleAck = message.try_into().map_err(BacnetError::Codec)?;
+
+        // return message is expected to have the same invoke_id as the request
+        Self::check_invoke_id(invoke_id, ack.invoke_id)?;
+
+        Ok(ack)
+    }
+
+    /// Reads the next packet from the network and returns its application layer payload (if any)
+    /// Use this to receive requests sent to you (e.g. notifications) rather than replies to your own requests
+    #[maybe_async()]
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub async fn receive<'a>(
+        &self,
+        buf: &'a mut [u8],
+    ) -> Result<Option<ApplicationPdu<'a>>, BacnetError<T>> {
+        let n = self.io.read(buf).await.map_err(BacnetError::Io)?;
+        let mut reader = Reader::default();
+        let message = DataLink::decode(&mut reader, &buf[..n])?;
+
+        if let Some(npdu) = message.npdu {
+            if let NetworkMessage::Apdu(apdu) = npdu.network_message {
+                return Ok(Some(apdu));
+            }
+        };
+
+        Ok(None)
+    }
+
+    /// Reads the next packet from the network and returns it if it is a text message
+    /// Confirmed text messages are acknowledged before returning. Anything else is discarded so use `receive` if you need to handle other requests too.
+    #[maybe_async()]
+    #[cfg_attr(feature = "alloc", bacnet_macros::remove_lifetimes_from_fn_args)]
+    pub async fn receive_text_message<'a>(
+        &self,
+        buf: &'a mut [u8],
+    ) -> Result<Option<TextMessage<'a>>, BacnetError<T>> {
+        match self.receive(buf).await? {
+            Some(ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::TextMessage(message))) => {
+                Ok(Some(message))
+            }
+            Some(ApplicationPdu::ConfirmedRequest(ConfirmedRequest {
+                invoke_id,
+                service: ConfirmedRequestService::TextMessage(message),
+                ..
+            })) => {
+                let ack = SimpleAck {
+                    invoke_id,
+                    service_choice: ConfirmedServiceChoice::TextMessage,
+                };
+                self.send_simple_ack(buf, ack).await?;
+                Ok(Some(message))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Acknowledges a confirmed request received from another device
+    #[maybe_async()]
+    pub async fn send_simple_ack(
+        &self,
+        buf: &mut [u8],
+        ack: SimpleAck,
+    ) -> Result<(), BacnetError<T>> {
+        let apdu = ApplicationPdu::SimpleAck(ack);
+        let message = NetworkMessage::Apdu(apdu);
+        let npdu = NetworkPdu::new(None, None, false, MessagePriority::Normal, message);
+        let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
+
+        let mut writer = Writer::new(buf);
+        data_link.encode(&mut writer);
+
+        // send packet
+        let buffer = writer.to_bytes();
+        self.io.write(buffer).await.map_err(BacnetError::Io)?;
+        Ok(())
+    }
+
+    /// Replies to a confirmed request received from another device
+    #[maybe_async()]
+    pub async fn send_complex_ack(
+        &self,
+        buf: &mut [u8],
+        ack: ComplexAck<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let apdu = ApplicationPdu::ComplexAck(ack);
+        let message = NetworkMessage::Apdu(apdu);
+        let npdu = NetworkPdu::new(None, None, false, MessagePriority::Normal, message);
+        let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
+
+        let mut writer = Writer::new(buf);
+        data_link.encode(&mut writer);
+
+        // send packet
+        let buffer = writer.to_bytes();
+        self.io.write(buffer).await.map_err(BacnetError::Io)?;
+        Ok(())
+    }
+
+    /// Rejects a confirmed request received from another device
+    #[maybe_async()]
+    pub async fn send_reject(&self, buf: &mut [u8], reject: Reject) -> Result<(), BacnetError<T>> {
+        let apdu = ApplicationPdu::Reject(reject);
+        let message = NetworkMessage::Apdu(apdu);
+        let npdu = NetworkPdu::new(None, None, false, MessagePriority::Normal, message);
+        let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
+
+        let mut writer = Writer::new(buf);
+        data_link.encode(&mut writer);
+
+        // send packet
+        let buffer = writer.to_bytes();
+        self.io.write(buffer).await.map_err(BacnetError::Io)?;
+        Ok(())
+    }
+
+    /// Responds with an error to a confirmed request received from another device that could not be carried out
+    #[maybe_async()]
+    pub async fn send_error(
+        &self,
+        buf: &mut [u8],
+        error: ConfirmedBacnetError,
+    ) -> Result<(), BacnetError<T>> {
+        let apdu = ApplicationPdu::Error(error);
+        let message = NetworkMessage::Apdu(apdu);
+        let npdu = NetworkPdu::new(None, None, false, MessagePriority::Normal, message);
+        let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
+
+        let mut writer = Writer::new(buf);
+        data_link.encode(&mut writer);
+
+        // send packet
+        let buffer = writer.to_bytes();
+        self.io.write(buffer).await.map_err(BacnetError::Io)?;
+        Ok(())
+    }
+
+    #[maybe_async()]
+    async fn send_unconfirmed(
+        &self,
+        buf: &mut [u8],
+        service: UnconfirmedRequest<'_>,
+    ) -> Result<(), BacnetError<T>> {
+        let apdu = ApplicationPdu::UnconfirmedRequest(service);
+        let message = NetworkMessage::Apdu(apdu);
+        let npdu = NetworkPdu::new(None, None, true, MessagePriority::Normal, message);
+        let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
+
+        let mut writer = Writer::new(buf);
+        data_link.encode(&mut writer);
+
+        // send packet
+        let buffer = writer.to_bytes();
+        self.io.write(buffer).await.map_err(BacnetError::Io)?;
+        Ok(())
+    }
+
+    // broadcasts on the network of dst (the local network if None)
+    #[maybe_async()]
+    async fn send_unconfirmed_broadcast(
+        &self,
+        buf: &mut [u8],
+        service: UnconfirmedRequest<'_>,
+        dst: Option<DestinationAddress>,
+    ) -> Result<(), BacnetError<T>> {
+        let apdu = ApplicationPdu::UnconfirmedRequest(service);
+        let message = NetworkMessage::Apdu(apdu);
+        let npdu = NetworkPdu::new(None, dst, false, MessagePriority::Normal, message);
+        let data_link = DataLink::new(DataLinkFunction::OriginalBroadcastNpdu, Some(npdu));
+
+        let mut writer = Writer::new(buf);
+        data_link.encode(&mut writer);
+
+        // send packet
+        let buffer = writer.to_bytes();
+        self.io.write(buffer).await.map_err(BacnetError::Io)?;
+        Ok(())
+    }
+
+    #[maybe_async()]
+    async fn send_confirmed(
+        &self,
+        buf: &mut [u8],
+        service: ConfirmedRequestService<'_>,
+    ) -> Result<u8, BacnetError<T>> {
+        let invoke_id = self.get_then_inc_invoke_id();
+        let apdu = ApplicationPdu::ConfirmedRequest(ConfirmedRequest::new(invoke_id, service));
         let message = NetworkMessage::Apdu(apdu);
         let npdu = NetworkPdu::new(None, None, true, MessagePriority::Normal, message);
         let data_link = DataLink::new(DataLinkFunction::OriginalUnicastNpdu, Some(npdu));
@@ -410,3 +1349,260 @@ where
         self.invoke_id.fetch_add(1, Ordering::SeqCst)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use crate::{
+        application_protocol::{
+            application_pdu::ApplicationPdu,
+            confirmed::{
+                ComplexAckService, ConfirmedRequest, ConfirmedRequestService,
+                ConfirmedServiceChoice,
+            },
+            primitives::data_value::{
+                ApplicationDataValue, BitString, CharacterString, OctetString,
+            },
+            services::{
+                atomic_read_file::{AtomicReadFileAck, FileData, FileRecords},
+                atomic_write_file::AtomicWriteFileAck,
+                i_am::IAm,
+                read_range::{ReadRangeAck, ReadRangeItem, ReadRangeItems, ReadRangeRequestType},
+                text_message::{MessageClass, TextMessage},
+            },
+            unconfirmed::UnconfirmedRequest,
+        },
+        common::{
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{
+                LogBufferResult, LogBufferResultFlags, Segmentation, TextMessagePriority,
+                BACNET_ARRAY_ALL,
+            },
+        },
+    };
+
+    use super::{
+        fake_network::{FakeNetwork, Reply},
+        Bacnet, FILE_CHUNK_OVERHEAD,
+    };
+
+    const FILE_ID: ObjectId = ObjectId {
+        object_type: ObjectType::ObjectFile,
+        id: 1,
+    };
+
+    const DEVICE_ID: ObjectId = ObjectId {
+        object_type: ObjectType::ObjectDevice,
+        id: 79079,
+    };
+
+    fn text_message(text: &'static str) -> TextMessage<'static> {
+        TextMessage::new(
+            DEVICE_ID,
+            Some(MessageClass::Numeric(3)),
+            TextMessagePriority::Urgent,
+            CharacterString::new(text),
+        )
+    }
+
+    fn records(end_of_file: bool, start_record: i32, records: &[&'static [u8]]) -> Reply {
+        let records = records.iter().map(|x| OctetString::new(x)).collect();
+        let data = FileData::new_record(start_record, FileRecords::new(records));
+        let ack = AtomicReadFileAck::new(end_of_file, data);
+        Reply::ComplexAck(ComplexAckService::AtomicReadFile(ack))
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn reads_file_records_until_the_end_of_the_file() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+        bacnet.io.push(records(false, 0, &[&[1, 2], &[3]]));
+        bacnet.io.push(records(true, 2, &[&[4, 5, 6]]));
+
+        let records = bacnet
+            .read_file_records(&mut buf, FILE_ID, 2)
+            .await
+            .unwrap();
+        assert_eq!(records, vec![vec![1, 2], vec![3], vec![4, 5, 6]]);
+        assert_eq!(bacnet.io.sent_count(), 2);
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn writes_as_many_file_records_as_fit_into_each_request() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+        let record: &[u8] = &[0; 4];
+        let records = [record; 3];
+
+        // room for two records (and their tags) per request
+        let max_apdu = FILE_CHUNK_OVERHEAD + 20;
+        for start_record in [10, 12] {
+            let ack = AtomicWriteFileAck::StartRecord(start_record);
+            bacnet
+                .io
+                .push(Reply::ComplexAck(ComplexAckService::AtomicWriteFile(ack)));
+        }
+        bacnet
+            .write_file_records(&mut buf, FILE_ID, 10, &records, max_apdu)
+            .await
+            .unwrap();
+
+        let written: Vec<(i32, u32)> = (0..bacnet.io.sent_count())
+            .map(|i| match bacnet.io.sent_apdu(i) {
+                ApplicationPdu::ConfirmedRequest(request) => match request.service {
+                    ConfirmedRequestService::AtomicWriteFile(x) => match x.data {
+                        FileData::Record {
+                            start_record,
+                            record_count,
+                            ..
+                        } => (start_record, record_count),
+                        x => panic!("unexpected file data {:?}", x),
+                    },
+                    x => panic!("unexpected request {:?}", x),
+                },
+                x => panic!("unexpected message {:?}", x),
+            })
+            .collect();
+        assert_eq!(written, vec![(10, 2), (12, 1)]);
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn sends_a_confirmed_text_message() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+        bacnet
+            .io
+            .push(Reply::SimpleAck(ConfirmedServiceChoice::TextMessage));
+
+        let result = bacnet
+            .text_message(&mut buf, text_message("Fire drill at 10:00"))
+            .await;
+        assert!(result.is_ok());
+        match bacnet.io.sent_request(0) {
+            ConfirmedRequestService::TextMessage(x) => {
+                assert_eq!(x.source_device, DEVICE_ID);
+                assert!(matches!(x.message_class, Some(MessageClass::Numeric(3))));
+                assert_eq!(x.message.inner, "Fire drill at 10:00");
+            }
+            x => panic!("unexpected request {:?}", x),
+        }
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn receives_and_acknowledges_text_messages() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+
+        // unconfirmed messages are not acknowledged
+        let message = UnconfirmedRequest::TextMessage(text_message("hello"));
+        bacnet
+            .io
+            .push(Reply::Message(ApplicationPdu::UnconfirmedRequest(message)));
+        let message = bacnet
+            .receive_text_message(&mut buf)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(message.message.inner, "hello");
+        assert_eq!(bacnet.io.sent_count(), 0);
+
+        let service = ConfirmedRequestService::TextMessage(text_message("world"));
+        let request = ConfirmedRequest::new(9, service);
+        bacnet
+            .io
+            .push(Reply::Message(ApplicationPdu::ConfirmedRequest(request)));
+        let message = bacnet
+            .receive_text_message(&mut buf)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(message.message.inner, "world");
+        match bacnet.io.sent_apdu(0) {
+            ApplicationPdu::SimpleAck(ack) => {
+                assert_eq!(ack.invoke_id, 9);
+                assert_eq!(ack.service_choice, ConfirmedServiceChoice::TextMessage);
+            }
+            x => panic!("unexpected message {:?}", x),
+        }
+
+        // anything else is discarded
+        let i_am = IAm {
+            device_id: DEVICE_ID,
+            max_apdu: 1476,
+            segmentation: Segmentation::Both,
+            vendor_id: 260,
+        };
+        bacnet
+            .io
+            .push(Reply::Message(ApplicationPdu::UnconfirmedRequest(
+                UnconfirmedRequest::IAm(i_am),
+            )));
+        let message = bacnet.receive_text_message(&mut buf).await.unwrap();
+        assert!(message.is_none());
+    }
+
+    // a page of the object list of the device
+    fn object_list(ids: &[u32], more_items: bool) -> Reply {
+        let mut flags = LogBufferResultFlags::LastItem as u8;
+        if more_items {
+            flags = LogBufferResultFlags::MoreItems as u8;
+        }
+        let items = ids
+            .iter()
+            .map(|id| {
+                let object_id = ObjectId::new(ObjectType::ObjectAnalogInput, *id);
+                ReadRangeItem::Value(ApplicationDataValue::ObjectId(object_id))
+            })
+            .collect();
+        Reply::ComplexAck(ComplexAckService::ReadRange(ReadRangeAck {
+            object_id: DEVICE_ID,
+            property_id: PropertyId::PropObjectList,
+            array_index: BACNET_ARRAY_ALL,
+            result_flags: BitString::LogBufferResult(LogBufferResult::new(flags)),
+            item_count: ids.len(),
+            item_data: ReadRangeItems::new(items),
+            first_sequence_number: None,
+        }))
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn read_list_pages_until_there_are_no_more_items() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+        bacnet.io.push(object_list(&[1, 2], true));
+        bacnet.io.push(object_list(&[3], false));
+
+        let items = bacnet
+            .read_list(&mut buf, DEVICE_ID, PropertyId::PropObjectList, 2)
+            .await
+            .unwrap();
+        let ids: Vec<u32> = items
+            .iter()
+            .map(|x| match x {
+                ReadRangeItem::Value(ApplicationDataValue::ObjectId(x)) => x.id,
+                x => panic!("unexpected item {:?}", x),
+            })
+            .collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+
+        // the second page starts after the items already read (positions start at 1)
+        for (request, expected_index) in [(0, 1), (1, 3)] {
+            match bacnet.io.sent_request(request) {
+                ConfirmedRequestService::ReadRange(x) => {
+                    assert_eq!(x.object_id, DEVICE_ID);
+                    assert_eq!(x.property_id, PropertyId::PropObjectList);
+                    match x.request_type {
+                        ReadRangeRequestType::ByPosition(x) => {
+                            assert_eq!((x.index, x.count), (expected_index, 2))
+                        }
+                        x => panic!("unexpected request type {:?}", x),
+                    }
+                }
+                x => panic!("unexpected request {:?}", x),
+            }
+        }
+        assert_eq!(bacnet.io.remaining_replies(), 0);
+    }
+}
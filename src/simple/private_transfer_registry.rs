@@ -0,0 +1,304 @@
+/// Dispatches vendor specific PrivateTransfer requests to handlers registered by the application
+/// Handlers are registered per vendor id and service number along with the types their service parameters and result block decode to.
+/// This means proprietary payloads are decoded by implementing `PrivateTransferData` once rather than by picking apart raw tags in every handler.
+use alloc::{boxed::Box, collections::BTreeMap};
+use core::fmt::Debug;
+
+use maybe_async::maybe_async;
+
+use crate::{
+    application_protocol::{
+        application_pdu::ApplicationPdu,
+        confirmed::{ComplexAck, ComplexAckService, ConfirmedRequestService, Reject},
+        services::private_transfer::{
+            PrivateTransfer, PrivateTransferAck, PrivateTransferData, ServiceParameters,
+        },
+        unconfirmed::UnconfirmedRequest,
+    },
+    common::{error::Error, io::Writer, spec::RejectReason},
+};
+
+use super::{Bacnet, BacnetError, NetworkIo};
+
+// decodes the service parameters, calls the application handler and encodes its result block (returns false if there is none)
+type Handler = Box<dyn FnMut(Option<&ServiceParameters<'_>>, &mut Writer) -> Result<bool, Error>>;
+
+#[derive(Default)]
+pub struct PrivateTransferRegistry {
+    handlers: BTreeMap<(u16, u32), Handler>,
+}
+
+impl PrivateTransferRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a handler for a vendor specific service, replacing any handler already registered for it
+    /// The service parameters are decoded as `P` before the handler is called and the result it returns is sent back as `R`
+    pub fn register<P, R, F>(&mut self, vendor_id: u16, service_number: u32, mut handler: F)
+    where
+        P: PrivateTransferData,
+        R: PrivateTransferData,
+        F: FnMut(Option<P>) -> Option<R> + 'static,
+    {
+        let handler = move |parameters: Option<&ServiceParameters<'_>>, writer: &mut Writer| {
+            let parameters = parameters.map(|x| x.decode_as::<P>()).transpose()?;
+            match handler(parameters) {
+                Some(result) => {
+                    result.encode(writer);
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        };
+
+        self.handlers
+            .insert((vendor_id, service_number), Box::new(handler));
+    }
+
+    pub fn unregister(&mut self, vendor_id: u16, service_number: u32) {
+        self.handlers.remove(&(vendor_id, service_number));
+    }
+
+    pub fn is_registered(&self, vendor_id: u16, service_number: u32) -> bool {
+        self.handlers.contains_key(&(vendor_id, service_number))
+    }
+
+    /// Calls the handler registered for the transfer and returns the ack to reply with
+    /// The result block is encoded into `buf`. Returns None if no handler is registered for the transfer.
+    pub fn handle<'a>(
+        &mut self,
+        transfer: &PrivateTransfer<'_>,
+        buf: &mut [u8],
+    ) -> Option<Result<PrivateTransferAck<'a>, Error>> {
+        let handler = self
+            .handlers
+            .get_mut(&(transfer.vendor_id, transfer.service_number))?;
+
+        let mut writer = Writer::new(buf);
+        let result = match handler(transfer.parameters.as_ref(), &mut writer) {
+            Ok(has_result_block) => {
+                let result_block =
+                    has_result_block.then(|| ServiceParameters::new(writer.to_bytes()));
+                Ok(PrivateTransferAck::new(
+                    transfer.vendor_id,
+                    transfer.service_number,
+                    result_block,
+                ))
+            }
+            Err(e) => Err(e),
+        };
+
+        Some(result)
+    }
+
+    /// Reads the next packet from the network and passes registered private transfers to their handlers
+    /// Confirmed transfers are answered with the result of the handler, or rejected if their service parameters cannot be decoded.
+    /// Anything else (including transfers without a registered handler) is returned to the caller.
+    #[maybe_async()]
+    pub async fn receive<'a, T>(
+        &mut self,
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+    ) -> Result<Option<ApplicationPdu<'a>>, BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        let apdu = match bacnet.receive(buf).await? {
+            Some(apdu) => apdu,
+            None => return Ok(None),
+        };
+
+        match &apdu {
+            ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::PrivateTransfer(transfer)) => {
+                match self.handle(transfer, buf) {
+                    Some(result) => {
+                        result?;
+                        Ok(None)
+                    }
+                    None => Ok(Some(apdu)),
+                }
+            }
+            ApplicationPdu::ConfirmedRequest(request) => match &request.service {
+                ConfirmedRequestService::PrivateTransfer(transfer) => {
+                    match self.handle(transfer, buf) {
+                        Some(Ok(ack)) => {
+                            let ack = ComplexAck {
+                                invoke_id: request.invoke_id,
+                                service: ComplexAckService::PrivateTransfer(ack),
+                            };
+                            bacnet.send_complex_ack(buf, ack).await?;
+                            Ok(None)
+                        }
+                        Some(Err(e)) => {
+                            // the service parameters did not decode as the registered type
+                            let reject = Reject::new(
+                                request.invoke_id,
+                                RejectReason::InvalidParameterDataType,
+                            );
+                            bacnet.send_reject(buf, reject).await?;
+                            Err(BacnetError::Codec(e))
+                        }
+                        None => Ok(Some(apdu)),
+                    }
+                }
+                _ => Ok(Some(apdu)),
+            },
+            _ => Ok(Some(apdu)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::{
+        application_protocol::{
+            application_pdu::ApplicationPdu,
+            confirmed::{ComplexAckService, ConfirmedRequest, ConfirmedRequestService},
+            services::private_transfer::{
+                PrivateTransfer, PrivateTransferAck, PrivateTransferData, ServiceParameters,
+            },
+        },
+        common::{
+            error::Error,
+            helper::{decode_unsigned, encode_application_unsigned},
+            io::{Reader, Writer},
+            spec::RejectReason,
+            tag::{ApplicationTagNumber, Tag, TagNumber},
+        },
+        simple::{
+            fake_network::{FakeNetwork, Reply},
+            Bacnet, BacnetError,
+        },
+    };
+
+    use super::PrivateTransferRegistry;
+
+    const VENDOR_ID: u16 = 260;
+    const SERVICE_NUMBER: u32 = 3;
+
+    #[derive(Debug, PartialEq)]
+    struct Setpoint(u64);
+
+    impl PrivateTransferData for Setpoint {
+        fn encode(&self, writer: &mut Writer) {
+            encode_application_unsigned(writer, self.0);
+        }
+
+        fn decode(reader: &mut Reader, buf: &[u8]) -> Result<Self, Error> {
+            let tag = Tag::decode_expected(
+                reader,
+                buf,
+                TagNumber::Application(ApplicationTagNumber::UnsignedInt),
+                "Setpoint decode",
+            )?;
+            Ok(Setpoint(decode_unsigned(tag.value, reader, buf)?))
+        }
+    }
+
+    // doubles the setpoint it is sent
+    fn registry() -> PrivateTransferRegistry {
+        let mut registry = PrivateTransferRegistry::new();
+        registry.register(VENDOR_ID, SERVICE_NUMBER, |x: Option<Setpoint>| {
+            x.map(|x| Setpoint(x.0 * 2))
+        });
+        registry
+    }
+
+    fn transfer(parameters: &[u8]) -> PrivateTransfer<'static> {
+        PrivateTransfer::new(
+            VENDOR_ID,
+            SERVICE_NUMBER,
+            Some(ServiceParameters::new(parameters)),
+        )
+    }
+
+    fn setpoint(value: u64) -> PrivateTransfer<'static> {
+        let mut buf = [0; 16];
+        let mut writer = Writer::new(&mut buf);
+        Setpoint(value).encode(&mut writer);
+        transfer(writer.to_bytes())
+    }
+
+    #[test]
+    fn handle() {
+        let mut registry = registry();
+        assert!(registry.is_registered(VENDOR_ID, SERVICE_NUMBER));
+        assert!(!registry.is_registered(VENDOR_ID, SERVICE_NUMBER + 1));
+
+        let mut buf = [0; 16];
+        let ack = registry.handle(&setpoint(21), &mut buf).unwrap().unwrap();
+        assert_eq!(ack.vendor_id, VENDOR_ID);
+        assert_eq!(ack.service_number, SERVICE_NUMBER);
+
+        // encoding
+        let mut buf = [0; 64];
+        let mut writer = Writer::new(&mut buf);
+        ack.encode(&mut writer);
+        let len = writer.index;
+
+        // decoding (skip the service choice byte)
+        let mut reader = Reader::new_with_len(len);
+        reader.index = 1;
+        let decoded = PrivateTransferAck::decode(&mut reader, &buf[..len]).unwrap();
+        let result: Setpoint = decoded.result_block.unwrap().decode_as().unwrap();
+        assert_eq!(result, Setpoint(42));
+
+        registry.unregister(VENDOR_ID, SERVICE_NUMBER);
+        assert!(registry.handle(&setpoint(21), &mut buf).is_none());
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn receive() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut registry = registry();
+        let mut buf = vec![0; 1500];
+        let service = ConfirmedRequestService::PrivateTransfer(setpoint(21));
+        let request = ConfirmedRequest::new(7, service);
+        bacnet
+            .io
+            .push(Reply::Message(ApplicationPdu::ConfirmedRequest(request)));
+
+        let unhandled = registry.receive(&bacnet, &mut buf).await.unwrap();
+        assert!(unhandled.is_none());
+
+        let ack = match bacnet.io.sent_apdu(0) {
+            ApplicationPdu::ComplexAck(x) => x,
+            x => panic!("unexpected apdu {:?}", x),
+        };
+        assert_eq!(ack.invoke_id, 7);
+        let ack = match ack.service {
+            ComplexAckService::PrivateTransfer(x) => x,
+            x => panic!("unexpected service {:?}", x),
+        };
+        let result: Setpoint = ack.result_block.unwrap().decode_as().unwrap();
+        assert_eq!(result, Setpoint(42));
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn receive_rejects_parameters_of_the_wrong_type() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut registry = registry();
+        let mut buf = vec![0; 1500];
+        // an application boolean (true) rather than an unsigned int
+        let service = ConfirmedRequestService::PrivateTransfer(transfer(&[0x11]));
+        let request = ConfirmedRequest::new(7, service);
+        bacnet
+            .io
+            .push(Reply::Message(ApplicationPdu::ConfirmedRequest(request)));
+
+        let result = registry.receive(&bacnet, &mut buf).await;
+        assert!(matches!(result, Err(BacnetError::Codec(_))));
+
+        assert_eq!(bacnet.io.sent_count(), 1);
+        match bacnet.io.sent_apdu(0) {
+            ApplicationPdu::Reject(x) => {
+                assert_eq!(x.invoke_id, 7);
+                assert_eq!(x.reason, RejectReason::InvalidParameterDataType);
+            }
+            x => panic!("unexpected apdu {:?}", x),
+        }
+    }
+}
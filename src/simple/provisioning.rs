@@ -0,0 +1,228 @@
+/// Answers Who-Am-I requests from unconfigured devices with identities taken from a table supplied by the application
+/// A device is identified by its vendor id, model name and serial number which is typically printed on a label and collected during commissioning.
+/// Each entry in the table assigns a device id and (optionally) a mac address and network number to one device.
+/// Devices that are not in the table are returned to the caller so that they can be reported and added to the table later.
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::fmt::Debug;
+
+use maybe_async::maybe_async;
+
+use crate::{
+    application_protocol::{
+        application_pdu::ApplicationPdu,
+        primitives::data_value::{CharacterString, OctetString},
+        services::who_am_i::{WhoAmI, YouAre},
+        unconfirmed::UnconfirmedRequest,
+    },
+    common::object_id::ObjectId,
+};
+
+use super::{Bacnet, BacnetError, NetworkIo};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceKey {
+    pub vendor_id: u16,
+    pub model_name: String,
+    pub serial_number: String,
+}
+
+impl DeviceKey {
+    pub fn new(vendor_id: u16, model_name: &str, serial_number: &str) -> Self {
+        Self {
+            vendor_id,
+            model_name: model_name.into(),
+            serial_number: serial_number.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceIdentity {
+    pub device_id: ObjectId,
+    pub mac_address: Option<Vec<u8>>, // left unchanged on the device if not specified
+    pub network_number: Option<u16>, // the network the device is on (the local network if not specified)
+}
+
+impl DeviceIdentity {
+    pub fn new(device_id: ObjectId) -> Self {
+        Self {
+            device_id,
+            mac_address: None,
+            network_number: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProvisioningResponder {
+    identities: BTreeMap<DeviceKey, DeviceIdentity>,
+}
+
+impl ProvisioningResponder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a device to the table, replacing any identity already assigned to it
+    pub fn insert(&mut self, key: DeviceKey, identity: DeviceIdentity) {
+        self.identities.insert(key, identity);
+    }
+
+    pub fn remove(&mut self, key: &DeviceKey) -> Option<DeviceIdentity> {
+        self.identities.remove(key)
+    }
+
+    pub fn get(&self, key: &DeviceKey) -> Option<&DeviceIdentity> {
+        self.identities.get(key)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&DeviceKey, &DeviceIdentity)> {
+        self.identities.iter()
+    }
+
+    /// Looks up the device asking for its identity and returns the reply along with the network to send it on
+    /// Returns None if the device is not in the table
+    pub fn handle<'a>(&self, request: &WhoAmI<'_>) -> Option<(YouAre<'a>, Option<u16>)> {
+        let key = DeviceKey::new(
+            request.vendor_id,
+            &request.model_name.inner,
+            &request.serial_number.inner,
+        );
+        let identity = self.identities.get(&key)?;
+
+        let reply = YouAre::new(
+            request.vendor_id,
+            CharacterString::new(&request.model_name.inner),
+            CharacterString::new(&request.serial_number.inner),
+            Some(identity.device_id),
+            identity.mac_address.as_deref().map(OctetString::new),
+        );
+        Some((reply, identity.network_number))
+    }
+
+    /// Reads the next packet from the network and replies to Who-Am-I requests from devices in the table
+    /// Anything else (including Who-Am-I requests from devices not in the table) is returned to the caller.
+    #[maybe_async()]
+    pub async fn receive<'a, T>(
+        &self,
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+    ) -> Result<Option<ApplicationPdu<'a>>, BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        let apdu = match bacnet.receive(buf).await? {
+            Some(apdu) => apdu,
+            None => return Ok(None),
+        };
+
+        if let ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::WhoAmI(request)) = &apdu {
+            if let Some((reply, network_number)) = self.handle(request) {
+                bacnet.you_are(buf, reply, network_number).await?;
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(apdu))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::{
+        application_protocol::{
+            application_pdu::ApplicationPdu, primitives::data_value::CharacterString,
+            services::who_am_i::WhoAmI, unconfirmed::UnconfirmedRequest,
+        },
+        common::object_id::{ObjectId, ObjectType},
+        simple::{
+            fake_network::{FakeNetwork, Reply},
+            Bacnet,
+        },
+    };
+
+    use super::{DeviceIdentity, DeviceKey, ProvisioningResponder};
+
+    const DEVICE_ID: ObjectId = ObjectId {
+        object_type: ObjectType::ObjectDevice,
+        id: 10123,
+    };
+
+    fn responder() -> ProvisioningResponder {
+        let mut responder = ProvisioningResponder::new();
+        let mut identity = DeviceIdentity::new(DEVICE_ID);
+        identity.mac_address = Some(vec![0x2a]);
+        identity.network_number = Some(5);
+        responder.insert(DeviceKey::new(260, "VAV-100", "SN-000123"), identity);
+        responder
+    }
+
+    fn who_am_i(serial_number: &'static str) -> WhoAmI<'static> {
+        WhoAmI::new(
+            260,
+            CharacterString::new("VAV-100"),
+            CharacterString::new(serial_number),
+        )
+    }
+
+    #[test]
+    fn handles_devices_in_the_table_only() {
+        let responder = responder();
+
+        let (reply, network_number) = responder.handle(&who_am_i("SN-000123")).unwrap();
+        assert_eq!(reply.device_id, Some(DEVICE_ID));
+        assert_eq!(reply.mac_address.unwrap().inner, [0x2a]);
+        assert_eq!(reply.serial_number.inner, "SN-000123");
+        assert_eq!(network_number, Some(5));
+
+        assert!(responder.handle(&who_am_i("SN-000999")).is_none());
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn replies_to_a_matching_who_am_i() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+        let responder = responder();
+
+        let request = UnconfirmedRequest::WhoAmI(who_am_i("SN-000123"));
+        bacnet
+            .io
+            .push(Reply::Message(ApplicationPdu::UnconfirmedRequest(request)));
+        let unhandled = responder.receive(&bacnet, &mut buf).await.unwrap();
+        assert!(unhandled.is_none());
+
+        match bacnet.io.sent_apdu(0) {
+            ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::YouAre(x)) => {
+                assert_eq!(x.vendor_id, 260);
+                assert_eq!(x.device_id, Some(DEVICE_ID));
+            }
+            x => panic!("unexpected message {:?}", x),
+        }
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn returns_a_who_am_i_from_an_unknown_device() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+        let responder = responder();
+
+        let request = UnconfirmedRequest::WhoAmI(who_am_i("SN-000999"));
+        bacnet
+            .io
+            .push(Reply::Message(ApplicationPdu::UnconfirmedRequest(request)));
+        let unhandled = responder.receive(&bacnet, &mut buf).await.unwrap();
+        match unhandled {
+            Some(ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::WhoAmI(x))) => {
+                assert_eq!(x.serial_number.inner, "SN-000999");
+            }
+            x => panic!("unexpected message {:?}", x),
+        }
+        assert_eq!(bacnet.io.sent_count(), 0);
+    }
+}
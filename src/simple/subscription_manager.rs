@@ -0,0 +1,592 @@
+/// Keeps change of value subscriptions to the objects of one device alive on behalf of a client
+/// All requests go to the peer of the `Bacnet` passed in so use a separate manager (and `Bacnet`) for each device.
+/// Subscriptions are renewed before their lifetime runs out and can be re-established when the device restarts (see `device_restarted`).
+/// If the device rejects a subscription the manager falls back to polling the monitored properties instead and periodically retries.
+/// Values received by notification and values read by polling are both reported as a `ValueUpdate` so the caller only deals with one kind of update.
+/// This module has no access to a clock so the caller passes in the current time (in seconds, from any monotonic source) when servicing subscriptions.
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::fmt::Debug;
+
+use maybe_async::maybe_async;
+
+use crate::{
+    application_protocol::{
+        application_pdu::ApplicationPdu,
+        confirmed::{ConfirmedRequestService, ConfirmedServiceChoice, Reject, SimpleAck},
+        services::{
+            change_of_value::{CovNotification, PropertyResult, SubscribeCov},
+            read_property_multiple::{
+                PropertyValue, ReadPropertyMultiple, ReadPropertyMultipleObject,
+            },
+        },
+        unconfirmed::UnconfirmedRequest,
+    },
+    common::{object_id::ObjectId, property_id::PropertyId, spec::RejectReason},
+};
+
+use super::{Bacnet, BacnetError, NetworkIo};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SubscriptionKey {
+    pub object_id: ObjectId,
+    pub process_id: u32,
+}
+
+impl SubscriptionKey {
+    pub fn new(object_id: ObjectId, process_id: u32) -> Self {
+        Self {
+            object_id,
+            process_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UpdateSource {
+    Cov,
+    Polling,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Subscription {
+    pub key: SubscriptionKey,
+    pub source: UpdateSource,
+    pub issue_confirmed_notifications: bool,
+    pub lifetime_seconds: u32, // zero for indefinite
+    renew_at: u64,
+    poll_at: u64,
+}
+
+impl Subscription {
+    // renew half way through the lifetime so that a lost request can be retried in time
+    fn schedule_renewal(&mut self, now: u64) {
+        self.renew_at = if self.lifetime_seconds == 0 {
+            u64::MAX // an indefinite subscription is only renewed if the device restarts
+        } else {
+            now + (self.lifetime_seconds as u64 / 2).max(1)
+        };
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ValueUpdate<'a> {
+    pub key: SubscriptionKey,
+    pub source: UpdateSource,
+    pub values: Vec<PropertyResult<'a>>,
+}
+
+// returned by `service` when the network fails part way through along with the updates gathered before it did
+#[derive(Debug)]
+pub struct ServiceError<'a, T>
+where
+    T: NetworkIo,
+{
+    pub updates: Vec<ValueUpdate<'a>>,
+    pub error: BacnetError<T>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SubscriptionManager {
+    subscriptions: BTreeMap<SubscriptionKey, Subscription>,
+    pub device_id: ObjectId,
+    pub poll_interval_seconds: u32,
+    pub retry_interval_seconds: u32, // how long to wait before trying a failed subscription again
+    pub poll_properties: Vec<PropertyId>,
+    // an I-Am is also the reply to every Who-Is so this resubscribes more often than needed
+    pub resubscribe_on_i_am: bool,
+}
+
+impl SubscriptionManager {
+    pub fn new(device_id: ObjectId, poll_interval_seconds: u32) -> Self {
+        Self {
+            subscriptions: BTreeMap::new(),
+            device_id,
+            poll_interval_seconds,
+            retry_interval_seconds: 60,
+            // the same properties a device reports in a change of value notification for most objects
+            poll_properties: alloc::vec![PropertyId::PropPresentValue, PropertyId::PropStatusFlags],
+            resubscribe_on_i_am: false,
+        }
+    }
+
+    /// Adds a subscription which will be established the next time `service` is called
+    pub fn subscribe(
+        &mut self,
+        key: SubscriptionKey,
+        issue_confirmed_notifications: bool,
+        lifetime_seconds: u32,
+    ) {
+        let subscription = Subscription {
+            key,
+            source: UpdateSource::Cov,
+            issue_confirmed_notifications,
+            lifetime_seconds,
+            renew_at: 0,
+            poll_at: u64::MAX,
+        };
+        self.subscriptions.insert(key, subscription);
+    }
+
+    pub fn get(&self, key: &SubscriptionKey) -> Option<&Subscription> {
+        self.subscriptions.get(key)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Subscription> {
+        self.subscriptions.values()
+    }
+
+    /// Forces all subscriptions to be re-established the next time `service` is called
+    /// A device loses its subscriptions when it restarts so call this when you detect a restart
+    /// (e.g. a change in its Time_Of_Device_Restart property or notifications stop arriving).
+    pub fn device_restarted(&mut self) {
+        for subscription in self.subscriptions.values_mut() {
+            subscription.renew_at = 0;
+        }
+    }
+
+    /// Converts a notification into a value update if it belongs to one of our subscriptions
+    pub fn handle_notification<'a>(
+        &mut self,
+        notification: CovNotification<'a>,
+    ) -> Option<ValueUpdate<'a>> {
+        if notification.device_id != self.device_id {
+            return None;
+        }
+
+        let key = SubscriptionKey::new(notification.object_id, notification.process_id);
+        if !self.subscriptions.contains_key(&key) {
+            return None;
+        }
+
+        Some(ValueUpdate {
+            key,
+            source: UpdateSource::Cov,
+            values: notification.values.property_results,
+        })
+    }
+
+    /// Renews subscriptions that are due and polls objects whose device rejected the subscription
+    /// Only network errors are returned, a device rejecting a request results in polling instead
+    /// Servicing stops at the first network error (every other request would most likely fail too) and the updates
+    /// read up to that point are returned with it. Subscriptions not serviced are picked up by the next call.
+    #[maybe_async()]
+    pub async fn service<'a, T>(
+        &mut self,
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+        now: u64,
+    ) -> Result<Vec<ValueUpdate<'a>>, ServiceError<'a, T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        let mut updates = Vec::new();
+
+        for subscription in self.subscriptions.values_mut() {
+            if subscription.renew_at <= now {
+                let request = SubscribeCov::new(
+                    subscription.key.process_id,
+                    subscription.key.object_id,
+                    subscription.issue_confirmed_notifications,
+                    subscription.lifetime_seconds,
+                );
+
+                match bacnet.subscribe_change_of_value(buf, request).await {
+                    Ok(()) => {
+                        subscription.source = UpdateSource::Cov;
+                        subscription.poll_at = u64::MAX;
+                        subscription.schedule_renewal(now);
+                    }
+                    Err(BacnetError::Io(e)) => {
+                        let error = BacnetError::Io(e);
+                        return Err(ServiceError { updates, error });
+                    }
+                    Err(_) => {
+                        if subscription.source == UpdateSource::Cov {
+                            subscription.source = UpdateSource::Polling;
+                            subscription.poll_at = now;
+                        }
+                        subscription.renew_at = now + self.retry_interval_seconds.max(1) as u64;
+                    }
+                }
+            }
+
+            if subscription.source == UpdateSource::Polling && subscription.poll_at <= now {
+                subscription.poll_at = now + self.poll_interval_seconds as u64;
+                let object = ReadPropertyMultipleObject::new(
+                    subscription.key.object_id,
+                    self.poll_properties.clone(),
+                );
+                let request = ReadPropertyMultiple::new(alloc::vec![object]);
+
+                let ack = match bacnet.read_property_multiple(buf, request).await {
+                    Ok(ack) => ack,
+                    Err(BacnetError::Io(e)) => {
+                        // poll again next time rather than waiting for a whole poll interval
+                        subscription.poll_at = now;
+                        let error = BacnetError::Io(e);
+                        return Err(ServiceError { updates, error });
+                    }
+                    Err(_) => continue,
+                };
+
+                // properties the device could not read are left out of the update
+                let values = ack
+                    .objects_with_results
+                    .into_iter()
+                    .flat_map(|object| object.property_results)
+                    .filter_map(|result| match result.value {
+                        PropertyValue::PropValue(value) => {
+                            Some(PropertyResult::new(result.id, value))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+
+                updates.push(ValueUpdate {
+                    key: subscription.key,
+                    source: UpdateSource::Polling,
+                    values,
+                });
+            }
+        }
+
+        Ok(updates)
+    }
+
+    /// Reads the next packet from the network and returns a value update if it was a notification for one of our subscriptions
+    /// Confirmed notifications are acknowledged and, if `resubscribe_on_i_am` is set, an I-Am from the device causes its subscriptions to be renewed
+    /// Any other confirmed request is rejected because the manager does not serve it
+    #[maybe_async()]
+    pub async fn receive<'a, T>(
+        &mut self,
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+    ) -> Result<Option<ValueUpdate<'a>>, BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        let apdu = match bacnet.receive(buf).await? {
+            Some(apdu) => apdu,
+            None => return Ok(None),
+        };
+
+        match apdu {
+            ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::CovNotification(x)) => {
+                Ok(self.handle_notification(x))
+            }
+            ApplicationPdu::ConfirmedRequest(request) => match request.service {
+                ConfirmedRequestService::CovNotification(x) => {
+                    let ack = SimpleAck {
+                        invoke_id: request.invoke_id,
+                        service_choice: ConfirmedServiceChoice::CovNotification,
+                    };
+                    bacnet.send_simple_ack(buf, ack).await?;
+                    Ok(self.handle_notification(x))
+                }
+                _ => {
+                    let reject = Reject::new(request.invoke_id, RejectReason::UnrecognizedService);
+                    bacnet.send_reject(buf, reject).await?;
+                    Ok(None)
+                }
+            },
+            ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::IAm(iam))
+                if self.resubscribe_on_i_am && iam.device_id == self.device_id =>
+            {
+                self.device_restarted();
+                Ok(None)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Removes a subscription and cancels it on the device
+    #[maybe_async()]
+    pub async fn unsubscribe<T>(
+        &mut self,
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+        key: &SubscriptionKey,
+    ) -> Result<(), BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        if let Some(subscription) = self.subscriptions.remove(key) {
+            if subscription.source == UpdateSource::Cov {
+                let request = SubscribeCov::cancel(key.process_id, key.object_id);
+                bacnet.subscribe_change_of_value(buf, request).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::{
+        application_protocol::{
+            application_pdu::ApplicationPdu,
+            confirmed::{
+                ComplexAckService, ConfirmedRequest, ConfirmedRequestService,
+                ConfirmedServiceChoice,
+            },
+            primitives::data_value::ApplicationDataValue,
+            services::{
+                change_of_value::{CovNotification, CovNotificationValues, PropertyResult},
+                i_am::IAm,
+                read_property::ReadProperty,
+                read_property_multiple::ReadPropertyMultipleAckBuilder,
+            },
+            unconfirmed::UnconfirmedRequest,
+        },
+        common::{
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{ErrorClass, ErrorCode, RejectReason, Segmentation},
+        },
+        simple::{
+            fake_network::{FakeNetwork, Reply},
+            Bacnet, BacnetError,
+        },
+    };
+
+    use super::{SubscriptionKey, SubscriptionManager, UpdateSource};
+
+    const DEVICE_ID: ObjectId = ObjectId {
+        object_type: ObjectType::ObjectDevice,
+        id: 79079,
+    };
+    const OBJECT_ID: ObjectId = ObjectId {
+        object_type: ObjectType::ObjectAnalogInput,
+        id: 1,
+    };
+
+    fn rejected() -> Reply {
+        Reply::Error(
+            ConfirmedServiceChoice::SubscribeCov,
+            ErrorClass::Services,
+            ErrorCode::ServiceRequestDenied,
+        )
+    }
+
+    fn polled_value() -> Reply {
+        let ack = ReadPropertyMultipleAckBuilder::new(OBJECT_ID)
+            .value(
+                PropertyId::PropPresentValue,
+                ApplicationDataValue::Real(21.5),
+            )
+            .error(
+                PropertyId::PropStatusFlags,
+                ErrorClass::Property,
+                ErrorCode::UnknownProperty,
+            )
+            .build();
+        Reply::ComplexAck(ComplexAckService::ReadPropertyMultiple(ack))
+    }
+
+    fn notification(device_id: ObjectId) -> Reply {
+        let values = CovNotificationValues::new(
+            OBJECT_ID,
+            vec![PropertyResult::new(
+                PropertyId::PropPresentValue,
+                ApplicationDataValue::Real(22.0),
+            )],
+        );
+        let notification = CovNotification::new(1, device_id, 60, values);
+        Reply::Message(ApplicationPdu::UnconfirmedRequest(
+            UnconfirmedRequest::CovNotification(notification),
+        ))
+    }
+
+    fn i_am() -> Reply {
+        Reply::Message(ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::IAm(
+            IAm {
+                device_id: DEVICE_ID,
+                max_apdu: 1476,
+                segmentation: Segmentation::Both,
+                vendor_id: 260,
+            },
+        )))
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn renews_half_way_through_the_lifetime() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+        let mut manager = SubscriptionManager::new(DEVICE_ID, 10);
+        manager.subscribe(SubscriptionKey::new(OBJECT_ID, 1), false, 60);
+
+        bacnet
+            .io
+            .push(Reply::SimpleAck(ConfirmedServiceChoice::SubscribeCov));
+        let updates = manager.service(&bacnet, &mut buf, 100).await.unwrap();
+        assert!(updates.is_empty());
+        assert_eq!(bacnet.io.sent_count(), 1);
+
+        manager.service(&bacnet, &mut buf, 129).await.unwrap();
+        assert_eq!(bacnet.io.sent_count(), 1);
+
+        bacnet
+            .io
+            .push(Reply::SimpleAck(ConfirmedServiceChoice::SubscribeCov));
+        manager.service(&bacnet, &mut buf, 130).await.unwrap();
+        assert_eq!(
+            bacnet.io.sent_confirmed(),
+            vec![
+                ConfirmedServiceChoice::SubscribeCov,
+                ConfirmedServiceChoice::SubscribeCov
+            ]
+        );
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn polls_and_retries_a_rejected_subscription() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+        let mut manager = SubscriptionManager::new(DEVICE_ID, 10);
+        manager.retry_interval_seconds = 30;
+        let key = SubscriptionKey::new(OBJECT_ID, 1);
+        manager.subscribe(key, false, 0); // an indefinite subscription must be retried too
+
+        bacnet.io.push(rejected());
+        bacnet.io.push(polled_value());
+        let updates = manager.service(&bacnet, &mut buf, 0).await.unwrap();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].source, UpdateSource::Polling);
+        assert_eq!(updates[0].values.len(), 1); // the status flags could not be read
+        assert_eq!(updates[0].values[0].id, PropertyId::PropPresentValue);
+
+        // next poll
+        manager.service(&bacnet, &mut buf, 9).await.unwrap();
+        assert_eq!(bacnet.io.sent_count(), 2);
+        bacnet.io.push(polled_value());
+        let updates = manager.service(&bacnet, &mut buf, 10).await.unwrap();
+        assert_eq!(updates.len(), 1);
+
+        // retry succeeds and polling stops
+        bacnet
+            .io
+            .push(Reply::SimpleAck(ConfirmedServiceChoice::SubscribeCov));
+        let updates = manager.service(&bacnet, &mut buf, 30).await.unwrap();
+        assert!(updates.is_empty());
+        assert_eq!(manager.get(&key).unwrap().source, UpdateSource::Cov);
+        assert_eq!(
+            bacnet.io.sent_confirmed(),
+            vec![
+                ConfirmedServiceChoice::SubscribeCov,
+                ConfirmedServiceChoice::ReadPropMultiple,
+                ConfirmedServiceChoice::ReadPropMultiple,
+                ConfirmedServiceChoice::SubscribeCov,
+            ]
+        );
+
+        // indefinite so never renewed again
+        manager.service(&bacnet, &mut buf, 100_000).await.unwrap();
+        assert_eq!(bacnet.io.sent_count(), 4);
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn receives_notifications_from_its_device_only() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+        let mut manager = SubscriptionManager::new(DEVICE_ID, 10);
+        manager.subscribe(SubscriptionKey::new(OBJECT_ID, 1), false, 60);
+
+        bacnet.io.push(notification(DEVICE_ID));
+        let update = manager.receive(&bacnet, &mut buf).await.unwrap().unwrap();
+        assert_eq!(update.source, UpdateSource::Cov);
+        assert_eq!(update.key, SubscriptionKey::new(OBJECT_ID, 1));
+
+        let other_device = ObjectId::new(ObjectType::ObjectDevice, 1);
+        bacnet.io.push(notification(other_device));
+        let update = manager.receive(&bacnet, &mut buf).await.unwrap();
+        assert!(update.is_none());
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn resubscribes_on_i_am_only_when_asked_to() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+        let mut manager = SubscriptionManager::new(DEVICE_ID, 10);
+        manager.subscribe(SubscriptionKey::new(OBJECT_ID, 1), false, 60);
+        bacnet
+            .io
+            .push(Reply::SimpleAck(ConfirmedServiceChoice::SubscribeCov));
+        manager.service(&bacnet, &mut buf, 0).await.unwrap();
+
+        // a routine Who-Is reply
+        bacnet.io.push(i_am());
+        manager.receive(&bacnet, &mut buf).await.unwrap();
+        manager.service(&bacnet, &mut buf, 1).await.unwrap();
+        assert_eq!(bacnet.io.sent_count(), 1);
+
+        manager.resubscribe_on_i_am = true;
+        bacnet.io.push(i_am());
+        manager.receive(&bacnet, &mut buf).await.unwrap();
+        bacnet
+            .io
+            .push(Reply::SimpleAck(ConfirmedServiceChoice::SubscribeCov));
+        manager.service(&bacnet, &mut buf, 2).await.unwrap();
+        assert_eq!(bacnet.io.sent_count(), 2);
+        assert_eq!(bacnet.io.remaining_replies(), 0);
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn returns_the_updates_read_before_a_network_error() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+        let mut manager = SubscriptionManager::new(DEVICE_ID, 10);
+        let polled = SubscriptionKey::new(OBJECT_ID, 1);
+        let timed_out = SubscriptionKey::new(ObjectId::new(ObjectType::ObjectAnalogInput, 2), 1);
+        manager.subscribe(polled, false, 60);
+        manager.subscribe(timed_out, false, 60);
+
+        // no reply to the second subscription
+        bacnet.io.push(rejected());
+        bacnet.io.push(polled_value());
+        let error = manager.service(&bacnet, &mut buf, 0).await.unwrap_err();
+        assert!(matches!(error.error, BacnetError::Io(())));
+        assert_eq!(error.updates.len(), 1);
+        assert_eq!(error.updates[0].key, polled);
+
+        // the subscription that timed out is tried again
+        bacnet
+            .io
+            .push(Reply::SimpleAck(ConfirmedServiceChoice::SubscribeCov));
+        let updates = manager.service(&bacnet, &mut buf, 1).await.unwrap();
+        assert!(updates.is_empty());
+        assert_eq!(manager.get(&timed_out).unwrap().source, UpdateSource::Cov);
+        assert_eq!(bacnet.io.sent_count(), 4);
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn rejects_other_confirmed_requests() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+        let mut manager = SubscriptionManager::new(DEVICE_ID, 10);
+
+        let read = ReadProperty::new(OBJECT_ID, PropertyId::PropPresentValue);
+        let request = ConfirmedRequest::new(7, ConfirmedRequestService::ReadProperty(read));
+        bacnet
+            .io
+            .push(Reply::Message(ApplicationPdu::ConfirmedRequest(request)));
+        let update = manager.receive(&bacnet, &mut buf).await.unwrap();
+        assert!(update.is_none());
+
+        match bacnet.io.sent_apdu(0) {
+            ApplicationPdu::Reject(reject) => {
+                assert_eq!(reject.invoke_id, 7);
+                assert_eq!(reject.reason, RejectReason::UnrecognizedService);
+            }
+            x => panic!("expected a reject, got {:?}", x),
+        }
+    }
+}
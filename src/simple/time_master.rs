@@ -0,0 +1,698 @@
+/// Acts as a time master by periodically sending time synchronization requests
+/// The schedule is read from the Time_Synchronization_Recipients, UTC_Time_Synchronization_Recipients, Time_Synchronization_Interval,
+/// Align_Intervals and Interval_Offset properties of a device object (usually the one representing the time master itself).
+/// A UTCTimeSynchronization is sent if there are any UTC recipients and a TimeSynchronization (in local time) is sent if there are any local recipients.
+/// Requests are broadcast to every device on every network (a global broadcast) rather than sent to each recipient because a `Bacnet` client only talks to one peer.
+/// The recipient lists therefore only determine which kind of request is sent and devices that are not on the lists receive the requests too.
+/// This module has no access to a clock so the caller passes in the current UTC date and time when servicing the schedule.
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use maybe_async::maybe_async;
+
+use crate::{
+    application_protocol::{
+        primitives::{
+            data_value::{ApplicationDataValue, Date, DateTime, Time},
+            recipient::Recipient,
+        },
+        services::{
+            read_property::{ReadProperty, ReadPropertyValue},
+            time_synchronization::{TimeSynchronization, UtcTimeSynchronization},
+        },
+    },
+    common::{error::Error, object_id::ObjectId, property_id::PropertyId, spec::ErrorCode},
+};
+
+use super::{Bacnet, BacnetError, NetworkIo};
+
+const SECONDS_PER_DAY: i64 = 86400;
+const MINUTES_PER_DAY: u32 = 1440;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TimeSyncConfig<'a> {
+    pub recipients: Vec<Recipient<'a>>,
+    pub utc_recipients: Vec<Recipient<'a>>,
+    pub interval_minutes: u32, // zero for no periodic synchronization
+    pub align_intervals: bool,
+    pub interval_offset_minutes: u32,
+}
+
+impl<'a> TimeSyncConfig<'a> {
+    /// Reads the time synchronization properties of a device
+    /// Properties the device does not support are left at their defaults (no recipients and no periodic synchronization)
+    #[maybe_async()]
+    pub async fn read<T>(
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+        device_id: ObjectId,
+    ) -> Result<Self, BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        let recipients = match read_optional(
+            bacnet,
+            buf,
+            device_id,
+            PropertyId::PropTimeSynchronizationRecipients,
+        )
+        .await?
+        {
+            Some(ReadPropertyValue::RecipientList(x)) => x.recipients,
+            Some(_) => return Err(unexpected("expected a list of recipients")),
+            None => Vec::new(),
+        };
+
+        let utc_recipients = match read_optional(
+            bacnet,
+            buf,
+            device_id,
+            PropertyId::PropUtcTimeSynchronizationRecipients,
+        )
+        .await?
+        {
+            Some(ReadPropertyValue::RecipientList(x)) => x.recipients,
+            Some(_) => return Err(unexpected("expected a list of recipients")),
+            None => Vec::new(),
+        };
+
+        let interval_minutes = match read_optional(
+            bacnet,
+            buf,
+            device_id,
+            PropertyId::PropTimeSynchronizationInterval,
+        )
+        .await?
+        {
+            Some(ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::UnsignedInt(x))) => {
+                x as u32
+            }
+            Some(_) => {
+                return Err(unexpected(
+                    "expected an unsigned Time_Synchronization_Interval",
+                ))
+            }
+            None => 0,
+        };
+
+        let align_intervals =
+            match read_optional(bacnet, buf, device_id, PropertyId::PropAlignIntervals).await? {
+                Some(ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::Boolean(x))) => {
+                    x
+                }
+                Some(_) => return Err(unexpected("expected a boolean Align_Intervals")),
+                None => false,
+            };
+
+        let interval_offset_minutes =
+            match read_optional(bacnet, buf, device_id, PropertyId::PropIntervalOffset).await? {
+                Some(ReadPropertyValue::ApplicationDataValue(
+                    ApplicationDataValue::UnsignedInt(x),
+                )) => x as u32,
+                Some(_) => return Err(unexpected("expected an unsigned Interval_Offset")),
+                None => 0,
+            };
+
+        Ok(Self {
+            recipients,
+            utc_recipients,
+            interval_minutes,
+            align_intervals,
+            interval_offset_minutes,
+        })
+    }
+}
+
+// the clock of a device as reported by its device object
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeviceClock {
+    pub local_date: Date,
+    pub local_time: Time,
+    pub utc_offset_minutes: i32, // positive west of UTC
+    pub daylight_savings: bool,
+}
+
+impl DeviceClock {
+    /// Reads the Local_Date, Local_Time, UTC_Offset and Daylight_Savings_Status properties of a device
+    #[maybe_async()]
+    pub async fn read<T>(
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+        device_id: ObjectId,
+    ) -> Result<Self, BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        let local_date = match read_value(bacnet, buf, device_id, PropertyId::PropLocalDate).await?
+        {
+            ApplicationDataValue::Date(x) => x,
+            _ => return Err(unexpected("expected a date for Local_Date")),
+        };
+
+        let local_time = match read_value(bacnet, buf, device_id, PropertyId::PropLocalTime).await?
+        {
+            ApplicationDataValue::Time(x) => x,
+            _ => return Err(unexpected("expected a time for Local_Time")),
+        };
+
+        // devices without these properties are assumed to be on UTC
+        let utc_offset_minutes =
+            match read_optional(bacnet, buf, device_id, PropertyId::PropUtcOffset).await? {
+                Some(ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::SignedInt(
+                    x,
+                ))) => x as i32,
+                Some(_) => return Err(unexpected("expected a signed UTC_Offset")),
+                None => 0,
+            };
+
+        let daylight_savings = match read_optional(
+            bacnet,
+            buf,
+            device_id,
+            PropertyId::PropDaylightSavingsStatus,
+        )
+        .await?
+        {
+            Some(ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::Boolean(x))) => x,
+            Some(_) => return Err(unexpected("expected a boolean Daylight_Savings_Status")),
+            None => false,
+        };
+
+        Ok(Self {
+            local_date,
+            local_time,
+            utc_offset_minutes,
+            daylight_savings,
+        })
+    }
+
+    /// The time of the device converted to UTC
+    pub fn utc(&self) -> DateTime {
+        let local = to_epoch_seconds(&self.local_date, &self.local_time);
+        let utc = local - local_offset_seconds(self.utc_offset_minutes, self.daylight_savings);
+        from_epoch_seconds(utc)
+    }
+
+    /// The number of seconds the device clock is ahead of `now_utc` (negative if it is behind)
+    pub fn drift_seconds(&self, now_utc: &DateTime) -> i64 {
+        let local = to_epoch_seconds(&self.local_date, &self.local_time);
+        let utc = local - local_offset_seconds(self.utc_offset_minutes, self.daylight_savings);
+        utc - to_epoch_seconds(&now_utc.date, &now_utc.time)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TimeMaster<'a> {
+    pub config: TimeSyncConfig<'a>,
+    pub utc_offset_minutes: i32, // of the time master, used to send local time to local recipients
+    pub daylight_savings: bool,
+    next_sync_at: Option<i64>, // seconds since 1970-01-01 UTC, none until the first sync and i64::MAX if there is no periodic sync
+}
+
+impl<'a> TimeMaster<'a> {
+    pub fn new(
+        config: TimeSyncConfig<'a>,
+        utc_offset_minutes: i32,
+        daylight_savings: bool,
+    ) -> Self {
+        Self {
+            config,
+            utc_offset_minutes,
+            daylight_savings,
+            next_sync_at: None,
+        }
+    }
+
+    /// When the next synchronization is due (none if it is due straight away or there is no periodic synchronization)
+    pub fn next_sync_at(&self) -> Option<DateTime> {
+        self.next_sync_at
+            .filter(|x| *x != i64::MAX)
+            .map(from_epoch_seconds)
+    }
+
+    pub fn is_due(&self, now_utc: &DateTime) -> bool {
+        match self.next_sync_at {
+            Some(next_sync_at) => to_epoch_seconds(&now_utc.date, &now_utc.time) >= next_sync_at,
+            None => true,
+        }
+    }
+
+    /// Sends time synchronization requests if they are due and schedules the next ones
+    /// Returns true if requests were sent. The first call always sends them.
+    #[maybe_async()]
+    pub async fn service<T>(
+        &mut self,
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+        now_utc: &DateTime,
+    ) -> Result<bool, BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        if !self.is_due(now_utc) {
+            return Ok(false);
+        }
+
+        self.sync_now(bacnet, buf, now_utc).await?;
+        self.schedule_next(to_epoch_seconds(&now_utc.date, &now_utc.time));
+        Ok(true)
+    }
+
+    /// Broadcasts time synchronization requests immediately without affecting the schedule
+    #[maybe_async()]
+    pub async fn sync_now<T>(
+        &self,
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+        now_utc: &DateTime,
+    ) -> Result<(), BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        if !self.config.utc_recipients.is_empty() {
+            let request = UtcTimeSynchronization {
+                date: now_utc.date.clone(),
+                time: now_utc.time.clone(),
+            };
+            bacnet.broadcast_utc_time_sync(buf, request).await?;
+        }
+
+        if !self.config.recipients.is_empty() {
+            let utc = to_epoch_seconds(&now_utc.date, &now_utc.time);
+            let local = utc + local_offset_seconds(self.utc_offset_minutes, self.daylight_savings);
+            let DateTime { date, time } = from_epoch_seconds(local);
+            bacnet
+                .broadcast_time_sync(buf, TimeSynchronization { date, time })
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the clock of a device and returns the number of seconds it is ahead of `now_utc` (negative if it is behind)
+    #[maybe_async()]
+    pub async fn measure_drift<T>(
+        &self,
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+        device_id: ObjectId,
+        now_utc: &DateTime,
+    ) -> Result<i64, BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        let clock = DeviceClock::read(bacnet, buf, device_id).await?;
+        Ok(clock.drift_seconds(now_utc))
+    }
+
+    fn schedule_next(&mut self, now: i64) {
+        let interval_minutes = self.config.interval_minutes;
+        if interval_minutes == 0 {
+            self.next_sync_at = Some(i64::MAX);
+            return;
+        }
+
+        let interval = interval_minutes as i64 * 60;
+
+        // intervals can only be aligned to the hour or day (in local time) if they divide into them
+        // anything that divides into an hour also divides into a day
+        #[allow(clippy::manual_is_multiple_of)] // u32::is_multiple_of needs rust 1.87
+        let aligned = self.config.align_intervals && MINUTES_PER_DAY % interval_minutes == 0;
+        if aligned {
+            let local_offset = local_offset_seconds(self.utc_offset_minutes, self.daylight_savings);
+            let offset = (self.config.interval_offset_minutes % interval_minutes) as i64 * 60;
+            let local = now + local_offset;
+            let period_start = (local - offset).div_euclid(interval) * interval + offset;
+            self.next_sync_at = Some(period_start + interval - local_offset);
+        } else {
+            self.next_sync_at = Some(now + interval);
+        }
+    }
+}
+
+// returns None if the device does not support the property
+#[maybe_async()]
+async fn read_optional<'a, T>(
+    bacnet: &Bacnet<T>,
+    buf: &mut [u8],
+    device_id: ObjectId,
+    property_id: PropertyId,
+) -> Result<Option<ReadPropertyValue<'a>>, BacnetError<T>>
+where
+    T: NetworkIo + Debug,
+{
+    let request = ReadProperty::new(device_id, property_id);
+    match bacnet.read_property(buf, request).await {
+        Ok(ack) => Ok(Some(ack.property_value)),
+        Err(BacnetError::Bacnet(e)) if matches!(e.error_code, ErrorCode::UnknownProperty) => {
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+#[maybe_async()]
+async fn read_value<'a, T>(
+    bacnet: &Bacnet<T>,
+    buf: &mut [u8],
+    device_id: ObjectId,
+    property_id: PropertyId,
+) -> Result<ApplicationDataValue<'a>, BacnetError<T>>
+where
+    T: NetworkIo + Debug,
+{
+    let request = ReadProperty::new(device_id, property_id);
+    let ack = bacnet.read_property(buf, request).await?;
+    match ack.property_value {
+        ReadPropertyValue::ApplicationDataValue(x) => Ok(x),
+        _ => Err(unexpected("expected an application data value")),
+    }
+}
+
+fn unexpected<T: NetworkIo>(message: &'static str) -> BacnetError<T> {
+    BacnetError::Codec(Error::InvalidValue(message))
+}
+
+// the number of seconds local time is ahead of UTC
+// the UTC offset is the number of minutes local standard time is behind UTC
+fn local_offset_seconds(utc_offset_minutes: i32, daylight_savings: bool) -> i64 {
+    let daylight_savings = if daylight_savings { 3600 } else { 0 };
+    daylight_savings - utc_offset_minutes as i64 * 60
+}
+
+// days since 1970-01-01 from a gregorian calendar date (Howard Hinnant's days_from_civil algorithm)
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12; // March is 0
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+// the reverse of days_from_civil
+fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153; // March is 0
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u8;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u8;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn to_epoch_seconds(date: &Date, time: &Time) -> i64 {
+    let days = days_from_civil(date.year as i64, date.month as i64, date.day as i64);
+    days * SECONDS_PER_DAY + time.hour as i64 * 3600 + time.minute as i64 * 60 + time.second as i64
+}
+
+fn from_epoch_seconds(seconds: i64) -> DateTime {
+    let days = seconds.div_euclid(SECONDS_PER_DAY);
+    let seconds = seconds.rem_euclid(SECONDS_PER_DAY);
+    let (year, month, day) = civil_from_days(days);
+
+    // 1970-01-01 was a Thursday (1 is Monday)
+    let wday = (days + 3).rem_euclid(7) as u8 + 1;
+
+    let date = Date {
+        year: year as u16,
+        month,
+        day,
+        wday,
+    };
+    let time = Time {
+        hour: (seconds / 3600) as u8,
+        minute: (seconds % 3600 / 60) as u8,
+        second: (seconds % 60) as u8,
+        hundredths: 0,
+    };
+    DateTime::new(date, time)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::{
+        application_protocol::{
+            application_pdu::ApplicationPdu,
+            confirmed::{ComplexAckService, ConfirmedServiceChoice},
+            primitives::{
+                data_value::{ApplicationDataValue, Date, DateTime, Time},
+                recipient::Recipient,
+            },
+            services::read_property::{ReadPropertyAck, ReadPropertyValue},
+            unconfirmed::UnconfirmedRequest,
+        },
+        common::{
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{ErrorClass, ErrorCode},
+        },
+        simple::{
+            fake_network::{FakeNetwork, Reply},
+            Bacnet, BacnetError,
+        },
+    };
+
+    use super::{
+        civil_from_days, days_from_civil, from_epoch_seconds, to_epoch_seconds, DeviceClock,
+        TimeMaster, TimeSyncConfig,
+    };
+
+    const CET: i32 = -60; // an hour ahead of UTC
+
+    fn date_time(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> DateTime {
+        let date = Date {
+            year,
+            month,
+            day,
+            wday: 255,
+        };
+        let time = Time {
+            hour,
+            minute,
+            second,
+            hundredths: 0,
+        };
+        DateTime::new(date, time)
+    }
+
+    fn config(interval_minutes: u32, interval_offset_minutes: u32) -> TimeSyncConfig<'static> {
+        TimeSyncConfig {
+            recipients: vec![Recipient::Device(ObjectId::new(
+                ObjectType::ObjectDevice,
+                12,
+            ))],
+            utc_recipients: vec![],
+            interval_minutes,
+            align_intervals: true,
+            interval_offset_minutes,
+        }
+    }
+
+    fn assert_time(date_time: &DateTime, hour: u8, minute: u8, second: u8) {
+        assert_eq!(
+            (
+                date_time.time.hour,
+                date_time.time.minute,
+                date_time.time.second
+            ),
+            (hour, minute, second)
+        );
+    }
+
+    #[test]
+    fn civil_days() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        assert_eq!(days_from_civil(2024, 2, 29), 19782);
+
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(11017), (2000, 3, 1));
+        assert_eq!(civil_from_days(19782), (2024, 2, 29));
+        assert_eq!(civil_from_days(19783), (2024, 3, 1));
+
+        // 2024-03-31 was a Sunday
+        let date_time = from_epoch_seconds(days_from_civil(2024, 3, 31) * 86400 + 3661);
+        assert_eq!(date_time.date.wday, 7);
+        assert_time(&date_time, 1, 1, 1);
+    }
+
+    #[test]
+    fn schedule_aligned_with_offset() {
+        // hourly at a quarter past in local time
+        let mut master = TimeMaster::new(config(60, 15), CET, false);
+        let now = date_time(2024, 3, 31, 10, 20, 0);
+        assert!(master.is_due(&now));
+        master.schedule_next(to_epoch_seconds(&now.date, &now.time));
+
+        // 11:20 local so the next sync is at 12:15 local
+        let next = master.next_sync_at().unwrap();
+        assert_time(&next, 11, 15, 0);
+        assert!(!master.is_due(&date_time(2024, 3, 31, 11, 14, 59)));
+        assert!(master.is_due(&date_time(2024, 3, 31, 11, 15, 0)));
+
+        // an offset longer than the interval wraps around
+        let mut master = TimeMaster::new(config(60, 75), CET, false);
+        master.schedule_next(to_epoch_seconds(&now.date, &now.time));
+        assert_time(&master.next_sync_at().unwrap(), 11, 15, 0);
+
+        // daily at 02:30 local time during daylight savings crosses midnight
+        let mut master = TimeMaster::new(config(1440, 150), CET, true);
+        master.schedule_next(to_epoch_seconds(&now.date, &now.time));
+        let next = master.next_sync_at().unwrap();
+        assert_eq!((next.date.month, next.date.day), (4, 1));
+        assert_time(&next, 0, 30, 0);
+    }
+
+    #[test]
+    fn schedule_unaligned() {
+        // 7 minutes does not divide into a day so the interval is not aligned
+        let mut master = TimeMaster::new(config(7, 0), CET, false);
+        let now = date_time(2024, 3, 31, 10, 20, 30);
+        master.schedule_next(to_epoch_seconds(&now.date, &now.time));
+        assert_time(&master.next_sync_at().unwrap(), 10, 27, 30);
+
+        // no periodic synchronization
+        let mut master = TimeMaster::new(config(0, 0), CET, false);
+        master.schedule_next(to_epoch_seconds(&now.date, &now.time));
+        assert!(master.next_sync_at().is_none());
+        assert!(!master.is_due(&date_time(2100, 1, 1, 0, 0, 0)));
+    }
+
+    #[test]
+    fn drift_seconds() {
+        // two hours ahead of UTC during daylight savings
+        let clock = DeviceClock {
+            local_date: Date {
+                year: 2024,
+                month: 4,
+                day: 1,
+                wday: 1,
+            },
+            local_time: Time {
+                hour: 1,
+                minute: 0,
+                second: 30,
+                hundredths: 0,
+            },
+            utc_offset_minutes: CET,
+            daylight_savings: true,
+        };
+
+        let utc = clock.utc();
+        assert_eq!((utc.date.month, utc.date.day), (3, 31));
+        assert_time(&utc, 23, 0, 30);
+        assert_eq!(clock.drift_seconds(&date_time(2024, 3, 31, 23, 0, 0)), 30);
+        assert_eq!(clock.drift_seconds(&date_time(2024, 4, 1, 0, 0, 0)), -3570);
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn service() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+        let mut config = config(60, 0);
+        config.utc_recipients = config.recipients.clone();
+        let mut master = TimeMaster::new(config, CET, false);
+
+        let now = date_time(2024, 3, 31, 23, 30, 0);
+        let sent = master.service(&bacnet, &mut buf, &now).await.unwrap();
+        assert!(sent);
+        let later = date_time(2024, 3, 31, 23, 45, 0);
+        let sent = master.service(&bacnet, &mut buf, &later).await.unwrap();
+        assert!(!sent);
+        assert_eq!(bacnet.io.sent_count(), 2);
+
+        match bacnet.io.sent_apdu(0) {
+            ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::UtcTimeSynchronization(x)) => {
+                assert_eq!(x.date.day, 31);
+                assert_time(&DateTime::new(x.date, x.time), 23, 30, 0);
+            }
+            x => panic!("unexpected apdu {:?}", x),
+        }
+
+        // local recipients are sent local time
+        match bacnet.io.sent_apdu(1) {
+            ApplicationPdu::UnconfirmedRequest(UnconfirmedRequest::TimeSynchronization(x)) => {
+                assert_eq!((x.date.month, x.date.day, x.date.wday), (4, 1, 1));
+                assert_time(&DateTime::new(x.date, x.time), 0, 30, 0);
+            }
+            x => panic!("unexpected apdu {:?}", x),
+        }
+    }
+
+    fn unknown_property() -> Reply {
+        Reply::Error(
+            ConfirmedServiceChoice::ReadProperty,
+            ErrorClass::Property,
+            ErrorCode::UnknownProperty,
+        )
+    }
+
+    fn unsigned(property_id: PropertyId, value: u64) -> Reply {
+        Reply::ComplexAck(ComplexAckService::ReadProperty(ReadPropertyAck {
+            object_id: ObjectId::new(ObjectType::ObjectDevice, 79079),
+            property_id,
+            property_value: ReadPropertyValue::ApplicationDataValue(
+                ApplicationDataValue::UnsignedInt(value),
+            ),
+        }))
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn read_config() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+        let device_id = ObjectId::new(ObjectType::ObjectDevice, 79079);
+
+        // only the interval is supported
+        bacnet.io.push(unknown_property());
+        bacnet.io.push(unknown_property());
+        bacnet
+            .io
+            .push(unsigned(PropertyId::PropTimeSynchronizationInterval, 60));
+        bacnet.io.push(unknown_property());
+        bacnet.io.push(unknown_property());
+
+        let config = TimeSyncConfig::read(&bacnet, &mut buf, device_id)
+            .await
+            .unwrap();
+        assert!(config.recipients.is_empty());
+        assert!(config.utc_recipients.is_empty());
+        assert_eq!(config.interval_minutes, 60);
+        assert!(!config.align_intervals);
+        assert_eq!(config.interval_offset_minutes, 0);
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn read_config_fails_on_other_errors() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+        let device_id = ObjectId::new(ObjectType::ObjectDevice, 79079);
+
+        bacnet.io.push(Reply::Error(
+            ConfirmedServiceChoice::ReadProperty,
+            ErrorClass::Device,
+            ErrorCode::DeviceBusy,
+        ));
+
+        let result = TimeSyncConfig::read(&bacnet, &mut buf, device_id).await;
+        assert!(matches!(result, Err(BacnetError::Bacnet(_))));
+        assert_eq!(bacnet.io.remaining_replies(), 0);
+    }
+}
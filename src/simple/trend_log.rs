@@ -0,0 +1,383 @@
+/// Downloads the Log_Buffer of a Trend Log (or Trend Log Multiple or Event Log) object a page at a time using ReadRange by sequence number
+/// Every record a log object collects is given a sequence number which is one more than the previous one (wrapping from 2^32 - 1 back to 1).
+/// The sequence number of the last record received is kept in `last_sequence_number` so that it can be persisted and the download resumed later,
+/// fetching only the records that were collected in the meantime.
+/// Records that were overwritten (the buffer is circular) or purged before they could be read are skipped and counted in `missed_records`.
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use maybe_async::maybe_async;
+
+use crate::{
+    application_protocol::{
+        primitives::data_value::{ApplicationDataValue, BitString},
+        services::{
+            read_property::{ReadProperty, ReadPropertyValue},
+            read_range::{ReadRange, ReadRangeBySequence, ReadRangeItem, ReadRangeRequestType},
+        },
+    },
+    common::{error::Error, object_id::ObjectId, property_id::PropertyId},
+};
+
+use super::{Bacnet, BacnetError, NetworkIo};
+
+// sequence numbers go from 1 to 2^32 - 1 and then wrap back to 1
+const SEQUENCE_NUMBER_COUNT: u64 = u32::MAX as u64;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TrendLogRecord {
+    pub sequence_number: u32,
+    pub item: ReadRangeItem<'static>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TrendLogPage {
+    pub records: Vec<TrendLogRecord>,
+    pub more_items: bool, // the device has more records after this page
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrendLogDownload {
+    pub object_id: ObjectId,
+    pub page_size: u32, // the device may return fewer records than this if they do not fit into a single message
+    pub last_sequence_number: Option<u32>, // persist this to resume the download later
+    pub missed_records: u64,
+}
+
+impl TrendLogDownload {
+    /// Starts at the oldest record in the buffer
+    pub fn new(object_id: ObjectId) -> Self {
+        Self {
+            object_id,
+            page_size: 50,
+            last_sequence_number: None,
+            missed_records: 0,
+        }
+    }
+
+    /// Starts at the record after `last_sequence_number` (or the oldest record if that has since been overwritten)
+    pub fn resume(object_id: ObjectId, last_sequence_number: u32) -> Self {
+        Self {
+            last_sequence_number: Some(last_sequence_number),
+            ..Self::new(object_id)
+        }
+    }
+
+    /// Fetches pages until the device has no more records
+    #[maybe_async()]
+    pub async fn download<T>(
+        &mut self,
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+    ) -> Result<Vec<TrendLogRecord>, BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        let mut records = Vec::new();
+        loop {
+            let page = self.next_page(bacnet, buf).await?;
+            let count = page.records.len();
+            records.extend(page.records);
+
+            // guard against a device that keeps saying there is more without returning anything
+            if !page.more_items || count == 0 {
+                return Ok(records);
+            }
+        }
+    }
+
+    /// Fetches the records after `last_sequence_number`
+    /// An empty page means that we are up to date.
+    #[maybe_async()]
+    pub async fn next_page<T>(
+        &mut self,
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+    ) -> Result<TrendLogPage, BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        let start = match self.last_sequence_number {
+            Some(last) => add_sequence(last, 1),
+            None => match self.read_sequence_range(bacnet, buf).await? {
+                Some((oldest, _)) => oldest,
+                None => return Ok(TrendLogPage::empty()),
+            },
+        };
+
+        let page = self.read_page(bacnet, buf, start).await?;
+        if !page.records.is_empty() {
+            return Ok(page);
+        }
+
+        // either we are up to date or the records we are after are no longer in the buffer
+        let (oldest, newest) = match self.read_sequence_range(bacnet, buf).await? {
+            Some(range) => range,
+            None => return Ok(page),
+        };
+        if sequence_distance(oldest, start) <= sequence_distance(oldest, newest) + 1 {
+            return Ok(page);
+        }
+
+        // a large gap means that the log was reset and its sequence numbers started again
+        let gap = sequence_distance(start, oldest);
+        if gap < SEQUENCE_NUMBER_COUNT / 2 {
+            self.missed_records += gap;
+        }
+        self.read_page(bacnet, buf, oldest).await
+    }
+
+    /// Returns the sequence numbers of the oldest and newest records in the buffer or None if the buffer is empty
+    #[maybe_async()]
+    pub async fn read_sequence_range<T>(
+        &self,
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+    ) -> Result<Option<(u32, u32)>, BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        // the newest record is given the total record count as its sequence number
+        let newest = self
+            .read_unsigned(bacnet, buf, PropertyId::PropTotalRecordCount)
+            .await?;
+        let record_count = self
+            .read_unsigned(bacnet, buf, PropertyId::PropRecordCount)
+            .await?;
+        if newest == 0 || record_count == 0 {
+            return Ok(None);
+        }
+
+        let oldest = add_sequence(newest, SEQUENCE_NUMBER_COUNT - (record_count as u64 - 1));
+        Ok(Some((oldest, newest)))
+    }
+
+    #[maybe_async()]
+    async fn read_page<T>(
+        &mut self,
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+        start: u32,
+    ) -> Result<TrendLogPage, BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        let request_type = ReadRangeRequestType::BySequence(ReadRangeBySequence {
+            sequence_num: start,
+            count: self.page_size,
+        });
+        let request = ReadRange::new(self.object_id, PropertyId::PropLogBuffer, request_type);
+        let ack = bacnet.read_range(buf, request).await?;
+
+        let more_items =
+            matches!(&ack.result_flags, BitString::LogBufferResult(x) if x.more_items());
+        let items: Vec<ReadRangeItem<'static>> = ack.item_data.items;
+        if items.is_empty() {
+            return Ok(TrendLogPage::empty());
+        }
+
+        // records between the one we asked for and the first one returned were overwritten
+        let first = ack.first_sequence_number.unwrap_or(start);
+        self.missed_records += sequence_distance(start, first);
+
+        let records: Vec<TrendLogRecord> = items
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| TrendLogRecord {
+                sequence_number: add_sequence(first, index as u64),
+                item,
+            })
+            .collect();
+        self.last_sequence_number = records.last().map(|x| x.sequence_number);
+
+        Ok(TrendLogPage {
+            records,
+            more_items,
+        })
+    }
+
+    #[maybe_async()]
+    async fn read_unsigned<T>(
+        &self,
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+        property_id: PropertyId,
+    ) -> Result<u32, BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        let request = ReadProperty::new(self.object_id, property_id);
+        let ack = bacnet.read_property(buf, request).await?;
+        match ack.property_value {
+            ReadPropertyValue::ApplicationDataValue(ApplicationDataValue::UnsignedInt(x)) => {
+                Ok(x as u32)
+            }
+            _ => Err(BacnetError::Codec(Error::InvalidValue(
+                "expected an unsigned record count",
+            ))),
+        }
+    }
+}
+
+impl TrendLogPage {
+    fn empty() -> Self {
+        Self {
+            records: Vec::new(),
+            more_items: false,
+        }
+    }
+}
+
+fn add_sequence(sequence_number: u32, count: u64) -> u32 {
+    ((sequence_number as u64 + SEQUENCE_NUMBER_COUNT - 1 + count) % SEQUENCE_NUMBER_COUNT + 1)
+        as u32
+}
+
+fn sequence_distance(from: u32, to: u32) -> u64 {
+    (to as u64 + SEQUENCE_NUMBER_COUNT - from as u64) % SEQUENCE_NUMBER_COUNT
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::{
+        application_protocol::{
+            confirmed::{ComplexAckService, ConfirmedRequestService},
+            primitives::data_value::{BitString, Date, Time},
+            services::read_range::{
+                LogRecord, ReadRangeAck, ReadRangeItem, ReadRangeItems, ReadRangeRequestType,
+                ReadRangeValue,
+            },
+        },
+        common::{
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{LogBufferResult, LogBufferResultFlags, BACNET_ARRAY_ALL},
+        },
+        simple::{
+            fake_network::{FakeNetwork, Reply},
+            Bacnet,
+        },
+    };
+
+    use super::{add_sequence, sequence_distance, TrendLogDownload, SEQUENCE_NUMBER_COUNT};
+
+    fn trend_log() -> ObjectId {
+        ObjectId::new(ObjectType::ObjectTrendlog, 1)
+    }
+
+    fn record(value: f32) -> ReadRangeItem<'static> {
+        ReadRangeItem::Log(LogRecord {
+            date: Date {
+                year: 2024,
+                month: 3,
+                day: 14,
+                wday: 4,
+            },
+            time: Time {
+                hour: 10,
+                minute: 15,
+                second: 0,
+                hundredths: 0,
+            },
+            value: ReadRangeValue::Real(value),
+            status_flags: None,
+        })
+    }
+
+    fn log_buffer(first_sequence_number: u32, values: &[f32], more_items: bool) -> Reply {
+        let mut flags = LogBufferResultFlags::LastItem as u8;
+        if more_items {
+            flags = LogBufferResultFlags::MoreItems as u8;
+        }
+        Reply::ComplexAck(ComplexAckService::ReadRange(ReadRangeAck {
+            object_id: trend_log(),
+            property_id: PropertyId::PropLogBuffer,
+            array_index: BACNET_ARRAY_ALL,
+            result_flags: BitString::LogBufferResult(LogBufferResult::new(flags)),
+            item_count: values.len(),
+            item_data: ReadRangeItems::new(values.iter().map(|x| record(*x)).collect()),
+            first_sequence_number: Some(first_sequence_number),
+        }))
+    }
+
+    // the sequence number the request at `index` asked for
+    fn requested_start(bacnet: &Bacnet<FakeNetwork>, index: usize) -> u32 {
+        match bacnet.io.sent_request(index) {
+            ConfirmedRequestService::ReadRange(x) => match x.request_type {
+                ReadRangeRequestType::BySequence(x) => x.sequence_num,
+                x => panic!("unexpected request type {:?}", x),
+            },
+            x => panic!("unexpected request {:?}", x),
+        }
+    }
+
+    #[test]
+    fn sequence_arithmetic() {
+        assert_eq!(add_sequence(5, 0), 5);
+        assert_eq!(add_sequence(u32::MAX - 1, 1), u32::MAX);
+
+        // there is no sequence number 0
+        assert_eq!(add_sequence(u32::MAX, 1), 1);
+        assert_eq!(add_sequence(u32::MAX - 1, 3), 2);
+
+        // adding one less than the count steps back by one
+        assert_eq!(add_sequence(1, SEQUENCE_NUMBER_COUNT - 1), u32::MAX);
+        assert_eq!(add_sequence(2, SEQUENCE_NUMBER_COUNT - 3), u32::MAX - 1);
+
+        assert_eq!(sequence_distance(7, 7), 0);
+        assert_eq!(sequence_distance(7, 10), 3);
+        assert_eq!(sequence_distance(u32::MAX, 1), 1);
+        assert_eq!(sequence_distance(u32::MAX - 1, 2), 3);
+        assert_eq!(sequence_distance(1, u32::MAX), u32::MAX as u64 - 1);
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn next_page_wraps() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+        let mut download = TrendLogDownload::resume(trend_log(), u32::MAX);
+
+        bacnet.io.push(log_buffer(1, &[18.0, 18.1], false));
+        let page = download.next_page(&bacnet, &mut buf).await.unwrap();
+        assert_eq!(requested_start(&bacnet, 0), 1);
+        assert_eq!(page.records.len(), 2);
+        assert_eq!(page.records[0].sequence_number, 1);
+        assert_eq!(page.records[1].sequence_number, 2);
+        assert!(!page.more_items);
+        assert_eq!(download.last_sequence_number, Some(2));
+        assert_eq!(download.missed_records, 0);
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn next_page_skips_purged_records() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+
+        // the device no longer has records 101 to 104
+        let mut download = TrendLogDownload::resume(trend_log(), 100);
+        bacnet.io.push(log_buffer(105, &[18.0, 18.1], true));
+        let page = download.next_page(&bacnet, &mut buf).await.unwrap();
+        assert_eq!(requested_start(&bacnet, 0), 101);
+        assert_eq!(page.records[0].sequence_number, 105);
+        assert_eq!(page.records[1].sequence_number, 106);
+        assert!(page.more_items);
+        assert_eq!(download.last_sequence_number, Some(106));
+        assert_eq!(download.missed_records, 4);
+
+        // the same across the wrap from 2^32 - 1 back to 1
+        let mut download = TrendLogDownload::resume(trend_log(), u32::MAX - 2);
+        bacnet.io.push(log_buffer(2, &[18.2], false));
+        let page = download.next_page(&bacnet, &mut buf).await.unwrap();
+        assert_eq!(requested_start(&bacnet, 1), u32::MAX - 1);
+        assert_eq!(page.records.len(), 1);
+        assert_eq!(page.records[0].sequence_number, 2);
+        assert_eq!(download.last_sequence_number, Some(2));
+        assert_eq!(download.missed_records, 3);
+    }
+}
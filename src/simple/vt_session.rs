@@ -0,0 +1,371 @@
+/// A client side virtual terminal session for interacting with the operator interface of a remote device
+/// Each side of a session has its own session id: we pick the local id when opening the session and the remote device picks the remote one.
+/// Data we send is addressed to the remote id and data the remote device sends us is addressed to the local id.
+/// Keystrokes are sent with `send` and terminal output is collected by calling `receive` in a loop (e.g. alongside reading from stdin).
+use alloc::{vec, vec::Vec};
+use core::fmt::Debug;
+
+use maybe_async::maybe_async;
+
+use crate::{
+    application_protocol::{
+        application_pdu::ApplicationPdu,
+        confirmed::{
+            ComplexAck, ComplexAckService, ConfirmedBacnetError, ConfirmedRequest,
+            ConfirmedRequestService, ConfirmedServiceChoice, Reject, SimpleAck,
+        },
+        primitives::data_value::OctetString,
+        services::virtual_terminal::{VtClose, VtData, VtDataAck, VtOpen},
+    },
+    common::{
+        error::Error,
+        spec::{ErrorClass, ErrorCode, RejectReason, VtClass},
+    },
+};
+
+use super::{Bacnet, BacnetError, NetworkIo, MAX_APDU};
+
+// room for the network headers and the tags surrounding the data in a vt data message
+const VT_DATA_OVERHEAD: usize = 32;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum VtOutput {
+    Data(Vec<u8>),
+    Closed, // the remote device closed the session
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VtSession {
+    pub vt_class: VtClass,
+    pub local_session_id: u8,
+    pub remote_session_id: u8,
+}
+
+impl VtSession {
+    #[maybe_async()]
+    pub async fn open<T>(
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+        vt_class: VtClass,
+        local_session_id: u8,
+    ) -> Result<Self, BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        let request = VtOpen::new(vt_class, local_session_id);
+        let ack = bacnet.vt_open(buf, request).await?;
+        Ok(Self {
+            vt_class,
+            local_session_id,
+            remote_session_id: ack.remote_session_id,
+        })
+    }
+
+    /// Sends data to the remote device, split into as many messages as needed to fit into `buf`
+    /// Returns the number of bytes the remote device accepted which is less than the length of `data` if its input buffer is full.
+    /// The caller should wait a while and send the rest again in that case.
+    #[maybe_async()]
+    pub async fn send<T>(
+        &self,
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+        data: &[u8],
+    ) -> Result<usize, BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        let chunk_size = Self::chunk_size(buf)?;
+        let mut sent = 0;
+
+        while sent < data.len() {
+            let end = (sent + chunk_size).min(data.len());
+            let is_last = end == data.len();
+            let request = VtData::new(
+                self.remote_session_id,
+                OctetString::new(&data[sent..end]),
+                is_last,
+            );
+            let ack = bacnet.vt_data(buf, request).await?;
+
+            if ack.all_new_data_accepted {
+                sent = end;
+            } else {
+                let accepted = ack.accepted_octet_count.unwrap_or(0) as usize;
+                return Ok(sent + accepted.min(end - sent));
+            }
+        }
+
+        Ok(sent)
+    }
+
+    /// Reads the next packet from the network and returns terminal output addressed to this session
+    /// Output and close requests from the remote device are acknowledged before returning.
+    /// Output and close requests for other sessions get an UnknownVtSession error, other confirmed requests are rejected and anything else is discarded.
+    #[maybe_async()]
+    pub async fn receive<T>(
+        &self,
+        bacnet: &Bacnet<T>,
+        buf: &mut [u8],
+    ) -> Result<Option<VtOutput>, BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        let (invoke_id, service) = match bacnet.receive(buf).await? {
+            Some(ApplicationPdu::ConfirmedRequest(ConfirmedRequest {
+                invoke_id, service, ..
+            })) => (invoke_id, service),
+            _ => return Ok(None),
+        };
+
+        match service {
+            ConfirmedRequestService::VtData(request)
+                if request.session_id == self.local_session_id =>
+            {
+                let ack = ComplexAck {
+                    invoke_id,
+                    service: ComplexAckService::VtData(VtDataAck::new(true, None)),
+                };
+                bacnet.send_complex_ack(buf, ack).await?;
+                Ok(Some(VtOutput::Data(request.data.inner.to_vec())))
+            }
+            ConfirmedRequestService::VtClose(request)
+                if request.remote_session_ids.contains(&self.local_session_id) =>
+            {
+                let ack = SimpleAck {
+                    invoke_id,
+                    service_choice: ConfirmedServiceChoice::VtClose,
+                };
+                bacnet.send_simple_ack(buf, ack).await?;
+                Ok(Some(VtOutput::Closed))
+            }
+            ConfirmedRequestService::VtData(_) => {
+                let error = Self::unknown_session(invoke_id, ConfirmedServiceChoice::VtData);
+                bacnet.send_error(buf, error).await?;
+                Ok(None)
+            }
+            ConfirmedRequestService::VtClose(_) => {
+                let error = Self::unknown_session(invoke_id, ConfirmedServiceChoice::VtClose);
+                bacnet.send_error(buf, error).await?;
+                Ok(None)
+            }
+            _ => {
+                let reject = Reject::new(invoke_id, RejectReason::UnrecognizedService);
+                bacnet.send_reject(buf, reject).await?;
+                Ok(None)
+            }
+        }
+    }
+
+    #[maybe_async()]
+    pub async fn close<T>(self, bacnet: &Bacnet<T>, buf: &mut [u8]) -> Result<(), BacnetError<T>>
+    where
+        T: NetworkIo + Debug,
+    {
+        let request = VtClose::new(vec![self.remote_session_id]);
+        bacnet.vt_close(buf, request).await
+    }
+
+    // the response to output or close requests addressed to a session other than this one
+    fn unknown_session(
+        invoke_id: u8,
+        service_choice: ConfirmedServiceChoice,
+    ) -> ConfirmedBacnetError {
+        ConfirmedBacnetError {
+            invoke_id,
+            service_choice,
+            error_class: ErrorClass::Services,
+            error_code: ErrorCode::UnknownVtSession,
+        }
+    }
+
+    // the number of data octets that can be sent in a single message
+    fn chunk_size<T: NetworkIo>(buf: &[u8]) -> Result<usize, BacnetError<T>> {
+        let len = buf.len().min(MAX_APDU);
+        if len <= VT_DATA_OVERHEAD {
+            return Err(BacnetError::Codec(Error::Length((
+                "buffer too small to send vt data",
+                buf.len() as u32,
+            ))));
+        }
+
+        Ok(len - VT_DATA_OVERHEAD)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::{
+        application_protocol::{
+            application_pdu::ApplicationPdu,
+            confirmed::{
+                ComplexAckService, ConfirmedRequest, ConfirmedRequestService,
+                ConfirmedServiceChoice,
+            },
+            primitives::data_value::OctetString,
+            services::{
+                read_property::ReadProperty,
+                virtual_terminal::{VtClose, VtData, VtDataAck, VtOpenAck},
+            },
+        },
+        common::{
+            object_id::{ObjectId, ObjectType},
+            property_id::PropertyId,
+            spec::{ErrorClass, ErrorCode, RejectReason, VtClass},
+        },
+        simple::{
+            fake_network::{FakeNetwork, Reply},
+            Bacnet,
+        },
+    };
+
+    use super::{VtOutput, VtSession};
+
+    fn request(invoke_id: u8, service: ConfirmedRequestService<'static>) -> Reply {
+        Reply::Message(ApplicationPdu::ConfirmedRequest(ConfirmedRequest::new(
+            invoke_id, service,
+        )))
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn opens_exchanges_data_and_closes() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+
+        bacnet.io.push(Reply::ComplexAck(ComplexAckService::VtOpen(
+            VtOpenAck::new(9),
+        )));
+        let session = VtSession::open(&bacnet, &mut buf, VtClass::DefaultTerminal, 3)
+            .await
+            .unwrap();
+        assert_eq!(session.remote_session_id, 9);
+        match bacnet.io.sent_request(0) {
+            ConfirmedRequestService::VtOpen(x) => assert_eq!(x.local_session_id, 3),
+            x => panic!("unexpected request {:?}", x),
+        }
+
+        // keystrokes are addressed to the remote session
+        bacnet.io.push(Reply::ComplexAck(ComplexAckService::VtData(
+            VtDataAck::new(true, None),
+        )));
+        let sent = session.send(&bacnet, &mut buf, b"dir\r").await.unwrap();
+        assert_eq!(sent, 4);
+        match bacnet.io.sent_request(1) {
+            ConfirmedRequestService::VtData(x) => {
+                assert_eq!(x.session_id, 9);
+                assert_eq!(x.data.as_slice(), b"dir\r");
+            }
+            x => panic!("unexpected request {:?}", x),
+        }
+
+        // output is addressed to the local session and acknowledged
+        let output = VtData::new(3, OctetString::new(b"login: "), true);
+        bacnet
+            .io
+            .push(request(4, ConfirmedRequestService::VtData(output)));
+        let received = session.receive(&bacnet, &mut buf).await.unwrap();
+        assert_eq!(received, Some(VtOutput::Data(b"login: ".to_vec())));
+        match bacnet.io.sent_apdu(2) {
+            ApplicationPdu::ComplexAck(ack) => {
+                assert_eq!(ack.invoke_id, 4);
+                assert!(matches!(
+                    ack.service,
+                    ComplexAckService::VtData(x) if x.all_new_data_accepted
+                ));
+            }
+            x => panic!("expected an ack, got {:?}", x),
+        }
+
+        bacnet
+            .io
+            .push(Reply::SimpleAck(ConfirmedServiceChoice::VtClose));
+        session.close(&bacnet, &mut buf).await.unwrap();
+        match bacnet.io.sent_request(2) {
+            ConfirmedRequestService::VtClose(x) => assert_eq!(x.remote_session_ids, [9]),
+            x => panic!("unexpected request {:?}", x),
+        }
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn acknowledges_a_close_from_the_remote_device() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+        let session = VtSession {
+            vt_class: VtClass::DefaultTerminal,
+            local_session_id: 3,
+            remote_session_id: 9,
+        };
+
+        let close = VtClose::new(vec![2, 3]);
+        bacnet
+            .io
+            .push(request(5, ConfirmedRequestService::VtClose(close)));
+        let received = session.receive(&bacnet, &mut buf).await.unwrap();
+        assert_eq!(received, Some(VtOutput::Closed));
+        match bacnet.io.sent_apdu(0) {
+            ApplicationPdu::SimpleAck(ack) => {
+                assert_eq!(ack.invoke_id, 5);
+                assert_eq!(ack.service_choice, ConfirmedServiceChoice::VtClose);
+            }
+            x => panic!("expected an ack, got {:?}", x),
+        }
+    }
+
+    #[maybe_async::test(feature = "is_sync", async(not(feature = "is_sync"), tokio::test))]
+    async fn answers_requests_for_other_sessions() {
+        let bacnet = Bacnet::new(FakeNetwork::default());
+        let mut buf = vec![0; 1500];
+        let session = VtSession {
+            vt_class: VtClass::DefaultTerminal,
+            local_session_id: 3,
+            remote_session_id: 9,
+        };
+
+        let output = VtData::new(5, OctetString::new(b"login: "), true);
+        bacnet
+            .io
+            .push(request(6, ConfirmedRequestService::VtData(output)));
+        let close = VtClose::new(vec![5]);
+        bacnet
+            .io
+            .push(request(7, ConfirmedRequestService::VtClose(close)));
+        let read = ReadProperty::new(
+            ObjectId::new(ObjectType::ObjectDevice, 1),
+            PropertyId::PropObjectName,
+        );
+        bacnet
+            .io
+            .push(request(8, ConfirmedRequestService::ReadProperty(read)));
+
+        for _ in 0..3 {
+            let received = session.receive(&bacnet, &mut buf).await.unwrap();
+            assert!(received.is_none());
+        }
+
+        for (index, invoke_id, service_choice) in [
+            (0, 6, ConfirmedServiceChoice::VtData),
+            (1, 7, ConfirmedServiceChoice::VtClose),
+        ] {
+            match bacnet.io.sent_apdu(index) {
+                ApplicationPdu::Error(error) => {
+                    assert_eq!(error.invoke_id, invoke_id);
+                    assert_eq!(error.service_choice, service_choice);
+                    assert!(matches!(error.error_class, ErrorClass::Services));
+                    assert!(matches!(error.error_code, ErrorCode::UnknownVtSession));
+                }
+                x => panic!("expected an error, got {:?}", x),
+            }
+        }
+        match bacnet.io.sent_apdu(2) {
+            ApplicationPdu::Reject(reject) => {
+                assert_eq!(reject.invoke_id, 8);
+                assert_eq!(reject.reason, RejectReason::UnrecognizedService);
+            }
+            x => panic!("expected a reject, got {:?}", x),
+        }
+    }
+}